@@ -0,0 +1,136 @@
+#![cfg(test)]
+
+use snafu::{
+    anyhow::{from_anyhow, AnyhowResultExt, AnyhowSource},
+    prelude::*,
+    ChainCompat, Report, Whatever,
+};
+use std::io;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not load the configuration from {path}"))]
+    LoadConfiguration { path: String, source: BoxError },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(whatever, display("{message}"))]
+struct MyWhatever {
+    message: String,
+    #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+    source: Option<Box<dyn std::error::Error>>,
+}
+
+impl From<anyhow::Error> for MyWhatever {
+    fn from(error: anyhow::Error) -> Self {
+        from_anyhow(error)
+    }
+}
+
+fn read_file() -> anyhow::Result<String> {
+    let result: io::Result<String> = Err(io::Error::new(io::ErrorKind::NotFound, "No such file"));
+    // Both `anyhow::Context` and `snafu::ResultExt` provide `context`
+    anyhow::Context::context(result, "Could not read the file")
+}
+
+fn messages(error: &dyn std::error::Error) -> Vec<String> {
+    ChainCompat::new(error).map(ToString::to_string).collect()
+}
+
+#[test]
+fn whatever_converts_from_anyhow() {
+    fn run() -> Result<String, Whatever> {
+        Ok(read_file()?)
+    }
+
+    let error = run().unwrap_err();
+    assert_eq!(
+        messages(&error),
+        ["Could not read the file", "No such file"],
+    );
+}
+
+#[test]
+fn whatever_without_an_anyhow_source_has_no_source() {
+    fn run() -> Result<(), Whatever> {
+        Err(anyhow::anyhow!("Something went wrong"))?;
+        Ok(())
+    }
+
+    let error = run().unwrap_err();
+    assert_eq!(messages(&error), ["Something went wrong"]);
+}
+
+#[test]
+fn user_whatever_types_convert_with_from_anyhow() {
+    fn run() -> Result<String, MyWhatever> {
+        Ok(read_file()?)
+    }
+
+    let error = run().unwrap_err();
+    assert_eq!(
+        messages(&error),
+        ["Could not read the file", "No such file"],
+    );
+}
+
+#[test]
+fn the_original_error_is_available() {
+    let error: Whatever = read_file().unwrap_err().into();
+    let source = std::error::Error::source(&error)
+        .and_then(|s| s.downcast_ref::<AnyhowSource>())
+        .expect("source should be the anyhow error");
+
+    assert!(source.get_ref().downcast_ref::<io::Error>().is_some());
+}
+
+#[test]
+fn reports_do_not_repeat_the_outermost_message() {
+    let error: Whatever = read_file().unwrap_err().into();
+    assert_eq!(
+        Report::from_error(error).to_string(),
+        "Could not read the file\nCaused by: No such file",
+    );
+}
+
+#[test]
+fn anyhow_context_wraps_the_error() {
+    let error = read_file()
+        .anyhow_context(LoadConfigurationSnafu { path: "app.toml" })
+        .unwrap_err();
+
+    assert_eq!(
+        messages(&error),
+        [
+            "Could not load the configuration from app.toml",
+            "Could not read the file",
+            "No such file",
+        ],
+    );
+}
+
+#[test]
+fn with_anyhow_context_wraps_the_error() {
+    let error = read_file()
+        .with_anyhow_context(|| LoadConfigurationSnafu {
+            path: format!("{}.toml", "app"),
+        })
+        .unwrap_err();
+
+    assert_eq!(
+        messages(&error)[0],
+        "Could not load the configuration from app.toml",
+    );
+}
+
+#[test]
+fn snafu_errors_convert_into_anyhow() {
+    fn run() -> anyhow::Result<String> {
+        Ok(read_file().anyhow_context(LoadConfigurationSnafu { path: "app.toml" })?)
+    }
+
+    let error = run().unwrap_err();
+    assert_eq!(error.chain().count(), 3);
+}
@@ -1,5 +1,5 @@
 #![cfg(test)]
-use snafu::{Backtrace, ErrorCompat, Snafu};
+use snafu::{Backtrace, ErrorCompat, Report, Snafu};
 
 #[derive(Debug, Snafu)]
 enum Error {
@@ -33,3 +33,48 @@ fn backtrace_contains_function_names() {
         .map(|n| n.to_string());
     assert!(names.any(|n| n.contains("::example::")));
 }
+
+#[test]
+fn report_shows_backtrace_when_requested() {
+    let report = Report::from_error(example().unwrap_err()).show_backtrace(true);
+    let text = report.to_string();
+    assert!(
+        text.starts_with("WithBacktrace\n\nBacktrace:\n"),
+        "{}",
+        text
+    );
+}
+
+#[test]
+fn report_hides_backtrace_when_requested() {
+    let report = Report::from_error(example().unwrap_err()).show_backtrace(false);
+    assert_eq!(report.to_string(), "WithBacktrace");
+}
+
+#[test]
+fn report_shows_backtrace_based_on_the_environment() {
+    let report = Report::from_error(example().unwrap_err());
+
+    std::env::set_var("SNAFU_BACKTRACE", "0");
+    std::env::set_var("RUST_BACKTRACE", "1");
+    assert_eq!(report.to_string(), "WithBacktrace");
+
+    std::env::set_var("SNAFU_BACKTRACE", "1");
+    assert!(report.to_string().contains("Backtrace:"));
+
+    std::env::remove_var("SNAFU_BACKTRACE");
+    assert!(report.to_string().contains("Backtrace:"));
+
+    std::env::remove_var("RUST_BACKTRACE");
+    assert_eq!(report.to_string(), "WithBacktrace");
+}
+
+#[test]
+fn report_colorizes_backtrace_frames() {
+    let report = Report::from_error(example().unwrap_err())
+        .show_backtrace(true)
+        .colorize(true);
+    let text = report.to_string();
+    assert!(text.contains(": \x1b[36m"), "{}", text);
+    assert!(text.contains(" \x1b[2mat "), "{}", text);
+}
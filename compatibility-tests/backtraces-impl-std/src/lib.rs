@@ -1,13 +1,31 @@
 #![cfg(test)]
-#![feature(backtrace)]
+#![feature(error_generic_member_access)]
 
-use snafu::{Backtrace, ErrorCompat, Snafu};
+use snafu::{prelude::*, Backtrace, ErrorCompat, Report};
+use std::error::{request_ref, Error as _};
 
 #[derive(Debug, Snafu)]
 enum Error {
-    WithBacktrace { backtrace: Backtrace },
+    WithBacktrace {
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Could not load the configuration"))]
+    LoadConfig {
+        source: InnerError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The file is missing"))]
+struct InnerError {
+    source: std::io::Error,
+    backtrace: Backtrace,
 }
 
+#[derive(Debug, Snafu)]
+struct OpaqueError(Error);
+
 type Result<T, E = Error> = std::result::Result<T, E>;
 
 fn example() -> Result<()> {
@@ -36,3 +54,64 @@ fn backtrace_contains_function_names() {
     let backtrace = ErrorCompat::backtrace(&error).unwrap();
     assert!(backtrace.to_string().contains("::example"));
 }
+
+#[test]
+fn backtrace_is_available_through_the_provider_api() {
+    let error = example().unwrap_err();
+    let provided = request_ref::<std::backtrace::Backtrace>(&error).unwrap();
+    let backtrace = ErrorCompat::backtrace(&error).unwrap();
+    assert!(std::ptr::eq(provided, backtrace));
+}
+
+#[test]
+fn opaque_errors_provide_the_backtrace_of_the_inner_error() {
+    let error = OpaqueError(example().unwrap_err());
+    assert!(request_ref::<std::backtrace::Backtrace>(&error).is_some());
+}
+
+#[test]
+fn errors_without_a_backtrace_provide_nothing() {
+    let io = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+    let error = Err::<(), _>(io)
+        .context(InnerSnafu)
+        .context(LoadConfigSnafu)
+        .unwrap_err();
+    assert!(request_ref::<std::backtrace::Backtrace>(&error).is_none());
+    assert!(request_ref::<std::backtrace::Backtrace>(error.source().unwrap()).is_some());
+}
+
+#[test]
+fn std_report_shows_the_chain_of_sources() {
+    let io = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+    let error = Err::<(), _>(io)
+        .context(InnerSnafu)
+        .context(LoadConfigSnafu)
+        .unwrap_err();
+
+    let report = Report::from_error(error)
+        .show_backtrace(false)
+        .into_std_report()
+        .unwrap();
+
+    assert_eq!(
+        report.to_string(),
+        "Could not load the configuration\n\n\
+         Caused by:\n   \
+         0: The file is missing\n   \
+         1: gone",
+    );
+}
+
+#[test]
+fn std_report_shows_the_provided_backtrace() {
+    let report = Report::from_error(example().unwrap_err())
+        .show_backtrace(true)
+        .into_std_report()
+        .unwrap();
+    assert!(report.to_string().contains("::example"));
+}
+
+#[test]
+fn reports_without_an_error_do_not_convert() {
+    assert!(Report::<Error>::ok().into_std_report().is_none());
+}
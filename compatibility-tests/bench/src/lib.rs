@@ -0,0 +1,52 @@
+#![cfg(test)]
+
+use criterion::Criterion;
+use snafu::{bench::ErrorBenchmarks, prelude::*, Backtrace};
+use std::time::Duration;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not parse {}", input))]
+    Parse {
+        input: String,
+        source: std::num::ParseIntError,
+        backtrace: Option<Backtrace>,
+    },
+}
+
+// Keep the measurements as short as possible; we only care that the
+// benchmarks run.
+fn criterion() -> Criterion {
+    Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(1))
+        .measurement_time(Duration::from_millis(10))
+        .without_plots()
+}
+
+#[test]
+fn all_benchmarks_can_be_run() {
+    let error = "x"
+        .parse::<i32>()
+        .context(ParseSnafu { input: "x" })
+        .unwrap_err();
+
+    let mut c = criterion();
+    ErrorBenchmarks::new(&mut c, "parse")
+        .context(|| "x".parse::<i32>(), || ParseSnafu { input: "x" })
+        .backtrace::<Option<Backtrace>>()
+        .display(&error)
+        .finish();
+}
+
+#[test]
+fn benchmarks_accept_trait_objects() {
+    let error: Box<dyn std::error::Error> = Box::new(
+        "x".parse::<i32>()
+            .context(ParseSnafu { input: "x" })
+            .unwrap_err(),
+    );
+
+    let mut c = criterion();
+    ErrorBenchmarks::new(&mut c, "boxed").display(&*error).finish();
+}
@@ -0,0 +1,147 @@
+#![cfg(test)]
+// The errors are only inspected through the catalog
+#![allow(dead_code)]
+
+use snafu::{prelude::*, CatalogEntry};
+use std::io;
+
+mod storage {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(display("The disk is full"), http_status(507))]
+        DiskFull,
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(catalog, http_status(400))]
+enum Error {
+    /// The user does not exist.
+    #[snafu(display("User {id} does not exist"), http_status(404), exit_code(67))]
+    MissingUser {
+        id: u32,
+    },
+
+    /// The name contains invalid characters
+    InvalidName {
+        name: String,
+    },
+
+    ReadConfig {
+        source: io::Error,
+    },
+
+    Unknown,
+
+    #[snafu(flatten)]
+    Storage {
+        source: storage::Error,
+    },
+
+    #[snafu(display("{}: {}", "Upstream", reason))]
+    Upstream {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(catalog, c_code = 12)]
+#[snafu(display("The value {value} is out of range"))]
+struct OutOfRangeError<T>
+where
+    T: std::fmt::Display,
+{
+    value: T,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Not in the catalog"))]
+struct UncataloguedError;
+
+fn entry(error: &str, variant: Option<&str>) -> &'static CatalogEntry {
+    snafu::catalog()
+        .find(|e| e.error() == error && e.variant() == variant)
+        .unwrap_or_else(|| panic!("{} {:?} is not in the catalog", error, variant))
+}
+
+#[test]
+fn every_variant_is_in_the_catalog() {
+    let mut variants: Vec<_> = snafu::catalog()
+        .filter(|e| e.error() == "Error")
+        .filter_map(CatalogEntry::variant)
+        .collect();
+    variants.sort_unstable();
+
+    assert_eq!(
+        variants,
+        [
+            "InvalidName",
+            "MissingUser",
+            "ReadConfig",
+            "Storage",
+            "Unknown",
+            "Upstream"
+        ],
+    );
+}
+
+#[test]
+fn entries_include_the_message_doc_comment_and_codes() {
+    let e = entry("Error", Some("MissingUser"));
+
+    assert_eq!(e.module_path(), module_path!());
+    assert_eq!(e.message(), Some("User {id} does not exist"));
+    assert_eq!(e.doc(), "The user does not exist.");
+    assert_eq!(e.exit_code(), Some(67));
+    assert_eq!(e.http_status(), Some(404));
+    assert_eq!(e.c_code(), None);
+}
+
+#[test]
+fn messages_fall_back_like_display() {
+    let e = entry("Error", Some("InvalidName"));
+    assert_eq!(e.message(), Some("The name contains invalid characters"));
+
+    let e = entry("Error", Some("ReadConfig"));
+    assert_eq!(e.message(), Some("ReadConfig: {}"));
+    assert_eq!(e.doc(), "");
+
+    let e = entry("Error", Some("Unknown"));
+    assert_eq!(e.message(), Some("Unknown"));
+}
+
+#[test]
+fn messages_do_not_include_format_arguments() {
+    let e = entry("Error", Some("Upstream"));
+    assert_eq!(e.message(), Some("{}: {}"));
+}
+
+#[test]
+fn flattened_variants_do_not_have_a_message_or_codes() {
+    let e = entry("Error", Some("Storage"));
+    assert_eq!(e.message(), None);
+    assert_eq!(e.http_status(), None);
+}
+
+#[test]
+fn variants_without_a_code_use_the_enum_default() {
+    let e = entry("Error", Some("Unknown"));
+    assert_eq!(e.http_status(), Some(400));
+}
+
+#[test]
+fn structs_are_in_the_catalog() {
+    let e = entry("OutOfRangeError", None);
+    assert_eq!(e.message(), Some("The value {value} is out of range"));
+    assert_eq!(e.c_code(), Some(12));
+    assert_eq!(e.http_status(), None);
+}
+
+#[test]
+fn errors_are_only_in_the_catalog_when_requested() {
+    assert!(snafu::catalog().all(|e| e.error() != "UncataloguedError"));
+    assert!(snafu::catalog().all(|e| e.module_path() != "catalog_compat::storage"));
+}
@@ -0,0 +1,10 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(accessors)]
+enum Error {
+    Alpha { id: u32 },
+    Beta { id: String },
+}
+
+fn main() {}
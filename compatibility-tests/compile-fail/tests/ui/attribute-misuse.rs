@@ -7,6 +7,8 @@ mod enum_misuse {
     #[snafu(source(true))]
     #[snafu(backtrace)]
     #[snafu(context)]
+    #[snafu(flatten)]
+    #[snafu(display(internal("display should not work here")))]
     enum EnumError {
         AVariant,
     }
@@ -22,6 +24,15 @@ mod variant_misuse {
         #[snafu(source)]
         #[snafu(backtrace)]
         #[snafu(crate_root(XXXX))]
+        #[snafu(kind)]
+        #[snafu(predicates)]
+        #[snafu(accessors)]
+        #[snafu(display(skip))]
+        #[snafu(strip_suffix(Error))]
+        #[snafu(convert(from(XXXX, AVariant)))]
+        #[snafu(debug(stable))]
+        #[snafu(dedup)]
+        #[snafu(report_conversions)]
         AVariant,
     }
 }
@@ -38,6 +49,17 @@ mod field_misuse {
             #[snafu(source(from(XXXX, Box::new)))]
             #[snafu(context)]
             #[snafu(crate_root(XXXX))]
+            #[snafu(kind)]
+            #[snafu(predicates)]
+            #[snafu(accessors)]
+            #[snafu(display(skip))]
+            #[snafu(flatten)]
+            #[snafu(strip_suffix(Error))]
+            #[snafu(display(internal("display should not work here")))]
+            #[snafu(convert(from(XXXX, AVariant)))]
+            #[snafu(debug(stable))]
+            #[snafu(dedup)]
+            #[snafu(report_conversions)]
             source: String,
         },
     }
@@ -56,6 +78,16 @@ mod struct_misuse {
     #[snafu(source(true))]
     #[snafu(backtrace)]
     #[snafu(context)]
+    #[snafu(kind)]
+    #[snafu(predicates)]
+    #[snafu(accessors)]
+    #[snafu(flatten)]
+    #[snafu(strip_suffix(Error))]
+    #[snafu(display(internal("display should not work here")))]
+    #[snafu(convert(from(UsableError)))]
+    #[snafu(debug(stable))]
+    #[snafu(dedup)]
+    #[snafu(report_conversions)]
     struct StructError(Box<UsableError>);
 }
 
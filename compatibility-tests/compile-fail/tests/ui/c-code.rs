@@ -0,0 +1,29 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(c_code = 1, c_code = 2)]
+enum EnumError {
+    #[snafu(c_code = 3000000000)]
+    TooLarge {
+        #[snafu(c_code = 4)]
+        id: u32,
+    },
+
+    #[snafu(c_code)]
+    Missing,
+
+    #[snafu(c_code = 5, c_code = 6)]
+    Duplicate,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(c_code = 1, c_code = 2)]
+struct NamedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(c_code = 1, c_code = 2)]
+struct OpaqueError(EnumError);
+
+fn main() {}
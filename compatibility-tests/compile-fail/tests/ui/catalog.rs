@@ -0,0 +1,17 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(catalog, catalog)]
+enum EnumError {
+    #[snafu(catalog)]
+    Leaf {
+        #[snafu(catalog)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(catalog)]
+struct OpaqueError(EnumError);
+
+fn main() {}
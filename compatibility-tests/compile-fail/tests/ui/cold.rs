@@ -0,0 +1,20 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(cold, cold(false))]
+enum EnumError {
+    #[snafu(cold(true), cold)]
+    Leaf {
+        #[snafu(cold)]
+        id: u32,
+    },
+
+    #[snafu(cold(nope))]
+    Other,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(cold)]
+struct OpaqueError(EnumError);
+
+fn main() {}
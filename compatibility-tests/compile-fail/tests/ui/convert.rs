@@ -0,0 +1,28 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum OldError {
+    Alpha,
+    Beta,
+    Gamma,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(convert(from(OldError, Alpha => First)))]
+enum UnmappedError {
+    First,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(convert(from(OldError, Alpha => First, Beta => Missing, Gamma => First)))]
+enum UnknownVariantError {
+    First,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(convert(from(&'static OldError, Alpha => First)))]
+enum NotAPathError {
+    First,
+}
+
+fn main() {}
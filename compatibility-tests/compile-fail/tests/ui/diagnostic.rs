@@ -0,0 +1,24 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(code("E1"), help("help"), url("url"), label("label"))]
+enum EnumError {
+    #[snafu(code("E1"), code("E2"), help("a"), help("b"), url("a"), url("b"), label("label"))]
+    Leaf {
+        #[snafu(code("E1"), help("help"), url("url"), label("a"), label("b"))]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(code("E1"), code("E2"), label("label"))]
+struct NamedError {
+    #[snafu(code("E1"))]
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(code("E1"), help("help"), url("url"), label("label"))]
+struct OpaqueError(EnumError);
+
+fn main() {}
@@ -0,0 +1,29 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(display_buffer(16), display_buffer(32))]
+enum EnumError {
+    #[snafu(display_buffer(16))]
+    Leaf {
+        #[snafu(display_buffer(16))]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_buffer(0))]
+enum EmptyError {
+    Leaf,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display(skip), display_buffer(16))]
+struct SkippedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_buffer(16))]
+struct OpaqueError(EnumError);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not reach {hots}:{port}"))]
+    Misspelled { host: String, port: u16 },
+
+    #[snafu(display("Nothing to see in {field}"))]
+    NoFields,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Read,
+    ReadError,
+
+    #[snafu(context(suffix(Ctx)))]
+    Write,
+    #[snafu(context(suffix(false)))]
+    WriteCtx,
+
+    // Variants without a context selector do not conflict
+    #[snafu(context(false))]
+    Io { source: std::io::Error },
+    #[snafu(context(false))]
+    IoError { source: std::fmt::Error },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(strip_suffix(Error, Failure))]
+enum StrippedError {
+    OpenError,
+    OpenFailure,
+}
+
+fn main() {}
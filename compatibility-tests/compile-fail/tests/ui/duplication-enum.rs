@@ -3,6 +3,18 @@ use snafu::Snafu;
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
 #[snafu(visibility(pub))]
+#[snafu(kind)]
+#[snafu(kind)]
+#[snafu(predicates)]
+#[snafu(predicates)]
+#[snafu(accessors)]
+#[snafu(accessors)]
+#[snafu(debug(stable))]
+#[snafu(debug(stable))]
+#[snafu(dedup)]
+#[snafu(dedup)]
+#[snafu(report_conversions)]
+#[snafu(report_conversions)]
 enum EnumError {
     AVariant,
 }
@@ -0,0 +1,23 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(fields, fields)]
+enum EnumError {
+    #[snafu(fields)]
+    Leaf {
+        #[snafu(fields)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(fields)]
+struct NamedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(fields)]
+struct OpaqueError(EnumError);
+
+fn main() {}
@@ -5,11 +5,6 @@ mod wrong_error_types {
     union AUnion {
         _a: i32,
     }
-
-    #[derive(Snafu)]
-    enum TupleEnumVariant {
-        Alpha(i32),
-    }
 }
 
 mod other_attributes {
@@ -0,0 +1,30 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(exit_code(1))]
+enum EnumError {
+    Leaf,
+}
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(exit_code(256))]
+    TooLarge,
+
+    #[snafu(exit_code(-1))]
+    Negative,
+
+    #[snafu(exit_code(1), exit_code(2))]
+    Duplicated,
+
+    Field {
+        #[snafu(exit_code(1))]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(exit_code(1))]
+struct OpaqueError(EnumError);
+
+fn main() {}
@@ -0,0 +1,26 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Leaf,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(flatten)]
+    NoSource,
+
+    #[snafu(flatten)]
+    ContextField { source: InnerError, name: String },
+
+    #[snafu(flatten, display("Not used"))]
+    WithDisplay { source: InnerError },
+
+    #[snafu(flatten)]
+    WithBacktrace {
+        source: InnerError,
+        backtrace: snafu::Backtrace,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(kind, accessors)]
+enum KindField {
+    Alpha { kind: u8 },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(predicates)]
+enum SameSnakeCase {
+    HttpError,
+    HTTPError,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(accessors)]
+enum FieldAndSourceAccessor {
+    Alpha {
+        path: String,
+    },
+    Beta {
+        #[snafu(source(accessor(path)))]
+        source: std::io::Error,
+    },
+}
+
+fn main() {}
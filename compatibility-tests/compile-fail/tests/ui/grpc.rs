@@ -0,0 +1,29 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(grpc(code = Internal, metadata(id)))]
+enum EnumError {
+    #[snafu(grpc(metadata(missing)))]
+    Leaf {
+        #[snafu(grpc(code = NotFound))]
+        id: u32,
+    },
+
+    #[snafu(grpc(code = NotFound, code = Aborted))]
+    Duplicate,
+
+    #[snafu(grpc(status = 5))]
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(grpc(code = NotFound), grpc(code = Aborted))]
+struct NamedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(grpc(code = NotFound))]
+struct OpaqueError(EnumError);
+
+fn main() {}
@@ -0,0 +1,32 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status)]
+enum EnumError {
+    #[snafu(http_status(42))]
+    TooSmall {
+        #[snafu(http_status(400))]
+        id: u32,
+    },
+
+    #[snafu(http_status(1000))]
+    TooLarge,
+
+    #[snafu(http_status)]
+    NoSource,
+
+    #[snafu(http_status(400), http_status(401))]
+    Duplicate,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status(400), http_status(401))]
+struct NamedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status(99))]
+struct OpaqueError(EnumError);
+
+fn main() {}
@@ -0,0 +1,27 @@
+use snafu::{prelude::*, Location};
+
+#[derive(Debug, Snafu)]
+#[snafu(implicit)]
+enum EnumError {
+    #[snafu(implicit)]
+    Leaf {
+        #[snafu(implicit, implicit)]
+        id: u32,
+    },
+
+    Sourced {
+        #[snafu(implicit)]
+        source: std::io::Error,
+    },
+
+    Located {
+        location: snafu::Location,
+    },
+
+    ImportedLocated {
+        #[snafu(implicit)]
+        location: Location,
+    },
+}
+
+fn main() {}
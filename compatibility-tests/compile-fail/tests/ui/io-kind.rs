@@ -0,0 +1,25 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(io_kind(Other), io_kind(NotFound))]
+enum EnumError {
+    Leaf {
+        #[snafu(io_kind(NotFound))]
+        id: u32,
+    },
+
+    #[snafu(io_kind(NotFound), io_kind(TimedOut))]
+    Duplicate,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(io_kind(NotFound), io_kind(TimedOut))]
+struct NamedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(io_kind(NotFound))]
+struct OpaqueError(EnumError);
+
+fn main() {}
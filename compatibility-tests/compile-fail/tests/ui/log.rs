@@ -0,0 +1,30 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(log, log(level = "info"))]
+enum EnumError {
+    #[snafu(log(level = "loud"))]
+    Leaf {
+        #[snafu(log)]
+        id: u32,
+    },
+
+    #[snafu(log(target = "a", target = "b"))]
+    Duplicate,
+
+    #[snafu(log(colour = "red"))]
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(log, log)]
+struct NamedError {
+    #[snafu(log)]
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(log)]
+struct OpaqueError(EnumError);
+
+fn main() {}
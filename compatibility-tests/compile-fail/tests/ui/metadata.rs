@@ -0,0 +1,17 @@
+use snafu::{prelude::*, ErrorMetadata};
+
+#[derive(Debug, Snafu)]
+struct NoMetadataError;
+
+#[derive(Debug, Snafu)]
+enum PartialMetadataError {
+    Enriched { metadata: snafu::Metadata },
+    Unenriched,
+}
+
+fn main() {
+    let _ = NoMetadataSnafu.fail::<()>().add_context("request_id", 1);
+
+    let mut e = UnenrichedSnafu.build();
+    e.add_context("request_id", "abc-123".to_string());
+}
@@ -0,0 +1,27 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(metric, metric = "errors")]
+enum EnumError {
+    #[snafu(metric = 42)]
+    Leaf {
+        #[snafu(metric)]
+        id: u32,
+    },
+
+    #[snafu(metric, metric)]
+    Other,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(metric, metric)]
+struct NamedError {
+    #[snafu(metric)]
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(metric)]
+struct OpaqueError(EnumError);
+
+fn main() {}
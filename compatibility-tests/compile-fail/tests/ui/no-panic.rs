@@ -0,0 +1,35 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(no_panic, no_panic, termination, serialize)]
+enum EnumError {
+    #[snafu(no_panic, log)]
+    Leaf {
+        #[snafu(no_panic)]
+        id: u32,
+    },
+
+    #[snafu(metric, tracing)]
+    Other,
+
+    #[snafu(whatever, display("{message}"))]
+    Stringly { message: String },
+
+    Located {
+        #[snafu(label("here"))]
+        span: (usize, usize),
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(no_panic, wasm)]
+struct NamedError {
+    #[snafu(label("here"))]
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(no_panic, termination, c_code = 3)]
+struct OpaqueError(EnumError);
+
+fn main() {}
@@ -0,0 +1,32 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(opentelemetry, opentelemetry)]
+enum EnumError {
+    #[snafu(opentelemetry(fields(id)), opentelemetry)]
+    Leaf {
+        #[snafu(opentelemetry)]
+        id: u32,
+    },
+
+    #[snafu(opentelemetry(fields(name)))]
+    Unknown { id: u32 },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(opentelemetry(fields(id)))]
+enum FieldsOnEnumError {
+    Leaf { id: u32 },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(opentelemetry, opentelemetry)]
+struct NamedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(opentelemetry)]
+struct OpaqueError(EnumError);
+
+fn main() {}
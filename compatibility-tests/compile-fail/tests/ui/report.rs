@@ -0,0 +1,9 @@
+#[snafu::report]
+fn no_return_type() {}
+
+#[snafu::report(verbose)]
+fn with_attribute_arguments() -> Result<(), String> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize, serialize(backtrace))]
+enum EnumError {
+    #[snafu(serialize)]
+    Leaf {
+        #[snafu(serialize)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize)]
+struct NamedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize)]
+struct OpaqueError(EnumError);
+
+fn main() {}
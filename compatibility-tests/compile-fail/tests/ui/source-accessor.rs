@@ -0,0 +1,44 @@
+mod mismatched_types {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Read {
+            #[snafu(source(accessor(io_source)))]
+            source: std::io::Error,
+        },
+
+        Parse {
+            #[snafu(source(accessor(io_source)))]
+            source: std::num::ParseIntError,
+        },
+    }
+}
+
+mod opted_out {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Read {
+            #[snafu(source(false), source(accessor))]
+            source: String,
+        },
+    }
+}
+
+mod on_a_struct {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(source(accessor))]
+    struct TupleError(Box<dyn std::error::Error>);
+
+    #[derive(Debug, Snafu)]
+    struct NamedError {
+        #[snafu(source(accessor))]
+        source: std::io::Error,
+    }
+}
+
+fn main() {}
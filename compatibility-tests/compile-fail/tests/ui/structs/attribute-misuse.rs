@@ -12,6 +12,12 @@ mod invalid_on_struct {
     #[derive(Debug, Snafu)]
     #[snafu(source(true))]
     #[snafu(backtrace)]
+    #[snafu(kind)]
+    #[snafu(predicates)]
+    #[snafu(accessors)]
+    #[snafu(flatten)]
+    #[snafu(dedup)]
+    #[snafu(report_conversions)]
     struct Error {}
 }
 
@@ -23,6 +29,7 @@ mod invalid_on_field {
         #[snafu(display("display should not work here"))]
         #[snafu(visibility(pub))]
         #[snafu(context)]
+        #[snafu(debug(stable))]
         id: i32,
     }
 }
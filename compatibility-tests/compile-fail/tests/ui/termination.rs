@@ -0,0 +1,24 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(termination, termination)]
+enum EnumError {
+    #[snafu(termination)]
+    Leaf {
+        #[snafu(termination)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(termination, termination)]
+struct NamedError {
+    #[snafu(termination)]
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(termination, termination)]
+struct OpaqueError(EnumError);
+
+fn main() {}
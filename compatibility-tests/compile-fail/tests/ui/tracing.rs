@@ -0,0 +1,27 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(tracing, tracing(level = "info"))]
+enum EnumError {
+    #[snafu(tracing(level = "loud"))]
+    Leaf {
+        #[snafu(tracing)]
+        id: u32,
+    },
+
+    #[snafu(tracing, tracing)]
+    Other,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(tracing, tracing)]
+struct NamedError {
+    #[snafu(tracing)]
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(tracing)]
+struct OpaqueError(EnumError);
+
+fn main() {}
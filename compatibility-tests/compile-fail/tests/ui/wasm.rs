@@ -0,0 +1,24 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(wasm, wasm)]
+enum EnumError {
+    #[snafu(wasm)]
+    Leaf {
+        #[snafu(wasm)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(wasm, wasm)]
+struct NamedError {
+    #[snafu(wasm)]
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(wasm, wasm)]
+struct OpaqueError(EnumError);
+
+fn main() {}
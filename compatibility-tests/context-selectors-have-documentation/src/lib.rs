@@ -10,4 +10,28 @@ use snafu::Snafu;
 pub enum Error {
     /// Variant docs
     Variant,
+
+    /// Variant with fields docs
+    ///
+    /// Only the summary is copied to the context selector
+    WithFields {
+        /// Field docs
+        name: String,
+        /// Field docs
+        id: u32,
+    },
+
+    /// Variant with a source docs
+    WithSource {
+        /// Field docs
+        source: std::io::Error,
+    },
+
+    /// Tuple variant docs
+    Tuple(
+        /// Field docs
+        u32,
+        /// Field docs
+        String,
+    ),
 }
@@ -0,0 +1,124 @@
+#![cfg(test)]
+
+use snafu::{
+    eyre::{EyreCompat, EyreSource},
+    prelude::*,
+    Backtrace, ChainCompat, Report,
+};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("The disk is full"))]
+    DiskFull,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not save the document"))]
+    SaveDocument {
+        source: InnerError,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Could not load the plugin {name}"))]
+    LoadPlugin {
+        name: String,
+        #[snafu(source(from(eyre::Report, EyreSource::new)))]
+        source: EyreSource,
+    },
+}
+
+fn save_document() -> Result<(), Error> {
+    DiskFullSnafu.fail().context(SaveDocumentSnafu)
+}
+
+fn legacy_load() -> eyre::Result<()> {
+    Err(eyre::eyre!("The file is missing")).map_err(|e| e.wrap_err("Could not open the plugin"))
+}
+
+fn messages(error: &dyn std::error::Error) -> Vec<String> {
+    ChainCompat::new(error).map(ToString::to_string).collect()
+}
+
+#[test]
+fn converting_into_a_report_keeps_the_chain() {
+    fn run() -> eyre::Result<()> {
+        save_document().map_err(EyreCompat::new)?;
+        Ok(())
+    }
+
+    let report = run().unwrap_err();
+    let messages: Vec<_> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        ["Could not save the document", "The disk is full"]
+    );
+}
+
+#[test]
+fn converting_into_a_report_keeps_the_backtrace() {
+    fn run() -> eyre::Result<()> {
+        save_document().map_err(EyreCompat::new)?;
+        Ok(())
+    }
+
+    let report = run().unwrap_err();
+    let error = report
+        .downcast_ref::<EyreCompat<Error>>()
+        .expect("report should contain the adapter");
+
+    let backtrace = error.backtrace().expect("backtrace should be present");
+    assert!(std::ptr::eq(
+        backtrace,
+        snafu::ErrorCompat::backtrace(error.get_ref()).unwrap(),
+    ));
+}
+
+#[test]
+fn reports_can_be_sources() {
+    let error = legacy_load()
+        .context(LoadPluginSnafu { name: "spellcheck" })
+        .unwrap_err();
+
+    assert_eq!(
+        messages(&error),
+        [
+            "Could not load the plugin spellcheck",
+            "Could not open the plugin",
+            "The file is missing",
+        ],
+    );
+    assert_eq!(
+        Report::from_error(error).to_string(),
+        "Could not load the plugin spellcheck\n\
+         Caused by: Could not open the plugin\n\
+         Caused by: The file is missing",
+    );
+}
+
+#[test]
+fn report_sources_keep_the_handler_output() {
+    let error = legacy_load()
+        .context(LoadPluginSnafu { name: "spellcheck" })
+        .unwrap_err();
+
+    let source = match &error {
+        Error::LoadPlugin { source, .. } => source,
+        _ => unreachable!(),
+    };
+    assert_eq!(format!("{:?}", source), format!("{:?}", source.get_ref()),);
+    assert!(format!("{:?}", source).contains("Caused by:"));
+}
+
+#[test]
+fn report_sources_can_be_unwrapped() {
+    let error = legacy_load()
+        .context(LoadPluginSnafu { name: "spellcheck" })
+        .unwrap_err();
+
+    let report = match error {
+        Error::LoadPlugin { source, .. } => source.into_inner(),
+        _ => unreachable!(),
+    };
+    assert_eq!(report.to_string(), "Could not open the plugin");
+}
@@ -0,0 +1,87 @@
+#![cfg(test)]
+
+use failure::Fail;
+use snafu::{
+    failure::{FailCompat, FailSource},
+    Backtrace, ResultExt, Snafu,
+};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("The disk is full"))]
+    DiskFull,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not save the document"))]
+    SaveDocument { source: InnerError },
+
+    #[snafu(display("Could not load the document"))]
+    LoadDocument { backtrace: Backtrace },
+}
+
+fn save_document() -> Result<(), Error> {
+    DiskFullSnafu.fail().context(SaveDocumentSnafu)
+}
+
+#[test]
+fn implements_fail() {
+    fn check<T: Fail>() {}
+    check::<FailCompat<Error>>();
+}
+
+#[test]
+fn causes_are_exposed() {
+    let error = FailCompat::new(save_document().unwrap_err());
+
+    let messages: Vec<_> = <dyn Fail>::iter_chain(&error)
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        messages,
+        ["Could not save the document", "The disk is full"]
+    );
+}
+
+#[test]
+fn causes_can_be_downcast() {
+    let error = FailCompat::new(save_document().unwrap_err());
+
+    let cause = error.cause().unwrap();
+    let source = cause.downcast_ref::<FailSource<Error>>().unwrap();
+    assert!(matches!(
+        source.downcast_ref::<InnerError>(),
+        Some(InnerError::DiskFull)
+    ));
+    assert!(source.cause().is_none());
+}
+
+#[test]
+fn backtrace_is_captured_when_the_wrapped_error_has_one() {
+    let error = FailCompat::new(LoadDocumentSnafu.build());
+    assert!(Fail::backtrace(&error).is_some());
+    assert!(snafu::ErrorCompat::backtrace(error.get_ref()).is_some());
+
+    let error = FailCompat::new(save_document().unwrap_err());
+    assert!(Fail::backtrace(&error).is_none());
+}
+
+#[test]
+fn converts_into_failure_error() {
+    fn legacy() -> Result<(), failure::Error> {
+        save_document().map_err(FailCompat::new)?;
+        Ok(())
+    }
+
+    let error = legacy().unwrap_err();
+    assert_eq!(error.iter_chain().count(), 2);
+    assert_eq!(error.to_string(), "Could not save the document");
+}
+
+#[test]
+fn wrapped_error_is_accessible() {
+    let error: FailCompat<_> = save_document().unwrap_err().into();
+    assert!(matches!(error.get_ref(), Error::SaveDocument { .. }));
+    assert!(matches!(error.into_inner(), Error::SaveDocument { .. }));
+}
@@ -0,0 +1,63 @@
+#![cfg(test)]
+
+use snafu::{ffi::FfiError, prelude::*};
+use std::ffi::CStr;
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The disk is full"))]
+struct DiskFullError;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not save {name}"), c_code = 7)]
+    Save { name: String, source: DiskFullError },
+
+    #[snafu(display("The name {name} is invalid"))]
+    InvalidName { name: String },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Not allowed"), c_code = -13)]
+struct ForbiddenError;
+
+fn message(error: &FfiError) -> &str {
+    unsafe { CStr::from_ptr(error.message) }.to_str().unwrap()
+}
+
+#[test]
+fn enums_convert_to_a_code_and_message() {
+    let e = DiskFullSnafu
+        .fail::<()>()
+        .context(SaveSnafu { name: "a.txt" })
+        .unwrap_err();
+    let e = FfiError::from(e);
+
+    assert_eq!(e.code, 7);
+    assert_eq!(message(&e), "Could not save a.txt\nCaused by: The disk is full");
+    unsafe { e.free() };
+}
+
+#[test]
+fn variants_without_a_code_use_negative_one() {
+    let e = FfiError::from(InvalidNameSnafu { name: "" }.build());
+
+    assert_eq!(e.code, -1);
+    unsafe { e.free() };
+}
+
+#[test]
+fn structs_convert_to_a_code_and_message() {
+    let e = FfiError::from(ForbiddenSnafu.build());
+
+    assert_eq!(e.code, -13);
+    assert_eq!(message(&e), "Not allowed");
+    unsafe { e.free() };
+}
+
+#[test]
+fn nul_bytes_are_replaced() {
+    let e = FfiError::from(InvalidNameSnafu { name: "a\0b" }.build());
+
+    assert_eq!(message(&e), "The name a\u{FFFD}b is invalid");
+    unsafe { e.free() };
+}
@@ -157,3 +157,225 @@ fn implements_error() {
     let d = block_on(load_stock_data_series());
     d.unwrap_err();
 }
+
+mod prelude {
+    use futures::executor::block_on;
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Wrapped { source: super::api::Error },
+    }
+
+    #[test]
+    fn futures_extensions_are_in_the_prelude() {
+        let result = block_on(super::api::fetch_page("example.com").context(WrappedSnafu));
+        assert!(matches!(result, Err(Error::Wrapped { .. })));
+    }
+}
+
+mod poll {
+    use snafu::{prelude::*, Whatever};
+    use std::task::Poll;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Reading { source: std::io::Error },
+    }
+
+    fn io_error() -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::Other, "boom")
+    }
+
+    #[test]
+    fn context_can_be_added_to_a_poll() {
+        let poll: Poll<std::io::Result<i32>> = Poll::Ready(Err(io_error()));
+        let poll = poll.context(ReadingSnafu);
+        assert!(matches!(poll, Poll::Ready(Err(Error::Reading { .. }))));
+
+        let poll: Poll<std::io::Result<i32>> = Poll::Pending;
+        let poll: Poll<Result<i32, Error>> =
+            poll.with_context(|| -> ReadingSnafu { panic!("Must not be called") });
+        assert!(poll.is_pending());
+    }
+
+    #[test]
+    fn whatever_context_can_be_added_to_a_poll() {
+        let poll: Poll<std::io::Result<i32>> = Poll::Ready(Err(io_error()));
+        let poll: Poll<Result<i32, Whatever>> =
+            poll.with_whatever_context(|e| format!("Reading failed: {}", e));
+        match poll {
+            Poll::Ready(Err(e)) => assert_eq!(e.to_string(), "Reading failed: boom"),
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn context_can_be_added_to_a_stream_poll() {
+        let poll: Poll<Option<std::io::Result<i32>>> = Poll::Ready(Some(Err(io_error())));
+        let poll = poll.context(ReadingSnafu);
+        assert!(matches!(
+            poll,
+            Poll::Ready(Some(Err(Error::Reading { .. })))
+        ));
+
+        let poll: Poll<Option<std::io::Result<i32>>> = Poll::Ready(None);
+        let poll: Poll<Option<Result<i32, Whatever>>> = poll.whatever_context("Unused");
+        assert!(matches!(poll, Poll::Ready(None)));
+    }
+}
+
+mod sink {
+    use futures::{executor::block_on, sink, SinkExt as _};
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        #[snafu(display("Could not publish to {}", topic))]
+        Publishing {
+            topic: String,
+            source: std::io::Error,
+        },
+    }
+
+    fn failing_sink() -> impl futures::Sink<i32, Error = std::io::Error> {
+        sink::unfold((), |(), _item: i32| async {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "boom"))
+        })
+    }
+
+    #[test]
+    fn context_can_be_added_to_a_sink() {
+        let mut sink = Box::pin(failing_sink().sink_context(PublishingSnafu { topic: "prices" }));
+        let error = block_on(sink.send(1)).unwrap_err();
+        assert_eq!(error.to_string(), "Could not publish to prices");
+    }
+
+    #[test]
+    fn lazy_context_can_be_added_to_a_sink() {
+        let mut calls = 0;
+        let mut sink = Box::pin(failing_sink().with_sink_context(|| {
+            calls += 1;
+            PublishingSnafu { topic: "prices" }
+        }));
+        let error = block_on(sink.send(1)).unwrap_err();
+        assert!(matches!(error, Error::Publishing { .. }));
+        drop(sink);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn successful_sends_are_unchanged() {
+        let mut items = Vec::new();
+        {
+            let mut sink = Box::pin(
+                sink::unfold(&mut items, |items, item: i32| async move {
+                    items.push(item);
+                    Ok::<_, std::io::Error>(items)
+                })
+                .sink_context(PublishingSnafu { topic: "prices" }),
+            );
+            block_on(sink.send(1)).unwrap();
+            block_on(sink.send(2)).unwrap();
+        }
+        assert_eq!(items, [1, 2]);
+    }
+}
+
+mod send {
+    use futures::{future, stream};
+    use snafu::{
+        futures::{SinkExt, TryFutureExt, TryStreamExt},
+        prelude::*,
+        Whatever,
+    };
+    use std::rc::Rc;
+
+    // Not `Send` or `Sync`, but never held by the adapters
+    #[derive(Debug, Snafu)]
+    struct LocalError {
+        source: std::io::Error,
+        data: Rc<str>,
+    }
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    #[test]
+    fn future_adapters_are_send_when_their_inputs_are() {
+        let fut = || future::err::<i32, std::io::Error>(std::io::ErrorKind::Other.into());
+
+        assert_send_sync(&fut().context(LocalSnafu { data: "a" }));
+        assert_send_sync(&fut().with_context(|| LocalSnafu { data: "a" }));
+        assert_send_sync(&TryFutureExt::whatever_context::<_, Whatever>(fut(), "a"));
+        assert_send_sync(&TryFutureExt::with_whatever_context::<_, _, Whatever>(
+            fut(),
+            |_| "a",
+        ));
+        assert_send_sync(&fut().timed_context(LocalSnafu { data: "a" }));
+    }
+
+    #[test]
+    fn stream_adapters_are_send_when_their_inputs_are() {
+        let st = || {
+            stream::iter(vec![Err::<i32, std::io::Error>(
+                std::io::ErrorKind::Other.into(),
+            )])
+        };
+
+        assert_send_sync(&st().context(LocalSnafu { data: "a" }));
+        assert_send_sync(&st().with_context(|| LocalSnafu { data: "a" }));
+        assert_send_sync(&TryStreamExt::whatever_context::<_, Whatever>(st(), "a"));
+        assert_send_sync(&TryStreamExt::with_whatever_context::<_, _, Whatever>(
+            st(),
+            |_| "a",
+        ));
+    }
+
+    #[test]
+    fn sink_adapters_are_send_when_their_inputs_are() {
+        fn si() -> impl futures::Sink<i32, Error = std::io::Error> + Send + Sync {
+            futures::SinkExt::sink_map_err(futures::sink::drain(), |e| match e {})
+        }
+
+        assert_send_sync(&SinkExt::<i32>::sink_context(
+            si(),
+            LocalSnafu { data: "a" },
+        ));
+        assert_send_sync(&SinkExt::<i32>::with_sink_context(si(), || LocalSnafu {
+            data: "a",
+        }));
+    }
+}
+
+mod timing {
+    use futures::{executor::block_on, future};
+    use snafu::{futures::TryFutureExt, prelude::*};
+    use std::time::Duration;
+
+    #[derive(Debug, Snafu)]
+    struct Error {
+        source: std::io::Error,
+        timing: snafu::Timing,
+    }
+
+    async fn slow_operation() -> Result<i32, std::io::Error> {
+        std::thread::sleep(Duration::from_millis(10));
+        Err(std::io::ErrorKind::TimedOut.into())
+    }
+
+    #[test]
+    fn records_the_elapsed_time() {
+        let e = block_on(slow_operation().timed_context(Snafu)).unwrap_err();
+        assert!(e.timing.elapsed().unwrap() >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn time_is_measured_from_the_first_poll() {
+        let fut = future::err::<i32, _>(std::io::Error::from(std::io::ErrorKind::Other))
+            .timed_context(Snafu);
+        std::thread::sleep(Duration::from_millis(50));
+
+        let e = block_on(fut).unwrap_err();
+        assert!(e.timing.elapsed().unwrap() < Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,44 @@
+#![cfg(test)]
+
+use http::StatusCode;
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(http_status(404))]
+    MissingUser { id: u32 },
+
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status(429))]
+struct RateLimitedError {
+    retry_after: u64,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status)]
+struct OpaqueError(Error);
+
+#[test]
+fn variants_return_a_status_code() {
+    let e = MissingUserSnafu { id: 1u32 }.build();
+    assert_eq!(e.http_status_code(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        UnknownSnafu.build().http_status_code(),
+        StatusCode::INTERNAL_SERVER_ERROR,
+    );
+}
+
+#[test]
+fn structs_return_a_status_code() {
+    let e = RateLimitedSnafu { retry_after: 5u64 }.build();
+    assert_eq!(e.http_status_code(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[test]
+fn opaque_errors_return_the_status_code_of_the_inner_error() {
+    let e = OpaqueError(MissingUserSnafu { id: 1u32 }.build());
+    assert_eq!(e.http_status_code(), StatusCode::NOT_FOUND);
+}
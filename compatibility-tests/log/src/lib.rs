@@ -0,0 +1,173 @@
+#![cfg(test)]
+
+use log::{Level, Log, Metadata, Record};
+use snafu::prelude::*;
+use std::{cell::RefCell, sync::Once};
+
+#[derive(Debug, Snafu)]
+#[snafu(log(level = "warn", target = "log_compat::users"))]
+enum Error {
+    #[snafu(display("Could not load user {}", id))]
+    LoadUser { id: u32, name: String },
+
+    #[snafu(display("Could not save the user"), log(level = "error"))]
+    SaveUser { source: std::io::Error },
+
+    #[snafu(context(false), display("Could not parse"))]
+    Parse { source: std::num::ParseIntError },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Timed out after {} seconds", seconds), log)]
+struct TimeoutError {
+    seconds: u64,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(whatever, display("{message}"), log(target = "app", level = "info"))]
+struct MyWhatever {
+    message: String,
+}
+
+#[derive(Debug, Snafu)]
+enum Unlogged {
+    #[snafu(display("Not logged"))]
+    Quiet,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Recorded {
+    level: Level,
+    target: String,
+    message: String,
+}
+
+thread_local! {
+    static RECORDS: RefCell<Vec<Recorded>> = RefCell::new(Vec::new());
+}
+
+// The logger is global, so each test thread records separately.
+struct Recorder;
+
+impl Log for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let recorded = Recorded {
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        RECORDS.with(|r| r.borrow_mut().push(recorded));
+    }
+
+    fn flush(&self) {}
+}
+
+fn record(f: impl FnOnce()) -> Vec<Recorded> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&Recorder).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+
+    RECORDS.with(|r| r.borrow_mut().clear());
+    f();
+    RECORDS.with(|r| r.borrow().clone())
+}
+
+#[test]
+fn building_an_error_writes_a_record() {
+    let records = record(|| {
+        let _ = LoadUserSnafu {
+            id: 42u32,
+            name: "alice",
+        }
+        .build();
+    });
+
+    assert_eq!(
+        records,
+        [Recorded {
+            level: Level::Warn,
+            target: "log_compat::users".into(),
+            message: r#"Could not load user 42 (variant = LoadUser, id = 42, name = "alice")"#
+                .into(),
+        }],
+    );
+}
+
+#[test]
+fn variants_can_override_the_level() {
+    let records = record(|| {
+        let _ = Err::<(), _>(std::io::Error::new(std::io::ErrorKind::Other, "disk"))
+            .context(SaveUserSnafu);
+    });
+
+    assert_eq!(
+        records,
+        [Recorded {
+            level: Level::Error,
+            target: "log_compat".into(),
+            message: "Could not save the user (variant = SaveUser)".into(),
+        }],
+    );
+}
+
+#[test]
+fn from_conversions_write_a_record() {
+    let records = record(|| {
+        let _ = Error::from("x".parse::<u8>().unwrap_err());
+    });
+
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].target, "log_compat::users");
+    assert_eq!(records[0].message, "Could not parse (variant = Parse)");
+}
+
+#[test]
+fn structs_default_to_warn_and_the_module_path() {
+    let records = record(|| {
+        let _ = TimeoutSnafu { seconds: 5u64 }.build();
+    });
+
+    assert_eq!(
+        records,
+        [Recorded {
+            level: Level::Warn,
+            target: "log_compat".into(),
+            message: "Timed out after 5 seconds (variant = TimeoutError, seconds = 5)".into(),
+        }],
+    );
+}
+
+#[test]
+fn whatever_errors_write_a_record() {
+    fn fail() -> Result<(), MyWhatever> {
+        whatever!("Something went wrong with {}", 42)
+    }
+
+    let records = record(|| {
+        let _ = fail();
+    });
+
+    assert_eq!(
+        records,
+        [Recorded {
+            level: Level::Info,
+            target: "app".into(),
+            message: "Something went wrong with 42 (variant = MyWhatever)".into(),
+        }],
+    );
+}
+
+#[test]
+fn errors_without_the_attribute_are_not_logged() {
+    let records = record(|| {
+        let _ = QuietSnafu.build();
+    });
+
+    assert!(records.is_empty());
+}
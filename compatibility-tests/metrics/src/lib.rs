@@ -0,0 +1,189 @@
+#![cfg(test)]
+
+use metrics::{
+    Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+};
+use snafu::prelude::*;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(metric)]
+enum Error {
+    #[snafu(display("Could not load user {}", id))]
+    LoadUser { id: u32 },
+
+    #[snafu(display("Could not save the user"), metric = "user_save_failures")]
+    SaveUser { source: std::io::Error },
+
+    #[snafu(context(false), display("Could not parse"))]
+    Parse { source: std::num::ParseIntError },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(metric = "storage_errors")]
+enum StorageError {
+    #[snafu(display("The disk is full"))]
+    DiskFull,
+
+    #[snafu(display("The disk is missing"), metric)]
+    DiskMissing,
+}
+
+#[derive(Debug, Snafu)]
+enum PartialError {
+    #[snafu(display("Counted"), metric)]
+    Counted,
+
+    #[snafu(display("Not counted"))]
+    NotCounted,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Timed out after {} seconds", seconds), metric)]
+struct TimeoutError {
+    seconds: u64,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(whatever, display("{message}"), metric = "unexpected_errors")]
+struct MyWhatever {
+    message: String,
+}
+
+/// The counter name and the `variant` label
+type CounterKey = (String, String);
+
+#[derive(Clone, Default)]
+struct Counters(Arc<Mutex<BTreeMap<CounterKey, u64>>>);
+
+impl Counters {
+    fn get(&self, name: &str, variant: &str) -> u64 {
+        let counters = self.0.lock().unwrap();
+        let key = (name.to_string(), variant.to_string());
+        counters.get(&key).copied().unwrap_or(0)
+    }
+
+    fn total(&self) -> u64 {
+        self.0.lock().unwrap().values().sum()
+    }
+}
+
+struct Handle(Counters, CounterKey);
+
+impl CounterFn for Handle {
+    fn increment(&self, value: u64) {
+        *(self.0).0.lock().unwrap().entry(self.1.clone()).or_insert(0) += value;
+    }
+
+    fn absolute(&self, value: u64) {
+        (self.0).0.lock().unwrap().insert(self.1.clone(), value);
+    }
+}
+
+impl Recorder for Counters {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let variant = key
+            .labels()
+            .find(|l| l.key() == "variant")
+            .map(|l| l.value().to_string())
+            .unwrap_or_default();
+        let key = (key.name().to_string(), variant);
+        Counter::from_arc(Arc::new(Handle(self.clone(), key)))
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+fn record(f: impl FnOnce()) -> Counters {
+    let counters = Counters::default();
+    metrics::with_local_recorder(&counters, f);
+    counters
+}
+
+#[test]
+fn building_an_error_increments_a_counter_named_after_the_enum() {
+    let counters = record(|| {
+        let _ = LoadUserSnafu { id: 1u32 }.build();
+        let _ = LoadUserSnafu { id: 2u32 }.build();
+    });
+
+    assert_eq!(counters.get("error", "LoadUser"), 2);
+    assert_eq!(counters.total(), 2);
+}
+
+#[test]
+fn variants_can_name_the_counter() {
+    let counters = record(|| {
+        let _ = Err::<(), _>(std::io::Error::new(std::io::ErrorKind::Other, "disk"))
+            .context(SaveUserSnafu);
+    });
+
+    assert_eq!(counters.get("user_save_failures", "SaveUser"), 1);
+    assert_eq!(counters.total(), 1);
+}
+
+#[test]
+fn from_conversions_increment_a_counter() {
+    let counters = record(|| {
+        let _ = Error::from("x".parse::<u8>().unwrap_err());
+    });
+
+    assert_eq!(counters.get("error", "Parse"), 1);
+}
+
+#[test]
+fn variants_use_the_name_given_on_the_enum() {
+    let counters = record(|| {
+        let _ = DiskFullSnafu.build();
+        let _ = DiskMissingSnafu.build();
+    });
+
+    assert_eq!(counters.get("storage_errors", "DiskFull"), 1);
+    assert_eq!(counters.get("storage_errors", "DiskMissing"), 1);
+}
+
+#[test]
+fn only_marked_variants_are_counted() {
+    let counters = record(|| {
+        let _ = CountedSnafu.build();
+        let _ = NotCountedSnafu.build();
+    });
+
+    assert_eq!(counters.get("partial_error", "Counted"), 1);
+    assert_eq!(counters.total(), 1);
+}
+
+#[test]
+fn structs_are_counted() {
+    let counters = record(|| {
+        let _ = TimeoutSnafu { seconds: 5u64 }.build();
+    });
+
+    assert_eq!(counters.get("timeout_error", "TimeoutError"), 1);
+}
+
+#[test]
+fn whatever_errors_are_counted() {
+    fn fail() -> Result<(), MyWhatever> {
+        whatever!("Something went wrong")
+    }
+
+    let counters = record(|| {
+        let _ = fail();
+    });
+
+    assert_eq!(counters.get("unexpected_errors", "MyWhatever"), 1);
+}
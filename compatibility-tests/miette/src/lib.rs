@@ -0,0 +1,139 @@
+#![cfg(test)]
+
+use miette::{Diagnostic, JSONReportHandler, SourceSpan};
+use snafu::{prelude::*, IntoError};
+use std::io;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(
+        display("Could not parse the configuration"),
+        code("config::parse"),
+        help("Check line {} of {path}", line),
+        url("https://example.com/errors/{}", line)
+    )]
+    Parse {
+        path: String,
+        line: usize,
+        #[snafu(label("the problem is here"))]
+        span: SourceSpan,
+        #[snafu(label("expected after `{}`", path))]
+        previous: (usize, usize),
+    },
+
+    #[snafu(display("Could not read the configuration"), code("config::read"))]
+    Read { source: io::Error },
+
+    #[snafu(display("Something else went wrong"))]
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(
+    display("Value {value} is out of range"),
+    help("Use a value below {}", max)
+)]
+struct RangeError<T>
+where
+    T: std::fmt::Display,
+{
+    value: T,
+    max: T,
+}
+
+fn parse_error() -> Error {
+    ParseSnafu {
+        path: "app.toml",
+        line: 42usize,
+        span: SourceSpan::from((10, 3)),
+        previous: (4, 1),
+    }
+    .build()
+}
+
+fn to_string(value: Option<Box<dyn std::fmt::Display + '_>>) -> Option<String> {
+    value.map(|v| v.to_string())
+}
+
+#[test]
+fn implements_diagnostic() {
+    fn check<T: Diagnostic>() {}
+    check::<Error>();
+    check::<RangeError<i32>>();
+}
+
+#[test]
+fn code_help_and_url_use_the_fields() {
+    let e = parse_error();
+
+    assert_eq!(to_string(e.code()).as_deref(), Some("config::parse"));
+    assert_eq!(
+        to_string(e.help()).as_deref(),
+        Some("Check line 42 of app.toml"),
+    );
+    assert_eq!(
+        to_string(e.url()).as_deref(),
+        Some("https://example.com/errors/42"),
+    );
+}
+
+#[test]
+fn labels_come_from_the_designated_fields() {
+    let e = parse_error();
+    let labels: Vec<_> = e.labels().unwrap().collect();
+
+    assert_eq!(labels.len(), 2);
+    assert_eq!(labels[0].label(), Some("the problem is here"));
+    assert_eq!(labels[0].offset(), 10);
+    assert_eq!(labels[0].len(), 3);
+    assert_eq!(labels[1].label(), Some("expected after `app.toml`"));
+    assert_eq!(labels[1].offset(), 4);
+    assert_eq!(labels[1].len(), 1);
+}
+
+#[test]
+fn variants_without_attributes_use_the_defaults() {
+    let e = ReadSnafu.into_error(io::Error::new(io::ErrorKind::Other, "Disk unavailable"));
+    assert_eq!(to_string(e.code()).as_deref(), Some("config::read"));
+    assert!(e.help().is_none());
+    assert!(e.url().is_none());
+    assert!(e.labels().is_none());
+
+    let e = UnknownSnafu.build();
+    assert!(e.code().is_none());
+    assert!(e.help().is_none());
+    assert!(e.labels().is_none());
+}
+
+#[test]
+fn structs_implement_diagnostic() {
+    let e: RangeError<i32> = RangeSnafu { value: 12, max: 10 }.build();
+
+    assert_eq!(to_string(e.help()).as_deref(), Some("Use a value below 10"));
+    assert!(e.code().is_none());
+}
+
+#[test]
+fn miette_handlers_render_the_diagnostic() {
+    let e = parse_error();
+    let mut rendered = String::new();
+    JSONReportHandler::new()
+        .render_report(&mut rendered, &e)
+        .unwrap();
+
+    assert!(
+        rendered.contains(r#""code": "config::parse""#),
+        "{}",
+        rendered
+    );
+    assert!(
+        rendered.contains(r#""help": "Check line 42 of app.toml""#),
+        "{}",
+        rendered
+    );
+    assert!(
+        rendered.contains(r#""label": "the problem is here""#),
+        "{}",
+        rendered
+    );
+}
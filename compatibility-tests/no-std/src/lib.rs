@@ -0,0 +1,183 @@
+//! Exercises SNAFU without the standard library or `alloc`, as
+//! firmware would. This crate must build for targets such as
+//! `thumbv6m-none-eabi`, which have neither.
+
+#![no_std]
+
+#[cfg(test)]
+extern crate std;
+
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub), kind, predicates, display_buffer(32))]
+pub enum Error {
+    #[snafu(display("Sensor {id} reported status {status:#04x}"))]
+    Sensor {
+        id: u8,
+        status: u8,
+    },
+
+    #[snafu(display("The bus timed out"))]
+    Timeout,
+
+    #[snafu(display("Could not read register {register}"))]
+    ReadRegister {
+        register: u16,
+        source: BusError,
+    },
+
+    MissingCalibration {
+        marker: core::marker::PhantomData<()>,
+    },
+
+    #[snafu(display("The watchdog fired"))]
+    Watchdog {
+        backtrace: snafu::TraceCell,
+    },
+
+    #[snafu(context(false))]
+    Bus {
+        source: BusError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The device at {address} did not acknowledge"))]
+pub struct BusError {
+    address: u8,
+}
+
+#[derive(Debug, Snafu)]
+pub struct DriverError(Error);
+
+fn transfer(address: u8) -> Result<u8, BusError> {
+    ensure!(address != 0, BusSnafu { address });
+    Ok(address)
+}
+
+pub fn read_register(register: u16) -> Result<u8, Error> {
+    let address = (register >> 8) as u8;
+    transfer(address).context(ReadRegisterSnafu { register })
+}
+
+pub fn read_sensor(id: u8, status: u8) -> Result<u8, Error> {
+    ensure!(status == 0, SensorSnafu { id, status });
+    let value = transfer(id)?;
+    Ok(value)
+}
+
+pub fn calibration(table: &[u8]) -> Result<u8, DriverError> {
+    let value = table.first().cloned().context(MissingCalibrationSnafu)?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::fmt::{self, Write};
+    use snafu::Error as _;
+
+    /// Formats into a fixed buffer, as there is no `String`.
+    struct Buffer {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl Buffer {
+        fn new() -> Self {
+            Buffer {
+                bytes: [0; 64],
+                len: 0,
+            }
+        }
+
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+        }
+    }
+
+    impl fmt::Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            let dest = self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?;
+            dest.copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    fn display(value: impl fmt::Display) -> Buffer {
+        let mut buffer = Buffer::new();
+        write!(buffer, "{}", value).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn display_is_formatted_from_fields() {
+        let error = read_sensor(7, 0x1f).unwrap_err();
+        assert_eq!(display(&error).as_str(), "Sensor 7 reported status 0x1f");
+        assert!(error.is_sensor());
+        assert_eq!(error.kind(), ErrorKind::Sensor);
+    }
+
+    #[test]
+    fn variants_without_a_display_use_their_name() {
+        let error = calibration(&[]).unwrap_err();
+        assert_eq!(display(&error).as_str(), "MissingCalibration");
+    }
+
+    #[test]
+    fn sources_are_available_without_the_standard_library() {
+        let error = read_register(0x0042).unwrap_err();
+        assert_eq!(display(&error).as_str(), "Could not read register 66");
+
+        let source = error.source().unwrap();
+        assert_eq!(
+            display(source).as_str(),
+            "The device at 0 did not acknowledge",
+        );
+    }
+
+    #[test]
+    fn messages_can_be_formatted_into_a_byte_buffer() {
+        let error = read_sensor(7, 0x1f).unwrap_err();
+        let mut bytes = [0; 13];
+        assert_eq!(error.format_into(&mut bytes), "Sensor 7 repo");
+    }
+
+    #[cfg(feature = "rust_1_81")]
+    #[test]
+    fn implements_the_core_error_trait() {
+        fn check<E: core::error::Error>() {}
+
+        check::<Error>();
+        check::<BusError>();
+        check::<DriverError>();
+    }
+
+    #[test]
+    fn trace_cells_are_empty_without_a_hook() {
+        match WatchdogSnafu.build() {
+            Error::Watchdog { backtrace } => assert!(backtrace.is_empty()),
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "rust_1_81")]
+    #[test]
+    fn generated_methods_are_const() {
+        const KIND: ErrorKind = Error::Timeout.kind();
+        const IS_TIMEOUT: bool = Error::Timeout.is_timeout();
+
+        assert_eq!(KIND, ErrorKind::Timeout);
+        assert!(IS_TIMEOUT);
+    }
+
+    #[test]
+    fn context_is_optional() {
+        let error = read_sensor(0, 0).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Bus);
+        assert_eq!(read_sensor(3, 0).unwrap(), 3);
+    }
+}
@@ -0,0 +1,153 @@
+#![cfg(test)]
+
+use opentelemetry::{
+    trace::{Status, Tracer, TracerProvider},
+    Key, Value,
+};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(opentelemetry)]
+enum Error {
+    #[snafu(display("User {id} does not exist"), opentelemetry(fields(id)))]
+    MissingUser { id: u32, name: String },
+
+    #[snafu(display("Could not read the configuration"))]
+    ReadConfiguration { source: std::io::Error },
+}
+
+#[derive(Debug, Snafu)]
+enum NotRecorded {
+    #[snafu(display("Checkout failed"))]
+    Checkout,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(
+    display("The cart {cart} is empty"),
+    opentelemetry(fields(cart, source))
+)]
+struct EmptyCartError {
+    cart: String,
+    source: std::io::Error,
+}
+
+fn in_span(f: impl FnOnce()) -> SpanData {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+
+    provider.tracer("test").in_span("request", |_cx| f());
+
+    let mut spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    spans.remove(0)
+}
+
+fn attribute<'a>(span: &'a SpanData, key: &'static str) -> Option<&'a Value> {
+    span.attributes
+        .iter()
+        .find(|kv| kv.key == Key::from_static_str(key))
+        .map(|kv| &kv.value)
+}
+
+#[test]
+fn constructing_an_error_records_it_on_the_active_span() {
+    let span = in_span(|| {
+        let _ = MissingUserSnafu {
+            id: 42u32,
+            name: "alice",
+        }
+        .build();
+    });
+
+    assert_eq!(span.status, Status::error("User 42 does not exist"));
+    assert_eq!(
+        attribute(&span, "exception.message"),
+        Some(&Value::from("User 42 does not exist")),
+    );
+    assert_eq!(
+        attribute(&span, "exception.type"),
+        Some(&Value::from("MissingUser")),
+    );
+}
+
+#[test]
+fn only_the_selected_fields_are_recorded() {
+    let span = in_span(|| {
+        let _ = MissingUserSnafu {
+            id: 42u32,
+            name: "alice",
+        }
+        .build();
+    });
+
+    assert_eq!(attribute(&span, "id"), Some(&Value::from("42")));
+    assert_eq!(attribute(&span, "name"), None);
+}
+
+#[test]
+fn variants_use_the_enum_default() {
+    let span = in_span(|| {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let _ = Err::<(), _>(io_error).context(ReadConfigurationSnafu);
+    });
+
+    assert_eq!(
+        attribute(&span, "exception.type"),
+        Some(&Value::from("ReadConfiguration")),
+    );
+}
+
+#[test]
+fn structs_can_record_their_source() {
+    let span = in_span(|| {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let _ = Err::<(), _>(io_error).context(EmptyCartSnafu { cart: "c1" });
+    });
+
+    assert_eq!(
+        attribute(&span, "exception.type"),
+        Some(&Value::from("EmptyCartError")),
+    );
+    assert_eq!(attribute(&span, "cart"), Some(&Value::from("c1")));
+    assert_eq!(attribute(&span, "source"), Some(&Value::from("gone")));
+}
+
+#[test]
+fn errors_without_the_attribute_are_not_recorded() {
+    let span = in_span(|| {
+        let _ = CheckoutSnafu.build();
+    });
+
+    assert_eq!(span.status, Status::Unset);
+    assert_eq!(attribute(&span, "exception.message"), None);
+}
+
+#[test]
+fn errors_can_be_recorded_explicitly() {
+    let span = in_span(|| {
+        snafu::opentelemetry::record_error(&CheckoutSnafu.build());
+    });
+
+    assert_eq!(span.status, Status::error("Checkout failed"));
+    assert!(matches!(
+        attribute(&span, "exception.type"),
+        Some(Value::String(s)) if s.as_str().ends_with("NotRecorded"),
+    ));
+}
+
+#[test]
+fn nothing_is_recorded_without_an_active_span() {
+    let _ = MissingUserSnafu {
+        id: 42u32,
+        name: "alice",
+    }
+    .build();
+
+    let span = in_span(|| {});
+    assert_eq!(span.status, Status::Unset);
+    assert!(span.attributes.is_empty());
+}
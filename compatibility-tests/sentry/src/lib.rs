@@ -0,0 +1,117 @@
+#![cfg(test)]
+
+use sentry_core::test::with_captured_events;
+use snafu::{
+    prelude::*,
+    sentry::{event_from_error, event_from_serializable, SentryResultExt},
+    Backtrace, SerializableError,
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The disk is full"))]
+struct DiskFullError;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not save {name}"))]
+    Save { name: String, source: DiskFullError },
+
+    #[snafu(display("Could not connect"))]
+    Connect { backtrace: Backtrace },
+}
+
+fn save_error() -> Error {
+    DiskFullSnafu
+        .fail::<()>()
+        .context(SaveSnafu { name: "a.txt" })
+        .unwrap_err()
+}
+
+#[test]
+fn events_include_the_chain_of_sources() {
+    let event = event_from_error(&save_error());
+
+    let exceptions: Vec<_> = event
+        .exception
+        .iter()
+        .map(|e| (e.ty.as_str(), e.value.as_deref()))
+        .collect();
+    assert_eq!(
+        exceptions,
+        [
+            ("DiskFullError", Some("The disk is full")),
+            ("Save", Some("Could not save a.txt")),
+        ],
+    );
+}
+
+#[test]
+fn events_include_the_backtrace() {
+    let event = event_from_error(&ConnectSnafu.build());
+    assert!(event.extra.contains_key("backtrace"));
+
+    let event = event_from_error(&save_error());
+    assert!(!event.extra.contains_key("backtrace"));
+}
+
+#[test]
+fn serializable_errors_include_the_variant_code_and_fields() {
+    let error = SerializableError::from_error(&save_error())
+        .with_variant("Save")
+        .with_code("E42")
+        .with_field("name", "a.txt");
+    let event = event_from_serializable(&error);
+
+    let exceptions: Vec<_> = event
+        .exception
+        .iter()
+        .map(|e| (e.ty.as_str(), e.value.as_deref()))
+        .collect();
+    assert_eq!(
+        exceptions,
+        [
+            ("Error", Some("The disk is full")),
+            ("Save", Some("Could not save a.txt")),
+        ],
+    );
+    assert_eq!(event.tags["code"], "E42");
+    assert_eq!(event.extra["name"], "a.txt");
+}
+
+#[test]
+fn capture_context_reports_the_new_error() {
+    let events = with_captured_events(|| {
+        let result = DiskFullSnafu
+            .fail::<()>()
+            .capture_context(SaveSnafu { name: "a.txt" });
+        assert!(matches!(result, Err(Error::Save { .. })));
+    });
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].exception.len(), 2);
+}
+
+#[test]
+fn with_capture_context_reports_the_new_error() {
+    let events = with_captured_events(|| {
+        let result = DiskFullSnafu
+            .fail::<()>()
+            .with_capture_context(|| SaveSnafu { name: "a.txt" });
+        assert!(result.is_err());
+    });
+
+    assert_eq!(events.len(), 1);
+}
+
+#[test]
+fn capture_err_reports_the_error_unchanged() {
+    let events = with_captured_events(|| {
+        let result = Err::<(), _>(save_error()).capture_err();
+        assert!(matches!(result, Err(Error::Save { .. })));
+
+        let result = Ok::<(), Error>(()).capture_err();
+        assert!(result.is_ok());
+    });
+
+    assert_eq!(events.len(), 1);
+}
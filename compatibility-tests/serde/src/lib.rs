@@ -0,0 +1,215 @@
+#![cfg(test)]
+
+use snafu::{prelude::*, FieldValue, SerializableError, Whatever};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("The disk is full"))]
+    DiskFull,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not save the document"))]
+    SaveDocument { source: InnerError },
+}
+
+fn save_document() -> Result<(), Error> {
+    DiskFullSnafu.fail().context(SaveDocumentSnafu)
+}
+
+fn serializable() -> SerializableError {
+    SerializableError::from_error(&save_document().unwrap_err())
+        .with_variant("SaveDocument")
+        .with_code("E001")
+}
+
+#[test]
+fn serializes_to_json() {
+    let json = serde_json::to_value(serializable()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "variant": "SaveDocument",
+            "code": "E001",
+            "message": "Could not save the document",
+            "chain": ["The disk is full"],
+        }),
+    );
+}
+
+#[test]
+fn round_trips_through_json() {
+    let original = serializable();
+    let json = serde_json::to_string(&original).unwrap();
+    let restored: SerializableError = serde_json::from_str(&json).unwrap();
+    assert_eq!(original, restored);
+}
+
+#[test]
+fn optional_parts_may_be_omitted() {
+    let restored: SerializableError = serde_json::from_str(r#"{ "message": "Oh no" }"#).unwrap();
+    assert_eq!(restored, SerializableError::new("Oh no"));
+}
+
+#[test]
+fn schema_describes_the_serialized_form() {
+    let schema = serde_json::to_value(schemars::schema_for!(SerializableError)).unwrap();
+
+    let properties = schema["properties"].as_object().unwrap();
+    let mut names: Vec<_> = properties.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        ["backtrace", "chain", "code", "fields", "message", "variant"],
+    );
+
+    assert_eq!(schema["required"], serde_json::json!(["message"]));
+}
+
+fn with_fields() -> SerializableError {
+    fn connect() -> Result<(), Whatever> {
+        whatever!(
+            "Failed to connect",
+            host = %"example.com",
+            attempt = 3u8,
+            offset = -2,
+            ratio = 0.25,
+            secure = true,
+        );
+    }
+
+    let error = connect().unwrap_err();
+    SerializableError::from_error(&error).with_fields(error.fields())
+}
+
+#[test]
+fn serializes_fields_to_json() {
+    let json = serde_json::to_value(with_fields()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "variant": null,
+            "code": null,
+            "message": "Failed to connect",
+            "chain": [],
+            "fields": {
+                "attempt": 3,
+                "host": "example.com",
+                "offset": -2,
+                "ratio": 0.25,
+                "secure": true,
+            },
+        }),
+    );
+}
+
+#[test]
+fn round_trips_fields_through_json() {
+    let original = with_fields();
+    let json = serde_json::to_string(&original).unwrap();
+    let restored: SerializableError = serde_json::from_str(&json).unwrap();
+    assert_eq!(original, restored);
+    assert_eq!(restored.fields()["offset"], FieldValue::I64(-2));
+}
+
+#[test]
+fn deserialized_errors_have_sources() {
+    use std::error::Error as _;
+
+    let json = r#"{ "message": "Request failed", "chain": ["Timed out", "Connection reset"] }"#;
+    let restored: SerializableError = serde_json::from_str(json).unwrap();
+
+    let source = restored.source().unwrap();
+    assert_eq!(source.to_string(), "Timed out");
+    let source = source.source().unwrap();
+    assert_eq!(source.to_string(), "Connection reset");
+    assert!(source.source().is_none());
+}
+
+mod derived {
+    use snafu::{prelude::*, Backtrace, SerializableError};
+
+    #[derive(Debug, Snafu)]
+    #[snafu(serialize)]
+    enum Error {
+        #[snafu(display("Could not open {}", path))]
+        Open {
+            path: String,
+            attempt: u8,
+            source: super::InnerError,
+        },
+
+        #[snafu(display("Invalid {}", r#type))]
+        Invalid {
+            r#type: &'static str,
+        },
+
+        Unit,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(serialize(backtrace))]
+    enum WithBacktrace {
+        Captured { backtrace: Backtrace },
+
+        Missing,
+    }
+
+    #[test]
+    fn serializes_the_variant_message_fields_and_chain() {
+        let error = super::DiskFullSnafu
+            .fail::<()>()
+            .context(OpenSnafu {
+                path: "a.txt",
+                attempt: 2u8,
+            })
+            .unwrap_err();
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "variant": "Open",
+                "code": null,
+                "message": "Could not open a.txt",
+                "chain": ["The disk is full"],
+                "fields": {
+                    "path": r#""a.txt""#,
+                    "attempt": "2",
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn raw_identifiers_are_not_included_in_field_names() {
+        let serializable = InvalidSnafu { r#type: "user" }.build().to_serializable();
+        assert_eq!(serializable.fields()["type"], r#""user""#.into());
+    }
+
+    #[test]
+    fn variants_without_fields_have_no_fields() {
+        let serializable = UnitSnafu.build().to_serializable();
+        assert_eq!(serializable.variant(), Some("Unit"));
+        assert!(serializable.fields().is_empty());
+        assert_eq!(serializable.backtrace(), None);
+    }
+
+    #[test]
+    fn the_backtrace_can_be_included() {
+        let serializable = CapturedSnafu.build().to_serializable();
+        assert!(serializable.backtrace().is_some());
+
+        let serializable = MissingSnafu.build().to_serializable();
+        assert_eq!(serializable.backtrace(), None);
+    }
+
+    #[test]
+    fn serialized_errors_can_be_deserialized() {
+        let error = UnitSnafu.build();
+        let json = serde_json::to_string(&error).unwrap();
+        let restored: SerializableError = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, error.to_serializable());
+    }
+}
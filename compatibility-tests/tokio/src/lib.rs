@@ -0,0 +1,144 @@
+#![cfg(test)]
+
+use snafu::{ErrorCompat, Snafu};
+use std::time::Duration;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("The background job failed"))]
+    BackgroundJob { source: snafu::tokio::TaskError },
+}
+
+#[tokio::test]
+async fn successful_tasks_return_their_output() {
+    let value = snafu::tokio::spawn(async { 42 }).await.unwrap();
+    assert_eq!(value, 42);
+}
+
+#[tokio::test]
+async fn panicking_tasks_report_where_they_were_spawned() {
+    let line = line!() + 1;
+    let handle = snafu::tokio::spawn(async { panic!("Oh no") });
+    assert_eq!(handle.location().line(), line);
+
+    let error = handle.await.unwrap_err();
+    assert!(error.is_panic());
+    assert_eq!(error.location().file(), file!());
+    assert_eq!(error.location().line(), line);
+    assert_eq!(
+        error.to_string(),
+        format!("The task spawned at {}:{}:18 failed", file!(), line),
+    );
+}
+
+#[tokio::test]
+async fn cancelled_tasks_report_where_they_were_spawned() {
+    let handle = snafu::tokio::spawn(tokio::time::sleep(Duration::from_secs(60)));
+    handle.abort();
+
+    let error = handle.await.unwrap_err();
+    assert!(error.is_cancelled());
+}
+
+#[tokio::test]
+async fn blocking_tasks_report_where_they_were_spawned() {
+    let line = line!() + 1;
+    let error = snafu::tokio::spawn_blocking(|| panic!("Oh no"))
+        .await
+        .unwrap_err();
+    assert!(error.is_panic());
+    assert_eq!(error.location().line(), line);
+}
+
+#[tokio::test]
+async fn can_be_used_as_a_source() {
+    use snafu::ResultExt;
+
+    let error = snafu::tokio::spawn(async { panic!("Oh no") })
+        .await
+        .context(BackgroundJobSnafu)
+        .unwrap_err();
+    assert_eq!(error.iter_chain().count(), 3);
+}
+
+mod deadline {
+    use snafu::{prelude::*, tokio::DeadlineError, ErrorCompat};
+    use std::{io, time::Duration};
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        #[snafu(display("Could not fetch {}", name))]
+        Fetch {
+            name: String,
+            source: DeadlineError<io::Error>,
+        },
+    }
+
+    async fn fetch(name: &str, result: io::Result<i32>, delay: Duration) -> Result<i32, Error> {
+        let operation = async move {
+            tokio::time::sleep(delay).await;
+            result
+        };
+        snafu::tokio::with_deadline(Duration::from_millis(50), FetchSnafu { name }, operation).await
+    }
+
+    #[tokio::test]
+    async fn values_completed_in_time_are_returned() {
+        let value = fetch("a", Ok(42), Duration::from_millis(0)).await.unwrap();
+        assert_eq!(value, 42);
+    }
+
+    #[tokio::test]
+    async fn errors_completed_in_time_are_wrapped() {
+        let result = Err(io::Error::new(io::ErrorKind::Other, "boom"));
+        let error = fetch("a", result, Duration::from_millis(0))
+            .await
+            .unwrap_err();
+
+        let Error::Fetch { name, source } = &error;
+        assert_eq!(name, "a");
+        assert!(!source.is_elapsed());
+        assert_eq!(error.iter_chain().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn elapsed_deadlines_are_wrapped() {
+        let error = fetch("a", Ok(42), Duration::from_secs(60))
+            .await
+            .unwrap_err();
+
+        let Error::Fetch { source, .. } = &error;
+        assert!(source.is_elapsed());
+        assert_eq!(source.to_string(), "The deadline of 50ms elapsed");
+    }
+}
+
+mod report {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        #[snafu(display("Could not parse the number"))]
+        Parse { source: std::num::ParseIntError },
+    }
+
+    async fn parse(text: &str) -> Result<i32, std::num::ParseIntError> {
+        text.parse()
+    }
+
+    #[tokio::test]
+    #[snafu::report]
+    async fn attribute_can_follow_the_runtime_attribute() -> Result<(), Error> {
+        let value = parse("42").await.context(ParseSnafu)?;
+        assert_eq!(value, 42);
+        Ok(())
+    }
+
+    #[snafu::report]
+    #[tokio::test]
+    async fn attribute_can_precede_the_runtime_attribute() -> Result<(), Error> {
+        let value = parse("42").await.context(ParseSnafu)?;
+        assert_eq!(value, 42);
+        Ok(())
+    }
+}
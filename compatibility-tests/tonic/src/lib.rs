@@ -0,0 +1,171 @@
+#![cfg(test)]
+
+use snafu::prelude::*;
+use tonic::{Code, Status};
+
+mod database {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(display("The database is unavailable"), grpc(code = Unavailable))]
+        Unavailable,
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(grpc(code = InvalidArgument))]
+enum Error {
+    #[snafu(
+        display("User {} does not exist", id),
+        grpc(code = NotFound, metadata(id, region))
+    )]
+    MissingUser {
+        id: u32,
+        region: String,
+        internal: u64,
+    },
+
+    #[snafu(display("The name {:?} is invalid", name))]
+    InvalidName { name: String },
+
+    #[snafu(flatten)]
+    Database { source: database::Error },
+
+    #[snafu(display("Could not store the user"), grpc(code = Aborted))]
+    Store { source: database::Error },
+}
+
+#[derive(Debug, Snafu)]
+enum WithoutDefault {
+    #[snafu(display("Slow down"), grpc(code = ResourceExhausted))]
+    Throttled,
+
+    #[snafu(display("Something else"))]
+    Other,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(
+    display("Permission denied for {}", user),
+    grpc(code = PermissionDenied, metadata(user))
+)]
+struct PermissionError {
+    user: String,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Invalid value {}", value), grpc(code = InvalidArgument, metadata(value)))]
+struct GenericError<T: std::fmt::Display> {
+    value: T,
+}
+
+#[test]
+fn variants_use_their_code_and_message() {
+    let status = Status::from(
+        MissingUserSnafu {
+            id: 42u32,
+            region: "eu",
+            internal: 7u64,
+        }
+        .build(),
+    );
+
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(status.message(), "User 42 does not exist");
+}
+
+#[test]
+fn selected_fields_are_included_as_metadata() {
+    let status = Status::from(
+        MissingUserSnafu {
+            id: 42u32,
+            region: "eu",
+            internal: 7u64,
+        }
+        .build(),
+    );
+
+    let metadata = status.metadata();
+    assert_eq!(metadata.get("id").unwrap(), "42");
+    assert_eq!(metadata.get("region").unwrap(), "eu");
+    assert!(metadata.get("internal").is_none());
+}
+
+#[test]
+fn values_that_are_not_valid_metadata_are_skipped() {
+    let status = Status::from(
+        MissingUserSnafu {
+            id: 42u32,
+            region: "line\nbreak",
+            internal: 7u64,
+        }
+        .build(),
+    );
+
+    assert_eq!(status.metadata().get("id").unwrap(), "42");
+    assert!(status.metadata().get("region").is_none());
+}
+
+#[test]
+fn variants_without_a_code_use_the_enum_default() {
+    let status = Status::from(InvalidNameSnafu { name: "" }.build());
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert!(status.metadata().is_empty());
+}
+
+#[test]
+fn variants_without_a_code_or_default_are_internal_errors() {
+    assert_eq!(ThrottledSnafu.build().grpc_code(), Code::ResourceExhausted);
+    assert_eq!(OtherSnafu.build().grpc_code(), Code::Internal);
+}
+
+#[test]
+fn flattened_variants_use_the_code_of_their_source() {
+    let status = Status::from(Error::from(database::UnavailableSnafu.build()));
+    assert_eq!(status.code(), Code::Unavailable);
+    assert_eq!(status.message(), "The database is unavailable");
+}
+
+#[test]
+fn variants_with_a_source_use_their_own_code() {
+    let error = Err::<(), _>(database::UnavailableSnafu.build())
+        .context(StoreSnafu)
+        .unwrap_err();
+    assert_eq!(error.grpc_code(), Code::Aborted);
+}
+
+#[test]
+fn structs_are_converted() {
+    let status = Status::from(PermissionSnafu { user: "alice" }.build());
+    assert_eq!(status.code(), Code::PermissionDenied);
+    assert_eq!(status.message(), "Permission denied for alice");
+    assert_eq!(status.metadata().get("user").unwrap(), "alice");
+}
+
+#[test]
+fn generic_structs_are_converted() {
+    let error: GenericError<f64> = GenericSnafu { value: 3.5 }.build();
+    let status = Status::from(error);
+    assert_eq!(status.metadata().get("value").unwrap(), "3.5");
+}
+
+#[test]
+fn errors_can_be_returned_from_services() {
+    fn lookup(id: u32) -> Result<(), Error> {
+        MissingUserSnafu {
+            id,
+            region: "us",
+            internal: 0u64,
+        }
+        .fail()
+    }
+
+    fn service() -> Result<(), Status> {
+        lookup(1)?;
+        Ok(())
+    }
+
+    assert_eq!(service().unwrap_err().code(), Code::NotFound);
+}
@@ -0,0 +1,319 @@
+#![cfg(test)]
+
+use snafu::{prelude::*, CurrentSpan, SpanId};
+use tracing_subscriber::Registry;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not load user {}", id))]
+    LoadUser { id: u32, span_id: snafu::SpanId },
+
+    #[snafu(display("Could not save the user"))]
+    SaveUser {
+        source: std::io::Error,
+        span_id: snafu::SpanId,
+    },
+}
+
+fn span_id(error: &Error) -> SpanId {
+    match error {
+        Error::LoadUser { span_id, .. } | Error::SaveUser { span_id, .. } => *span_id,
+    }
+}
+
+fn with_subscriber<T>(f: impl FnOnce() -> T) -> T {
+    tracing::subscriber::with_default(Registry::default(), f)
+}
+
+#[test]
+fn records_the_current_span() {
+    with_subscriber(|| {
+        let span = tracing::info_span!("request");
+        let error = span.in_scope(|| LoadUserSnafu { id: 42u32 }.build());
+
+        let expected = span.id().map(|id| id.into_u64());
+        assert!(expected.is_some());
+        assert_eq!(span_id(&error).get(), expected);
+    });
+}
+
+#[test]
+fn records_the_innermost_span() {
+    with_subscriber(|| {
+        let outer = tracing::info_span!("outer");
+        let _outer = outer.enter();
+        let inner = tracing::info_span!("inner");
+
+        let error = inner.in_scope(|| {
+            Err::<(), _>(std::io::Error::from(std::io::ErrorKind::Other))
+                .context(SaveUserSnafu)
+                .unwrap_err()
+        });
+
+        assert_eq!(span_id(&error).get(), inner.id().map(|id| id.into_u64()));
+    });
+}
+
+#[test]
+fn is_empty_outside_of_a_span() {
+    with_subscriber(|| {
+        let error = LoadUserSnafu { id: 42u32 }.build();
+        assert_eq!(span_id(&error).get(), None);
+        assert_eq!(span_id(&error).to_string(), "none");
+    });
+}
+
+mod current_span {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Could not load user {}", id))]
+    struct Error {
+        id: u32,
+        span: snafu::CurrentSpan,
+    }
+
+    #[test]
+    fn records_the_metadata_of_the_current_span() {
+        with_subscriber(|| {
+            let span = tracing::info_span!("request", user = 42, path = "/");
+            let error = span.in_scope(|| Snafu { id: 42u32 }.build());
+
+            let captured: CurrentSpan = error.span;
+            assert_eq!(captured.id(), span.id().map(|id| id.into_u64()));
+            assert_eq!(captured.name(), Some("request"));
+            assert_eq!(captured.target(), Some(module_path!()));
+            assert_eq!(captured.to_string(), format!("{}: request", module_path!()));
+
+            let metadata = captured.metadata().expect("Must have metadata");
+            assert_eq!(*metadata.level(), tracing::Level::INFO);
+            let fields: Vec<_> = metadata.fields().iter().map(|f| f.name()).collect();
+            assert_eq!(fields, ["user", "path"]);
+        });
+    }
+
+    #[test]
+    fn is_empty_outside_of_a_span() {
+        with_subscriber(|| {
+            let error = Snafu { id: 42u32 }.build();
+            assert_eq!(error.span.id(), None);
+            assert!(error.span.metadata().is_none());
+            assert_eq!(error.span.to_string(), "none");
+        });
+    }
+}
+
+mod events {
+    use snafu::{prelude::*, Whatever};
+    use std::{
+        fmt,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        Event, Level, Subscriber,
+    };
+    use tracing_subscriber::{
+        layer::{Context, SubscriberExt},
+        Layer, Registry,
+    };
+
+    #[derive(Debug, Snafu)]
+    #[snafu(tracing(level = "warn"))]
+    enum Error {
+        #[snafu(display("Could not load user {}", id))]
+        LoadUser { id: u32, name: String },
+
+        #[snafu(display("Could not save the user"), tracing(level = "error"))]
+        SaveUser { source: std::io::Error },
+
+        #[snafu(context(false), display("Could not parse"))]
+        Parse { source: std::num::ParseIntError },
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Timed out after {} seconds", seconds), tracing)]
+    struct TimeoutError {
+        seconds: u64,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(whatever, display("{message}"), tracing(level = "info"))]
+    struct MyWhatever {
+        message: String,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(tracing(level = "debug"))]
+    enum GenericError<T> {
+        #[snafu(display("Invalid value"))]
+        Invalid { value: T },
+    }
+
+    #[derive(Debug, Snafu)]
+    enum Untraced {
+        #[snafu(display("Not traced"))]
+        Quiet,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Recorded {
+        level: Option<Level>,
+        fields: Vec<(String, String)>,
+    }
+
+    impl Visit for Recorded {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.fields
+                .push((field.name().to_string(), format!("{:?}", value)));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields
+                .push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<Recorded>>>);
+
+    impl<S: Subscriber> Layer<S> for Recorder {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let mut recorded = Recorded {
+                level: Some(*event.metadata().level()),
+                ..Recorded::default()
+            };
+            event.record(&mut recorded);
+            self.0.lock().unwrap().push(recorded);
+        }
+    }
+
+    fn record(f: impl FnOnce()) -> Vec<Recorded> {
+        let recorder = Recorder::default();
+        let subscriber = Registry::default().with(recorder.clone());
+        tracing::subscriber::with_default(subscriber, f);
+        let events = recorder.0.lock().unwrap();
+        events.clone()
+    }
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn building_an_error_emits_an_event() {
+        let events = record(|| {
+            let _ = LoadUserSnafu {
+                id: 42u32,
+                name: "alice",
+            }
+            .build();
+        });
+
+        assert_eq!(
+            events,
+            [Recorded {
+                level: Some(Level::WARN),
+                fields: fields(&[
+                    ("message", "Could not load user 42"),
+                    ("variant", "LoadUser"),
+                    ("id", "42"),
+                    ("name", "\"alice\""),
+                ]),
+            }],
+        );
+    }
+
+    #[test]
+    fn variants_can_override_the_level() {
+        let events = record(|| {
+            let _ = Err::<(), _>(std::io::Error::new(std::io::ErrorKind::Other, "disk"))
+                .context(SaveUserSnafu);
+        });
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, Some(Level::ERROR));
+        assert_eq!(
+            events[0].fields,
+            fields(&[
+                ("message", "Could not save the user"),
+                ("variant", "SaveUser"),
+            ]),
+        );
+    }
+
+    #[test]
+    fn from_conversions_emit_an_event() {
+        let events = record(|| {
+            let _ = Error::from("x".parse::<u8>().unwrap_err());
+        });
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].fields[1], ("variant".into(), "Parse".into()));
+    }
+
+    #[test]
+    fn structs_default_to_warn() {
+        let events = record(|| {
+            let _ = TimeoutSnafu { seconds: 5u64 }.build();
+        });
+
+        assert_eq!(
+            events,
+            [Recorded {
+                level: Some(Level::WARN),
+                fields: fields(&[
+                    ("message", "Timed out after 5 seconds"),
+                    ("variant", "TimeoutError"),
+                    ("seconds", "5"),
+                ]),
+            }],
+        );
+    }
+
+    #[test]
+    fn whatever_errors_emit_an_event() {
+        fn fail() -> Result<(), MyWhatever> {
+            whatever!("Something went wrong with {}", 42)
+        }
+
+        let events = record(|| {
+            let _ = fail();
+        });
+
+        assert_eq!(
+            events,
+            [Recorded {
+                level: Some(Level::INFO),
+                fields: fields(&[
+                    ("message", "Something went wrong with 42"),
+                    ("variant", "MyWhatever"),
+                ]),
+            }],
+        );
+    }
+
+    #[test]
+    fn generic_errors_emit_an_event() {
+        let events = record(|| {
+            let _: GenericError<Vec<u8>> = InvalidSnafu { value: vec![1u8] }.build();
+        });
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, Some(Level::DEBUG));
+        assert_eq!(events[0].fields[2], ("value".into(), "[1]".into()));
+    }
+
+    #[test]
+    fn errors_without_the_attribute_are_silent() {
+        let events = record(|| {
+            let _ = QuietSnafu.build();
+            let _: Whatever = snafu::FromString::without_source("quiet".into());
+        });
+
+        assert!(events.is_empty());
+    }
+}
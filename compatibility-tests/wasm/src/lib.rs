@@ -0,0 +1,71 @@
+#![cfg(test)]
+
+// Calling into JavaScript panics when not running in a WebAssembly
+// host, so these tests only check that the conversions exist.
+
+use snafu::prelude::*;
+use std::fmt::Debug;
+use wasm_bindgen::JsValue;
+
+#[derive(Debug, Snafu)]
+#[snafu(wasm)]
+enum Error {
+    #[snafu(display("Could not load user {id}"))]
+    LoadUser { id: u32, source: LowLevelError },
+
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(wasm, display("The database is unavailable"))]
+struct LowLevelError;
+
+#[derive(Debug, Snafu)]
+#[snafu(wasm)]
+struct OpaqueError(Error);
+
+#[derive(Debug, Snafu)]
+#[snafu(wasm)]
+enum GenericError<T>
+where
+    T: Debug,
+{
+    #[snafu(display("Invalid value {value:?}"))]
+    InvalidValue { value: T },
+}
+
+fn converts_to_js_value<E>()
+where
+    JsValue: From<E>,
+{
+}
+
+#[test]
+fn enums_convert_to_js_values() {
+    converts_to_js_value::<Error>();
+}
+
+#[test]
+fn structs_convert_to_js_values() {
+    converts_to_js_value::<LowLevelError>();
+    converts_to_js_value::<OpaqueError>();
+}
+
+#[test]
+fn generic_enums_convert_to_js_values() {
+    converts_to_js_value::<GenericError<i32>>();
+
+    let e = InvalidValueSnafu { value: 1 }.build();
+    let _: &GenericError<i32> = &e;
+}
+
+#[test]
+fn functions_can_return_js_values() {
+    fn exported() -> Result<(), JsValue> {
+        LowLevelSnafu.fail().context(LoadUserSnafu { id: 42u32 })?;
+        UnknownSnafu.fail()?;
+        Ok(())
+    }
+
+    let _ = exported;
+}
@@ -0,0 +1,110 @@
+#![cfg(test)]
+
+use snafu::{prelude::*, windows::WindowsError, ErrorCompat};
+use windows_core::{Error as WinError, HRESULT};
+
+const ERROR_FILE_NOT_FOUND: u32 = 2;
+const E_NOTIMPL: HRESULT = HRESULT(0x8000_4001_u32 as i32);
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not open {path}"))]
+    Open {
+        path: String,
+        #[snafu(source(from(WinError, WindowsError::from)))]
+        source: WindowsError,
+    },
+
+    #[snafu(display("Could not query the interface"))]
+    Query { source: WinError },
+
+    #[snafu(display("Could not start the service"))]
+    Start { source: Box<Error> },
+
+    #[snafu(display("Could not read the file"))]
+    Read { source: std::io::Error },
+
+    #[snafu(display("The service is not installed"))]
+    NotInstalled,
+}
+
+fn open(path: &str) -> Result<(), Error> {
+    let code = HRESULT::from_win32(ERROR_FILE_NOT_FOUND);
+    Err(WinError::from_hresult(code)).context(OpenSnafu { path })
+}
+
+#[test]
+fn source_transformation_keeps_the_code() {
+    let error = open("C:\\config.toml").unwrap_err();
+
+    match &error {
+        Error::Open { source, .. } => {
+            assert_eq!(source.code(), HRESULT::from_win32(ERROR_FILE_NOT_FOUND));
+            assert_eq!(source.win32_error(), Some(ERROR_FILE_NOT_FOUND));
+            assert_eq!(source.get_ref().code(), source.code());
+        }
+        other => panic!("Unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn hresult_is_found_in_the_chain() {
+    let error = open("C:\\config.toml")
+        .map_err(Box::new)
+        .context(StartSnafu)
+        .unwrap_err();
+
+    assert_eq!(
+        error.hresult(),
+        Some(HRESULT::from_win32(ERROR_FILE_NOT_FOUND))
+    );
+}
+
+#[test]
+fn hresult_is_found_for_untransformed_errors() {
+    let error = Err::<(), _>(WinError::from_hresult(E_NOTIMPL))
+        .context(QuerySnafu)
+        .unwrap_err();
+
+    assert_eq!(error.hresult(), Some(E_NOTIMPL));
+    assert_eq!(snafu::windows::win32_error(E_NOTIMPL), None);
+}
+
+#[test]
+fn hresult_is_none_without_a_windows_error() {
+    assert_eq!(NotInstalledSnafu.build().hresult(), None);
+}
+
+#[test]
+fn hresult_includes_the_error_itself() {
+    let error = WindowsError::from(E_NOTIMPL);
+    assert_eq!(snafu::windows::hresult(&error), Some(E_NOTIMPL));
+}
+
+#[test]
+fn display_matches_the_original_error() {
+    let original = WinError::from_hresult(E_NOTIMPL);
+    let error = WindowsError::from(original.clone());
+    assert_eq!(error.to_string(), original.to_string());
+}
+
+#[cfg(windows)]
+#[test]
+fn io_errors_provide_win32_codes() {
+    let io = std::io::Error::from_raw_os_error(ERROR_FILE_NOT_FOUND as i32);
+    let error = Err::<(), _>(io).context(ReadSnafu).unwrap_err();
+
+    assert_eq!(
+        error.hresult(),
+        Some(HRESULT::from_win32(ERROR_FILE_NOT_FOUND))
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn io_errors_do_not_provide_codes_on_other_platforms() {
+    let io = std::io::Error::from_raw_os_error(2);
+    let error = Err::<(), _>(io).context(ReadSnafu).unwrap_err();
+
+    assert_eq!(error.hresult(), None);
+}
@@ -1,3 +1,5 @@
+// The parsed representations are short-lived; boxing them would only add noise.
+#![allow(clippy::large_enum_variant)]
 #![recursion_limit = "128"] // https://github.com/rust-lang/rust/issues/62059
 
 extern crate proc_macro;
@@ -9,6 +11,7 @@ use std::collections::VecDeque;
 use std::fmt;
 
 mod parse;
+mod report;
 mod shared;
 
 // The snafu crate re-exports this and adds useful documentation.
@@ -19,6 +22,16 @@ pub fn snafu_derive(input: TokenStream) -> TokenStream {
     impl_snafu_macro(ast)
 }
 
+// The snafu crate re-exports this and adds useful documentation.
+#[proc_macro_attribute]
+pub fn report(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item as syn::ItemFn);
+
+    report::body(attr.into(), item)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 type MultiSynResult<T> = std::result::Result<T, Vec<syn::Error>>;
 
 /// Some arbitrary tokens we treat as a black box
@@ -36,15 +49,131 @@ struct EnumInfo {
     generics: syn::Generics,
     variants: Vec<FieldContainer>,
     default_visibility: UserInput,
+    visibility: syn::Visibility,
+    kind: bool,
+    predicates: bool,
+    accessors: bool,
+    fields: bool,
+    /// Every variant is added to the error catalog
+    catalog: bool,
+    skip_display: bool,
+    /// The size of the buffer the message is formatted into
+    display_buffer: Option<usize>,
+    stable_debug: bool,
+    dedup: bool,
+    report_conversions: bool,
+    /// `Some(true)` when the backtrace is included
+    serialize: Option<bool>,
+    termination: bool,
+    wasm: bool,
+    /// The default level of the event emitted when an error is
+    /// constructed
+    tracing: Option<syn::Ident>,
+    /// The default `log` record written when an error is constructed
+    log: Option<Log>,
+    /// The default counter incremented when an error is constructed
+    metric: Option<Metric>,
+    /// Errors are recorded on the active OpenTelemetry span when
+    /// constructed
+    opentelemetry: bool,
+    /// The HTTP status code of variants without one
+    http_status: Option<u16>,
+    /// The C error code of variants without one
+    c_code: Option<i32>,
+    /// The gRPC status code of variants without one
+    grpc: Option<Grpc>,
+    /// The name of the `std::io::ErrorKind` variant of variants
+    /// without one
+    io_kind: Option<syn::Ident>,
+    /// Whether constructing errors is outlined and marked cold for
+    /// variants without their own setting
+    cold: Option<bool>,
+    strip_suffixes: Vec<String>,
+    conversions: Vec<Conversion>,
 }
 
 struct FieldContainer {
     name: syn::Ident,
+    /// Every field, in the order they were declared
+    fields: Vec<Field>,
     backtrace_field: Option<Field>,
+    implicit_fields: Vec<Field>,
+    metadata_field: Option<Field>,
+    exit_code: Option<u8>,
     selector_kind: ContextSelectorKind,
     display_format: Option<UserInput>,
+    display_internal_format: Option<UserInput>,
+    diagnostic: Diagnostic,
     doc_comment: String,
     visibility: Option<UserInput>,
+    /// The level of the event emitted when this error is constructed
+    tracing: Option<syn::Ident>,
+    /// The `log` record written when this error is constructed
+    log: Option<Log>,
+    /// The counter incremented when this error is constructed
+    metric: Option<Metric>,
+    /// How this error is recorded on the active OpenTelemetry span
+    /// when constructed
+    opentelemetry: Option<OpenTelemetry>,
+    http_status: Option<HttpStatus>,
+    c_code: Option<i32>,
+    grpc: Option<Grpc>,
+    /// The name of the `std::io::ErrorKind` variant
+    io_kind: Option<syn::Ident>,
+    /// Whether constructing this error is outlined and marked cold
+    cold: Option<bool>,
+}
+
+/// Where and how errors are reported to the `log` crate when they are
+/// constructed.
+struct Log {
+    /// The name of the `log::Level` variant
+    level: syn::Ident,
+    target: Option<syn::LitStr>,
+}
+
+/// The HTTP status code of an error.
+#[derive(Copy, Clone)]
+enum HttpStatus {
+    Code(u16),
+    /// The status code of the source error is used
+    Source,
+}
+
+/// How an error is converted into a `tonic::Status`.
+struct Grpc {
+    /// The name of the `tonic::Code` variant
+    code: Option<syn::Ident>,
+    /// The fields included in the status metadata
+    metadata: Vec<syn::Ident>,
+}
+
+/// The `metrics` counter incremented when errors are constructed.
+struct Metric {
+    /// Defaults to the snake case name of the error type
+    name: Option<syn::LitStr>,
+}
+
+/// How errors are recorded on OpenTelemetry spans.
+struct OpenTelemetry {
+    /// The fields added as span attributes
+    fields: Vec<syn::Ident>,
+}
+
+/// The parts of a `miette::Diagnostic` implementation, each a format
+/// string and its arguments.
+#[derive(Default)]
+struct Diagnostic {
+    code: Option<UserInput>,
+    help: Option<UserInput>,
+    url: Option<UserInput>,
+    labels: Vec<(Field, UserInput)>,
+}
+
+impl Diagnostic {
+    fn is_empty(&self) -> bool {
+        self.code.is_none() && self.help.is_none() && self.url.is_none() && self.labels.is_empty()
+    }
 }
 
 enum SuffixKind {
@@ -67,6 +196,9 @@ enum ContextSelectorKind {
 
     NoContext {
         source_field: SourceField,
+        /// The variant wraps another error type and passes its
+        /// message, source, and backtrace through unchanged.
+        flatten: bool,
     },
 }
 
@@ -90,7 +222,14 @@ impl ContextSelectorKind {
         match self {
             ContextSelectorKind::Context { source_field, .. } => source_field.as_ref(),
             ContextSelectorKind::Whatever { source_field, .. } => source_field.as_ref(),
-            ContextSelectorKind::NoContext { source_field } => Some(source_field),
+            ContextSelectorKind::NoContext { source_field, .. } => Some(source_field),
+        }
+    }
+
+    fn is_flatten(&self) -> bool {
+        match self {
+            ContextSelectorKind::NoContext { flatten, .. } => *flatten,
+            _ => false,
         }
     }
 
@@ -107,6 +246,14 @@ struct NamedStructInfo {
     crate_root: UserInput,
     field_container: FieldContainer,
     generics: syn::Generics,
+    visibility: syn::Visibility,
+    skip_display: bool,
+    display_buffer: Option<usize>,
+    stable_debug: bool,
+    termination: bool,
+    wasm: bool,
+    catalog: bool,
+    strip_suffixes: Vec<String>,
 }
 
 struct TupleStructInfo {
@@ -114,11 +261,20 @@ struct TupleStructInfo {
     name: syn::Ident,
     generics: syn::Generics,
     transformation: Transformation,
+    visibility: syn::Visibility,
+    skip_display: bool,
+    termination: bool,
+    wasm: bool,
+    http_status: Option<HttpStatus>,
+    c_code: Option<i32>,
 }
 
 #[derive(Clone)]
 pub(crate) struct Field {
+    /// The variable the field is bound to; positional fields are
+    /// bound to `_0`, `_1`, etc.
     name: syn::Ident,
+    member: syn::Member,
     ty: syn::Type,
     original: syn::Field,
 }
@@ -127,18 +283,51 @@ impl Field {
     fn name(&self) -> &syn::Ident {
         &self.name
     }
+
+    fn is_positional(&self) -> bool {
+        is_positional(&self.member)
+    }
+
+    fn binding(&self) -> proc_macro2::TokenStream {
+        binding(&self.member, &self.name)
+    }
+}
+
+fn is_positional(member: &syn::Member) -> bool {
+    match member {
+        syn::Member::Named(_) => false,
+        syn::Member::Unnamed(_) => true,
+    }
+}
+
+/// The pattern that binds a field by reference, such as `ref name`
+/// or `0: ref _0`.
+fn binding(member: &syn::Member, name: &syn::Ident) -> proc_macro2::TokenStream {
+    match member {
+        syn::Member::Named(_) => quote! { ref #name },
+        syn::Member::Unnamed(_) => quote! { #member: ref #name },
+    }
 }
 
 struct SourceField {
     name: syn::Ident,
+    member: syn::Member,
+    /// The type of the field itself, regardless of any transformation
+    ty: syn::Type,
     transformation: Transformation,
     backtrace_delegate: bool,
+    /// The name of the method returning this source, if requested
+    accessor: Option<syn::Ident>,
 }
 
 impl SourceField {
     fn name(&self) -> &syn::Ident {
         &self.name
     }
+
+    fn binding(&self) -> proc_macro2::TokenStream {
+        binding(&self.member, &self.name)
+    }
 }
 
 enum Transformation {
@@ -407,7 +596,7 @@ where
     ///
     /// This can help with additional manual error checks beyond the duplication checks that
     /// `AtMostOne` handles for you.
-    fn iter(&self) -> std::collections::vec_deque::Iter<(T, U)> {
+    fn iter(&self) -> std::collections::vec_deque::Iter<'_, (T, U)> {
         self.values.iter()
     }
 
@@ -452,12 +641,14 @@ fn parse_snafu_information(ty: syn::DeriveInput) -> MultiSynResult<SnafuInfo> {
         generics,
         data,
         attrs,
-        ..
+        vis,
     } = ty;
 
     match data {
-        Data::Enum(enum_) => parse_snafu_enum(enum_, ident, generics, attrs).map(SnafuInfo::Enum),
-        Data::Struct(struct_) => parse_snafu_struct(struct_, ident, generics, attrs, span),
+        Data::Enum(enum_) => {
+            parse_snafu_enum(enum_, ident, vis, generics, attrs).map(SnafuInfo::Enum)
+        }
+        Data::Struct(struct_) => parse_snafu_struct(struct_, ident, vis, generics, attrs, span),
         _ => Err(vec![syn::Error::new(
             span,
             "Can only derive `Snafu` for an enum or a newtype",
@@ -470,6 +661,31 @@ const ATTR_DISPLAY: OnlyValidOn = OnlyValidOn {
     valid_on: "enum variants or structs with named fields",
 };
 
+const ATTR_DISPLAY_INTERNAL: OnlyValidOn = OnlyValidOn {
+    attribute: "display(internal)",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_DISPLAY_SKIP: OnlyValidOn = OnlyValidOn {
+    attribute: "display(skip)",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_DISPLAY_BUFFER: OnlyValidOn = OnlyValidOn {
+    attribute: "display_buffer",
+    valid_on: "an enum or a struct with named fields",
+};
+
+const ATTR_STRIP_SUFFIX: OnlyValidOn = OnlyValidOn {
+    attribute: "strip_suffix",
+    valid_on: "an enum or a struct with named fields",
+};
+
+const ATTR_FLATTEN: OnlyValidOn = OnlyValidOn {
+    attribute: "flatten",
+    valid_on: "enum variants",
+};
+
 const ATTR_SOURCE: OnlyValidOn = OnlyValidOn {
     attribute: "source",
     valid_on: "enum variant or struct fields with a name",
@@ -490,6 +706,11 @@ const ATTR_SOURCE_FROM: OnlyValidOn = OnlyValidOn {
     valid_on: "enum variant or struct fields with a name",
 };
 
+const ATTR_SOURCE_ACCESSOR: OnlyValidOn = OnlyValidOn {
+    attribute: "source(accessor)",
+    valid_on: "enum variant fields",
+};
+
 const ATTR_BACKTRACE: OnlyValidOn = OnlyValidOn {
     attribute: "backtrace",
     valid_on: "enum variant or struct fields with a name",
@@ -505,6 +726,11 @@ const ATTR_VISIBILITY: OnlyValidOn = OnlyValidOn {
     valid_on: "an enum, enum variants, or a struct with named fields",
 };
 
+const ATTR_CONVERT: OnlyValidOn = OnlyValidOn {
+    attribute: "convert",
+    valid_on: "an enum",
+};
+
 const ATTR_CONTEXT: OnlyValidOn = OnlyValidOn {
     attribute: "context",
     valid_on: "enum variants or structs with named fields",
@@ -520,43 +746,247 @@ const ATTR_CRATE_ROOT: OnlyValidOn = OnlyValidOn {
     valid_on: "an enum or a struct",
 };
 
+const ATTR_EXIT_CODE: OnlyValidOn = OnlyValidOn {
+    attribute: "exit_code",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_TRACING: OnlyValidOn = OnlyValidOn {
+    attribute: "tracing",
+    valid_on: "enums, enum variants, or structs with named fields",
+};
+
+const ATTR_LOG: OnlyValidOn = OnlyValidOn {
+    attribute: "log",
+    valid_on: "enums, enum variants, or structs with named fields",
+};
+
+const ATTR_OPENTELEMETRY: OnlyValidOn = OnlyValidOn {
+    attribute: "opentelemetry",
+    valid_on: "enums, enum variants, or structs with named fields",
+};
+
+const ATTR_METRIC: OnlyValidOn = OnlyValidOn {
+    attribute: "metric",
+    valid_on: "enums, enum variants, or structs with named fields",
+};
+
+const ATTR_HTTP_STATUS: OnlyValidOn = OnlyValidOn {
+    attribute: "http_status",
+    valid_on: "enums, enum variants, or structs",
+};
+
+const ATTR_C_CODE: OnlyValidOn = OnlyValidOn {
+    attribute: "c_code",
+    valid_on: "enums, enum variants, or structs",
+};
+
+const ATTR_GRPC: OnlyValidOn = OnlyValidOn {
+    attribute: "grpc",
+    valid_on: "enums, enum variants, or structs with named fields",
+};
+
+const ATTR_IO_KIND: OnlyValidOn = OnlyValidOn {
+    attribute: "io_kind",
+    valid_on: "enums, enum variants, or structs with named fields",
+};
+
+const ATTR_CODE: OnlyValidOn = OnlyValidOn {
+    attribute: "code",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_HELP: OnlyValidOn = OnlyValidOn {
+    attribute: "help",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_URL: OnlyValidOn = OnlyValidOn {
+    attribute: "url",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_LABEL: OnlyValidOn = OnlyValidOn {
+    attribute: "label",
+    valid_on: "enum variant or struct fields",
+};
+
+const ATTR_KIND: OnlyValidOn = OnlyValidOn {
+    attribute: "kind",
+    valid_on: "an enum",
+};
+
+const ATTR_ACCESSORS: OnlyValidOn = OnlyValidOn {
+    attribute: "accessors",
+    valid_on: "an enum",
+};
+
+const ATTR_FIELDS: OnlyValidOn = OnlyValidOn {
+    attribute: "fields",
+    valid_on: "an enum",
+};
+
+const ATTR_CATALOG: OnlyValidOn = OnlyValidOn {
+    attribute: "catalog",
+    valid_on: "an enum or a struct with named fields",
+};
+
+const ATTR_COLD: OnlyValidOn = OnlyValidOn {
+    attribute: "cold",
+    valid_on: "enums, enum variants, or structs with named fields",
+};
+
+const ATTR_IMPLICIT: OnlyValidOn = OnlyValidOn {
+    attribute: "implicit",
+    valid_on: "enum variant or struct fields",
+};
+
+const ATTR_NO_PANIC: OnlyValidOn = OnlyValidOn {
+    attribute: "no_panic",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_DEBUG_STABLE: OnlyValidOn = OnlyValidOn {
+    attribute: "debug(stable)",
+    valid_on: "an enum or a struct with named fields",
+};
+
+const ATTR_SERIALIZE: OnlyValidOn = OnlyValidOn {
+    attribute: "serialize",
+    valid_on: "an enum",
+};
+
+const ATTR_TERMINATION: OnlyValidOn = OnlyValidOn {
+    attribute: "termination",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_WASM: OnlyValidOn = OnlyValidOn {
+    attribute: "wasm",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_DEDUP: OnlyValidOn = OnlyValidOn {
+    attribute: "dedup",
+    valid_on: "an enum",
+};
+
+const ATTR_PREDICATES: OnlyValidOn = OnlyValidOn {
+    attribute: "predicates",
+    valid_on: "an enum",
+};
+
+const ATTR_REPORT_CONVERSIONS: OnlyValidOn = OnlyValidOn {
+    attribute: "report_conversions",
+    valid_on: "an enum",
+};
+
 const SOURCE_BOOL_FROM_INCOMPATIBLE: IncompatibleAttributes =
     IncompatibleAttributes(&["source(false)", "source(from)"]);
 
+const SOURCE_BOOL_ACCESSOR_INCOMPATIBLE: IncompatibleAttributes =
+    IncompatibleAttributes(&["source(false)", "source(accessor)"]);
+
 fn parse_snafu_enum(
     enum_: syn::DataEnum,
     name: syn::Ident,
+    visibility: syn::Visibility,
     generics: syn::Generics,
     attrs: Vec<syn::Attribute>,
 ) -> MultiSynResult<EnumInfo> {
-    use syn::spanned::Spanned;
     use syn::Fields;
 
     let mut errors = SyntaxErrors::default();
 
     let mut default_visibilities = AtMostOne::new("visibility", ErrorLocation::OnEnum);
     let mut crate_roots = AtMostOne::new("crate_root", ErrorLocation::OnEnum);
+    let mut kinds = AtMostOne::new("kind", ErrorLocation::OnEnum);
+    let mut predicates = AtMostOne::new("predicates", ErrorLocation::OnEnum);
+    let mut accessors = AtMostOne::new("accessors", ErrorLocation::OnEnum);
+    let mut fields = AtMostOne::new("fields", ErrorLocation::OnEnum);
+    let mut catalogs = AtMostOne::new("catalog", ErrorLocation::OnEnum);
+    let mut no_panics = AtMostOne::new("no_panic", ErrorLocation::OnEnum);
+    let mut display_skips = AtMostOne::new("display(skip)", ErrorLocation::OnEnum);
+    let mut display_buffers = AtMostOne::new("display_buffer", ErrorLocation::OnEnum);
+    let mut debug_stables = AtMostOne::new("debug(stable)", ErrorLocation::OnEnum);
+    let mut dedups = AtMostOne::new("dedup", ErrorLocation::OnEnum);
+    let mut report_conversions = AtMostOne::new("report_conversions", ErrorLocation::OnEnum);
+    let mut serializes = AtMostOne::new("serialize", ErrorLocation::OnEnum);
+    let mut terminations = AtMostOne::new("termination", ErrorLocation::OnEnum);
+    let mut wasms = AtMostOne::new("wasm", ErrorLocation::OnEnum);
+    let mut tracings = AtMostOne::new("tracing", ErrorLocation::OnEnum);
+    let mut logs = AtMostOne::new("log", ErrorLocation::OnEnum);
+    let mut metrics = AtMostOne::new("metric", ErrorLocation::OnEnum);
+    let mut opentelemetries = AtMostOne::new("opentelemetry", ErrorLocation::OnEnum);
+    let mut http_statuses = AtMostOne::new("http_status", ErrorLocation::OnEnum);
+    let mut c_codes = AtMostOne::new("c_code", ErrorLocation::OnEnum);
+    let mut grpcs = AtMostOne::new("grpc", ErrorLocation::OnEnum);
+    let mut io_kinds = AtMostOne::new("io_kind", ErrorLocation::OnEnum);
+    let mut colds = AtMostOne::new("cold", ErrorLocation::OnEnum);
+    let mut strip_suffixes = AtMostOne::new("strip_suffix", ErrorLocation::OnEnum);
+    let mut conversions = Vec::new();
     let mut enum_errors = errors.scoped(ErrorLocation::OnEnum);
 
-    for attr in attributes_from_syn(attrs)? {
+    let attrs = attributes_from_syn(attrs)?;
+    let panicking = panicking_attributes(&attrs);
+
+    for attr in attrs {
         match attr {
             SnafuAttribute::Visibility(tokens, v) => {
                 default_visibilities.add(v, tokens);
             }
             SnafuAttribute::Display(tokens, ..) => enum_errors.add(tokens, ATTR_DISPLAY),
+            SnafuAttribute::DisplaySkip(tokens) => display_skips.add((), tokens),
+            SnafuAttribute::DisplayBuffer(tokens, size) => display_buffers.add(size, tokens),
+            SnafuAttribute::DisplayInternal(tokens, ..) => {
+                enum_errors.add(tokens, ATTR_DISPLAY_INTERNAL)
+            }
+            SnafuAttribute::DebugStable(tokens) => debug_stables.add((), tokens),
+            SnafuAttribute::Dedup(tokens) => dedups.add((), tokens),
+            SnafuAttribute::ExitCode(tokens, ..) => enum_errors.add(tokens, ATTR_EXIT_CODE),
+            SnafuAttribute::Code(tokens, ..) => enum_errors.add(tokens, ATTR_CODE),
+            SnafuAttribute::Help(tokens, ..) => enum_errors.add(tokens, ATTR_HELP),
+            SnafuAttribute::Url(tokens, ..) => enum_errors.add(tokens, ATTR_URL),
+            SnafuAttribute::Label(tokens, ..) => enum_errors.add(tokens, ATTR_LABEL),
+            SnafuAttribute::Flatten(tokens) => enum_errors.add(tokens, ATTR_FLATTEN),
             SnafuAttribute::Source(tokens, ss) => {
                 for s in ss {
                     match s {
                         Source::Flag(..) => enum_errors.add(tokens.clone(), ATTR_SOURCE_BOOL),
                         Source::From(..) => enum_errors.add(tokens.clone(), ATTR_SOURCE_FROM),
+                        Source::Accessor(..) => {
+                            enum_errors.add(tokens.clone(), ATTR_SOURCE_ACCESSOR)
+                        }
                     }
                 }
             }
             SnafuAttribute::CrateRoot(tokens, root) => {
                 crate_roots.add(root, tokens);
             }
+            SnafuAttribute::Kind(tokens) => kinds.add((), tokens),
+            SnafuAttribute::Predicates(tokens) => predicates.add((), tokens),
+            SnafuAttribute::ReportConversions(tokens) => report_conversions.add((), tokens),
+            SnafuAttribute::StripSuffix(tokens, words) => strip_suffixes.add(words, tokens),
+            SnafuAttribute::Serialize(tokens, backtrace) => serializes.add(backtrace, tokens),
+            SnafuAttribute::Termination(tokens) => terminations.add((), tokens),
+            SnafuAttribute::Wasm(tokens) => wasms.add((), tokens),
+            SnafuAttribute::Tracing(tokens, level) => tracings.add(level, tokens),
+            SnafuAttribute::Log(tokens, log) => logs.add(log, tokens),
+            SnafuAttribute::Metric(tokens, metric) => metrics.add(metric, tokens),
+            SnafuAttribute::OpenTelemetry(tokens, o) => opentelemetries.add(o, tokens),
+            SnafuAttribute::HttpStatus(tokens, code) => http_statuses.add(code, tokens),
+            SnafuAttribute::CCode(tokens, code) => c_codes.add(code, tokens),
+            SnafuAttribute::Grpc(tokens, grpc) => grpcs.add(grpc, tokens),
+            SnafuAttribute::IoKind(tokens, kind) => io_kinds.add(kind, tokens),
+            SnafuAttribute::Implicit(tokens) => enum_errors.add(tokens, ATTR_IMPLICIT),
+            SnafuAttribute::Accessors(tokens) => accessors.add((), tokens),
+            SnafuAttribute::Fields(tokens) => fields.add((), tokens),
+            SnafuAttribute::Catalog(tokens) => catalogs.add((), tokens),
+            SnafuAttribute::NoPanic(tokens) => no_panics.add((), tokens),
+            SnafuAttribute::Cold(tokens, cold) => colds.add(cold, tokens),
             SnafuAttribute::Backtrace(tokens, ..) => enum_errors.add(tokens, ATTR_BACKTRACE),
             SnafuAttribute::Context(tokens, ..) => enum_errors.add(tokens, ATTR_CONTEXT),
+            SnafuAttribute::Convert(_, conversion) => conversions.push(conversion),
             SnafuAttribute::Whatever(tokens) => enum_errors.add(tokens, ATTR_WHATEVER),
             SnafuAttribute::DocComment(..) => { /* Just a regular doc comment. */ }
         }
@@ -570,18 +1000,128 @@ fn parse_snafu_enum(
     let crate_root = maybe_crate_root.unwrap_or_else(default_crate_root);
     errors.extend(errs);
 
+    let (kind, errs) = kinds.finish();
+    let kind = kind.is_some();
+    errors.extend(errs);
+
+    let (predicates, errs) = predicates.finish();
+    let predicates = predicates.is_some();
+    errors.extend(errs);
+
+    let (accessors, errs) = accessors.finish();
+    let accessors = accessors.is_some();
+    errors.extend(errs);
+
+    let (fields, errs) = fields.finish();
+    let fields = fields.is_some();
+    errors.extend(errs);
+
+    let (catalog, errs) = catalogs.finish();
+    let catalog = catalog.is_some();
+    errors.extend(errs);
+
+    let (no_panic, errs) = no_panics.finish();
+    let no_panic = no_panic.is_some();
+    errors.extend(errs);
+    if no_panic {
+        check_no_panic(panicking, &mut errors);
+    }
+
+    let (skip_display, errs) = display_skips.finish();
+    let skip_display = skip_display.is_some();
+    errors.extend(errs);
+
+    let (display_buffer, errs) = display_buffers.finish_with_location();
+    errors.extend(errs);
+    let display_buffer = display_buffer_from_arg(display_buffer, skip_display, &mut errors);
+
+    let (stable_debug, errs) = debug_stables.finish();
+    let stable_debug = stable_debug.is_some();
+    errors.extend(errs);
+
+    let (dedup, errs) = dedups.finish();
+    let dedup = dedup.is_some();
+    errors.extend(errs);
+
+    let (report_conversions, errs) = report_conversions.finish();
+    let report_conversions = report_conversions.is_some();
+    errors.extend(errs);
+
+    let (serialize, errs) = serializes.finish();
+    errors.extend(errs);
+
+    let (termination, errs) = terminations.finish();
+    let termination = termination.is_some();
+    errors.extend(errs);
+
+    let (wasm, errs) = wasms.finish();
+    let wasm = wasm.is_some();
+    errors.extend(errs);
+
+    let (tracing, errs) = tracings.finish();
+    errors.extend(errs);
+
+    let (log, errs) = logs.finish();
+    errors.extend(errs);
+
+    let (metric, errs) = metrics.finish();
+    errors.extend(errs);
+
+    let (opentelemetry, errs) = opentelemetries.finish_with_location();
+    errors.extend(errs);
+    let opentelemetry = opentelemetry.map_or(false, |(opentelemetry, location)| {
+        if !opentelemetry.fields.is_empty() {
+            errors.add(
+                location,
+                "`opentelemetry(fields)` is not supported on an enum",
+            );
+        }
+        true
+    });
+
+    let (http_status, errs) = http_statuses.finish_with_location();
+    errors.extend(errs);
+    let http_status =
+        http_status.and_then(
+            |(code, location)| match http_status_from_arg(code, &mut errors)? {
+                HttpStatus::Code(code) => Some(code),
+                HttpStatus::Source => {
+                    errors.add(location, "`http_status` on an enum requires a status code");
+                    None
+                }
+            },
+        );
+
+    let (c_code, errs) = c_codes.finish();
+    errors.extend(errs);
+    let c_code = c_code.and_then(|code| c_code_from_arg(code, &mut errors));
+
+    let (grpc, errs) = grpcs.finish_with_location();
+    errors.extend(errs);
+    let grpc = grpc.map(|(grpc, location)| {
+        if !grpc.metadata.is_empty() {
+            errors.add(location, "`grpc(metadata)` is not supported on an enum");
+        }
+        grpc
+    });
+
+    let (io_kind, errs) = io_kinds.finish();
+    errors.extend(errs);
+
+    let (cold, errs) = colds.finish();
+    errors.extend(errs);
+
+    let (strip_suffixes, errs) = strip_suffixes.finish();
+    let strip_suffixes = strip_suffixes.unwrap_or_else(default_strip_suffixes);
+    errors.extend(errs);
+
     let variants: sponge::AllErrors<_, _> = enum_
         .variants
         .into_iter()
         .map(|variant| {
             let fields = match variant.fields {
                 Fields::Named(f) => f.named.into_iter().collect(),
-                Fields::Unnamed(_) => {
-                    return Err(vec![syn::Error::new(
-                        variant.fields.span(),
-                        "Only struct-like and unit enum variants are supported",
-                    )]);
-                }
+                Fields::Unnamed(f) => f.unnamed.into_iter().collect(),
                 Fields::Unit => vec![],
             };
 
@@ -589,6 +1129,10 @@ fn parse_snafu_enum(
             let span = name.span();
 
             let attrs = attributes_from_syn(variant.attrs)?;
+            if no_panic {
+                check_no_panic(panicking_attributes(&attrs), &mut errors);
+                check_no_panic(field_panicking_attributes(&fields), &mut errors);
+            }
 
             field_container(
                 name,
@@ -602,7 +1146,16 @@ fn parse_snafu_enum(
         })
         .collect();
 
-    let variants = errors.absorb(variants.into_result())?;
+    let variants: Vec<FieldContainer> = errors.absorb(variants.into_result())?;
+
+    check_duplicate_selector_names(&variants, &strip_suffixes)?;
+    check_conversions(&variants, &conversions)?;
+
+    if accessors {
+        check_accessor_field_types(&variants)?;
+    }
+    check_source_accessor_types(&variants)?;
+    check_duplicate_method_names(&name, &variants, kind, predicates, accessors)?;
 
     Ok(EnumInfo {
         crate_root,
@@ -610,96 +1163,440 @@ fn parse_snafu_enum(
         generics,
         variants,
         default_visibility,
+        visibility,
+        kind,
+        predicates,
+        accessors,
+        fields,
+        catalog,
+        skip_display,
+        display_buffer,
+        stable_debug,
+        dedup,
+        report_conversions,
+        serialize,
+        termination,
+        wasm,
+        tracing,
+        log,
+        metric,
+        opentelemetry,
+        http_status,
+        c_code,
+        grpc,
+        io_kind,
+        cold,
+        strip_suffixes,
+        conversions,
     })
 }
 
-fn field_container(
-    name: syn::Ident,
-    variant_span: proc_macro2::Span,
-    attrs: Vec<SnafuAttribute>,
-    fields: Vec<syn::Field>,
-    errors: &mut SyntaxErrors,
-    outer_error_location: ErrorLocation,
-    inner_error_location: ErrorLocation,
-) -> MultiSynResult<FieldContainer> {
-    use quote::ToTokens;
-    use syn::spanned::Spanned;
-
-    let mut outer_errors = errors.scoped(outer_error_location);
+/// Every conversion must name an enum and map onto variants that
+/// exist. Variants of the other enum that are not mapped are
+/// reported by the compiler as a non-exhaustive match.
+fn check_conversions(
+    variants: &[FieldContainer],
+    conversions: &[Conversion],
+) -> MultiSynResult<()> {
+    let mut errors = SyntaxErrors::default();
 
-    let mut display_formats = AtMostOne::new("display", outer_error_location);
-    let mut visibilities = AtMostOne::new("visibility", outer_error_location);
-    let mut contexts = AtMostOne::new("context", outer_error_location);
-    let mut whatevers = AtMostOne::new("whatever", outer_error_location);
-    let mut doc_comment = String::new();
-    let mut reached_end_of_doc_comment = false;
+    for conversion in conversions {
+        match conversion.from_type {
+            syn::Type::Path(syn::TypePath { qself: None, .. }) => {}
+            _ => errors.add(
+                &conversion.from_type,
+                "Can only convert from an enum referred to by its path",
+            ),
+        }
 
-    for attr in attrs {
-        match attr {
-            SnafuAttribute::Display(tokens, d) => display_formats.add(d, tokens),
-            SnafuAttribute::Visibility(tokens, v) => visibilities.add(v, tokens),
-            SnafuAttribute::Context(tokens, c) => contexts.add(c, tokens),
-            SnafuAttribute::Whatever(tokens) => whatevers.add((), tokens),
-            SnafuAttribute::Source(tokens, ..) => outer_errors.add(tokens, ATTR_SOURCE),
-            SnafuAttribute::Backtrace(tokens, ..) => outer_errors.add(tokens, ATTR_BACKTRACE),
-            SnafuAttribute::CrateRoot(tokens, ..) => outer_errors.add(tokens, ATTR_CRATE_ROOT),
-            SnafuAttribute::DocComment(_tts, doc_comment_line) => {
-                // We join all the doc comment attributes with a space,
-                // but end once the summary of the doc comment is
-                // complete, which is indicated by an empty line.
-                if !reached_end_of_doc_comment {
-                    let trimmed = doc_comment_line.trim();
-                    if trimmed.is_empty() {
-                        reached_end_of_doc_comment = true;
-                    } else {
-                        if !doc_comment.is_empty() {
-                            doc_comment.push_str(" ");
-                        }
-                        doc_comment.push_str(trimmed);
-                    }
-                }
+        for (_, to) in &conversion.mappings {
+            if !variants.iter().any(|v| v.name == *to) {
+                errors.add(to, format!("`{}` is not a variant of this enum", to));
             }
         }
     }
 
-    let mut user_fields = Vec::new();
-    let mut source_fields = AtMostOne::new("source", inner_error_location);
-    let mut backtrace_fields = AtMostOne::new("backtrace", inner_error_location);
+    errors.finish()
+}
 
-    for syn_field in fields {
-        let original = syn_field.clone();
-        let span = syn_field.span();
-        let name = syn_field
-            .ident
-            .as_ref()
-            .ok_or_else(|| vec![syn::Error::new(span, "Must have a named field")])?;
-        let field = Field {
-            name: name.clone(),
-            ty: syn_field.ty.clone(),
-            original,
-        };
+/// Accessors return a single type, so every context field with the
+/// same name must also have the same type.
+fn check_accessor_field_types(variants: &[FieldContainer]) -> MultiSynResult<()> {
+    use quote::ToTokens;
 
-        // Check whether we have multiple source/backtrace attributes on this field.
-        // We can't just add to source_fields/backtrace_fields from inside the attribute
-        // loop because source and backtrace are connected and require a bit of special
-        // logic after the attribute loop.  For example, we need to know whether there's a
-        // source transformation before we record a source field, but it might be on a
-        // later attribute.  We use the data field of `source_attrs` to track any
-        // transformations in case it was a `source(from(...))`, but for backtraces we
-        // don't need any more data.
-        let mut source_attrs = AtMostOne::new("source", ErrorLocation::OnField);
-        let mut backtrace_attrs = AtMostOne::new("backtrace", ErrorLocation::OnField);
+    let mut errors = SyntaxErrors::default();
+    let mut seen: Vec<(&syn::Ident, String, &syn::Ident)> = Vec::new();
 
-        // Keep track of the negative markers so we can check for inconsistencies and
-        // exclude fields even if they have the "source" or "backtrace" name.
-        let mut source_opt_out = false;
-        let mut backtrace_opt_out = false;
+    for variant in variants {
+        for field in variant.selector_kind.user_fields() {
+            if field.is_positional() {
+                continue;
+            }
+            let ty = field.ty.to_token_stream().to_string();
+            match seen.iter().find(|(name, _, _)| **name == field.name) {
+                Some((_, first_ty, first_variant)) if *first_ty != ty => errors.add(
+                    &field.ty,
+                    format!(
+                        "Cannot generate an accessor for `{}` as it has a different type in `{}`",
+                        field.name, first_variant,
+                    ),
+                ),
+                Some(_) => {}
+                None => seen.push((&field.name, ty, &variant.name)),
+            }
+        }
+    }
 
-        let mut field_errors = errors.scoped(ErrorLocation::OnField);
+    errors.finish()
+}
 
-        for attr in attributes_from_syn(syn_field.attrs.clone())? {
-            match attr {
-                SnafuAttribute::Source(tokens, ss) => {
+/// Variants may share a source accessor, but only when their sources
+/// have the same type.
+fn check_source_accessor_types(variants: &[FieldContainer]) -> MultiSynResult<()> {
+    use quote::ToTokens;
+
+    let mut errors = SyntaxErrors::default();
+    let mut seen: Vec<(&syn::Ident, String, &syn::Ident)> = Vec::new();
+
+    for variant in variants {
+        let source_field = match variant.selector_kind.source_field() {
+            Some(source_field) => source_field,
+            None => continue,
+        };
+        let accessor = match &source_field.accessor {
+            Some(accessor) => accessor,
+            None => continue,
+        };
+
+        let ty = source_field.ty.to_token_stream().to_string();
+        match seen.iter().find(|(name, _, _)| *name == accessor) {
+            Some((_, first_ty, first_variant)) if *first_ty != ty => errors.add(
+                accessor,
+                format!(
+                    "Cannot generate the source accessor `{}` as the source has a different type in `{}`",
+                    accessor, first_variant,
+                ),
+            ),
+            Some(_) => {}
+            None => seen.push((accessor, ty, &variant.name)),
+        }
+    }
+
+    errors.finish()
+}
+
+/// Variant names and suffixes can combine to give two variants the
+/// same context selector, which the compiler would report as a
+/// confusing conflict between the generated items.
+fn check_duplicate_selector_names(
+    variants: &[FieldContainer],
+    strip_suffixes: &[String],
+) -> MultiSynResult<()> {
+    let mut errors = SyntaxErrors::default();
+    let mut seen: Vec<(proc_macro2::Ident, &syn::Ident)> = Vec::new();
+
+    for variant in variants {
+        if let ContextSelectorKind::Context { .. } = variant.selector_kind {
+            let selector_name =
+                shared::selector_ident(&variant.name, &variant.selector_kind, strip_suffixes);
+
+            match seen.iter().find(|(name, _)| *name == selector_name) {
+                Some((_, first_variant)) => {
+                    let message = format!(
+                        "The context selector `{}` is generated for both `{}` and `{}`",
+                        selector_name, first_variant, variant.name,
+                    );
+                    errors.add(first_variant, &message);
+                    errors.add(&variant.name, message);
+                }
+                None => seen.push((selector_name, &variant.name)),
+            }
+        }
+    }
+
+    errors.finish()
+}
+
+/// The `kind` method, predicates, and accessors are all generated as
+/// inherent methods, so a field named `kind` or two variants with
+/// the same snake case name would otherwise produce a confusing
+/// error about duplicate definitions.
+fn check_duplicate_method_names(
+    enum_name: &syn::Ident,
+    variants: &[FieldContainer],
+    kind: bool,
+    predicates: bool,
+    accessors: bool,
+) -> MultiSynResult<()> {
+    // The method's name, a description of why it exists, and where
+    type Method<'a> = (String, String, &'a syn::Ident);
+
+    // Variants may share accessors, so only the first use is a method
+    fn add_shared<'a>(methods: &mut Vec<Method<'a>>, name: &'a syn::Ident, origin: String) {
+        let method = name.to_string().trim_start_matches("r#").to_string();
+        if !methods.iter().any(|(m, o, _)| *m == method && *o == origin) {
+            methods.push((method, origin, name));
+        }
+    }
+
+    let mut methods: Vec<Method<'_>> = Vec::new();
+
+    if kind {
+        methods.push(("kind".into(), "the `kind` method".into(), enum_name));
+    }
+
+    if predicates {
+        for variant in variants {
+            let name = format!("is_{}", to_snake_case(&variant.name.to_string()));
+            let origin = format!("the predicate for `{}`", variant.name);
+            methods.push((name, origin, &variant.name));
+        }
+    }
+
+    for variant in variants {
+        if accessors {
+            for field in variant.selector_kind.user_fields() {
+                if !field.is_positional() {
+                    let origin = format!("the accessor for the `{}` field", field.name);
+                    add_shared(&mut methods, &field.name, origin);
+                }
+            }
+        }
+
+        let source_accessor = variant
+            .selector_kind
+            .source_field()
+            .and_then(|f| f.accessor.as_ref());
+        if let Some(accessor) = source_accessor {
+            let origin = format!("the source accessor `{}`", accessor);
+            add_shared(&mut methods, accessor, origin);
+        }
+    }
+
+    let mut errors = SyntaxErrors::default();
+
+    for (i, (method, origin, tokens)) in methods.iter().enumerate() {
+        let first = methods[..i].iter().find(|(m, _, _)| m == method);
+        if let Some((_, first_origin, _)) = first {
+            errors.add(
+                tokens,
+                format!(
+                    "The method `{}` is generated for both {} and {}",
+                    method, first_origin, origin,
+                ),
+            );
+        }
+    }
+
+    errors.finish()
+}
+
+/// Finds the attributes whose generated code can panic, such as by
+/// formatting the error into a `String` or by calling into another
+/// crate.
+fn panicking_attributes(attrs: &[SnafuAttribute]) -> Vec<(proc_macro2::TokenStream, &'static str)> {
+    attrs
+        .iter()
+        .filter_map(|attr| match attr {
+            SnafuAttribute::Termination(tokens) => Some((tokens.clone(), "termination")),
+            SnafuAttribute::Wasm(tokens) => Some((tokens.clone(), "wasm")),
+            SnafuAttribute::Serialize(tokens, ..) => Some((tokens.clone(), "serialize")),
+            SnafuAttribute::Grpc(tokens, ..) => Some((tokens.clone(), "grpc")),
+            SnafuAttribute::Tracing(tokens, ..) => Some((tokens.clone(), "tracing")),
+            SnafuAttribute::Log(tokens, ..) => Some((tokens.clone(), "log")),
+            SnafuAttribute::Metric(tokens, ..) => Some((tokens.clone(), "metric")),
+            SnafuAttribute::OpenTelemetry(tokens, ..) => Some((tokens.clone(), "opentelemetry")),
+            SnafuAttribute::CCode(tokens, ..) => Some((tokens.clone(), "c_code")),
+            SnafuAttribute::Code(tokens, ..) => Some((tokens.clone(), "code")),
+            SnafuAttribute::Help(tokens, ..) => Some((tokens.clone(), "help")),
+            SnafuAttribute::Url(tokens, ..) => Some((tokens.clone(), "url")),
+            SnafuAttribute::Label(tokens, ..) => Some((tokens.clone(), "label")),
+            // The message is stored in a `String`
+            SnafuAttribute::Whatever(tokens) => Some((tokens.clone(), "whatever")),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The field attributes are parsed again, and any problems with them
+/// reported, by `field_container`.
+fn field_panicking_attributes(
+    fields: &[syn::Field],
+) -> Vec<(proc_macro2::TokenStream, &'static str)> {
+    fields
+        .iter()
+        .filter_map(|field| attributes_from_syn(field.attrs.clone()).ok())
+        .flat_map(|attrs| panicking_attributes(&attrs))
+        .collect()
+}
+
+fn check_no_panic(
+    panicking: Vec<(proc_macro2::TokenStream, &'static str)>,
+    errors: &mut SyntaxErrors,
+) {
+    for (tokens, attribute) in panicking {
+        errors.add(
+            tokens,
+            format!(
+                "`{}` generates code that can panic and cannot be used with `no_panic`",
+                attribute,
+            ),
+        );
+    }
+}
+
+fn field_container(
+    name: syn::Ident,
+    variant_span: proc_macro2::Span,
+    attrs: Vec<SnafuAttribute>,
+    fields: Vec<syn::Field>,
+    errors: &mut SyntaxErrors,
+    outer_error_location: ErrorLocation,
+    inner_error_location: ErrorLocation,
+) -> MultiSynResult<FieldContainer> {
+    use quote::ToTokens;
+    use syn::spanned::Spanned;
+
+    let mut outer_errors = errors.scoped(outer_error_location);
+
+    let mut display_formats = AtMostOne::new("display", outer_error_location);
+    let mut display_internal_formats = AtMostOne::new("display(internal)", outer_error_location);
+    let mut visibilities = AtMostOne::new("visibility", outer_error_location);
+    let mut contexts = AtMostOne::new("context", outer_error_location);
+    let mut whatevers = AtMostOne::new("whatever", outer_error_location);
+    let mut flattens = AtMostOne::new("flatten", outer_error_location);
+    let mut exit_codes = AtMostOne::new("exit_code", outer_error_location);
+    let mut codes = AtMostOne::new("code", outer_error_location);
+    let mut helps = AtMostOne::new("help", outer_error_location);
+    let mut urls = AtMostOne::new("url", outer_error_location);
+    let mut tracings = AtMostOne::new("tracing", outer_error_location);
+    let mut logs = AtMostOne::new("log", outer_error_location);
+    let mut metrics = AtMostOne::new("metric", outer_error_location);
+    let mut opentelemetries = AtMostOne::new("opentelemetry", outer_error_location);
+    let mut http_statuses = AtMostOne::new("http_status", outer_error_location);
+    let mut c_codes = AtMostOne::new("c_code", outer_error_location);
+    let mut grpcs = AtMostOne::new("grpc", outer_error_location);
+    let mut io_kinds = AtMostOne::new("io_kind", outer_error_location);
+    let mut colds = AtMostOne::new("cold", outer_error_location);
+    let mut doc_comment = snafu_messages::parse::DocSummary::default();
+
+    for attr in attrs {
+        match attr {
+            SnafuAttribute::Display(tokens, d) => display_formats.add(d, tokens),
+            SnafuAttribute::DisplaySkip(tokens) => outer_errors.add(tokens, ATTR_DISPLAY_SKIP),
+            SnafuAttribute::DisplayBuffer(tokens, ..) => {
+                outer_errors.add(tokens, ATTR_DISPLAY_BUFFER)
+            }
+            SnafuAttribute::DisplayInternal(tokens, d) => display_internal_formats.add(d, tokens),
+            SnafuAttribute::DebugStable(tokens) => outer_errors.add(tokens, ATTR_DEBUG_STABLE),
+            SnafuAttribute::Dedup(tokens) => outer_errors.add(tokens, ATTR_DEDUP),
+            SnafuAttribute::Visibility(tokens, v) => visibilities.add(v, tokens),
+            SnafuAttribute::Context(tokens, c) => contexts.add(c, tokens),
+            SnafuAttribute::Convert(tokens, ..) => outer_errors.add(tokens, ATTR_CONVERT),
+            SnafuAttribute::Whatever(tokens) => whatevers.add((), tokens),
+            SnafuAttribute::Flatten(tokens) => flattens.add((), tokens),
+            SnafuAttribute::ExitCode(tokens, code) => exit_codes.add(code, tokens),
+            SnafuAttribute::Code(tokens, c) => codes.add(c, tokens),
+            SnafuAttribute::Help(tokens, h) => helps.add(h, tokens),
+            SnafuAttribute::Url(tokens, u) => urls.add(u, tokens),
+            SnafuAttribute::Label(tokens, ..) => outer_errors.add(tokens, ATTR_LABEL),
+            SnafuAttribute::Source(tokens, ..) => outer_errors.add(tokens, ATTR_SOURCE),
+            SnafuAttribute::Backtrace(tokens, ..) => outer_errors.add(tokens, ATTR_BACKTRACE),
+            SnafuAttribute::CrateRoot(tokens, ..) => outer_errors.add(tokens, ATTR_CRATE_ROOT),
+            SnafuAttribute::Kind(tokens) => outer_errors.add(tokens, ATTR_KIND),
+            SnafuAttribute::Predicates(tokens) => outer_errors.add(tokens, ATTR_PREDICATES),
+            SnafuAttribute::ReportConversions(tokens) => {
+                outer_errors.add(tokens, ATTR_REPORT_CONVERSIONS)
+            }
+            SnafuAttribute::StripSuffix(tokens, ..) => outer_errors.add(tokens, ATTR_STRIP_SUFFIX),
+            SnafuAttribute::Serialize(tokens, ..) => outer_errors.add(tokens, ATTR_SERIALIZE),
+            SnafuAttribute::Termination(tokens) => outer_errors.add(tokens, ATTR_TERMINATION),
+            SnafuAttribute::Wasm(tokens) => outer_errors.add(tokens, ATTR_WASM),
+            SnafuAttribute::Tracing(tokens, level) => tracings.add(level, tokens),
+            SnafuAttribute::Log(tokens, log) => logs.add(log, tokens),
+            SnafuAttribute::Metric(tokens, metric) => metrics.add(metric, tokens),
+            SnafuAttribute::OpenTelemetry(tokens, o) => opentelemetries.add(o, tokens),
+            SnafuAttribute::HttpStatus(tokens, code) => http_statuses.add(code, tokens),
+            SnafuAttribute::CCode(tokens, code) => c_codes.add(code, tokens),
+            SnafuAttribute::Grpc(tokens, grpc) => grpcs.add(grpc, tokens),
+            SnafuAttribute::IoKind(tokens, kind) => io_kinds.add(kind, tokens),
+            SnafuAttribute::Implicit(tokens) => outer_errors.add(tokens, ATTR_IMPLICIT),
+            SnafuAttribute::Accessors(tokens) => outer_errors.add(tokens, ATTR_ACCESSORS),
+            SnafuAttribute::Fields(tokens) => outer_errors.add(tokens, ATTR_FIELDS),
+            SnafuAttribute::Catalog(tokens) => outer_errors.add(tokens, ATTR_CATALOG),
+            SnafuAttribute::NoPanic(tokens) => outer_errors.add(tokens, ATTR_NO_PANIC),
+            SnafuAttribute::Cold(tokens, cold) => colds.add(cold, tokens),
+            SnafuAttribute::DocComment(_tts, doc_comment_line) => {
+                doc_comment.push_line(&doc_comment_line)
+            }
+        }
+    }
+
+    let mut all_fields = Vec::new();
+    let mut field_names = Vec::new();
+    let mut user_fields = Vec::new();
+    let mut implicit_fields = Vec::new();
+    let mut source_fields = AtMostOne::new("source", inner_error_location);
+    let mut backtrace_fields = AtMostOne::new("backtrace", inner_error_location);
+    let mut metadata_fields = AtMostOne::new("metadata", inner_error_location);
+    let mut labels = Vec::new();
+
+    let is_variant = match inner_error_location {
+        ErrorLocation::InVariant => true,
+        _ => false,
+    };
+    let default_accessor = quote::format_ident!("{}_source", to_snake_case(&name.to_string()));
+
+    // The only field of a tuple variant is its source
+    let single_positional = fields.len() == 1 && fields[0].ident.is_none();
+
+    for (index, syn_field) in fields.into_iter().enumerate() {
+        let original = syn_field.clone();
+        let span = syn_field.span();
+        let (name, member) = match &syn_field.ident {
+            Some(name) => (name.clone(), syn::Member::Named(name.clone())),
+            None => (
+                quote::format_ident!("_{}", index, span = span),
+                syn::Member::Unnamed(syn::Index {
+                    index: index as u32,
+                    span,
+                }),
+            ),
+        };
+        let name = &name;
+        let field = Field {
+            name: name.clone(),
+            member,
+            ty: syn_field.ty.clone(),
+            original,
+        };
+        all_fields.push(field.clone());
+
+        // Check whether we have multiple source/backtrace attributes on this field.
+        // We can't just add to source_fields/backtrace_fields from inside the attribute
+        // loop because source and backtrace are connected and require a bit of special
+        // logic after the attribute loop.  For example, we need to know whether there's a
+        // source transformation before we record a source field, but it might be on a
+        // later attribute.  We use the data field of `source_attrs` to track any
+        // transformations in case it was a `source(from(...))`, but for backtraces we
+        // don't need any more data.
+        let mut source_attrs = AtMostOne::new("source", ErrorLocation::OnField);
+        let mut backtrace_attrs = AtMostOne::new("backtrace", ErrorLocation::OnField);
+        let mut accessor_attrs = AtMostOne::new("source(accessor)", ErrorLocation::OnField);
+        let mut label_attrs = AtMostOne::new("label", ErrorLocation::OnField);
+        let mut implicit_attrs = AtMostOne::new("implicit", ErrorLocation::OnField);
+        let mut misplaced_accessor = None;
+
+        // Keep track of the negative markers so we can check for inconsistencies and
+        // exclude fields even if they have the "source" or "backtrace" name.
+        let mut source_opt_out = false;
+        let mut backtrace_opt_out = false;
+
+        let mut field_errors = errors.scoped(ErrorLocation::OnField);
+
+        for attr in attributes_from_syn(syn_field.attrs.clone())? {
+            match attr {
+                SnafuAttribute::Source(tokens, ss) => {
                     for s in ss {
                         match s {
                             Source::Flag(v) => {
@@ -714,7 +1611,7 @@ fn field_container(
                                 }
                                 if v {
                                     source_attrs.add(None, tokens.clone());
-                                } else if name == "source" {
+                                } else if name == "source" || single_positional {
                                     source_opt_out = true;
                                 } else {
                                     field_errors.add(tokens.clone(), ATTR_SOURCE_FALSE);
@@ -726,6 +1623,13 @@ fn field_container(
                                 }
                                 source_attrs.add(Some((t, e)), tokens.clone());
                             }
+                            Source::Accessor(_) if !is_variant => {
+                                misplaced_accessor = Some(tokens.clone());
+                            }
+                            Source::Accessor(accessor) => {
+                                let accessor = accessor.unwrap_or_else(|| default_accessor.clone());
+                                accessor_attrs.add(accessor, tokens.clone());
+                            }
                         }
                     }
                 }
@@ -740,9 +1644,54 @@ fn field_container(
                 }
                 SnafuAttribute::Visibility(tokens, ..) => field_errors.add(tokens, ATTR_VISIBILITY),
                 SnafuAttribute::Display(tokens, ..) => field_errors.add(tokens, ATTR_DISPLAY),
+                SnafuAttribute::DisplaySkip(tokens) => field_errors.add(tokens, ATTR_DISPLAY_SKIP),
+                SnafuAttribute::DisplayBuffer(tokens, ..) => {
+                    field_errors.add(tokens, ATTR_DISPLAY_BUFFER)
+                }
+                SnafuAttribute::DisplayInternal(tokens, ..) => {
+                    field_errors.add(tokens, ATTR_DISPLAY_INTERNAL)
+                }
+                SnafuAttribute::DebugStable(tokens) => field_errors.add(tokens, ATTR_DEBUG_STABLE),
+                SnafuAttribute::Dedup(tokens) => field_errors.add(tokens, ATTR_DEDUP),
+                SnafuAttribute::ExitCode(tokens, ..) => field_errors.add(tokens, ATTR_EXIT_CODE),
+                SnafuAttribute::Code(tokens, ..) => field_errors.add(tokens, ATTR_CODE),
+                SnafuAttribute::Help(tokens, ..) => field_errors.add(tokens, ATTR_HELP),
+                SnafuAttribute::Url(tokens, ..) => field_errors.add(tokens, ATTR_URL),
+                SnafuAttribute::Label(tokens, l) => label_attrs.add(l, tokens),
+                SnafuAttribute::Flatten(tokens) => field_errors.add(tokens, ATTR_FLATTEN),
                 SnafuAttribute::Context(tokens, ..) => field_errors.add(tokens, ATTR_CONTEXT),
+                SnafuAttribute::Convert(tokens, ..) => field_errors.add(tokens, ATTR_CONVERT),
                 SnafuAttribute::Whatever(tokens) => field_errors.add(tokens, ATTR_WHATEVER),
                 SnafuAttribute::CrateRoot(tokens, ..) => field_errors.add(tokens, ATTR_CRATE_ROOT),
+                SnafuAttribute::Kind(tokens) => field_errors.add(tokens, ATTR_KIND),
+                SnafuAttribute::Predicates(tokens) => field_errors.add(tokens, ATTR_PREDICATES),
+                SnafuAttribute::ReportConversions(tokens) => {
+                    field_errors.add(tokens, ATTR_REPORT_CONVERSIONS)
+                }
+                SnafuAttribute::StripSuffix(tokens, ..) => {
+                    field_errors.add(tokens, ATTR_STRIP_SUFFIX)
+                }
+                SnafuAttribute::Serialize(tokens, ..) => field_errors.add(tokens, ATTR_SERIALIZE),
+                SnafuAttribute::Termination(tokens) => field_errors.add(tokens, ATTR_TERMINATION),
+                SnafuAttribute::Wasm(tokens) => field_errors.add(tokens, ATTR_WASM),
+                SnafuAttribute::Tracing(tokens, ..) => field_errors.add(tokens, ATTR_TRACING),
+                SnafuAttribute::Log(tokens, ..) => field_errors.add(tokens, ATTR_LOG),
+                SnafuAttribute::Metric(tokens, ..) => field_errors.add(tokens, ATTR_METRIC),
+                SnafuAttribute::OpenTelemetry(tokens, ..) => {
+                    field_errors.add(tokens, ATTR_OPENTELEMETRY)
+                }
+                SnafuAttribute::HttpStatus(tokens, ..) => {
+                    field_errors.add(tokens, ATTR_HTTP_STATUS)
+                }
+                SnafuAttribute::CCode(tokens, ..) => field_errors.add(tokens, ATTR_C_CODE),
+                SnafuAttribute::Grpc(tokens, ..) => field_errors.add(tokens, ATTR_GRPC),
+                SnafuAttribute::IoKind(tokens, ..) => field_errors.add(tokens, ATTR_IO_KIND),
+                SnafuAttribute::Implicit(tokens) => implicit_attrs.add((), tokens),
+                SnafuAttribute::Accessors(tokens) => field_errors.add(tokens, ATTR_ACCESSORS),
+                SnafuAttribute::Fields(tokens) => field_errors.add(tokens, ATTR_FIELDS),
+                SnafuAttribute::Catalog(tokens) => field_errors.add(tokens, ATTR_CATALOG),
+                SnafuAttribute::NoPanic(tokens) => field_errors.add(tokens, ATTR_NO_PANIC),
+                SnafuAttribute::Cold(tokens, ..) => field_errors.add(tokens, ATTR_COLD),
                 SnafuAttribute::DocComment(..) => { /* Just a regular doc comment. */ }
             }
         }
@@ -752,9 +1701,46 @@ fn field_container(
         errors.extend(errs);
         let (backtrace_attr, errs) = backtrace_attrs.finish_with_location();
         errors.extend(errs);
+        let (accessor_attr, errs) = accessor_attrs.finish_with_location();
+        errors.extend(errs);
+        let (label_attr, errs) = label_attrs.finish();
+        errors.extend(errs);
+        let (implicit_attr, errs) = implicit_attrs.finish_with_location();
+        errors.extend(errs);
+
+        let implicit = implicit_attr.is_some() || is_implicit(&field.ty);
+        if !implicit {
+            field_names.push(name.clone());
+        }
+
+        if let Some(label) = label_attr {
+            labels.push((field.clone(), label));
+        }
+
+        if let Some(tokens) = misplaced_accessor {
+            errors
+                .scoped(inner_error_location)
+                .add(tokens, ATTR_SOURCE_ACCESSOR);
+        }
+
+        if let (true, Some((_, location))) = (source_opt_out, &accessor_attr) {
+            errors
+                .scoped(ErrorLocation::OnField)
+                .add(location, SOURCE_BOOL_ACCESSOR_INCOMPATIBLE);
+        }
+
+        // Requesting an accessor also marks the field as the source
+        let source_attr = source_attr.or_else(|| {
+            let (_, location) = accessor_attr.as_ref()?;
+            if source_opt_out {
+                None
+            } else {
+                Some((None, location.clone()))
+            }
+        });
 
         let source_attr = source_attr.or_else(|| {
-            if field.name == "source" && !source_opt_out {
+            if (field.name == "source" || single_positional) && !source_opt_out {
                 Some((None, syn_field.clone().into_token_stream()))
             } else {
                 None
@@ -769,24 +1755,44 @@ fn field_container(
             }
         });
 
+        if let (Some((_, implicit_location)), true) = (
+            &implicit_attr,
+            source_attr.is_some() || backtrace_attr.is_some(),
+        ) {
+            errors.add(
+                implicit_location,
+                "`implicit` cannot be used on a source or backtrace field",
+            );
+        }
+
         if let Some((maybe_transformation, location)) = source_attr {
-            let Field { name, ty, .. } = field;
+            let Field {
+                name, member, ty, ..
+            } = field;
             let transformation = maybe_transformation
                 .map(|(ty, expr)| Transformation::Transform { ty, expr })
-                .unwrap_or_else(|| Transformation::None { ty });
+                .unwrap_or_else(|| Transformation::None { ty: ty.clone() });
 
             source_fields.add(
                 SourceField {
                     name,
+                    member,
+                    ty,
                     transformation,
                     // Specifying `backtrace` on a source field is how you request
                     // delegation of the backtrace to the source error type.
                     backtrace_delegate: backtrace_attr.is_some(),
+                    accessor: accessor_attr.map(|(accessor, _)| accessor),
                 },
                 location,
             );
         } else if let Some((_, location)) = backtrace_attr {
             backtrace_fields.add(field, location);
+        } else if implicit && is_implicit_named(&field.ty, "Metadata") {
+            metadata_fields.add(field.clone(), syn_field.clone().into_token_stream());
+            implicit_fields.push(field);
+        } else if implicit {
+            implicit_fields.push(field);
         } else {
             user_fields.push(field);
         }
@@ -798,6 +1804,53 @@ fn field_container(
     let (backtrace, errs) = backtrace_fields.finish_with_location();
     errors.extend(errs);
 
+    let (metadata_field, errs) = metadata_fields.finish();
+    errors.extend(errs);
+
+    let (exit_code, errs) = exit_codes.finish();
+    errors.extend(errs);
+    let exit_code = exit_code.and_then(|code| match code.base10_parse::<u8>() {
+        Ok(code) => Some(code),
+        Err(_) => {
+            errors.add(code, "Exit codes must be between 0 and 255");
+            None
+        }
+    });
+
+    let (http_status, errs) = http_statuses.finish_with_location();
+    errors.extend(errs);
+    let http_status = http_status.and_then(|(code, location)| {
+        let status = http_status_from_arg(code, errors)?;
+        if let (HttpStatus::Source, None) = (status, &source) {
+            errors.add(
+                location,
+                "`http_status` without a status code requires a source field",
+            );
+            return None;
+        }
+        Some(status)
+    });
+
+    let (c_code, errs) = c_codes.finish();
+    errors.extend(errs);
+    let c_code = c_code.and_then(|code| c_code_from_arg(code, errors));
+
+    let (grpc, errs) = grpcs.finish();
+    errors.extend(errs);
+    if let Some(grpc) = &grpc {
+        for name in &grpc.metadata {
+            if !all_fields.iter().any(|f| f.name == *name) {
+                errors.add(name, "`grpc(metadata)` must only name fields of the error");
+            }
+        }
+    }
+
+    let (io_kind, errs) = io_kinds.finish();
+    errors.extend(errs);
+
+    let (cold, errs) = colds.finish();
+    errors.extend(errs);
+
     match (&source, &backtrace) {
         (Some(source), Some(backtrace)) if source.0.backtrace_delegate => {
             let source_location = source.1.clone();
@@ -814,8 +1867,58 @@ fn field_container(
         _ => {} // no conflict
     }
 
+    let backtrace_field = backtrace.map(|(val, _tts)| val);
+
     let (display_format, errs) = display_formats.finish();
     errors.extend(errs);
+    let display_format = display_format
+        .map(|exprs| display_format_with_field_arguments(exprs, &field_names, errors));
+
+    let (display_internal_format, errs) = display_internal_formats.finish();
+    errors.extend(errs);
+    let display_internal_format = display_internal_format
+        .map(|exprs| display_format_with_field_arguments(exprs, &field_names, errors));
+
+    let (tracing, errs) = tracings.finish();
+    errors.extend(errs);
+
+    let (log, errs) = logs.finish();
+    errors.extend(errs);
+
+    let (metric, errs) = metrics.finish();
+    errors.extend(errs);
+
+    let (opentelemetry, errs) = opentelemetries.finish();
+    errors.extend(errs);
+    if let Some(opentelemetry) = &opentelemetry {
+        for name in &opentelemetry.fields {
+            if !all_fields.iter().any(|f| f.name == *name) {
+                errors.add(
+                    name,
+                    "`opentelemetry(fields)` must only name fields of the error",
+                );
+            }
+        }
+    }
+
+    let (code, errs) = codes.finish();
+    errors.extend(errs);
+    let (help, errs) = helps.finish();
+    errors.extend(errs);
+    let (url, errs) = urls.finish();
+    errors.extend(errs);
+
+    let mut diagnostic_format =
+        |exprs| display_format_with_field_arguments(exprs, &field_names, errors);
+    let diagnostic = Diagnostic {
+        code: code.map(&mut diagnostic_format),
+        help: help.map(&mut diagnostic_format),
+        url: url.map(&mut diagnostic_format),
+        labels: labels
+            .into_iter()
+            .map(|(field, exprs)| (field, diagnostic_format(exprs)))
+            .collect(),
+    };
 
     let (visibility, errs) = visibilities.finish();
     errors.extend(errs);
@@ -827,108 +1930,413 @@ fn field_container(
     let (is_whatever, errs) = whatevers.finish_with_location();
     errors.extend(errs);
 
+    if is_whatever.is_none() {
+        for Field { ty, original, .. } in &implicit_fields {
+            if is_implicit_named(ty, "Location") {
+                errors.add(
+                    original,
+                    "`Location` fields are only set by `whatever!` and can only be used in `whatever` errors",
+                );
+            }
+        }
+    }
+
+    let (is_flatten, errs) = flattens.finish_with_location();
+    errors.extend(errs);
+
     let source_field = source.map(|(val, _tts)| val);
 
-    let selector_kind = match (is_context, is_whatever) {
-        (Some(((true, _), c_tt)), Some(((), o_tt))) => {
-            let txt = "Cannot be both a `context` and `whatever` error";
-            return Err(vec![
-                syn::Error::new_spanned(c_tt, txt),
-                syn::Error::new_spanned(o_tt, txt),
-            ]);
+    let selector_kind = if let Some(((), flatten_tt)) = is_flatten {
+        if is_context.is_some()
+            || is_whatever.is_some()
+            || display_format.is_some()
+            || display_internal_format.is_some()
+        {
+            errors.add(
+                &flatten_tt,
+                "`flatten` cannot be combined with `context`, `whatever`, or `display`",
+            );
         }
 
-        (Some(((true, suffix), _)), None) => ContextSelectorKind::Context {
-            suffix,
-            source_field,
-            user_fields,
-        },
+        errors.extend(user_fields.into_iter().map(|Field { original, .. }| {
+            syn::Error::new_spanned(original, "Flattened variants must not have context fields")
+        }));
 
-        (None, None) => ContextSelectorKind::Context {
-            suffix: SuffixKind::Default,
-            source_field,
-            user_fields,
-        },
+        if let Some(Field { original, .. }) = &backtrace_field {
+            errors.add(
+                original,
+                "Flattened variants must not have a backtrace field; the backtrace of the source is used",
+            );
+        }
 
-        (Some(((false, _), _)), Some(_)) | (None, Some(_)) => {
-            let mut messages = AtMostOne::new("message", outer_error_location);
+        let mut source_field = source_field.ok_or_else(|| {
+            vec![syn::Error::new(
+                variant_span,
+                "Flattened variants must have a source field",
+            )]
+        })?;
+        source_field.backtrace_delegate = true;
 
-            for f in user_fields {
-                if f.name == "message" {
-                    let l = f.original.clone();
-                    messages.add(f, l);
-                } else {
-                    errors.add(
-                        f.original,
-                        "Whatever selectors must not have context fields",
-                    );
-                    // todo: phrasing?
-                }
+        ContextSelectorKind::NoContext {
+            source_field,
+            flatten: true,
+        }
+    } else {
+        match (is_context, is_whatever) {
+            (Some(((true, _), c_tt)), Some(((), o_tt))) => {
+                let txt = "Cannot be both a `context` and `whatever` error";
+                return Err(vec![
+                    syn::Error::new_spanned(c_tt, txt),
+                    syn::Error::new_spanned(o_tt, txt),
+                ]);
             }
 
-            let (message_field, errs) = messages.finish();
-            errors.extend(errs);
-
-            let message_field = message_field.ok_or_else(|| {
-                vec![syn::Error::new(
-                    variant_span,
-                    "Whatever selectors must have a message field",
-                )]
-            })?;
+            (Some(((true, suffix), _)), None) => ContextSelectorKind::Context {
+                suffix,
+                source_field,
+                user_fields,
+            },
 
-            ContextSelectorKind::Whatever {
+            (None, None) => ContextSelectorKind::Context {
+                suffix: SuffixKind::Default,
                 source_field,
-                message_field,
-            }
-        }
+                user_fields,
+            },
 
-        (Some(((false, _), _)), None) => {
-            errors.extend(user_fields.into_iter().map(|Field { original, .. }| {
-                syn::Error::new_spanned(
-                    original,
-                    "Context selectors without context must not have context fields",
-                )
-            }));
+            (Some(((false, _), _)), Some(_)) | (None, Some(_)) => {
+                let mut messages = AtMostOne::new("message", outer_error_location);
 
-            let source_field = source_field.ok_or_else(|| {
-                vec![syn::Error::new(
-                    variant_span,
-                    "Context selectors without context must have a source field",
-                )]
-            })?;
+                for f in user_fields {
+                    if f.name == "message" {
+                        let l = f.original.clone();
+                        messages.add(f, l);
+                    } else {
+                        errors.add(
+                            f.original,
+                            "Whatever selectors must not have context fields",
+                        );
+                        // todo: phrasing?
+                    }
+                }
+
+                let (message_field, errs) = messages.finish();
+                errors.extend(errs);
+
+                let message_field = message_field.ok_or_else(|| {
+                    vec![syn::Error::new(
+                        variant_span,
+                        "Whatever selectors must have a message field",
+                    )]
+                })?;
+
+                ContextSelectorKind::Whatever {
+                    source_field,
+                    message_field,
+                }
+            }
 
-            ContextSelectorKind::NoContext { source_field }
+            (Some(((false, _), _)), None) => {
+                errors.extend(user_fields.into_iter().map(|Field { original, .. }| {
+                    syn::Error::new_spanned(
+                        original,
+                        "Context selectors without context must not have context fields",
+                    )
+                }));
+
+                let source_field = source_field.ok_or_else(|| {
+                    vec![syn::Error::new(
+                        variant_span,
+                        "Context selectors without context must have a source field",
+                    )]
+                })?;
+
+                ContextSelectorKind::NoContext {
+                    source_field,
+                    flatten: false,
+                }
+            }
         }
     };
 
     Ok(FieldContainer {
         name,
-        backtrace_field: backtrace.map(|(val, _tts)| val),
+        fields: all_fields,
+        backtrace_field,
+        implicit_fields,
+        metadata_field,
+        exit_code,
         selector_kind,
         display_format,
-        doc_comment,
+        display_internal_format,
+        diagnostic,
+        doc_comment: doc_comment.into_string(),
         visibility,
+        tracing,
+        log,
+        metric,
+        opentelemetry,
+        http_status,
+        c_code,
+        grpc,
+        io_kind,
+        cold,
     })
 }
 
-fn parse_snafu_struct(
-    struct_: syn::DataStruct,
-    name: syn::Ident,
-    generics: syn::Generics,
-    attrs: Vec<syn::Attribute>,
-    span: proc_macro2::Span,
-) -> MultiSynResult<SnafuInfo> {
-    use syn::Fields;
+/// Implicit fields are not part of the context selector and are
+/// filled in automatically using `Default`.
+fn is_implicit(ty: &syn::Type) -> bool {
+    is_phantom_data(ty)
+        || is_metadata(ty)
+        || is_span_id(ty)
+        || is_current_span(ty)
+        || is_location(ty)
+        || is_timing(ty)
+        || is_fields(ty)
+}
 
-    match struct_.fields {
-        Fields::Named(f) => {
-            let f = f.named.into_iter().collect();
-            parse_snafu_named_struct(f, name, generics, attrs, span).map(SnafuInfo::NamedStruct)
-        }
-        Fields::Unnamed(f) => {
-            parse_snafu_tuple_struct(f, name, generics, attrs, span).map(SnafuInfo::TupleStruct)
-        }
-        Fields::Unit => parse_snafu_named_struct(vec![], name, generics, attrs, span)
+/// `PhantomData` fields carry no information.
+fn is_phantom_data(ty: &syn::Type) -> bool {
+    is_implicit_named(ty, "PhantomData")
+}
+
+/// Checks only the last segment of the path. Use this for fields that
+/// are already known to be implicit, such as those marked with
+/// `#[snafu(implicit)]`, where the type may have been imported or
+/// referred to through a renamed crate.
+fn is_implicit_named(ty: &syn::Type, name: &str) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { path, .. }) => path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == name),
+        _ => false,
+    }
+}
+
+/// A source of `Box<Self>` or `Box<TheError>` holds another error of
+/// the same type, which may already be the variant being created.
+fn is_boxed_self(ty: &syn::Type, name: &syn::Ident) -> bool {
+    fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+        match ty {
+            syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last(),
+            _ => None,
+        }
+    }
+
+    let boxed = match last_segment(ty) {
+        Some(segment) if segment.ident == "Box" => &segment.arguments,
+        _ => return false,
+    };
+
+    match boxed {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(inner) => last_segment(inner).map_or(false, |segment| {
+                segment.ident == *name || segment.ident == "Self"
+            }),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// `snafu::Metadata` fields are filled in after construction. The
+/// crate name is required as `Metadata` alone is commonly used for
+/// other types, such as `std::fs::Metadata`; other spellings need
+/// `#[snafu(implicit)]`.
+fn is_metadata(ty: &syn::Type) -> bool {
+    is_snafu_type(ty, "Metadata")
+}
+
+/// `snafu::SpanId` fields capture the current span when created. The
+/// crate name is required as `SpanId` alone is commonly used for
+/// other types.
+fn is_span_id(ty: &syn::Type) -> bool {
+    is_snafu_type(ty, "SpanId")
+}
+
+/// `snafu::CurrentSpan` fields capture the current span when created.
+fn is_current_span(ty: &syn::Type) -> bool {
+    is_snafu_type(ty, "CurrentSpan")
+}
+
+/// `snafu::Timing` fields are filled in by `timed_context`.
+fn is_timing(ty: &syn::Type) -> bool {
+    is_snafu_type(ty, "Timing")
+}
+
+/// `snafu::Fields` fields are filled in by `whatever!`. The crate name
+/// is required as `Fields` alone is commonly used for other types.
+fn is_fields(ty: &syn::Type) -> bool {
+    is_snafu_type(ty, "Fields")
+}
+
+/// `snafu::Location` fields are filled in by `whatever!`. The crate
+/// name is required as `Location` alone is commonly used for other
+/// types.
+fn is_location(ty: &syn::Type) -> bool {
+    is_snafu_type(ty, "Location")
+}
+
+fn is_snafu_type(ty: &syn::Type, name: &str) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { path, .. }) => {
+            let mut segments = path.segments.iter().rev();
+            let last = segments.next();
+            let crate_name = segments.next();
+            match (crate_name, last) {
+                (Some(crate_name), Some(last)) => crate_name.ident == "snafu" && last.ident == name,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Adds a named argument for each field that is referenced by name
+/// in the format string but is not otherwise provided, allowing
+/// templates such as `"{b} then {a} and {b} again"` to reorder and
+/// repeat fields freely.
+fn display_format_with_field_arguments(
+    exprs: Vec<syn::Expr>,
+    field_names: &[syn::Ident],
+    errors: &mut SyntaxErrors,
+) -> UserInput {
+    use syn::{Expr, ExprAssign, ExprLit, ExprPath, Lit};
+
+    let format = match exprs.first() {
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Str(format),
+            ..
+        })) => format.clone(),
+        _ => return Box::new(quote! { #(#exprs),* }),
+    };
+
+    let explicit_names: Vec<_> = exprs
+        .iter()
+        .skip(1)
+        .flat_map(|expr| match expr {
+            Expr::Assign(ExprAssign { left, .. }) => match &**left {
+                Expr::Path(ExprPath { path, .. }) => path.get_ident().map(ToString::to_string),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    let mut implicit_names = Vec::new();
+
+    for name in format_string_named_arguments(&format.value()) {
+        if explicit_names.contains(&name) {
+            continue;
+        }
+
+        match field_names.iter().find(|field_name| **field_name == name) {
+            Some(field_name) => {
+                if !implicit_names.contains(&field_name) {
+                    implicit_names.push(field_name);
+                }
+            }
+            // Constants and statics are resolved by the compiler
+            None if is_constant_name(&name) => {}
+            None => {
+                let available = field_names
+                    .iter()
+                    .map(|name| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let available = if available.is_empty() {
+                    "there are no fields".to_string()
+                } else {
+                    format!("available fields are {}", available)
+                };
+                errors.add(
+                    &format,
+                    format!(
+                        "The display format references `{}`, which is not a field; {}",
+                        name, available,
+                    ),
+                );
+            }
+        }
+    }
+
+    Box::new(quote! { #(#exprs),* #(, #implicit_names = #implicit_names)* })
+}
+
+/// Finds the named arguments of a format string, including named
+/// width and precision arguments such as `{:width$.precision$}`.
+fn format_string_named_arguments(format: &str) -> Vec<String> {
+    fn is_named(argument: &str) -> bool {
+        argument
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_alphabetic() || c == '_')
+    }
+
+    let mut names = Vec::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '{' => {
+                let placeholder: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let mut parts = placeholder.splitn(2, ':');
+                let argument = parts.next().unwrap_or("").trim();
+                let spec = parts.next().unwrap_or("");
+
+                if is_named(argument) {
+                    names.push(argument.to_string());
+                }
+
+                let mut current = String::new();
+                for c in spec.chars() {
+                    if c.is_alphanumeric() || c == '_' {
+                        current.push(c);
+                    } else {
+                        if c == '$' && is_named(&current) {
+                            names.push(current.clone());
+                        }
+                        current.clear();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+fn is_constant_name(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn parse_snafu_struct(
+    struct_: syn::DataStruct,
+    name: syn::Ident,
+    visibility: syn::Visibility,
+    generics: syn::Generics,
+    attrs: Vec<syn::Attribute>,
+    span: proc_macro2::Span,
+) -> MultiSynResult<SnafuInfo> {
+    use syn::Fields;
+
+    match struct_.fields {
+        Fields::Named(f) => {
+            let f = f.named.into_iter().collect();
+            parse_snafu_named_struct(f, name, visibility, generics, attrs, span)
+                .map(SnafuInfo::NamedStruct)
+        }
+        Fields::Unnamed(f) => parse_snafu_tuple_struct(f, name, visibility, generics, attrs, span)
+            .map(SnafuInfo::TupleStruct),
+        Fields::Unit => parse_snafu_named_struct(vec![], name, visibility, generics, attrs, span)
             .map(SnafuInfo::NamedStruct),
     }
 }
@@ -936,6 +2344,7 @@ fn parse_snafu_struct(
 fn parse_snafu_named_struct(
     fields: Vec<syn::Field>,
     name: syn::Ident,
+    visibility: syn::Visibility,
     generics: syn::Generics,
     attrs: Vec<syn::Attribute>,
     span: proc_macro2::Span,
@@ -945,6 +2354,17 @@ fn parse_snafu_named_struct(
     let attrs = attributes_from_syn(attrs)?;
 
     let mut crate_roots = AtMostOne::new("crate_root", ErrorLocation::OnNamedStruct);
+    let mut display_skips = AtMostOne::new("display(skip)", ErrorLocation::OnNamedStruct);
+    let mut display_buffers = AtMostOne::new("display_buffer", ErrorLocation::OnNamedStruct);
+    let mut debug_stables = AtMostOne::new("debug(stable)", ErrorLocation::OnNamedStruct);
+    let mut strip_suffixes = AtMostOne::new("strip_suffix", ErrorLocation::OnNamedStruct);
+    let mut terminations = AtMostOne::new("termination", ErrorLocation::OnNamedStruct);
+    let mut wasms = AtMostOne::new("wasm", ErrorLocation::OnNamedStruct);
+    let mut catalogs = AtMostOne::new("catalog", ErrorLocation::OnNamedStruct);
+    let mut no_panics = AtMostOne::new("no_panic", ErrorLocation::OnNamedStruct);
+
+    let mut panicking = panicking_attributes(&attrs);
+    panicking.extend(field_panicking_attributes(&fields));
 
     let attrs = attrs
         .into_iter()
@@ -953,6 +2373,44 @@ fn parse_snafu_named_struct(
                 crate_roots.add(root, tokens);
                 None
             }
+            SnafuAttribute::DisplaySkip(tokens) => {
+                display_skips.add((), tokens);
+                None
+            }
+            SnafuAttribute::DisplayBuffer(tokens, size) => {
+                display_buffers.add(size, tokens);
+                None
+            }
+            SnafuAttribute::DebugStable(tokens) => {
+                debug_stables.add((), tokens);
+                None
+            }
+            SnafuAttribute::StripSuffix(tokens, words) => {
+                strip_suffixes.add(words, tokens);
+                None
+            }
+            SnafuAttribute::Termination(tokens) => {
+                terminations.add((), tokens);
+                None
+            }
+            SnafuAttribute::Wasm(tokens) => {
+                wasms.add((), tokens);
+                None
+            }
+            SnafuAttribute::Catalog(tokens) => {
+                catalogs.add((), tokens);
+                None
+            }
+            SnafuAttribute::NoPanic(tokens) => {
+                no_panics.add((), tokens);
+                None
+            }
+            SnafuAttribute::Flatten(tokens) => {
+                errors
+                    .scoped(ErrorLocation::OnNamedStruct)
+                    .add(tokens, ATTR_FLATTEN);
+                None
+            }
             other => Some(other),
         })
         .collect();
@@ -971,44 +2429,140 @@ fn parse_snafu_named_struct(
     let crate_root = maybe_crate_root.unwrap_or_else(default_crate_root);
     errors.extend(errs);
 
+    let (skip_display, errs) = display_skips.finish();
+    let skip_display = skip_display.is_some();
+    errors.extend(errs);
+
+    let (display_buffer, errs) = display_buffers.finish_with_location();
+    errors.extend(errs);
+    let display_buffer = display_buffer_from_arg(display_buffer, skip_display, &mut errors);
+
+    let (stable_debug, errs) = debug_stables.finish();
+    let stable_debug = stable_debug.is_some();
+    errors.extend(errs);
+
+    let (strip_suffixes, errs) = strip_suffixes.finish();
+    let strip_suffixes = strip_suffixes.unwrap_or_else(default_strip_suffixes);
+    errors.extend(errs);
+
+    let (termination, errs) = terminations.finish();
+    let termination = termination.is_some();
+    errors.extend(errs);
+
+    let (wasm, errs) = wasms.finish();
+    let wasm = wasm.is_some();
+    errors.extend(errs);
+
+    let (catalog, errs) = catalogs.finish();
+    let catalog = catalog.is_some();
+    errors.extend(errs);
+
+    let (no_panic, errs) = no_panics.finish();
+    errors.extend(errs);
+    if no_panic.is_some() {
+        check_no_panic(panicking, &mut errors);
+    }
+
     errors.finish()?;
 
     Ok(NamedStructInfo {
         crate_root,
         field_container,
         generics,
+        visibility,
+        skip_display,
+        display_buffer,
+        stable_debug,
+        termination,
+        wasm,
+        catalog,
+        strip_suffixes,
     })
 }
 
 fn parse_snafu_tuple_struct(
     mut fields: syn::FieldsUnnamed,
     name: syn::Ident,
+    visibility: syn::Visibility,
     generics: syn::Generics,
     attrs: Vec<syn::Attribute>,
     span: proc_macro2::Span,
 ) -> MultiSynResult<TupleStructInfo> {
     let mut transformations = AtMostOne::new("source(from)", ErrorLocation::OnTupleStruct);
     let mut crate_roots = AtMostOne::new("crate_root", ErrorLocation::OnTupleStruct);
+    let mut display_skips = AtMostOne::new("display(skip)", ErrorLocation::OnTupleStruct);
+    let mut terminations = AtMostOne::new("termination", ErrorLocation::OnTupleStruct);
+    let mut wasms = AtMostOne::new("wasm", ErrorLocation::OnTupleStruct);
+    let mut http_statuses = AtMostOne::new("http_status", ErrorLocation::OnTupleStruct);
+    let mut c_codes = AtMostOne::new("c_code", ErrorLocation::OnTupleStruct);
+    let mut no_panics = AtMostOne::new("no_panic", ErrorLocation::OnTupleStruct);
 
     let mut errors = SyntaxErrors::default();
     let mut struct_errors = errors.scoped(ErrorLocation::OnTupleStruct);
 
-    for attr in attributes_from_syn(attrs)? {
+    let attrs = attributes_from_syn(attrs)?;
+    let panicking = panicking_attributes(&attrs);
+
+    for attr in attrs {
         match attr {
             SnafuAttribute::Display(tokens, ..) => struct_errors.add(tokens, ATTR_DISPLAY),
+            SnafuAttribute::DisplaySkip(tokens) => display_skips.add((), tokens),
+            SnafuAttribute::DisplayBuffer(tokens, ..) => {
+                struct_errors.add(tokens, ATTR_DISPLAY_BUFFER)
+            }
+            SnafuAttribute::DisplayInternal(tokens, ..) => {
+                struct_errors.add(tokens, ATTR_DISPLAY_INTERNAL)
+            }
+            SnafuAttribute::DebugStable(tokens) => struct_errors.add(tokens, ATTR_DEBUG_STABLE),
+            SnafuAttribute::Dedup(tokens) => struct_errors.add(tokens, ATTR_DEDUP),
+            SnafuAttribute::ExitCode(tokens, ..) => struct_errors.add(tokens, ATTR_EXIT_CODE),
+            SnafuAttribute::Code(tokens, ..) => struct_errors.add(tokens, ATTR_CODE),
+            SnafuAttribute::Help(tokens, ..) => struct_errors.add(tokens, ATTR_HELP),
+            SnafuAttribute::Url(tokens, ..) => struct_errors.add(tokens, ATTR_URL),
+            SnafuAttribute::Label(tokens, ..) => struct_errors.add(tokens, ATTR_LABEL),
+            SnafuAttribute::Flatten(tokens) => struct_errors.add(tokens, ATTR_FLATTEN),
             SnafuAttribute::Visibility(tokens, ..) => struct_errors.add(tokens, ATTR_VISIBILITY),
             SnafuAttribute::Source(tokens, ss) => {
                 for s in ss {
                     match s {
                         Source::Flag(..) => struct_errors.add(tokens.clone(), ATTR_SOURCE_BOOL),
                         Source::From(t, e) => transformations.add((t, e), tokens.clone()),
+                        Source::Accessor(..) => {
+                            struct_errors.add(tokens.clone(), ATTR_SOURCE_ACCESSOR)
+                        }
                     }
                 }
             }
             SnafuAttribute::Backtrace(tokens, ..) => struct_errors.add(tokens, ATTR_BACKTRACE),
             SnafuAttribute::Context(tokens, ..) => struct_errors.add(tokens, ATTR_CONTEXT),
+            SnafuAttribute::Convert(tokens, ..) => struct_errors.add(tokens, ATTR_CONVERT),
             SnafuAttribute::Whatever(tokens) => struct_errors.add(tokens, ATTR_CONTEXT),
             SnafuAttribute::CrateRoot(tokens, root) => crate_roots.add(root, tokens),
+            SnafuAttribute::Kind(tokens) => struct_errors.add(tokens, ATTR_KIND),
+            SnafuAttribute::Predicates(tokens) => struct_errors.add(tokens, ATTR_PREDICATES),
+            SnafuAttribute::ReportConversions(tokens) => {
+                struct_errors.add(tokens, ATTR_REPORT_CONVERSIONS)
+            }
+            SnafuAttribute::StripSuffix(tokens, ..) => struct_errors.add(tokens, ATTR_STRIP_SUFFIX),
+            SnafuAttribute::Serialize(tokens, ..) => struct_errors.add(tokens, ATTR_SERIALIZE),
+            SnafuAttribute::Termination(tokens) => terminations.add((), tokens),
+            SnafuAttribute::Wasm(tokens) => wasms.add((), tokens),
+            SnafuAttribute::Tracing(tokens, ..) => struct_errors.add(tokens, ATTR_TRACING),
+            SnafuAttribute::Log(tokens, ..) => struct_errors.add(tokens, ATTR_LOG),
+            SnafuAttribute::Metric(tokens, ..) => struct_errors.add(tokens, ATTR_METRIC),
+            SnafuAttribute::OpenTelemetry(tokens, ..) => {
+                struct_errors.add(tokens, ATTR_OPENTELEMETRY)
+            }
+            SnafuAttribute::HttpStatus(tokens, code) => http_statuses.add(code, tokens),
+            SnafuAttribute::CCode(tokens, code) => c_codes.add(code, tokens),
+            SnafuAttribute::Grpc(tokens, ..) => struct_errors.add(tokens, ATTR_GRPC),
+            SnafuAttribute::IoKind(tokens, ..) => struct_errors.add(tokens, ATTR_IO_KIND),
+            SnafuAttribute::Implicit(tokens) => struct_errors.add(tokens, ATTR_IMPLICIT),
+            SnafuAttribute::Accessors(tokens) => struct_errors.add(tokens, ATTR_ACCESSORS),
+            SnafuAttribute::Fields(tokens) => struct_errors.add(tokens, ATTR_FIELDS),
+            SnafuAttribute::Catalog(tokens) => struct_errors.add(tokens, ATTR_CATALOG),
+            SnafuAttribute::NoPanic(tokens) => no_panics.add((), tokens),
+            SnafuAttribute::Cold(tokens, ..) => struct_errors.add(tokens, ATTR_COLD),
             SnafuAttribute::DocComment(..) => { /* Just a regular doc comment. */ }
         }
     }
@@ -1040,6 +2594,32 @@ fn parse_snafu_tuple_struct(
     let crate_root = maybe_crate_root.unwrap_or_else(default_crate_root);
     errors.extend(errs);
 
+    let (skip_display, errs) = display_skips.finish();
+    let skip_display = skip_display.is_some();
+    errors.extend(errs);
+
+    let (termination, errs) = terminations.finish();
+    let termination = termination.is_some();
+    errors.extend(errs);
+
+    let (wasm, errs) = wasms.finish();
+    let wasm = wasm.is_some();
+    errors.extend(errs);
+
+    let (http_status, errs) = http_statuses.finish();
+    errors.extend(errs);
+    let http_status = http_status.and_then(|code| http_status_from_arg(code, &mut errors));
+
+    let (c_code, errs) = c_codes.finish();
+    errors.extend(errs);
+    let c_code = c_code.and_then(|code| c_code_from_arg(code, &mut errors));
+
+    let (no_panic, errs) = no_panics.finish();
+    errors.extend(errs);
+    if no_panic.is_some() {
+        check_no_panic(panicking, &mut errors);
+    }
+
     errors.finish()?;
 
     Ok(TupleStructInfo {
@@ -1047,6 +2627,12 @@ fn parse_snafu_tuple_struct(
         name,
         generics,
         transformation,
+        visibility,
+        skip_display,
+        termination,
+        wasm,
+        http_status,
+        c_code,
     })
 }
 
@@ -1067,6 +2653,15 @@ impl Context {
 enum Source {
     Flag(bool),
     From(syn::Type, syn::Expr),
+    Accessor(Option<syn::Ident>),
+}
+
+/// A `From` implementation converting another error enum into this
+/// one, one variant at a time.
+struct Conversion {
+    from_type: syn::Type,
+    /// Pairs of the other enum's variant and this enum's variant
+    mappings: Vec<(syn::Ident, syn::Ident)>,
 }
 
 /// A SnafuAttribute represents one SNAFU-specific attribute inside of `#[snafu(...)]`.  For
@@ -1077,11 +2672,44 @@ enum Source {
 /// with the data.  The location can be used to give accurate error messages in case there was a
 /// problem with the use of the attribute.
 enum SnafuAttribute {
-    Display(proc_macro2::TokenStream, UserInput),
+    Display(proc_macro2::TokenStream, Vec<syn::Expr>),
+    DisplaySkip(proc_macro2::TokenStream),
+    DisplayBuffer(proc_macro2::TokenStream, syn::LitInt),
+    DisplayInternal(proc_macro2::TokenStream, Vec<syn::Expr>),
+    DebugStable(proc_macro2::TokenStream),
+    Dedup(proc_macro2::TokenStream),
+    ExitCode(proc_macro2::TokenStream, syn::LitInt),
+    Code(proc_macro2::TokenStream, Vec<syn::Expr>),
+    Help(proc_macro2::TokenStream, Vec<syn::Expr>),
+    Url(proc_macro2::TokenStream, Vec<syn::Expr>),
+    Label(proc_macro2::TokenStream, Vec<syn::Expr>),
+    Flatten(proc_macro2::TokenStream),
+    Kind(proc_macro2::TokenStream),
+    Predicates(proc_macro2::TokenStream),
+    ReportConversions(proc_macro2::TokenStream),
+    Serialize(proc_macro2::TokenStream, bool),
+    StripSuffix(proc_macro2::TokenStream, Vec<String>),
+    Termination(proc_macro2::TokenStream),
+    Wasm(proc_macro2::TokenStream),
+    Tracing(proc_macro2::TokenStream, syn::Ident),
+    Log(proc_macro2::TokenStream, Log),
+    Metric(proc_macro2::TokenStream, Metric),
+    NoPanic(proc_macro2::TokenStream),
+    OpenTelemetry(proc_macro2::TokenStream, OpenTelemetry),
+    HttpStatus(proc_macro2::TokenStream, Option<syn::LitInt>),
+    CCode(proc_macro2::TokenStream, syn::LitInt),
+    Grpc(proc_macro2::TokenStream, Grpc),
+    IoKind(proc_macro2::TokenStream, syn::Ident),
+    Implicit(proc_macro2::TokenStream),
+    Accessors(proc_macro2::TokenStream),
+    Fields(proc_macro2::TokenStream),
+    Catalog(proc_macro2::TokenStream),
+    Cold(proc_macro2::TokenStream, bool),
     Visibility(proc_macro2::TokenStream, UserInput),
     Source(proc_macro2::TokenStream, Vec<Source>),
     Backtrace(proc_macro2::TokenStream, bool),
     Context(proc_macro2::TokenStream, Context),
+    Convert(proc_macro2::TokenStream, Conversion),
     Whatever(proc_macro2::TokenStream),
     CrateRoot(proc_macro2::TokenStream, UserInput),
     DocComment(proc_macro2::TokenStream, String),
@@ -1091,6 +2719,10 @@ fn default_crate_root() -> UserInput {
     Box::new(quote! { ::snafu })
 }
 
+fn default_strip_suffixes() -> Vec<String> {
+    vec!["Error".to_string()]
+}
+
 fn private_visibility() -> UserInput {
     Box::new(quote! {})
 }
@@ -1135,24 +2767,41 @@ trait GenericAwareNames {
         Box::new(quote! { #enum_name<#(#original_generics,)*> })
     }
 
-    fn provided_generic_types_without_defaults(&self) -> Vec<proc_macro2::TokenStream> {
-        use syn::TypeParam;
+    /// Type and const parameters are returned in the order they were
+    /// declared, as the two kinds may be interleaved.
+    fn provided_generic_types_and_consts_without_defaults(&self) -> Vec<proc_macro2::TokenStream> {
+        use syn::{ConstParam, GenericParam, TypeParam};
         self.generics()
-            .type_params()
-            .map(|t: &TypeParam| {
-                let TypeParam {
+            .params
+            .iter()
+            .flat_map(|p| match p {
+                GenericParam::Type(TypeParam {
                     attrs,
                     ident,
                     colon_token,
                     bounds,
                     ..
-                } = t;
-                quote! {
+                }) => Some(quote! {
                     #(#attrs)*
                     #ident
                     #colon_token
                     #bounds
-                }
+                }),
+                GenericParam::Const(ConstParam {
+                    attrs,
+                    const_token,
+                    ident,
+                    colon_token,
+                    ty,
+                    ..
+                }) => Some(quote! {
+                    #(#attrs)*
+                    #const_token
+                    #ident
+                    #colon_token
+                    #ty
+                }),
+                GenericParam::Lifetime(..) => None,
             })
             .collect()
     }
@@ -1160,7 +2809,7 @@ trait GenericAwareNames {
     fn provided_generics_without_defaults(&self) -> Vec<proc_macro2::TokenStream> {
         self.provided_generic_lifetimes()
             .into_iter()
-            .chain(self.provided_generic_types_without_defaults().into_iter())
+            .chain(self.provided_generic_types_and_consts_without_defaults())
             .collect()
     }
 
@@ -1200,18 +2849,165 @@ trait GenericAwareNames {
     }
 }
 
+/// Bounds required by source fields whose types use one of the
+/// error's generic type parameters. These are added to the generated
+/// implementations so that users do not need to restate them.
+#[derive(Default)]
+struct SourceBounds {
+    error: Vec<proc_macro2::TokenStream>,
+    display: Vec<proc_macro2::TokenStream>,
+    error_compat: Vec<proc_macro2::TokenStream>,
+}
+
+impl SourceBounds {
+    fn new<'a>(
+        name: &syn::Ident,
+        generics: &syn::Generics,
+        crate_root: &dyn quote::ToTokens,
+        field_containers: impl IntoIterator<Item = &'a FieldContainer>,
+    ) -> Self {
+        let type_params: Vec<_> = generics.type_params().map(|t| &t.ident).collect();
+        let self_ty = syn::Ident::new("Self", proc_macro2::Span::call_site());
+        let mut bounds = SourceBounds::default();
+        let mut seen = Vec::new();
+
+        for field_container in field_containers {
+            let selector_kind = &field_container.selector_kind;
+            if selector_kind.is_whatever() {
+                continue;
+            }
+
+            let source_field = match selector_kind.source_field() {
+                Some(source_field) => source_field,
+                None => continue,
+            };
+
+            let ty = &source_field.ty;
+            let key = (quote! { #ty }.to_string(), source_field.backtrace_delegate);
+            if seen.contains(&key) || !mentions_any(quote! { #ty }, &type_params) {
+                continue;
+            }
+            // A recursive error's bounds would refer to the
+            // implementations being generated, which the compiler
+            // cannot resolve.
+            if mentions_any(quote! { #ty }, &[name, &self_ty]) {
+                continue;
+            }
+            seen.push(key);
+
+            bounds
+                .error
+                .push(quote! { #ty: ::core::fmt::Debug + ::core::fmt::Display + #crate_root::AsErrorSource });
+            bounds.display.push(quote! { #ty: ::core::fmt::Display });
+            if source_field.backtrace_delegate {
+                bounds
+                    .error_compat
+                    .push(quote! { #ty: #crate_root::ErrorCompat });
+            }
+        }
+
+        bounds
+    }
+
+    fn error(&self, where_clauses: &[proc_macro2::TokenStream]) -> Vec<proc_macro2::TokenStream> {
+        [where_clauses, &self.error].concat()
+    }
+
+    fn display(&self, where_clauses: &[proc_macro2::TokenStream]) -> Vec<proc_macro2::TokenStream> {
+        [where_clauses, &self.display].concat()
+    }
+
+    fn error_compat(
+        &self,
+        where_clauses: &[proc_macro2::TokenStream],
+    ) -> Vec<proc_macro2::TokenStream> {
+        [where_clauses, &self.error_compat].concat()
+    }
+
+    /// Context selectors produce the error, so it must implement
+    /// `Error` and `ErrorCompat`.
+    fn context_selector(
+        &self,
+        where_clauses: &[proc_macro2::TokenStream],
+    ) -> Vec<proc_macro2::TokenStream> {
+        [where_clauses, &self.error, &self.error_compat].concat()
+    }
+}
+
+/// Like the standard derive, a generated `Debug` implementation
+/// requires every generic type parameter to implement `Debug`.
+fn debug_bounds(
+    generics: &syn::Generics,
+    where_clauses: &[proc_macro2::TokenStream],
+) -> Vec<proc_macro2::TokenStream> {
+    let bounds = generics.type_params().map(|t| {
+        let ident = &t.ident;
+        quote! { #ident: ::core::fmt::Debug }
+    });
+    where_clauses.iter().cloned().chain(bounds).collect()
+}
+
+/// Checks if any of the identifiers appear anywhere in the tokens.
+fn mentions_any(tokens: proc_macro2::TokenStream, idents: &[&syn::Ident]) -> bool {
+    use proc_macro2::TokenTree;
+
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ident) => idents.iter().any(|i| **i == ident),
+        TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false,
+    })
+}
+
 impl EnumInfo {
+    fn source_bounds(&self) -> SourceBounds {
+        SourceBounds::new(&self.name, &self.generics, &self.crate_root, &self.variants)
+    }
+
     fn generate_snafu(self) -> proc_macro2::TokenStream {
         let context_selectors = ContextSelectors(&self);
         let display_impl = DisplayImpl(&self);
         let error_impl = ErrorImpl(&self);
         let error_compat_impl = ErrorCompatImpl(&self);
+        let kind_impl = KindImpl(&self);
+        let predicates_impl = PredicatesImpl(&self);
+        let accessors_impl = AccessorsImpl(&self);
+        let fields_impl = FieldsImpl(&self);
+        let source_accessors_impl = SourceAccessorsImpl(&self);
+        let convert_impl = ConvertImpl(&self);
+        let stable_debug_impl = StableDebugImpl(&self);
+        let report_conversions_impl = ReportConversionsImpl(&self);
+        let serialize_impl = SerializeImpl(&self);
+        let termination_impl = TerminationImpl(&self);
+        let wasm_impl = WasmImpl(&self);
+        let diagnostic_impl = DiagnosticImpl(&self);
+        let http_status_impl = HttpStatusImpl(&self);
+        let c_code_impl = CCodeImpl(&self);
+        let grpc_impl = GrpcImpl(&self);
+        let io_kind_impl = IoKindImpl(&self);
+        let catalog_impl = CatalogImpl(&self);
 
         quote! {
             #context_selectors
             #display_impl
             #error_impl
             #error_compat_impl
+            #kind_impl
+            #predicates_impl
+            #accessors_impl
+            #fields_impl
+            #source_accessors_impl
+            #convert_impl
+            #stable_debug_impl
+            #report_conversions_impl
+            #serialize_impl
+            #termination_impl
+            #wasm_impl
+            #diagnostic_impl
+            #http_status_impl
+            #c_code_impl
+            #grpc_impl
+            #io_kind_impl
+            #catalog_impl
         }
     }
 }
@@ -1269,18 +3065,43 @@ impl<'a> quote::ToTokens for ContextSelector<'a> {
             enum_name, variant_name,
         );
 
+        let dedup = self.0.dedup
+            && selector_kind
+                .source_field()
+                .map_or(false, |f| is_boxed_self(&f.ty, enum_name));
+
+        let metric = counter_name(self.1.metric.as_ref(), self.0.metric.as_ref(), enum_name);
+        let opentelemetry = opentelemetry_fields(self.1, self.0.opentelemetry);
+
         let context_selector = ContextSelector {
             backtrace_field: self.1.backtrace_field.as_ref(),
+            cold: self.1.cold.or(self.0.cold).unwrap_or(true),
+            dedup,
+            implicit_fields: &self.1.implicit_fields,
             crate_root: &self.0.crate_root,
+            doc_comment: &self.1.doc_comment,
             error_constructor_name: &quote! { #enum_name::#variant_name },
             original_generics_without_defaults: &self.0.provided_generics_without_defaults(),
             parameterized_error_name: &self.0.parameterized_name(),
             selector_doc_string: &selector_doc_string,
-            selector_kind: &selector_kind,
+            selector_kind,
             selector_name: variant_name,
-            user_fields: &selector_kind.user_fields(),
+            strip_suffixes: &self.0.strip_suffixes,
+            tracing: self
+                .1
+                .tracing
+                .as_ref()
+                .or(self.0.tracing.as_ref())
+                .map(|l| l as _),
+            log: self.1.log.as_ref().or(self.0.log.as_ref()),
+            metric: metric.as_ref(),
+            opentelemetry: opentelemetry.as_ref().map(|f| &**f),
+            user_fields: selector_kind.user_fields(),
             visibility: Some(&visibility),
-            where_clauses: &self.0.provided_where_clauses(),
+            where_clauses: &self
+                .0
+                .source_bounds()
+                .context_selector(&self.0.provided_where_clauses()),
         };
 
         stream.extend(quote! { #context_selector });
@@ -1291,7 +3112,11 @@ struct DisplayImpl<'a>(&'a EnumInfo);
 
 impl<'a> quote::ToTokens for DisplayImpl<'a> {
     fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
-        use self::shared::{Display, DisplayMatchArm};
+        if self.0.skip_display {
+            return;
+        }
+
+        use self::shared::{Display, DisplayMatchArm, FormatInternal};
 
         let enum_name = &self.0.name;
 
@@ -1311,7 +3136,7 @@ impl<'a> quote::ToTokens for DisplayImpl<'a> {
 
                 let arm = DisplayMatchArm {
                     backtrace_field: backtrace_field.as_ref(),
-                    default_name: &variant_name,
+                    default_name: variant_name,
                     display_format: display_format.as_ref().map(|f| &**f),
                     doc_comment,
                     pattern_ident: &quote! { #enum_name::#variant_name },
@@ -1322,98 +3147,1268 @@ impl<'a> quote::ToTokens for DisplayImpl<'a> {
             })
             .collect();
 
+        let internal_arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .flat_map(|variant| {
+                let FieldContainer {
+                    backtrace_field,
+                    display_internal_format,
+                    doc_comment,
+                    name: variant_name,
+                    selector_kind,
+                    ..
+                } = variant;
+
+                let display_internal_format = display_internal_format.as_ref()?;
+
+                let arm = DisplayMatchArm {
+                    backtrace_field: backtrace_field.as_ref(),
+                    default_name: variant_name,
+                    display_format: Some(&**display_internal_format),
+                    doc_comment,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                    selector_kind,
+                };
+
+                Some(quote! { #arm })
+            })
+            .collect();
+
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self
+            .0
+            .source_bounds()
+            .display(&self.0.provided_where_clauses());
+
         let display = Display {
+            crate_root: &self.0.crate_root,
+            visibility: &self.0.visibility,
             arms: &arms,
-            original_generics: &self.0.provided_generics_without_defaults(),
-            parameterized_error_name: &self.0.parameterized_name(),
-            where_clauses: &self.0.provided_where_clauses(),
+            buffer: self.0.display_buffer,
+            original_generics: &original_generics,
+            parameterized_error_name: &parameterized_error_name,
+            where_clauses: &where_clauses,
+        };
+
+        let format_internal = FormatInternal {
+            crate_root: &self.0.crate_root,
+            arms: &internal_arms,
+            original_generics: &original_generics,
+            parameterized_error_name: &parameterized_error_name,
+            where_clauses: &where_clauses,
         };
 
-        let display_impl = quote! { #display };
+        let display_impl = quote! {
+            #display
+            #format_internal
+        };
+
+        stream.extend(display_impl)
+    }
+}
+
+struct ErrorImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for ErrorImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Error, ErrorSourceMatchArm};
+
+        let (variants_to_description, variants_to_source): (Vec<_>, Vec<_>) = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+                let pattern_ident = &quote! { #enum_name::#variant_name };
+
+                let error_description_match_arm = quote! {
+                    #pattern_ident { .. } => stringify!(#pattern_ident),
+                };
+
+                let error_source_match_arm = ErrorSourceMatchArm {
+                    field_container,
+                    pattern_ident,
+                };
+                let error_source_match_arm = quote! { #error_source_match_arm };
+
+                (error_description_match_arm, error_source_match_arm)
+            })
+            .unzip();
+
+        let error_impl = Error {
+            crate_root: &self.0.crate_root,
+            parameterized_error_name: &self.0.parameterized_name(),
+            description_arms: &variants_to_description,
+            source_arms: &variants_to_source,
+            original_generics: &self.0.provided_generics_without_defaults(),
+            where_clauses: &self
+                .0
+                .source_bounds()
+                .error(&self.0.provided_where_clauses()),
+        };
+        let error_impl = quote! { #error_impl };
+
+        stream.extend(error_impl);
+    }
+}
+
+struct ErrorCompatImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for ErrorCompatImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{
+            ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatMetadataMatchArm,
+        };
+
+        let enum_name = &self.0.name;
+
+        let variants_to_backtrace: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let crate_root = &self.0.crate_root;
+                let variant_name = &field_container.name;
+
+                let match_arm = ErrorCompatBacktraceMatchArm {
+                    field_container,
+                    crate_root,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
+
+        let metadata_match_arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let variant_name = &field_container.name;
+
+                let match_arm = ErrorCompatMetadataMatchArm {
+                    field_container,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                (match_arm.add_context(), match_arm.metadata())
+            })
+            .collect();
+        // Key-value pairs can only be attached when every variant can
+        // hold them.
+        let variants_to_add_context: Vec<_> = metadata_match_arms
+            .iter()
+            .map(|(add_context, _)| add_context.clone())
+            .collect::<Option<_>>()
+            .unwrap_or_default();
+        let variants_to_metadata: Vec<_> = metadata_match_arms
+            .into_iter()
+            .flat_map(|(_, metadata)| metadata)
+            .collect();
+
+        let variants_to_exit_code: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .flat_map(|field_container| {
+                let variant_name = &field_container.name;
+                shared::exit_code_match_arm(field_container, &quote! { #enum_name::#variant_name })
+            })
+            .collect();
+
+        let error_compat_impl = ErrorCompat {
+            crate_root: &self.0.crate_root,
+            parameterized_error_name: &self.0.parameterized_name(),
+            backtrace_arms: &variants_to_backtrace,
+            add_context_arms: &variants_to_add_context,
+            metadata_arms: &variants_to_metadata,
+            exit_code_arms: &variants_to_exit_code,
+            original_generics: &self.0.provided_generics_without_defaults(),
+            where_clauses: &self
+                .0
+                .source_bounds()
+                .error_compat(&self.0.provided_where_clauses()),
+        };
+
+        let error_compat_impl = quote! { #error_compat_impl };
+
+        stream.extend(error_compat_impl);
+    }
+}
+
+struct KindImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for KindImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if !self.0.kind {
+            return;
+        }
+        let visibility = &self.0.visibility;
+        let constness = constness();
+
+        let enum_name = &self.0.name;
+        let kind_name = quote::format_ident!("{}Kind", enum_name);
+        let kind_doc_string = format!("The kinds of errors represented by `{}`", enum_name);
+
+        let variant_names: Vec<_> = self.0.variants.iter().map(|v| &v.name).collect();
+        let variant_doc_strings = variant_names
+            .iter()
+            .map(|variant_name| format!("The kind of `{}::{}`", enum_name, variant_name));
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        stream.extend(quote! {
+            #[doc = #kind_doc_string]
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+            #visibility enum #kind_name {
+                #(
+                    #[doc = #variant_doc_strings]
+                    #variant_names,
+                )*
+            }
+
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                /// Returns the kind of this error, allowing it to be
+                /// categorized without matching on its fields.
+                #visibility #constness fn kind(&self) -> #kind_name {
+                    match *self {
+                        #(#enum_name::#variant_names { .. } => #kind_name::#variant_names,)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct PredicatesImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for PredicatesImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if !self.0.predicates {
+            return;
+        }
+        let visibility = &self.0.visibility;
+        let constness = constness();
+        let enum_name = &self.0.name;
+
+        let predicates = self.0.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            let predicate_name =
+                quote::format_ident!("is_{}", to_snake_case(&variant_name.to_string()));
+            let predicate_doc_string = format!(
+                "Returns true if this is the `{}::{}` variant",
+                enum_name, variant_name,
+            );
+
+            quote! {
+                #[doc = #predicate_doc_string]
+                #[allow(unreachable_patterns)]
+                #visibility #constness fn #predicate_name(&self) -> bool {
+                    match *self {
+                        #enum_name::#variant_name { .. } => true,
+                        _ => false,
+                    }
+                }
+            }
+        });
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        stream.extend(quote! {
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #(#predicates)*
+            }
+        })
+    }
+}
+
+struct StableDebugImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for StableDebugImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::{StableDebug, StableDebugMatchArm};
+
+        if !self.0.stable_debug {
+            return;
+        }
+        let enum_name = &self.0.name;
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_name = &variant.name;
+                let arm = StableDebugMatchArm {
+                    field_container: variant,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+                quote! { #arm }
+            })
+            .collect();
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let debug_impl = StableDebug {
+            arms: &arms,
+            original_generics: &original_generics,
+            parameterized_error_name: &parameterized_error_name,
+            where_clauses: &debug_bounds(&self.0.generics, &where_clauses),
+        };
+
+        stream.extend(quote! { #debug_impl })
+    }
+}
+
+struct SerializeImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for SerializeImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        let include_backtrace = match self.0.serialize {
+            Some(include_backtrace) => include_backtrace,
+            None => return,
+        };
+        let crate_root = &self.0.crate_root;
+        let visibility = &self.0.visibility;
+        let enum_name = &self.0.name;
+
+        let arms = self.0.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            let variant_name_string = variant_name.to_string();
+            let user_fields = variant.selector_kind.user_fields();
+
+            let bindings = user_fields.iter().map(|f| f.binding());
+            let fields = user_fields.iter().map(|f| {
+                let name = f.name();
+                let key = name.to_string();
+                let key = key.trim_start_matches("r#");
+                quote! {
+                    .with_field(#key, ::std::format!("{:?}", #name))
+                }
+            });
+
+            quote! {
+                #enum_name::#variant_name { #(#bindings,)* .. } => {
+                    __snafu_serializable.with_variant(#variant_name_string) #(#fields)*
+                }
+            }
+        });
+
+        let (backtrace, backtrace_bound) = if include_backtrace {
+            let backtrace = quote! {
+                let __snafu_serializable = match #crate_root::ErrorCompat::backtrace(self) {
+                    ::core::option::Option::Some(backtrace) => {
+                        __snafu_serializable.with_backtrace(backtrace)
+                    }
+                    ::core::option::Option::None => __snafu_serializable,
+                };
+            };
+            (backtrace, quote! { + #crate_root::ErrorCompat })
+        } else {
+            (quote! {}, quote! {})
+        };
+
+        let doc_string = format!(
+            "Creates a `SerializableError` with the variant name, message, context fields, and sources of this `{}`",
+            enum_name,
+        );
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        stream.extend(quote! {
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc = #doc_string]
+                #visibility fn to_serializable(&self) -> #crate_root::SerializableError
+                where
+                    Self: #crate_root::Error #backtrace_bound,
+                {
+                    let __snafu_serializable = #crate_root::SerializableError::from_error(self);
+                    let __snafu_serializable = match *self {
+                        #(#arms)*
+                    };
+                    #backtrace
+                    __snafu_serializable
+                }
+            }
+
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #crate_root::__serde::Serialize for #parameterized_error_name
+            where
+                #(#where_clauses,)*
+                Self: #crate_root::Error #backtrace_bound,
+            {
+                fn serialize<__S>(
+                    &self,
+                    serializer: __S,
+                ) -> ::core::result::Result<__S::Ok, __S::Error>
+                where
+                    __S: #crate_root::__serde::Serializer,
+                {
+                    #crate_root::__serde::Serialize::serialize(&self.to_serializable(), serializer)
+                }
+            }
+        })
+    }
+}
+
+struct TerminationImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for TerminationImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::Termination;
+
+        if !self.0.termination {
+            return;
+        }
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let termination_impl = Termination {
+            crate_root: &self.0.crate_root,
+            parameterized_error_name: &parameterized_error_name,
+            original_generics: &original_generics,
+            where_clauses: &where_clauses,
+        };
+
+        stream.extend(quote! { #termination_impl })
+    }
+}
+
+struct WasmImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for WasmImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::Wasm;
+
+        if !self.0.wasm {
+            return;
+        }
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let wasm_impl = Wasm {
+            crate_root: &self.0.crate_root,
+            parameterized_error_name: &parameterized_error_name,
+            original_generics: &original_generics,
+            where_clauses: &where_clauses,
+        };
+
+        stream.extend(quote! { #wasm_impl })
+    }
+}
+
+struct HttpStatusImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for HttpStatusImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::{http_status_match_arm, HttpStatus};
+
+        if self.0.http_status.is_none() && self.0.variants.iter().all(|v| v.http_status.is_none()) {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let default = self.0.http_status.unwrap_or(500);
+
+        let arms = self.0.variants.iter().map(|field_container| {
+            let variant_name = &field_container.name;
+            http_status_match_arm(
+                field_container,
+                &quote! { #enum_name::#variant_name },
+                default,
+            )
+        });
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let http_status_impl = HttpStatus {
+            crate_root: &self.0.crate_root,
+            parameterized_error_name: &parameterized_error_name,
+            original_generics: &original_generics,
+            where_clauses: &where_clauses,
+            visibility: &self.0.visibility,
+            status: &quote! {
+                match *self {
+                    #(#arms,)*
+                }
+            },
+        };
+
+        stream.extend(quote! { #http_status_impl })
+    }
+}
+
+struct CCodeImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for CCodeImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::{c_code_match_arm, CCode};
+
+        if self.0.c_code.is_none() && self.0.variants.iter().all(|v| v.c_code.is_none()) {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let default = self.0.c_code.unwrap_or(-1);
+
+        let arms = self.0.variants.iter().map(|field_container| {
+            let variant_name = &field_container.name;
+            c_code_match_arm(
+                field_container,
+                &quote! { #enum_name::#variant_name },
+                default,
+            )
+        });
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let c_code_impl = CCode {
+            crate_root: &self.0.crate_root,
+            parameterized_error_name: &parameterized_error_name,
+            original_generics: &original_generics,
+            where_clauses: &where_clauses,
+            visibility: &self.0.visibility,
+            code: &quote! {
+                match *self {
+                    #(#arms,)*
+                }
+            },
+        };
+
+        stream.extend(quote! { #c_code_impl })
+    }
+}
+
+struct GrpcImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for GrpcImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::{grpc_metadata_types, Grpc, GrpcMatchArms};
+
+        if self.0.grpc.is_none() && self.0.variants.iter().all(|v| v.grpc.is_none()) {
+            return;
+        }
+
+        let crate_root = &self.0.crate_root;
+        let enum_name = &self.0.name;
+        let default = match self.0.grpc.as_ref().and_then(|g| g.code.as_ref()) {
+            Some(code) => quote! { #crate_root::__tonic::Code::#code },
+            None => quote! { #crate_root::__tonic::Code::Internal },
+        };
+
+        let mut code_arms = Vec::new();
+        let mut metadata_arms = Vec::new();
+        let mut metadata_types = Vec::new();
+
+        for field_container in &self.0.variants {
+            let variant_name = &field_container.name;
+            let arms = GrpcMatchArms {
+                crate_root,
+                field_container,
+                pattern_ident: &quote! { #enum_name::#variant_name },
+            };
+
+            code_arms.push(arms.code(&default));
+            metadata_arms.extend(arms.metadata());
+            metadata_types.extend(grpc_metadata_types(field_container));
+        }
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let grpc_impl = Grpc {
+            crate_root,
+            parameterized_error_name: &parameterized_error_name,
+            original_generics: &original_generics,
+            where_clauses: &where_clauses,
+            visibility: &self.0.visibility,
+            code_arms: &code_arms,
+            metadata_arms: &metadata_arms,
+            metadata_types: &metadata_types,
+        };
+
+        stream.extend(quote! { #grpc_impl })
+    }
+}
+
+struct CatalogImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for CatalogImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::CatalogEntry;
+
+        if !self.0.catalog {
+            return;
+        }
+
+        let variants = &self.0.variants;
+
+        // Match the defaults of the generated `http_status` and
+        // `error_code` methods, when they are generated at all
+        let default_http_status =
+            if self.0.http_status.is_none() && variants.iter().all(|v| v.http_status.is_none()) {
+                None
+            } else {
+                Some(self.0.http_status.unwrap_or(500))
+            };
+
+        let default_c_code =
+            if self.0.c_code.is_none() && variants.iter().all(|v| v.c_code.is_none()) {
+                None
+            } else {
+                Some(self.0.c_code.unwrap_or(-1))
+            };
+
+        for field_container in variants {
+            let entry = CatalogEntry {
+                crate_root: &self.0.crate_root,
+                error_name: &self.0.name,
+                variant_name: Some(&field_container.name),
+                field_container,
+                skip_display: self.0.skip_display,
+                default_http_status,
+                default_c_code,
+            };
+
+            stream.extend(quote! { #entry });
+        }
+    }
+}
+
+struct IoKindImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for IoKindImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::{io_kind_match_arm, IoKind};
+
+        if self.0.io_kind.is_none() && self.0.variants.iter().all(|v| v.io_kind.is_none()) {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let default = match &self.0.io_kind {
+            Some(kind) => quote! { ::std::io::ErrorKind::#kind },
+            None => quote! { ::std::io::ErrorKind::Other },
+        };
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let variant_name = &field_container.name;
+                io_kind_match_arm(
+                    field_container,
+                    &quote! { #enum_name::#variant_name },
+                    &default,
+                )
+            })
+            .collect();
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let io_kind_impl = IoKind {
+            parameterized_error_name: &parameterized_error_name,
+            original_generics: &original_generics,
+            where_clauses: &where_clauses,
+            visibility: &self.0.visibility,
+            arms: &arms,
+        };
+
+        stream.extend(quote! { #io_kind_impl })
+    }
+}
+
+struct DiagnosticImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for DiagnosticImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use crate::shared::{Diagnostic, DiagnosticMatchArms};
+
+        if self.0.variants.iter().all(|v| v.diagnostic.is_empty()) {
+            return;
+        }
+
+        let crate_root = &self.0.crate_root;
+        let enum_name = &self.0.name;
+
+        let mut code_arms = Vec::new();
+        let mut help_arms = Vec::new();
+        let mut url_arms = Vec::new();
+        let mut labels_arms = Vec::new();
+
+        for field_container in &self.0.variants {
+            let variant_name = &field_container.name;
+            let arms = DiagnosticMatchArms {
+                field_container,
+                pattern_ident: &quote! { #enum_name::#variant_name },
+            };
+
+            code_arms.extend(arms.code());
+            help_arms.extend(arms.help());
+            url_arms.extend(arms.url());
+            labels_arms.extend(arms.labels(crate_root));
+        }
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let diagnostic_impl = Diagnostic {
+            crate_root,
+            parameterized_error_name: &parameterized_error_name,
+            code_arms: &code_arms,
+            help_arms: &help_arms,
+            url_arms: &url_arms,
+            labels_arms: &labels_arms,
+            original_generics: &original_generics,
+            where_clauses: &where_clauses,
+        };
+
+        stream.extend(quote! { #diagnostic_impl })
+    }
+}
+
+struct AccessorsImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for AccessorsImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if !self.0.accessors {
+            return;
+        }
+        let visibility = &self.0.visibility;
+        let constness = constness();
+        let enum_name = &self.0.name;
+
+        // Preserve the order in which the fields first appear
+        let mut fields: Vec<(&Field, Vec<&syn::Ident>)> = Vec::new();
+        for variant in &self.0.variants {
+            // Positional fields have no name to use for the accessor
+            for field in variant.selector_kind.user_fields() {
+                if field.is_positional() {
+                    continue;
+                }
+                match fields.iter_mut().find(|(f, _)| f.name == field.name) {
+                    Some((_, variants)) => variants.push(&variant.name),
+                    None => fields.push((field, vec![&variant.name])),
+                }
+            }
+        }
+
+        let accessors = fields.iter().map(|(field, variant_names)| {
+            let Field { name, ty, .. } = field;
+            let accessor_doc_string = format!(
+                "Returns the `{}` field of the variants that have one",
+                name,
+            );
+
+            // The conversions to the deref target are not `const`
+            let (constness, target, value) = match accessor_deref_target(ty) {
+                Some((target, conversion)) => (None, target, quote! { #name.#conversion() }),
+                None => (constness.clone(), quote! { #ty }, quote! { #name }),
+            };
+
+            quote! {
+                #[doc = #accessor_doc_string]
+                #[allow(unreachable_patterns)]
+                #visibility #constness fn #name(&self) -> ::core::option::Option<&#target> {
+                    match *self {
+                        #(#enum_name::#variant_names { ref #name, .. } => ::core::option::Option::Some(#value),)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        });
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        stream.extend(quote! {
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #(#accessors)*
+            }
+        })
+    }
+}
+
+/// Owned types whose accessors return the borrowed form, along with
+/// the method that converts to it, such as `Option<&str>` instead of
+/// `Option<&String>`.
+fn accessor_deref_target(ty: &syn::Type) -> Option<(proc_macro2::TokenStream, syn::Ident)> {
+    let path = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+
+    let (target, conversion) = match (segment.ident.to_string().as_str(), &segment.arguments) {
+        ("String", syn::PathArguments::None) => (quote! { str }, "as_str"),
+        ("PathBuf", syn::PathArguments::None) => (quote! { ::std::path::Path }, "as_path"),
+        ("Vec", syn::PathArguments::AngleBracketed(args)) if args.args.len() == 1 => {
+            match &args.args[0] {
+                syn::GenericArgument::Type(element) => (quote! { [#element] }, "as_slice"),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    Some((target, quote::format_ident!("{}", conversion)))
+}
+
+struct FieldsImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for FieldsImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if !self.0.fields {
+            return;
+        }
+        let crate_root = &self.0.crate_root;
+        let enum_name = &self.0.name;
+
+        let mut field_types = Vec::new();
+        let arms = self.0.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            let user_fields = variant.selector_kind.user_fields();
+
+            let bindings = user_fields.iter().map(|f| f.binding());
+            let visits = user_fields.iter().map(|f| {
+                let name = f.name();
+                let key = name.to_string();
+                let key = key.trim_start_matches("r#");
+                quote! { visitor(#key, #name); }
+            });
+            field_types.extend(user_fields.iter().map(|f| &f.ty));
+
+            quote! {
+                #enum_name::#variant_name { #(#bindings,)* .. } => {
+                    #(#visits)*
+                }
+            }
+        });
+        let arms: Vec<_> = arms.collect();
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        stream.extend(quote! {
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #crate_root::ErrorFields for #parameterized_error_name
+            where
+                #(#where_clauses,)*
+                #(#field_types: ::core::fmt::Display,)*
+            {
+                fn visit_fields(
+                    &self,
+                    visitor: &mut dyn ::core::ops::FnMut(&'static str, &dyn ::core::fmt::Display),
+                ) {
+                    match *self {
+                        #(#arms)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct SourceAccessorsImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for SourceAccessorsImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        let visibility = &self.0.visibility;
+        let constness = constness();
+        let enum_name = &self.0.name;
+
+        // Preserve the order in which the accessors first appear
+        // The accessor's name and type, then each variant using it
+        type Accessor<'a> = (
+            &'a syn::Ident,
+            &'a syn::Type,
+            Vec<(&'a syn::Ident, &'a SourceField)>,
+        );
+
+        let mut accessors: Vec<Accessor<'_>> = Vec::new();
+        for variant in &self.0.variants {
+            let source_field = match variant.selector_kind.source_field() {
+                Some(source_field) => source_field,
+                None => continue,
+            };
+            if let Some(accessor) = &source_field.accessor {
+                match accessors.iter_mut().find(|(name, _, _)| *name == accessor) {
+                    Some((_, _, variants)) => variants.push((&variant.name, source_field)),
+                    None => accessors.push((
+                        accessor,
+                        &source_field.ty,
+                        vec![(&variant.name, source_field)],
+                    )),
+                }
+            }
+        }
+
+        if accessors.is_empty() {
+            return;
+        }
+
+        let accessors = accessors.iter().map(|(accessor, ty, variants)| {
+            let variant_names: Vec<_> = variants
+                .iter()
+                .map(|(variant_name, _)| format!("`{}::{}`", enum_name, variant_name))
+                .collect();
+            let accessor_doc_string = format!(
+                "Returns the source of the {} variant{}",
+                variant_names.join(", "),
+                if variant_names.len() == 1 { "" } else { "s" },
+            );
+
+            let arms = variants.iter().map(|(variant_name, source_field)| {
+                let binding = source_field.binding();
+                let name = source_field.name();
+                quote! {
+                    #enum_name::#variant_name { #binding, .. } => ::core::option::Option::Some(#name),
+                }
+            });
+
+            quote! {
+                #[doc = #accessor_doc_string]
+                #[allow(unreachable_patterns)]
+                #visibility #constness fn #accessor(&self) -> ::core::option::Option<&#ty> {
+                    match *self {
+                        #(#arms)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        });
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        stream.extend(quote! {
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #(#accessors)*
+            }
+        })
+    }
+}
+
+struct ReportConversionsImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for ReportConversionsImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if !self.0.report_conversions {
+            return;
+        }
+
+        let crate_root = &self.0.crate_root;
+        let visibility = &self.0.visibility;
+
+        let conversions = self.0.variants.iter().filter_map(|variant| {
+            if variant.selector_kind.is_whatever() {
+                return None;
+            }
+
+            let source_field = variant.selector_kind.source_field()?;
+            let source_type = type_name(source_field.transformation.ty());
+            let variant_name = variant.name.to_string();
+
+            Some(quote! {
+                #crate_root::SourceConversion {
+                    source_type: #source_type,
+                    variant: #variant_name,
+                },
+            })
+        });
+
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        stream.extend(quote! {
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc(hidden)]
+                #visibility const SNAFU_CONVERSIONS: &'static [#crate_root::SourceConversion] = &[
+                    #(#conversions)*
+                ];
+            }
+        })
+    }
+}
 
-        stream.extend(display_impl)
+/// Writes the type as it would be written by hand, without the
+/// spaces that separate every token.
+fn type_name(ty: &syn::Type) -> String {
+    let tokens = quote! { #ty }.to_string();
+    let chars: Vec<char> = tokens.chars().collect();
+
+    let mut name = String::with_capacity(tokens.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let after_joining = i > 0 && ":<&([".contains(chars[i - 1]);
+            let before_joining = chars.get(i + 1).map_or(false, |&n| ":<>,;)]".contains(n));
+            if after_joining || before_joining {
+                continue;
+            }
+        }
+        name.push(c);
     }
+    name
 }
 
-struct ErrorImpl<'a>(&'a EnumInfo);
+struct ConvertImpl<'a>(&'a EnumInfo);
 
-impl<'a> quote::ToTokens for ErrorImpl<'a> {
+impl<'a> quote::ToTokens for ConvertImpl<'a> {
     fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
-        use self::shared::{Error, ErrorSourceMatchArm};
+        use quote::quote_spanned;
+        use syn::spanned::Spanned;
 
-        let (variants_to_description, variants_to_source): (Vec<_>, Vec<_>) = self
-            .0
-            .variants
-            .iter()
-            .map(|field_container| {
-                let enum_name = &self.0.name;
-                let variant_name = &field_container.name;
-                let pattern_ident = &quote! { #enum_name::#variant_name };
+        let enum_name = &self.0.name;
+        let parameterized_error_name = self.0.parameterized_name();
+        let original_generics = self.0.provided_generics_without_defaults();
+        let where_clauses = self.0.provided_where_clauses();
+
+        for Conversion {
+            from_type,
+            mappings,
+        } in &self.0.conversions
+        {
+            // The generic arguments of the other enum are inferred
+            // from the value being matched.
+            let mut pattern_path = match from_type {
+                syn::Type::Path(p) => p.path.clone(),
+                _ => continue,
+            };
+            for segment in &mut pattern_path.segments {
+                segment.arguments = syn::PathArguments::None;
+            }
 
-                let error_description_match_arm = quote! {
-                    #pattern_ident { .. } => stringify!(#pattern_ident),
-                };
+            let arms = mappings.iter().flat_map(|(from, to)| {
+                let variant = self.0.variants.iter().find(|v| v.name == *to)?;
+                let selector_kind = &variant.selector_kind;
+
+                let members: Vec<_> = selector_kind
+                    .user_fields()
+                    .iter()
+                    .chain(&variant.backtrace_field)
+                    .chain(selector_kind.message_field())
+                    .map(|f| (&f.member, &f.name))
+                    .chain(selector_kind.source_field().map(|f| (&f.member, &f.name)))
+                    .collect();
+                let bindings = members.iter().map(|(member, name)| match member {
+                    syn::Member::Named(_) => quote! { #name },
+                    syn::Member::Unnamed(_) => quote! { #member: #name },
+                });
+                let conversions = members
+                    .iter()
+                    .map(|(member, name)| quote! { #member: ::core::convert::Into::into(#name) });
+                let implicit_fields = variant.implicit_fields.iter().map(
+                    |Field { member, .. }| quote! { #member: ::core::default::Default::default() },
+                );
+
+                Some(quote! {
+                    #pattern_path::#from { #(#bindings,)* .. } => #enum_name::#to {
+                        #(#conversions,)*
+                        #(#implicit_fields,)*
+                    }
+                })
+            });
 
-                let error_source_match_arm = ErrorSourceMatchArm {
-                    field_container,
-                    pattern_ident,
-                };
-                let error_source_match_arm = quote! { #error_source_match_arm };
+            // Point at the attribute when variants are not mapped
+            let match_expr = quote_spanned! { from_type.span() =>
+                match other {
+                    #(#arms,)*
+                }
+            };
 
-                (error_description_match_arm, error_source_match_arm)
+            stream.extend(quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::convert::From<#from_type> for #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    fn from(other: #from_type) -> Self {
+                        #match_expr
+                    }
+                }
             })
-            .unzip();
+        }
+    }
+}
 
-        let error_impl = Error {
-            crate_root: &self.0.crate_root,
-            parameterized_error_name: &self.0.parameterized_name(),
-            description_arms: &variants_to_description,
-            source_arms: &variants_to_source,
-            original_generics: &self.0.provided_generics_without_defaults(),
-            where_clauses: &self.0.provided_where_clauses(),
-        };
-        let error_impl = quote! { #error_impl };
+/// Converts an identifier such as `HTTPNotFound` to `http_not_found`.
+/// Validates the argument of `#[snafu(http_status)]`. Without a
+/// status code, the status code of the source error is used.
+fn http_status_from_arg(
+    code: Option<syn::LitInt>,
+    errors: &mut SyntaxErrors,
+) -> Option<HttpStatus> {
+    let code = match code {
+        Some(code) => code,
+        None => return Some(HttpStatus::Source),
+    };
 
-        stream.extend(error_impl);
+    match code.base10_parse::<u16>() {
+        Ok(status) if status >= 100 && status <= 999 => Some(HttpStatus::Code(status)),
+        _ => {
+            errors.add(code, "HTTP status codes must be between 100 and 999");
+            None
+        }
     }
 }
 
-struct ErrorCompatImpl<'a>(&'a EnumInfo);
+/// The fields added as span attributes when an error is recorded on
+/// an OpenTelemetry span, if it is recorded at all.
+fn opentelemetry_fields(field_container: &FieldContainer, default: bool) -> Option<Vec<Field>> {
+    match &field_container.opentelemetry {
+        Some(opentelemetry) => Some(
+            opentelemetry
+                .fields
+                .iter()
+                .flat_map(|name| field_container.fields.iter().find(|f| f.name == *name))
+                .cloned()
+                .collect(),
+        ),
+        None if default => Some(Vec::new()),
+        None => None,
+    }
+}
 
-impl<'a> quote::ToTokens for ErrorCompatImpl<'a> {
-    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
-        use self::shared::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+/// Validates the argument of `#[snafu(c_code)]`.
+fn display_buffer_from_arg(
+    size: Option<(syn::LitInt, proc_macro2::TokenStream)>,
+    skip_display: bool,
+    errors: &mut SyntaxErrors,
+) -> Option<usize> {
+    let (size, location) = size?;
 
-        let variants_to_backtrace: Vec<_> = self
-            .0
-            .variants
-            .iter()
-            .map(|field_container| {
-                let crate_root = &self.0.crate_root;
-                let enum_name = &self.0.name;
-                let variant_name = &field_container.name;
+    if skip_display {
+        errors.add(
+            location,
+            "`display_buffer` cannot be used with `display(skip)`",
+        );
+        return None;
+    }
 
-                let match_arm = ErrorCompatBacktraceMatchArm {
-                    field_container,
-                    crate_root,
-                    pattern_ident: &quote! { #enum_name::#variant_name },
-                };
+    match size.base10_parse::<usize>() {
+        Ok(size) if size > 0 => Some(size),
+        _ => {
+            errors.add(size, "The display buffer size must be a positive integer");
+            None
+        }
+    }
+}
 
-                quote! { #match_arm }
-            })
-            .collect();
+fn c_code_from_arg(code: syn::LitInt, errors: &mut SyntaxErrors) -> Option<i32> {
+    match code.base10_parse::<i32>() {
+        Ok(code) => Some(code),
+        Err(_) => {
+            errors.add(code, "C error codes must fit in an `i32`");
+            None
+        }
+    }
+}
 
-        let error_compat_impl = ErrorCompat {
-            crate_root: &self.0.crate_root,
-            parameterized_error_name: &self.0.parameterized_name(),
-            backtrace_arms: &variants_to_backtrace,
-            original_generics: &self.0.provided_generics_without_defaults(),
-            where_clauses: &self.0.provided_where_clauses(),
-        };
+/// The name of the counter incremented when an error is constructed,
+/// if any. A name given on a variant takes precedence over one given
+/// on the enum.
+fn counter_name(
+    metric: Option<&Metric>,
+    default: Option<&Metric>,
+    type_name: &syn::Ident,
+) -> Option<syn::LitStr> {
+    if metric.is_none() && default.is_none() {
+        return None;
+    }
 
-        let error_compat_impl = quote! { #error_compat_impl };
+    let name = metric
+        .and_then(|m| m.name.as_ref())
+        .or_else(|| default.and_then(|m| m.name.as_ref()));
 
-        stream.extend(error_compat_impl);
+    Some(match name {
+        Some(name) => name.clone(),
+        None => syn::LitStr::new(&to_snake_case(&type_name.to_string()), type_name.span()),
+    })
+}
+
+fn to_snake_case(name: &str) -> String {
+    let name = name.trim_start_matches("r#");
+    let chars: Vec<_> = name.chars().collect();
+    let mut snake = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).map_or(false, |n| n.is_lowercase());
+            let starts_word = previous.is_lowercase()
+                || previous.is_numeric()
+                || (previous.is_uppercase() && next_is_lowercase);
+            if starts_word {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+/// The `const` keyword for generated methods that only match on
+/// `self`, when the compiler allows trait bounds on `const fn`.
+fn constness() -> Option<proc_macro2::TokenStream> {
+    if cfg!(feature = "rust_1_61") {
+        Some(quote! { const })
+    } else {
+        None
     }
 }
 
@@ -1422,6 +4417,12 @@ impl NamedStructInfo {
         let parameterized_struct_name = self.parameterized_name();
         let original_generics = self.provided_generics_without_defaults();
         let where_clauses = self.provided_where_clauses();
+        let source_bounds = SourceBounds::new(
+            &self.field_container.name,
+            &self.generics,
+            &self.crate_root,
+            std::iter::once(&self.field_container),
+        );
 
         let Self {
             crate_root,
@@ -1431,9 +4432,16 @@ impl NamedStructInfo {
                     selector_kind,
                     backtrace_field,
                     display_format,
+                    display_internal_format,
                     doc_comment,
                     visibility,
+                    ..
                 },
+            skip_display,
+            display_buffer,
+            stable_debug,
+            termination,
+            wasm,
             ..
         } = &self;
         let field_container = &self.field_container;
@@ -1449,79 +4457,332 @@ impl NamedStructInfo {
         };
 
         let error_source_match_arm = ErrorSourceMatchArm {
-            field_container: &field_container,
+            field_container,
             pattern_ident,
         };
         let error_source_match_arm = quote! { #error_source_match_arm };
 
         let error_impl = Error {
-            crate_root: &crate_root,
+            crate_root,
             parameterized_error_name: &parameterized_struct_name,
             description_arms: &[error_description_match_arm],
             source_arms: &[error_source_match_arm],
             original_generics: &original_generics,
-            where_clauses: &where_clauses,
+            where_clauses: &source_bounds.error(&where_clauses),
         };
         let error_impl = quote! { #error_impl };
 
-        use self::shared::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+        use self::shared::{
+            ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatMetadataMatchArm,
+        };
 
         let match_arm = ErrorCompatBacktraceMatchArm {
             field_container,
-            crate_root: &crate_root,
+            crate_root,
             pattern_ident: &quote! { Self },
         };
         let match_arm = quote! { #match_arm };
 
+        let metadata_match_arm = ErrorCompatMetadataMatchArm {
+            field_container,
+            pattern_ident: &quote! { Self },
+        };
+        let add_context_arm: Vec<_> = metadata_match_arm.add_context().into_iter().collect();
+        let metadata_arm: Vec<_> = metadata_match_arm.metadata().into_iter().collect();
+        let exit_code_arm: Vec<_> = shared::exit_code_match_arm(field_container, &quote! { Self })
+            .into_iter()
+            .collect();
+
         let error_compat_impl = ErrorCompat {
-            crate_root: &crate_root,
+            crate_root,
             parameterized_error_name: &parameterized_struct_name,
             backtrace_arms: &[match_arm],
+            add_context_arms: &add_context_arm,
+            metadata_arms: &metadata_arm,
+            exit_code_arms: &exit_code_arm,
             original_generics: &original_generics,
-            where_clauses: &where_clauses,
+            where_clauses: &source_bounds.error_compat(&where_clauses),
         };
 
-        use crate::shared::{Display, DisplayMatchArm};
+        use crate::shared::{Display, DisplayMatchArm, FormatInternal};
 
         let arm = DisplayMatchArm {
             backtrace_field: backtrace_field.as_ref(),
-            default_name: &name,
+            default_name: name,
             display_format: display_format.as_ref().map(|f| &**f),
-            doc_comment: &doc_comment,
+            doc_comment,
             pattern_ident: &quote! { Self },
-            selector_kind: &selector_kind,
+            selector_kind,
         };
         let arm = quote! { #arm };
 
+        let internal_arm: Vec<_> = display_internal_format
+            .as_ref()
+            .map(|display_internal_format| {
+                let arm = DisplayMatchArm {
+                    backtrace_field: backtrace_field.as_ref(),
+                    default_name: name,
+                    display_format: Some(&**display_internal_format),
+                    doc_comment,
+                    pattern_ident: &quote! { Self },
+                    selector_kind,
+                };
+                quote! { #arm }
+            })
+            .into_iter()
+            .collect();
+
+        let display_where_clauses = source_bounds.display(&where_clauses);
+
         let display_impl = Display {
+            crate_root,
+            visibility: &self.visibility,
             arms: &[arm],
+            buffer: *display_buffer,
             original_generics: &original_generics,
             parameterized_error_name: &parameterized_struct_name,
-            where_clauses: &where_clauses,
+            where_clauses: &display_where_clauses,
+        };
+
+        let format_internal_impl = FormatInternal {
+            crate_root,
+            arms: &internal_arm,
+            original_generics: &original_generics,
+            parameterized_error_name: &parameterized_struct_name,
+            where_clauses: &display_where_clauses,
+        };
+
+        let display_impl = if *skip_display {
+            quote! {}
+        } else {
+            quote! {
+                #display_impl
+                #format_internal_impl
+            }
+        };
+
+        let debug_impl = if *stable_debug {
+            use crate::shared::{StableDebug, StableDebugMatchArm};
+
+            let arm = StableDebugMatchArm {
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let arm = quote! { #arm };
+
+            let debug_impl = StableDebug {
+                arms: &[arm],
+                original_generics: &original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                where_clauses: &debug_bounds(&self.generics, &where_clauses),
+            };
+            quote! { #debug_impl }
+        } else {
+            quote! {}
+        };
+
+        let termination_impl = if *termination {
+            use crate::shared::Termination;
+
+            let termination_impl = Termination {
+                crate_root,
+                parameterized_error_name: &parameterized_struct_name,
+                original_generics: &original_generics,
+                where_clauses: &where_clauses,
+            };
+            quote! { #termination_impl }
+        } else {
+            quote! {}
+        };
+
+        let wasm_impl = if *wasm {
+            use crate::shared::Wasm;
+
+            let wasm_impl = Wasm {
+                crate_root,
+                parameterized_error_name: &parameterized_struct_name,
+                original_generics: &original_generics,
+                where_clauses: &where_clauses,
+            };
+            quote! { #wasm_impl }
+        } else {
+            quote! {}
+        };
+
+        let diagnostic_impl = if field_container.diagnostic.is_empty() {
+            quote! {}
+        } else {
+            use crate::shared::{Diagnostic, DiagnosticMatchArms};
+
+            let arms = DiagnosticMatchArms {
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let code_arm: Vec<_> = arms.code().into_iter().collect();
+            let help_arm: Vec<_> = arms.help().into_iter().collect();
+            let url_arm: Vec<_> = arms.url().into_iter().collect();
+            let labels_arm: Vec<_> = arms.labels(crate_root).into_iter().collect();
+
+            let diagnostic_impl = Diagnostic {
+                crate_root,
+                parameterized_error_name: &parameterized_struct_name,
+                code_arms: &code_arm,
+                help_arms: &help_arm,
+                url_arms: &url_arm,
+                labels_arms: &labels_arm,
+                original_generics: &original_generics,
+                where_clauses: &where_clauses,
+            };
+            quote! { #diagnostic_impl }
+        };
+
+        let http_status_impl = if field_container.http_status.is_some() {
+            use crate::shared::{http_status_match_arm, HttpStatus};
+
+            let arm = http_status_match_arm(field_container, &quote! { Self }, 500);
+
+            let http_status_impl = HttpStatus {
+                crate_root,
+                parameterized_error_name: &parameterized_struct_name,
+                original_generics: &original_generics,
+                where_clauses: &where_clauses,
+                visibility: &self.visibility,
+                status: &quote! {
+                    match *self {
+                        #arm,
+                    }
+                },
+            };
+            quote! { #http_status_impl }
+        } else {
+            quote! {}
+        };
+
+        let c_code_impl = match field_container.c_code {
+            Some(code) => {
+                use crate::shared::CCode;
+
+                let c_code_impl = CCode {
+                    crate_root,
+                    parameterized_error_name: &parameterized_struct_name,
+                    original_generics: &original_generics,
+                    where_clauses: &where_clauses,
+                    visibility: &self.visibility,
+                    code: &code,
+                };
+                quote! { #c_code_impl }
+            }
+            None => quote! {},
+        };
+
+        let grpc_impl = if field_container.grpc.is_some() {
+            use crate::shared::{grpc_metadata_types, Grpc, GrpcMatchArms};
+
+            // `Self` would refer to `Status` in the `From` implementation
+            let arms = GrpcMatchArms {
+                crate_root,
+                field_container,
+                pattern_ident: &name,
+            };
+            let code_arm = arms.code(&quote! { #crate_root::__tonic::Code::Internal });
+            let metadata_arm: Vec<_> = arms.metadata().into_iter().collect();
+            let metadata_types = grpc_metadata_types(field_container);
+
+            let grpc_impl = Grpc {
+                crate_root,
+                parameterized_error_name: &parameterized_struct_name,
+                original_generics: &original_generics,
+                where_clauses: &where_clauses,
+                visibility: &self.visibility,
+                code_arms: &[code_arm],
+                metadata_arms: &metadata_arm,
+                metadata_types: &metadata_types,
+            };
+            quote! { #grpc_impl }
+        } else {
+            quote! {}
+        };
+
+        let io_kind_impl = if field_container.io_kind.is_some() {
+            use crate::shared::{io_kind_match_arm, IoKind};
+
+            let arm = io_kind_match_arm(
+                field_container,
+                &name,
+                &quote! { ::std::io::ErrorKind::Other },
+            );
+
+            let io_kind_impl = IoKind {
+                parameterized_error_name: &parameterized_struct_name,
+                original_generics: &original_generics,
+                where_clauses: &where_clauses,
+                visibility: &self.visibility,
+                arms: &[arm],
+            };
+            quote! { #io_kind_impl }
+        } else {
+            quote! {}
+        };
+
+        let catalog_impl = if self.catalog {
+            use crate::shared::CatalogEntry;
+
+            let catalog_entry = CatalogEntry {
+                crate_root,
+                error_name: name,
+                variant_name: None,
+                field_container,
+                skip_display: self.skip_display,
+                default_http_status: None,
+                default_c_code: None,
+            };
+            quote! { #catalog_entry }
+        } else {
+            quote! {}
         };
 
         use crate::shared::ContextSelector;
 
         let selector_doc_string = format!("SNAFU context selector for the `{}` error", name);
+        let metric = counter_name(field_container.metric.as_ref(), None, name);
+        let opentelemetry = opentelemetry_fields(field_container, false);
 
         let context_selector = ContextSelector {
             backtrace_field: backtrace_field.as_ref(),
-            crate_root: &crate_root,
+            cold: field_container.cold.unwrap_or(true),
+            crate_root,
+            dedup: false,
+            doc_comment,
+            implicit_fields: &field_container.implicit_fields,
             error_constructor_name: &name,
             original_generics_without_defaults: &original_generics,
             parameterized_error_name: &parameterized_struct_name,
             selector_doc_string: &selector_doc_string,
-            selector_kind: &selector_kind,
+            selector_kind,
             selector_name: &field_container.name,
-            user_fields: &user_fields,
+            strip_suffixes: &self.strip_suffixes,
+            tracing: field_container.tracing.as_ref().map(|l| l as _),
+            log: field_container.log.as_ref(),
+            metric: metric.as_ref(),
+            opentelemetry: opentelemetry.as_ref().map(|f| &**f),
+            user_fields,
             visibility: visibility.as_ref().map(|x| &**x),
-            where_clauses: &where_clauses,
+            where_clauses: &source_bounds.context_selector(&where_clauses),
         };
 
         quote! {
             #error_impl
             #error_compat_impl
             #display_impl
+            #debug_impl
+            #termination_impl
+            #wasm_impl
+            #diagnostic_impl
+            #http_status_impl
+            #c_code_impl
+            #grpc_impl
+            #io_kind_impl
+            #catalog_impl
             #context_selector
         }
     }
@@ -1540,23 +4801,25 @@ impl GenericAwareNames for NamedStructInfo {
 impl TupleStructInfo {
     fn generate_snafu(self) -> proc_macro2::TokenStream {
         let parameterized_struct_name = self.parameterized_name();
+        let original_generics = self.provided_generics_without_defaults();
+        let where_clauses = self.provided_where_clauses();
 
         let TupleStructInfo {
             crate_root,
-            generics,
             name,
             transformation,
+            visibility,
+            skip_display,
+            termination,
+            wasm,
+            http_status,
+            c_code,
+            ..
         } = self;
 
         let inner_type = transformation.ty();
         let transformation = transformation.transformation();
 
-        let where_clauses: Vec<_> = generics
-            .where_clause
-            .iter()
-            .flat_map(|c| c.predicates.iter().map(|p| quote! { #p }))
-            .collect();
-
         let description_fn = quote! {
             fn description(&self) -> &str {
                 #crate_root::Error::description(&self.0)
@@ -1581,10 +4844,10 @@ impl TupleStructInfo {
             }
         };
 
-        let std_backtrace_fn = if cfg!(feature = "unstable-backtraces-impl-std") {
+        let provide_fn = if cfg!(feature = "unstable-provider-api") {
             quote! {
-                fn backtrace(&self) -> ::core::option::Option<&std::backtrace::Backtrace> {
-                    #crate_root::ErrorCompat::backtrace(self)
+                fn provide<'a>(&'a self, request: &mut ::core::error::Request<'a>) {
+                    #crate_root::Error::provide(&self.0, request)
                 }
             }
         } else {
@@ -1593,41 +4856,58 @@ impl TupleStructInfo {
 
         let error_impl = quote! {
             #[allow(single_use_lifetimes)]
-            impl#generics #crate_root::Error for #parameterized_struct_name
+            impl<#(#original_generics),*> #crate_root::Error for #parameterized_struct_name
             where
                 #(#where_clauses),*
             {
                 #description_fn
                 #cause_fn
                 #source_fn
-                #std_backtrace_fn
+                #provide_fn
+            }
+        };
+
+        let exit_code_fn = quote! {
+            fn exit_code(&self) -> ::core::option::Option<u8> {
+                #crate_root::ErrorCompat::exit_code(&self.0)
             }
         };
 
         let error_compat_impl = quote! {
             #[allow(single_use_lifetimes)]
-            impl#generics #crate_root::ErrorCompat for #parameterized_struct_name
+            impl<#(#original_generics),*> #crate_root::ErrorCompat for #parameterized_struct_name
             where
                 #(#where_clauses),*
             {
                 #backtrace_fn
+                #exit_code_fn
             }
         };
 
-        let display_impl = quote! {
-            #[allow(single_use_lifetimes)]
-            impl#generics ::core::fmt::Display for #parameterized_struct_name
-            where
-                #(#where_clauses),*
-            {
-                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                    ::core::fmt::Display::fmt(&self.0, f)
+        let display_impl = if skip_display {
+            quote! {}
+        } else {
+            quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::fmt::Display for #parameterized_struct_name
+                where
+                    #(#where_clauses),*
+                {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        ::core::fmt::Display::fmt(&self.0, f)
+                    }
                 }
+
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #crate_root::FormatInternal for #parameterized_struct_name
+                where
+                    #(#where_clauses),*
+                {}
             }
         };
 
         let from_impl = quote! {
-            impl#generics ::core::convert::From<#inner_type> for #parameterized_struct_name
+            impl<#(#original_generics),*> ::core::convert::From<#inner_type> for #parameterized_struct_name
             where
                 #(#where_clauses),*
             {
@@ -1637,11 +4917,82 @@ impl TupleStructInfo {
             }
         };
 
+        let termination_impl = if termination {
+            use crate::shared::Termination;
+
+            let termination_impl = Termination {
+                crate_root: &crate_root,
+                parameterized_error_name: &parameterized_struct_name,
+                original_generics: &original_generics,
+                where_clauses: &where_clauses,
+            };
+            quote! { #termination_impl }
+        } else {
+            quote! {}
+        };
+
+        let wasm_impl = if wasm {
+            use crate::shared::Wasm;
+
+            let wasm_impl = Wasm {
+                crate_root: &crate_root,
+                parameterized_error_name: &parameterized_struct_name,
+                original_generics: &original_generics,
+                where_clauses: &where_clauses,
+            };
+            quote! { #wasm_impl }
+        } else {
+            quote! {}
+        };
+
+        let http_status_impl = match http_status {
+            Some(status) => {
+                use crate::shared::HttpStatus;
+
+                let status = match status {
+                    crate::HttpStatus::Code(code) => quote! { #code },
+                    crate::HttpStatus::Source => quote! { self.0.http_status() },
+                };
+
+                let http_status_impl = HttpStatus {
+                    crate_root: &crate_root,
+                    parameterized_error_name: &parameterized_struct_name,
+                    original_generics: &original_generics,
+                    where_clauses: &where_clauses,
+                    visibility: &visibility,
+                    status: &status,
+                };
+                quote! { #http_status_impl }
+            }
+            None => quote! {},
+        };
+
+        let c_code_impl = match c_code {
+            Some(code) => {
+                use crate::shared::CCode;
+
+                let c_code_impl = CCode {
+                    crate_root: &crate_root,
+                    parameterized_error_name: &parameterized_struct_name,
+                    original_generics: &original_generics,
+                    where_clauses: &where_clauses,
+                    visibility: &visibility,
+                    code: &code,
+                };
+                quote! { #c_code_impl }
+            }
+            None => quote! {},
+        };
+
         quote! {
             #error_impl
             #error_compat_impl
             #display_impl
             #from_impl
+            #termination_impl
+            #wasm_impl
+            #http_status_impl
+            #c_code_impl
         }
     }
 }
@@ -1656,20 +5007,6 @@ impl GenericAwareNames for TupleStructInfo {
     }
 }
 
-trait Transpose<T, E> {
-    fn my_transpose(self) -> Result<Option<T>, E>;
-}
-
-impl<T, E> Transpose<T, E> for Option<Result<T, E>> {
-    fn my_transpose(self) -> Result<Option<T>, E> {
-        match self {
-            Some(Ok(v)) => Ok(Some(v)),
-            Some(Err(e)) => Err(e),
-            None => Ok(None),
-        }
-    }
-}
-
 mod sponge {
     use std::iter::FromIterator;
 
@@ -1,26 +1,62 @@
 use crate::SnafuAttribute;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
+use snafu_messages::parse::{Display, DocComment};
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
-    token, Expr, Ident, LitBool, LitStr, Path, Type,
+    token, Expr, Ident, LitBool, LitInt, LitStr, Path, Type,
 };
 
 mod kw {
     use syn::custom_keyword;
 
+    custom_keyword!(accessors);
     custom_keyword!(backtrace);
+    custom_keyword!(c_code);
+    custom_keyword!(catalog);
+    custom_keyword!(cold);
+    custom_keyword!(code);
     custom_keyword!(context);
+    custom_keyword!(convert);
     custom_keyword!(crate_root);
-    custom_keyword!(display);
+    custom_keyword!(debug);
+    custom_keyword!(dedup);
+    custom_keyword!(display_buffer);
+    custom_keyword!(exit_code);
+    custom_keyword!(fields);
+    custom_keyword!(flatten);
+    custom_keyword!(grpc);
+    custom_keyword!(help);
+    custom_keyword!(http_status);
+    custom_keyword!(implicit);
+    custom_keyword!(io_kind);
+    custom_keyword!(kind);
+    custom_keyword!(label);
+    custom_keyword!(log);
+    custom_keyword!(metric);
+    custom_keyword!(no_panic);
+    custom_keyword!(opentelemetry);
+    custom_keyword!(predicates);
+    custom_keyword!(report_conversions);
+    custom_keyword!(serialize);
+    custom_keyword!(strip_suffix);
+    custom_keyword!(termination);
+    custom_keyword!(tracing);
+    custom_keyword!(url);
+    custom_keyword!(wasm);
     custom_keyword!(whatever);
     custom_keyword!(source);
     custom_keyword!(visibility);
 
+    custom_keyword!(accessor);
     custom_keyword!(from);
+    custom_keyword!(level);
+    custom_keyword!(metadata);
+    custom_keyword!(target);
 
+    custom_keyword!(stable);
     custom_keyword!(suffix);
 }
 
@@ -58,10 +94,41 @@ pub(crate) fn attributes_from_syn(
 }
 
 enum Attribute {
+    Accessors(Accessors),
     Backtrace(Backtrace),
+    CCode(CCode),
+    Catalog(Catalog),
+    Cold(Cold),
+    Code(Code),
     Context(Context),
+    Convert(Convert),
     CrateRoot(CrateRoot),
+    Debug(Debug),
+    Dedup(Dedup),
     Display(Display),
+    DisplayBuffer(DisplayBuffer),
+    ExitCode(ExitCode),
+    Fields(Fields),
+    Flatten(Flatten),
+    Grpc(Grpc),
+    Help(Help),
+    HttpStatus(HttpStatus),
+    Implicit(Implicit),
+    IoKind(IoKind),
+    Kind(Kind),
+    Label(Label),
+    Log(Log),
+    Metric(Metric),
+    NoPanic(NoPanic),
+    OpenTelemetry(OpenTelemetry),
+    Predicates(Predicates),
+    ReportConversions(ReportConversions),
+    Serialize(Serialize),
+    StripSuffix(StripSuffix),
+    Termination(Termination),
+    Tracing(Tracing),
+    Url(Url),
+    Wasm(Wasm),
     Whatever(Whatever),
     Source(Source),
     Visibility(Visibility),
@@ -72,10 +139,47 @@ impl From<Attribute> for SnafuAttribute {
         use self::Attribute::*;
 
         match other {
+            Accessors(a) => SnafuAttribute::Accessors(a.to_token_stream()),
             Backtrace(b) => SnafuAttribute::Backtrace(b.to_token_stream(), b.into_bool()),
+            CCode(c) => SnafuAttribute::CCode(c.to_token_stream(), c.value),
+            Catalog(c) => SnafuAttribute::Catalog(c.to_token_stream()),
+            Cold(c) => SnafuAttribute::Cold(c.to_token_stream(), c.into_bool()),
+            Code(c) => SnafuAttribute::Code(c.to_token_stream(), c.into_exprs()),
             Context(c) => SnafuAttribute::Context(c.to_token_stream(), c.into_component()),
+            Convert(c) => SnafuAttribute::Convert(c.to_token_stream(), c.into_conversion()),
             CrateRoot(cr) => SnafuAttribute::CrateRoot(cr.to_token_stream(), cr.into_arbitrary()),
-            Display(d) => SnafuAttribute::Display(d.to_token_stream(), d.into_arbitrary()),
+            Debug(d) => SnafuAttribute::DebugStable(d.to_token_stream()),
+            Dedup(d) => SnafuAttribute::Dedup(d.to_token_stream()),
+            Display(d) if d.is_skip() => SnafuAttribute::DisplaySkip(d.to_token_stream()),
+            Display(d) if d.is_internal() => {
+                SnafuAttribute::DisplayInternal(d.to_token_stream(), d.into_internal_exprs())
+            }
+            Display(d) => SnafuAttribute::Display(d.to_token_stream(), d.into_exprs()),
+            DisplayBuffer(d) => SnafuAttribute::DisplayBuffer(d.to_token_stream(), d.size),
+            ExitCode(e) => SnafuAttribute::ExitCode(e.to_token_stream(), e.arg),
+            Fields(f) => SnafuAttribute::Fields(f.to_token_stream()),
+            Flatten(f) => SnafuAttribute::Flatten(f.to_token_stream()),
+            Grpc(g) => SnafuAttribute::Grpc(g.to_token_stream(), g.into_grpc()),
+            Help(h) => SnafuAttribute::Help(h.to_token_stream(), h.into_exprs()),
+            HttpStatus(h) => SnafuAttribute::HttpStatus(h.to_token_stream(), h.arg.into_option()),
+            Implicit(i) => SnafuAttribute::Implicit(i.to_token_stream()),
+            IoKind(i) => SnafuAttribute::IoKind(i.to_token_stream(), i.kind),
+            Kind(k) => SnafuAttribute::Kind(k.to_token_stream()),
+            Label(l) => SnafuAttribute::Label(l.to_token_stream(), l.into_exprs()),
+            Log(l) => SnafuAttribute::Log(l.to_token_stream(), l.into_log()),
+            Metric(m) => SnafuAttribute::Metric(m.to_token_stream(), m.into_metric()),
+            NoPanic(n) => SnafuAttribute::NoPanic(n.to_token_stream()),
+            OpenTelemetry(o) => {
+                SnafuAttribute::OpenTelemetry(o.to_token_stream(), o.into_opentelemetry())
+            }
+            Predicates(p) => SnafuAttribute::Predicates(p.to_token_stream()),
+            ReportConversions(r) => SnafuAttribute::ReportConversions(r.to_token_stream()),
+            Serialize(s) => SnafuAttribute::Serialize(s.to_token_stream(), s.includes_backtrace()),
+            StripSuffix(s) => SnafuAttribute::StripSuffix(s.to_token_stream(), s.into_words()),
+            Termination(t) => SnafuAttribute::Termination(t.to_token_stream()),
+            Tracing(t) => SnafuAttribute::Tracing(t.to_token_stream(), t.into_level()),
+            Url(u) => SnafuAttribute::Url(u.to_token_stream(), u.into_exprs()),
+            Wasm(w) => SnafuAttribute::Wasm(w.to_token_stream()),
             Whatever(o) => SnafuAttribute::Whatever(o.to_token_stream()),
             Source(s) => SnafuAttribute::Source(s.to_token_stream(), s.into_components()),
             Visibility(v) => SnafuAttribute::Visibility(v.to_token_stream(), v.into_arbitrary()),
@@ -86,14 +190,76 @@ impl From<Attribute> for SnafuAttribute {
 impl Parse for Attribute {
     fn parse(input: ParseStream) -> Result<Self> {
         let lookahead = input.lookahead1();
-        if lookahead.peek(kw::backtrace) {
+        if lookahead.peek(kw::accessors) {
+            input.parse().map(Attribute::Accessors)
+        } else if lookahead.peek(kw::backtrace) {
             input.parse().map(Attribute::Backtrace)
+        } else if lookahead.peek(kw::c_code) {
+            input.parse().map(Attribute::CCode)
+        } else if lookahead.peek(kw::catalog) {
+            input.parse().map(Attribute::Catalog)
+        } else if lookahead.peek(kw::cold) {
+            input.parse().map(Attribute::Cold)
+        } else if lookahead.peek(kw::code) {
+            input.parse().map(Attribute::Code)
         } else if lookahead.peek(kw::context) {
             input.parse().map(Attribute::Context)
+        } else if lookahead.peek(kw::convert) {
+            input.parse().map(Attribute::Convert)
         } else if lookahead.peek(kw::crate_root) {
             input.parse().map(Attribute::CrateRoot)
-        } else if lookahead.peek(kw::display) {
+        } else if lookahead.peek(kw::debug) {
+            input.parse().map(Attribute::Debug)
+        } else if lookahead.peek(kw::dedup) {
+            input.parse().map(Attribute::Dedup)
+        } else if lookahead.peek(snafu_messages::parse::kw::display) {
             input.parse().map(Attribute::Display)
+        } else if lookahead.peek(kw::display_buffer) {
+            input.parse().map(Attribute::DisplayBuffer)
+        } else if lookahead.peek(kw::exit_code) {
+            input.parse().map(Attribute::ExitCode)
+        } else if lookahead.peek(kw::fields) {
+            input.parse().map(Attribute::Fields)
+        } else if lookahead.peek(kw::flatten) {
+            input.parse().map(Attribute::Flatten)
+        } else if lookahead.peek(kw::grpc) {
+            input.parse().map(Attribute::Grpc)
+        } else if lookahead.peek(kw::help) {
+            input.parse().map(Attribute::Help)
+        } else if lookahead.peek(kw::http_status) {
+            input.parse().map(Attribute::HttpStatus)
+        } else if lookahead.peek(kw::implicit) {
+            input.parse().map(Attribute::Implicit)
+        } else if lookahead.peek(kw::io_kind) {
+            input.parse().map(Attribute::IoKind)
+        } else if lookahead.peek(kw::kind) {
+            input.parse().map(Attribute::Kind)
+        } else if lookahead.peek(kw::label) {
+            input.parse().map(Attribute::Label)
+        } else if lookahead.peek(kw::log) {
+            input.parse().map(Attribute::Log)
+        } else if lookahead.peek(kw::metric) {
+            input.parse().map(Attribute::Metric)
+        } else if lookahead.peek(kw::no_panic) {
+            input.parse().map(Attribute::NoPanic)
+        } else if lookahead.peek(kw::opentelemetry) {
+            input.parse().map(Attribute::OpenTelemetry)
+        } else if lookahead.peek(kw::predicates) {
+            input.parse().map(Attribute::Predicates)
+        } else if lookahead.peek(kw::report_conversions) {
+            input.parse().map(Attribute::ReportConversions)
+        } else if lookahead.peek(kw::serialize) {
+            input.parse().map(Attribute::Serialize)
+        } else if lookahead.peek(kw::strip_suffix) {
+            input.parse().map(Attribute::StripSuffix)
+        } else if lookahead.peek(kw::termination) {
+            input.parse().map(Attribute::Termination)
+        } else if lookahead.peek(kw::tracing) {
+            input.parse().map(Attribute::Tracing)
+        } else if lookahead.peek(kw::url) {
+            input.parse().map(Attribute::Url)
+        } else if lookahead.peek(kw::wasm) {
+            input.parse().map(Attribute::Wasm)
         } else if lookahead.peek(kw::whatever) {
             input.parse().map(Attribute::Whatever)
         } else if lookahead.peek(kw::source) {
@@ -106,6 +272,24 @@ impl Parse for Attribute {
     }
 }
 
+struct Accessors {
+    accessors_token: kw::accessors,
+}
+
+impl Parse for Accessors {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            accessors_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Accessors {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.accessors_token.to_tokens(tokens);
+    }
+}
+
 struct Backtrace {
     backtrace_token: kw::backtrace,
     arg: MaybeArg<BacktraceArg>,
@@ -151,6 +335,107 @@ impl ToTokens for BacktraceArg {
     }
 }
 
+struct CCode {
+    c_code_token: kw::c_code,
+    eq_token: token::Eq,
+    value: LitInt,
+}
+
+impl Parse for CCode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            c_code_token: input.parse()?,
+            eq_token: input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for CCode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.c_code_token.to_tokens(tokens);
+        self.eq_token.to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
+struct Catalog {
+    catalog_token: kw::catalog,
+}
+
+impl Parse for Catalog {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            catalog_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Catalog {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.catalog_token.to_tokens(tokens);
+    }
+}
+
+struct Cold {
+    cold_token: kw::cold,
+    arg: MaybeArg<LitBool>,
+}
+
+impl Cold {
+    fn into_bool(self) -> bool {
+        self.arg.into_option().map_or(true, |a| a.value)
+    }
+}
+
+impl Parse for Cold {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            cold_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Cold {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.cold_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Code {
+    code_token: kw::code,
+    paren_token: token::Paren,
+    args: Punctuated<Expr, token::Comma>,
+}
+
+impl Code {
+    fn into_exprs(self) -> Vec<Expr> {
+        self.args.into_iter().collect()
+    }
+}
+
+impl Parse for Code {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            code_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            args: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl ToTokens for Code {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.code_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.args.to_tokens(tokens);
+        });
+    }
+}
+
 struct Context {
     context_token: kw::context,
     arg: MaybeArg<ContextArg>,
@@ -289,6 +574,90 @@ impl ToTokens for SuffixArg {
     }
 }
 
+struct Convert {
+    convert_token: kw::convert,
+    paren_token: token::Paren,
+    from_token: kw::from,
+    from_paren_token: token::Paren,
+    r#type: Type,
+    comma_token: Option<token::Comma>,
+    mappings: Punctuated<ConvertMapping, token::Comma>,
+}
+
+impl Convert {
+    fn into_conversion(self) -> super::Conversion {
+        super::Conversion {
+            from_type: self.r#type,
+            mappings: self
+                .mappings
+                .into_iter()
+                .map(|ConvertMapping { from, to }| {
+                    let to = to.map_or_else(|| from.clone(), |(_, to)| to);
+                    (from, to)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Parse for Convert {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let from_content;
+        Ok(Self {
+            convert_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            from_token: content.parse()?,
+            from_paren_token: parenthesized!(from_content in content),
+            r#type: from_content.parse()?,
+            comma_token: from_content.parse()?,
+            mappings: Punctuated::parse_terminated(&from_content)?,
+        })
+    }
+}
+
+impl ToTokens for Convert {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.convert_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.from_token.to_tokens(tokens);
+            self.from_paren_token.surround(tokens, |tokens| {
+                self.r#type.to_tokens(tokens);
+                self.comma_token.to_tokens(tokens);
+                self.mappings.to_tokens(tokens);
+            });
+        });
+    }
+}
+
+/// `Variant` or `FromVariant => ToVariant`
+struct ConvertMapping {
+    from: Ident,
+    to: Option<(token::FatArrow, Ident)>,
+}
+
+impl Parse for ConvertMapping {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let from = input.parse()?;
+        let to = if input.peek(token::FatArrow) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        Ok(Self { from, to })
+    }
+}
+
+impl ToTokens for ConvertMapping {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.from.to_tokens(tokens);
+        if let Some((arrow, to)) = &self.to {
+            arrow.to_tokens(tokens);
+            to.to_tokens(tokens);
+        }
+    }
+}
+
 struct CrateRoot {
     crate_root_token: kw::crate_root,
     paren_token: token::Paren,
@@ -322,47 +691,47 @@ impl ToTokens for CrateRoot {
     }
 }
 
-struct Display {
-    display_token: kw::display,
+struct Debug {
+    debug_token: kw::debug,
     paren_token: token::Paren,
-    args: Punctuated<Expr, token::Comma>,
-}
-
-impl Display {
-    // TODO: Remove boxed trait object
-    fn into_arbitrary(self) -> Box<dyn ToTokens> {
-        Box::new(self.args)
-    }
+    stable_token: kw::stable,
 }
 
-impl Parse for Display {
+impl Parse for Debug {
     fn parse(input: ParseStream) -> Result<Self> {
         let content;
         Ok(Self {
-            display_token: input.parse()?,
+            debug_token: input.parse()?,
             paren_token: parenthesized!(content in input),
-            args: Punctuated::parse_terminated(&content)?,
+            stable_token: content.parse()?,
         })
     }
 }
 
-impl ToTokens for Display {
+impl ToTokens for Debug {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.display_token.to_tokens(tokens);
+        self.debug_token.to_tokens(tokens);
         self.paren_token.surround(tokens, |tokens| {
-            self.args.to_tokens(tokens);
+            self.stable_token.to_tokens(tokens);
         });
     }
 }
 
-struct DocComment {
-    eq_token: token::Eq,
-    str: LitStr,
+struct Dedup {
+    dedup_token: kw::dedup,
+}
+
+impl Parse for Dedup {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            dedup_token: input.parse()?,
+        })
+    }
 }
 
-impl DocComment {
-    fn into_value(self) -> String {
-        self.str.value()
+impl ToTokens for Dedup {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.dedup_token.to_tokens(tokens);
     }
 }
 
@@ -372,104 +741,183 @@ impl From<DocComment> for SnafuAttribute {
     }
 }
 
-impl Parse for DocComment {
+struct DisplayBuffer {
+    display_buffer_token: kw::display_buffer,
+    paren_token: token::Paren,
+    size: LitInt,
+}
+
+impl Parse for DisplayBuffer {
     fn parse(input: ParseStream) -> Result<Self> {
+        let content;
         Ok(Self {
-            eq_token: input.parse()?,
-            str: input.parse()?,
+            display_buffer_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            size: content.parse()?,
         })
     }
 }
 
-impl ToTokens for DocComment {
+impl ToTokens for DisplayBuffer {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.eq_token.to_tokens(tokens);
-        self.str.to_tokens(tokens);
+        self.display_buffer_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.size.to_tokens(tokens);
+        });
     }
 }
 
-struct Whatever {
-    whatever_token: kw::whatever,
+struct ExitCode {
+    exit_code_token: kw::exit_code,
+    paren_token: token::Paren,
+    arg: LitInt,
 }
 
-impl Parse for Whatever {
+impl Parse for ExitCode {
     fn parse(input: ParseStream) -> Result<Self> {
+        let content;
         Ok(Self {
-            whatever_token: input.parse()?,
+            exit_code_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            arg: content.parse()?,
         })
     }
 }
 
-impl ToTokens for Whatever {
+impl ToTokens for ExitCode {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.whatever_token.to_tokens(tokens);
+        self.exit_code_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.arg.to_tokens(tokens);
+        });
     }
 }
 
-struct Source {
-    source_token: kw::source,
-    args: MaybeArg<Punctuated<SourceArg, token::Comma>>,
+struct Fields {
+    fields_token: kw::fields,
 }
 
-impl Source {
-    fn into_components(self) -> Vec<super::Source> {
-        match self.args.into_option() {
-            None => vec![super::Source::Flag(true)],
-            Some(args) => args
-                .into_iter()
-                .map(|sa| match sa {
-                    SourceArg::Flag { value } => super::Source::Flag(value.value),
-                    SourceArg::From { r#type, expr, .. } => super::Source::From(r#type, expr),
-                })
-                .collect(),
+impl Parse for Fields {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            fields_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Fields {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.fields_token.to_tokens(tokens);
+    }
+}
+
+struct Flatten {
+    flatten_token: kw::flatten,
+}
+
+impl Parse for Flatten {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            flatten_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Flatten {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.flatten_token.to_tokens(tokens);
+    }
+}
+
+struct Grpc {
+    grpc_token: kw::grpc,
+    paren_token: token::Paren,
+    args: Punctuated<GrpcArg, token::Comma>,
+}
+
+impl Grpc {
+    fn into_grpc(self) -> super::Grpc {
+        let mut code = None;
+        let mut metadata = Vec::new();
+
+        for arg in self.args {
+            match arg {
+                GrpcArg::Code { value, .. } => code = Some(value),
+                GrpcArg::Metadata { fields, .. } => metadata.extend(fields),
+            }
         }
+
+        super::Grpc { code, metadata }
     }
 }
 
-impl Parse for Source {
+impl Parse for Grpc {
     fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let grpc_token = input.parse()?;
+        let paren_token = parenthesized!(content in input);
+        let args: Punctuated<GrpcArg, token::Comma> = Punctuated::parse_terminated(&content)?;
+
+        let (mut has_code, mut has_metadata) = (false, false);
+        for arg in &args {
+            let seen = match arg {
+                GrpcArg::Code { .. } => &mut has_code,
+                GrpcArg::Metadata { .. } => &mut has_metadata,
+            };
+            if *seen {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    "Only one of each `grpc` argument is supported",
+                ));
+            }
+            *seen = true;
+        }
+
         Ok(Self {
-            source_token: input.parse()?,
-            args: MaybeArg::parse_with(&input, Punctuated::parse_terminated)?,
+            grpc_token,
+            paren_token,
+            args,
         })
     }
 }
 
-impl ToTokens for Source {
+impl ToTokens for Grpc {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.source_token.to_tokens(tokens);
-        self.args.to_tokens(tokens);
+        self.grpc_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.args.to_tokens(tokens);
+        });
     }
 }
 
-enum SourceArg {
-    Flag {
-        value: LitBool,
+enum GrpcArg {
+    Code {
+        code_token: kw::code,
+        eq_token: token::Eq,
+        value: Ident,
     },
-    From {
-        from_token: kw::from,
+    Metadata {
+        metadata_token: kw::metadata,
         paren_token: token::Paren,
-        r#type: Type,
-        comma_token: token::Comma,
-        expr: Expr,
+        fields: Punctuated<Ident, token::Comma>,
     },
 }
 
-impl Parse for SourceArg {
+impl Parse for GrpcArg {
     fn parse(input: ParseStream) -> Result<Self> {
         let lookahead = input.lookahead1();
-        if lookahead.peek(LitBool) {
-            Ok(SourceArg::Flag {
+        if lookahead.peek(kw::code) {
+            Ok(GrpcArg::Code {
+                code_token: input.parse()?,
+                eq_token: input.parse()?,
                 value: input.parse()?,
             })
-        } else if lookahead.peek(kw::from) {
+        } else if lookahead.peek(kw::metadata) {
             let content;
-            Ok(SourceArg::From {
-                from_token: input.parse()?,
+            Ok(GrpcArg::Metadata {
+                metadata_token: input.parse()?,
                 paren_token: parenthesized!(content in input),
-                r#type: content.parse()?,
-                comma_token: content.parse()?,
-                expr: content.parse()?,
+                fields: Punctuated::parse_terminated(&content)?,
             })
         } else {
             Err(lookahead.error())
@@ -477,25 +925,773 @@ impl Parse for SourceArg {
     }
 }
 
-impl ToTokens for SourceArg {
+impl ToTokens for GrpcArg {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
-            SourceArg::Flag { value } => {
+            GrpcArg::Code {
+                code_token,
+                eq_token,
+                value,
+            } => {
+                code_token.to_tokens(tokens);
+                eq_token.to_tokens(tokens);
                 value.to_tokens(tokens);
             }
-            SourceArg::From {
-                from_token,
+            GrpcArg::Metadata {
+                metadata_token,
                 paren_token,
-                r#type,
-                comma_token,
-                expr,
+                fields,
             } => {
-                from_token.to_tokens(tokens);
+                metadata_token.to_tokens(tokens);
                 paren_token.surround(tokens, |tokens| {
-                    r#type.to_tokens(tokens);
-                    comma_token.to_tokens(tokens);
-                    expr.to_tokens(tokens);
-                })
+                    fields.to_tokens(tokens);
+                });
+            }
+        }
+    }
+}
+
+struct Help {
+    help_token: kw::help,
+    paren_token: token::Paren,
+    args: Punctuated<Expr, token::Comma>,
+}
+
+impl Help {
+    fn into_exprs(self) -> Vec<Expr> {
+        self.args.into_iter().collect()
+    }
+}
+
+impl Parse for Help {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            help_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            args: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl ToTokens for Help {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.help_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.args.to_tokens(tokens);
+        });
+    }
+}
+
+struct HttpStatus {
+    http_status_token: kw::http_status,
+    arg: MaybeArg<LitInt>,
+}
+
+impl Parse for HttpStatus {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            http_status_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for HttpStatus {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.http_status_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct IoKind {
+    io_kind_token: kw::io_kind,
+    paren_token: token::Paren,
+    kind: Ident,
+}
+
+impl Parse for IoKind {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            io_kind_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            kind: content.parse()?,
+        })
+    }
+}
+
+impl ToTokens for IoKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.io_kind_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.kind.to_tokens(tokens);
+        });
+    }
+}
+
+struct Kind {
+    kind_token: kw::kind,
+}
+
+impl Parse for Kind {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            kind_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Kind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kind_token.to_tokens(tokens);
+    }
+}
+
+struct Label {
+    label_token: kw::label,
+    paren_token: token::Paren,
+    args: Punctuated<Expr, token::Comma>,
+}
+
+impl Label {
+    fn into_exprs(self) -> Vec<Expr> {
+        self.args.into_iter().collect()
+    }
+}
+
+impl Parse for Label {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            label_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            args: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl ToTokens for Label {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.label_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.args.to_tokens(tokens);
+        });
+    }
+}
+
+struct Log {
+    log_token: kw::log,
+    arg: MaybeArg<Punctuated<LogArg, token::Comma>>,
+}
+
+impl Log {
+    /// The level defaults to `Warn` and the target to the module
+    /// containing the error.
+    fn into_log(self) -> super::Log {
+        let mut level = Ident::new("Warn", self.log_token.span);
+        let mut target = None;
+
+        for arg in self.arg.into_option().into_iter().flatten() {
+            match arg {
+                LogArg::Level { value, .. } => {
+                    let mut name = value.value();
+                    name[..1].make_ascii_uppercase();
+                    level = Ident::new(&name, value.span());
+                }
+                LogArg::Target { value, .. } => target = Some(value),
+            }
+        }
+
+        super::Log { level, target }
+    }
+}
+
+impl Parse for Log {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let log_token = input.parse()?;
+        let arg = MaybeArg::parse_with(input, Punctuated::parse_terminated)?;
+
+        if let MaybeArg::Some { content, .. } = &arg {
+            let (mut has_level, mut has_target) = (false, false);
+
+            for arg in content {
+                let seen = match arg {
+                    LogArg::Level { .. } => &mut has_level,
+                    LogArg::Target { .. } => &mut has_target,
+                };
+                if *seen {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        "Only one of each `log` argument is supported",
+                    ));
+                }
+                *seen = true;
+            }
+        }
+
+        Ok(Self { log_token, arg })
+    }
+}
+
+impl ToTokens for Log {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.log_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+enum LogArg {
+    Level {
+        level_token: kw::level,
+        eq_token: token::Eq,
+        value: LitStr,
+    },
+    Target {
+        target_token: kw::target,
+        eq_token: token::Eq,
+        value: LitStr,
+    },
+}
+
+impl Parse for LogArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::level) {
+            Ok(LogArg::Level {
+                level_token: input.parse()?,
+                eq_token: input.parse()?,
+                value: parse_level(input)?,
+            })
+        } else if lookahead.peek(kw::target) {
+            Ok(LogArg::Target {
+                target_token: input.parse()?,
+                eq_token: input.parse()?,
+                value: input.parse()?,
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl ToTokens for LogArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            LogArg::Level {
+                level_token,
+                eq_token,
+                value,
+            } => {
+                level_token.to_tokens(tokens);
+                eq_token.to_tokens(tokens);
+                value.to_tokens(tokens);
+            }
+            LogArg::Target {
+                target_token,
+                eq_token,
+                value,
+            } => {
+                target_token.to_tokens(tokens);
+                eq_token.to_tokens(tokens);
+                value.to_tokens(tokens);
+            }
+        }
+    }
+}
+
+struct Implicit {
+    implicit_token: kw::implicit,
+}
+
+impl Parse for Implicit {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            implicit_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Implicit {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.implicit_token.to_tokens(tokens);
+    }
+}
+
+struct NoPanic {
+    no_panic_token: kw::no_panic,
+}
+
+impl Parse for NoPanic {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            no_panic_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for NoPanic {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.no_panic_token.to_tokens(tokens);
+    }
+}
+
+struct OpenTelemetry {
+    opentelemetry_token: kw::opentelemetry,
+    arg: MaybeArg<OpenTelemetryArg>,
+}
+
+impl OpenTelemetry {
+    fn into_opentelemetry(self) -> super::OpenTelemetry {
+        let fields = match self.arg.into_option() {
+            Some(arg) => arg.fields.into_iter().collect(),
+            None => Vec::new(),
+        };
+
+        super::OpenTelemetry { fields }
+    }
+}
+
+impl Parse for OpenTelemetry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            opentelemetry_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for OpenTelemetry {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.opentelemetry_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct OpenTelemetryArg {
+    fields_token: kw::fields,
+    paren_token: token::Paren,
+    fields: Punctuated<Ident, token::Comma>,
+}
+
+impl Parse for OpenTelemetryArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            fields_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            fields: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl ToTokens for OpenTelemetryArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.fields_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.fields.to_tokens(tokens);
+        });
+    }
+}
+
+struct Predicates {
+    predicates_token: kw::predicates,
+}
+
+impl Parse for Predicates {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            predicates_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Predicates {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.predicates_token.to_tokens(tokens);
+    }
+}
+
+struct ReportConversions {
+    report_conversions_token: kw::report_conversions,
+}
+
+impl Parse for ReportConversions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            report_conversions_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for ReportConversions {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.report_conversions_token.to_tokens(tokens);
+    }
+}
+
+struct Serialize {
+    serialize_token: kw::serialize,
+    arg: MaybeArg<kw::backtrace>,
+}
+
+impl Serialize {
+    fn includes_backtrace(&self) -> bool {
+        match self.arg {
+            MaybeArg::None => false,
+            MaybeArg::Some { .. } => true,
+        }
+    }
+}
+
+impl Parse for Serialize {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            serialize_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Serialize {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.serialize_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct StripSuffix {
+    strip_suffix_token: kw::strip_suffix,
+    paren_token: token::Paren,
+    words: Punctuated<Ident, token::Comma>,
+}
+
+impl StripSuffix {
+    fn into_words(self) -> Vec<String> {
+        self.words.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl Parse for StripSuffix {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            strip_suffix_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            words: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl ToTokens for StripSuffix {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.strip_suffix_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.words.to_tokens(tokens);
+        });
+    }
+}
+
+struct Termination {
+    termination_token: kw::termination,
+}
+
+impl Parse for Termination {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            termination_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Termination {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.termination_token.to_tokens(tokens);
+    }
+}
+
+struct Tracing {
+    tracing_token: kw::tracing,
+    arg: MaybeArg<TracingArg>,
+}
+
+impl Tracing {
+    /// The name of the `tracing::Level` constant, defaulting to `WARN`.
+    fn into_level(self) -> Ident {
+        match self.arg.into_option() {
+            Some(arg) => Ident::new(&arg.level.value().to_uppercase(), arg.level.span()),
+            None => Ident::new("WARN", self.tracing_token.span),
+        }
+    }
+}
+
+impl Parse for Tracing {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            tracing_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Tracing {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.tracing_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Metric {
+    metric_token: kw::metric,
+    name: Option<(token::Eq, LitStr)>,
+}
+
+impl Metric {
+    fn into_metric(self) -> super::Metric {
+        super::Metric {
+            name: self.name.map(|(_, name)| name),
+        }
+    }
+}
+
+impl Parse for Metric {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let metric_token = input.parse()?;
+        let name = if input.peek(token::Eq) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+
+        Ok(Self { metric_token, name })
+    }
+}
+
+impl ToTokens for Metric {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.metric_token.to_tokens(tokens);
+        if let Some((eq_token, name)) = &self.name {
+            eq_token.to_tokens(tokens);
+            name.to_tokens(tokens);
+        }
+    }
+}
+
+const LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+
+/// Parses the name of a level shared by `tracing` and `log`.
+fn parse_level(input: ParseStream) -> Result<LitStr> {
+    let level: LitStr = input.parse()?;
+
+    if LEVELS.contains(&&*level.value()) {
+        Ok(level)
+    } else {
+        Err(syn::Error::new(
+            level.span(),
+            format!("expected one of: {}", LEVELS.join(", ")),
+        ))
+    }
+}
+
+struct TracingArg {
+    level_token: kw::level,
+    eq_token: token::Eq,
+    level: LitStr,
+}
+
+impl Parse for TracingArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            level_token: input.parse()?,
+            eq_token: input.parse()?,
+            level: parse_level(input)?,
+        })
+    }
+}
+
+impl ToTokens for TracingArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.level_token.to_tokens(tokens);
+        self.eq_token.to_tokens(tokens);
+        self.level.to_tokens(tokens);
+    }
+}
+
+struct Url {
+    url_token: kw::url,
+    paren_token: token::Paren,
+    args: Punctuated<Expr, token::Comma>,
+}
+
+impl Url {
+    fn into_exprs(self) -> Vec<Expr> {
+        self.args.into_iter().collect()
+    }
+}
+
+impl Parse for Url {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            url_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            args: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl ToTokens for Url {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.url_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.args.to_tokens(tokens);
+        });
+    }
+}
+
+struct Wasm {
+    wasm_token: kw::wasm,
+}
+
+impl Parse for Wasm {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            wasm_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Wasm {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.wasm_token.to_tokens(tokens);
+    }
+}
+
+struct Whatever {
+    whatever_token: kw::whatever,
+}
+
+impl Parse for Whatever {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            whatever_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Whatever {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.whatever_token.to_tokens(tokens);
+    }
+}
+
+struct Source {
+    source_token: kw::source,
+    args: MaybeArg<Punctuated<SourceArg, token::Comma>>,
+}
+
+impl Source {
+    fn into_components(self) -> Vec<super::Source> {
+        match self.args.into_option() {
+            None => vec![super::Source::Flag(true)],
+            Some(args) => args
+                .into_iter()
+                .map(|sa| match sa {
+                    SourceArg::Flag { value } => super::Source::Flag(value.value),
+                    SourceArg::From { r#type, expr, .. } => super::Source::From(r#type, expr),
+                    SourceArg::Accessor { name, .. } => super::Source::Accessor(name.into_option()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Parse for Source {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            source_token: input.parse()?,
+            args: MaybeArg::parse_with(input, Punctuated::parse_terminated)?,
+        })
+    }
+}
+
+impl ToTokens for Source {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.source_token.to_tokens(tokens);
+        self.args.to_tokens(tokens);
+    }
+}
+
+enum SourceArg {
+    Flag {
+        value: LitBool,
+    },
+    From {
+        from_token: kw::from,
+        paren_token: token::Paren,
+        r#type: Type,
+        comma_token: token::Comma,
+        expr: Expr,
+    },
+    Accessor {
+        accessor_token: kw::accessor,
+        name: MaybeArg<Ident>,
+    },
+}
+
+impl Parse for SourceArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(LitBool) {
+            Ok(SourceArg::Flag {
+                value: input.parse()?,
+            })
+        } else if lookahead.peek(kw::from) {
+            let content;
+            Ok(SourceArg::From {
+                from_token: input.parse()?,
+                paren_token: parenthesized!(content in input),
+                r#type: content.parse()?,
+                comma_token: content.parse()?,
+                expr: content.parse()?,
+            })
+        } else if lookahead.peek(kw::accessor) {
+            Ok(SourceArg::Accessor {
+                accessor_token: input.parse()?,
+                name: input.parse()?,
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl ToTokens for SourceArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            SourceArg::Flag { value } => {
+                value.to_tokens(tokens);
+            }
+            SourceArg::From {
+                from_token,
+                paren_token,
+                r#type,
+                comma_token,
+                expr,
+            } => {
+                from_token.to_tokens(tokens);
+                paren_token.surround(tokens, |tokens| {
+                    r#type.to_tokens(tokens);
+                    comma_token.to_tokens(tokens);
+                    expr.to_tokens(tokens);
+                })
+            }
+            SourceArg::Accessor {
+                accessor_token,
+                name,
+            } => {
+                accessor_token.to_tokens(tokens);
+                name.to_tokens(tokens);
             }
         }
     }
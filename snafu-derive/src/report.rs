@@ -0,0 +1,52 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+pub(crate) fn body(attr: TokenStream, item: syn::ItemFn) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "`#[snafu::report]` does not accept arguments",
+        ));
+    }
+
+    let syn::ItemFn {
+        attrs,
+        vis,
+        mut sig,
+        block,
+    } = item;
+
+    let output = match sig.output {
+        syn::ReturnType::Type(_, ty) => ty,
+        syn::ReturnType::Default => {
+            return Err(syn::Error::new(
+                sig.ident.span(),
+                "`#[snafu::report]` can only be used on functions that return `Result<(), E>`",
+            ))
+        }
+    };
+    sig.output = syn::ReturnType::Default;
+
+    let result = if sig.asyncness.is_some() {
+        quote! {
+            let __snafu_result: #output = async move #block.await;
+        }
+    } else {
+        quote! {
+            let __snafu_body = move || -> #output #block;
+            let __snafu_result = __snafu_body();
+        }
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #result
+            let __snafu_report = ::snafu::Report::capture(move || __snafu_result);
+            if __snafu_report.error().is_some() {
+                __snafu_report.exit()
+            }
+        }
+    })
+}
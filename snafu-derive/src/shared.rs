@@ -1,7 +1,26 @@
-pub(crate) use self::context_selector::ContextSelector;
-pub(crate) use self::display::{Display, DisplayMatchArm};
+pub(crate) use self::c_code::{c_code_match_arm, CCode};
+pub(crate) use self::catalog::CatalogEntry;
+pub(crate) use self::context_selector::{selector_ident, ContextSelector};
+pub(crate) use self::debug::{StableDebug, StableDebugMatchArm};
+pub(crate) use self::diagnostic::{Diagnostic, DiagnosticMatchArms};
+pub(crate) use self::display::{Display, DisplayMatchArm, FormatInternal};
 pub(crate) use self::error::{Error, ErrorSourceMatchArm};
-pub(crate) use self::error_compat::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+pub(crate) use self::error_compat::{
+    exit_code_match_arm, ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatMetadataMatchArm,
+};
+pub(crate) use self::grpc::{grpc_metadata_types, Grpc, GrpcMatchArms};
+pub(crate) use self::http_status::{http_status_match_arm, HttpStatus};
+pub(crate) use self::io_kind::{io_kind_match_arm, IoKind};
+pub(crate) use self::termination::Termination;
+pub(crate) use self::wasm::Wasm;
+
+struct StaticIdent(&'static str);
+
+impl quote::ToTokens for StaticIdent {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        proc_macro2::Ident::new(self.0, proc_macro2::Span::call_site()).to_tokens(tokens)
+    }
+}
 
 pub mod context_selector {
     use crate::{ContextSelectorKind, Field, SuffixKind};
@@ -13,13 +32,36 @@ pub mod context_selector {
     #[derive(Copy, Clone)]
     pub(crate) struct ContextSelector<'a> {
         pub backtrace_field: Option<&'a Field>,
+        /// Constructing the error is outlined and marked cold so that
+        /// it does not bloat the successful path of the caller.
+        pub cold: bool,
         pub crate_root: &'a dyn ToTokens,
+        /// The source is a boxed error of the same type; if it is
+        /// already this variant with equal fields, it is returned
+        /// instead of being wrapped again.
+        pub dedup: bool,
+        pub doc_comment: &'a str,
         pub error_constructor_name: &'a dyn ToTokens,
         pub original_generics_without_defaults: &'a [TokenStream],
         pub parameterized_error_name: &'a dyn ToTokens,
+        pub implicit_fields: &'a [Field],
         pub selector_doc_string: &'a str,
         pub selector_kind: &'a ContextSelectorKind,
         pub selector_name: &'a proc_macro2::Ident,
+        pub strip_suffixes: &'a [String],
+        /// The `tracing::Level` of the event emitted whenever the
+        /// error is constructed.
+        pub tracing: Option<&'a dyn ToTokens>,
+        /// The `log` record written whenever the error is
+        /// constructed.
+        pub log: Option<&'a crate::Log>,
+        /// The name of the `metrics` counter incremented whenever the
+        /// error is constructed.
+        pub metric: Option<&'a syn::LitStr>,
+        /// The fields added as attributes when the error is recorded
+        /// on the active OpenTelemetry span whenever it is
+        /// constructed.
+        pub opentelemetry: Option<&'a [Field]>,
         pub user_fields: &'a [Field],
         pub visibility: Option<&'a dyn ToTokens>,
         pub where_clauses: &'a [TokenStream],
@@ -49,7 +91,7 @@ pub mod context_selector {
                     source_field,
                     message_field,
                 } => self.generate_whatever(source_field.as_ref(), message_field),
-                NoContext { source_field } => self.generate_from_source(source_field),
+                NoContext { source_field, .. } => self.generate_from_source(source_field),
             };
 
             stream.extend(context_selector)
@@ -71,30 +113,50 @@ pub mod context_selector {
         }
 
         fn parameterized_selector_name(&self) -> TokenStream {
-            let selector_name = self.selector_name.to_string();
-            let selector_name = selector_name.trim_end_matches("Error");
-            let suffix: &dyn IdentFragment = match self.selector_kind {
-                ContextSelectorKind::Context {
-                    suffix: SuffixKind::Some(suffix),
-                    ..
-                } => suffix,
-                ContextSelectorKind::Context {
-                    suffix: SuffixKind::None,
-                    ..
-                } => &"",
-                _ => &DEFAULT_SUFFIX,
-            };
-            let selector_name = format_ident!(
-                "{}{}",
-                selector_name,
-                suffix,
-                span = self.selector_name.span()
-            );
+            let selector_name = self.selector_ident();
             let user_generics = self.user_field_generics();
 
             quote! { #selector_name<#(#user_generics,)*> }
         }
 
+        fn selector_ident(&self) -> proc_macro2::Ident {
+            selector_ident(self.selector_name, self.selector_kind, self.strip_suffixes)
+        }
+
+        /// The selector's documentation repeats the summary of the
+        /// variant's documentation and shows how the selector is
+        /// used, as the selector is what downstream code interacts
+        /// with.
+        fn doc_string(&self) -> String {
+            let selector_name = self.selector_ident();
+            let user_field_names = self.user_field_names();
+
+            let selector = if user_field_names.is_empty() {
+                selector_name.to_string()
+            } else if self.is_positional() {
+                format!("{}({})", selector_name, join(&user_field_names))
+            } else {
+                format!("{} {{ {} }}", selector_name, join(&user_field_names))
+            };
+
+            let has_source = self.selector_kind.source_field().is_some();
+            let example = if has_source {
+                format!("result.context({})", selector)
+            } else {
+                format!("{}.fail()", selector)
+            };
+
+            let mut doc = String::from(self.selector_doc_string);
+            if !self.doc_comment.is_empty() {
+                doc.push_str("\n\n");
+                doc.push_str(self.doc_comment);
+            }
+            doc.push_str("\n\n```rust,ignore\n");
+            doc.push_str(&example);
+            doc.push_str("\n```");
+            doc
+        }
+
         fn extended_where_clauses(&self) -> Vec<TokenStream> {
             let user_fields = self.user_fields;
             let user_field_generics = self.user_field_generics();
@@ -109,14 +171,167 @@ pub mod context_selector {
                 .zip(target_types)
                 .map(|(gen, bound)| quote! { #gen: #bound })
                 .chain(where_clauses.iter().cloned())
+                .chain(self.event_where_clauses())
+                .collect()
+        }
+
+        /// The events include the error's message and the `Debug`
+        /// representation of each context field. Span attributes use
+        /// the `Display` representation of the selected fields.
+        fn event_where_clauses(&self) -> Vec<TokenStream> {
+            let has_events = self.tracing.is_some() || self.log.is_some();
+            if !has_events && self.opentelemetry.is_none() {
+                return vec![];
+            }
+
+            let parameterized_error_name = self.parameterized_error_name;
+            let event_fields = if has_events { self.user_fields } else { &[] };
+            let attribute_fields = self.opentelemetry.unwrap_or(&[]);
+
+            event_fields
+                .iter()
+                .map(|Field { ty, .. }| quote! { #ty: ::core::fmt::Debug })
+                .chain(
+                    attribute_fields
+                        .iter()
+                        .map(|Field { ty, .. }| quote! { #ty: ::core::fmt::Display }),
+                )
+                .chain(Some(
+                    quote! { #parameterized_error_name: ::core::fmt::Display },
+                ))
                 .collect()
         }
 
+        /// Emits the `tracing` event, writes the `log` record,
+        /// records the error on the OpenTelemetry span, and
+        /// increments the `metrics` counter, if requested, after the
+        /// error is constructed.
+        fn with_events(&self, construct_error: TokenStream) -> TokenStream {
+            let construct_error = self.with_counter(construct_error);
+
+            if self.tracing.is_none() && self.log.is_none() && self.opentelemetry.is_none() {
+                return construct_error;
+            }
+
+            let crate_root = self.crate_root;
+            let error_constructor_name = self.error_constructor_name;
+            let selector_name = self.selector_name.to_string();
+            let user_field_names = self.user_field_names();
+
+            // Only bind the fields that are used, to avoid warnings
+            let event_fields = if self.tracing.is_some() || self.log.is_some() {
+                self.user_fields
+            } else {
+                &[]
+            };
+            let attribute_fields = self.opentelemetry.unwrap_or(&[]);
+            let bound_fields = event_fields.iter().chain(
+                attribute_fields
+                    .iter()
+                    .filter(|f| !event_fields.iter().any(|e| e.name == f.name)),
+            );
+            let bound_field_members = bound_fields.clone().map(|f| &f.member);
+            let bound_field_names = bound_fields.map(|f| &f.name);
+
+            let tracing_event = self.tracing.map(|level| {
+                quote! {
+                    #crate_root::__tracing::event!(
+                        #crate_root::__tracing::Level::#level,
+                        variant = #selector_name,
+                        #(#user_field_names = ?#user_field_names,)*
+                        "{}",
+                        __snafu_error,
+                    );
+                }
+            });
+
+            let log_record = self.log.map(|crate::Log { level, target }| {
+                let target = target.as_ref().map(|t| quote! { target: #t, });
+                let format = std::iter::once(String::from("{} (variant = {}"))
+                    .chain(user_field_names.iter().map(|n| format!(", {} = {{:?}}", n)))
+                    .chain(Some(String::from(")")))
+                    .collect::<String>();
+
+                quote! {
+                    #crate_root::__log::log!(
+                        #target
+                        #crate_root::__log::Level::#level,
+                        #format,
+                        __snafu_error,
+                        #selector_name,
+                        #(#user_field_names,)*
+                    );
+                }
+            });
+
+            let span_record = self.opentelemetry.map(|fields| {
+                let attribute_names = fields.iter().map(|f| &f.name);
+                let attribute_keys = fields.iter().map(|f| f.name.to_string());
+
+                quote! {
+                    #crate_root::opentelemetry::record_error_as(
+                        &__snafu_error,
+                        #selector_name,
+                        ::std::vec![
+                            #(
+                                #crate_root::__opentelemetry::KeyValue::new(
+                                    #attribute_keys,
+                                    ::std::string::ToString::to_string(#attribute_names),
+                                ),
+                            )*
+                        ],
+                    );
+                }
+            });
+
+            quote! {{
+                let __snafu_error = #construct_error;
+                #[allow(unreachable_patterns)]
+                match __snafu_error {
+                    #error_constructor_name { #(#bound_field_members: ref #bound_field_names,)* .. } => {
+                        #tracing_event
+                        #log_record
+                        #span_record
+                    }
+                    _ => {}
+                }
+                __snafu_error
+            }}
+        }
+
+        /// The counter is labelled with the name of the variant so
+        /// that a single counter can cover an entire enum.
+        fn with_counter(&self, construct_error: TokenStream) -> TokenStream {
+            let name = match self.metric {
+                Some(name) => name,
+                None => return construct_error,
+            };
+
+            let crate_root = self.crate_root;
+            let selector_name = self.selector_name.to_string();
+
+            quote! {{
+                #crate_root::__metrics::counter!(#name, "variant" => #selector_name).increment(1);
+                #construct_error
+            }}
+        }
+
+        /// Tuple variants have positional selectors.
+        fn is_positional(&self) -> bool {
+            self.user_fields.iter().any(Field::is_positional)
+        }
+
         fn transfer_user_fields(&self) -> Vec<TokenStream> {
-            self.user_field_names()
-                .into_iter()
-                .map(|name| {
-                    quote! { #name: ::core::convert::Into::into(self.#name) }
+            self.user_fields
+                .iter()
+                .enumerate()
+                .map(|(index, Field { name, member, .. })| {
+                    if self.is_positional() {
+                        let index = syn::Index::from(index);
+                        quote! { #member: ::core::convert::Into::into(self.#index) }
+                    } else {
+                        quote! { #member: ::core::convert::Into::into(self.#name) }
+                    }
                 })
                 .collect()
         }
@@ -124,20 +339,137 @@ pub mod context_selector {
         fn construct_backtrace_field(&self) -> Option<TokenStream> {
             self.backtrace_field.map(|field| {
                 let crate_root = self.crate_root;
-                let name = &field.name;
-                quote! { #name: #crate_root::GenerateBacktrace::generate(), }
+                let member = &field.member;
+                quote! { #member: #crate_root::GenerateBacktrace::generate(), }
             })
         }
 
+        fn construct_implicit_fields(&self) -> Vec<TokenStream> {
+            self.implicit_fields
+                .iter()
+                .map(
+                    |Field { member, .. }| quote! { #member: ::core::default::Default::default(), },
+                )
+                .collect()
+        }
+
+        fn has_timing_field(&self) -> bool {
+            self.implicit_fields
+                .iter()
+                .any(|Field { ty, .. }| crate::is_implicit_named(ty, "Timing"))
+        }
+
+        /// Like `construct_implicit_fields`, but timing fields are
+        /// taken from the variable `__snafu_timing`.
+        fn construct_implicit_fields_with_timing(&self) -> Vec<TokenStream> {
+            self.implicit_fields
+                .iter()
+                .map(|Field { member, ty, .. }| {
+                    if crate::is_implicit_named(ty, "Timing") {
+                        quote! { #member: __snafu_timing, }
+                    } else {
+                        quote! { #member: ::core::default::Default::default(), }
+                    }
+                })
+                .collect()
+        }
+
+        /// The methods of `IntoError` that run `body`. Errors with a
+        /// timing field are constructed by `into_error_with_timing`,
+        /// which `into_error` calls without an elapsed time.
+        fn generate_into_error_methods(&self, body: TokenStream) -> TokenStream {
+            let crate_root = self.crate_root;
+            let parameterized_error_name = self.parameterized_error_name;
+            let cold_attribute = self.cold_attribute();
+            let cold_attributes = self.cold_attributes();
+
+            if self.has_timing_field() {
+                quote! {
+                    #cold_attribute
+                    fn into_error(self, error: Self::Source) -> #parameterized_error_name {
+                        #crate_root::IntoError::into_error_with_timing(
+                            self,
+                            error,
+                            ::core::default::Default::default(),
+                        )
+                    }
+
+                    #cold_attributes
+                    fn into_error_with_timing(
+                        self,
+                        error: Self::Source,
+                        __snafu_timing: #crate_root::Timing,
+                    ) -> #parameterized_error_name {
+                        #body
+                    }
+                }
+            } else {
+                quote! {
+                    #cold_attributes
+                    fn into_error(self, error: Self::Source) -> #parameterized_error_name {
+                        #body
+                    }
+                }
+            }
+        }
+
+        /// `#[cold]`, for functions that only forward to one marked
+        /// with `cold_attributes`.
+        fn cold_attribute(&self) -> Option<TokenStream> {
+            if self.cold {
+                Some(quote! { #[cold] })
+            } else {
+                None
+            }
+        }
+
+        /// Keeps the construction of the error out of the caller.
+        fn cold_attributes(&self) -> Option<TokenStream> {
+            if self.cold {
+                Some(quote! { #[cold] #[inline(never)] })
+            } else {
+                None
+            }
+        }
+
+        /// Like `construct_implicit_fields`, but location, fields, and
+        /// metadata fields are taken from the `WhateverDetails` in the variable
+        /// `__snafu_details`.
+        fn construct_implicit_fields_from_details(&self) -> Vec<TokenStream> {
+            self.implicit_fields
+                .iter()
+                .map(|Field { member, ty, .. }| {
+                    if crate::is_implicit_named(ty, "Location") {
+                        quote! { #member: __snafu_details.location(), }
+                    } else if crate::is_implicit_named(ty, "Fields") {
+                        quote! { #member: ::core::clone::Clone::clone(__snafu_details.fields()), }
+                    } else if crate::is_implicit_named(ty, "Metadata") {
+                        quote! { #member: __snafu_details.metadata(), }
+                    } else {
+                        quote! { #member: ::core::default::Default::default(), }
+                    }
+                })
+                .collect()
+        }
+
         fn generate_type(self) -> TokenStream {
             let visibility = self.visibility;
             let parameterized_selector_name = self.parameterized_selector_name();
             let user_field_generics = self.user_field_generics();
             let user_field_names = self.user_field_names();
-            let selector_doc_string = self.selector_doc_string;
+            let selector_doc_string = self.doc_string();
 
             let body = if user_field_names.is_empty() {
                 quote! { ; }
+            } else if self.is_positional() {
+                quote! {
+                    (
+                        #(
+                            #[allow(missing_docs)]
+                            #visibility #user_field_generics
+                        ),*
+                    );
+                }
             } else {
                 quote! {
                     {
@@ -166,22 +498,32 @@ pub mod context_selector {
             let extended_where_clauses = self.extended_where_clauses();
             let transfer_user_fields = self.transfer_user_fields();
             let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_implicit_fields = self.construct_implicit_fields();
+            let cold_attribute = self.cold_attribute();
+            let cold_attributes = self.cold_attributes();
+
+            let construct_error = self.with_events(quote! {
+                #error_constructor_name {
+                    #construct_backtrace_field
+                    #(#construct_implicit_fields)*
+                    #(#transfer_user_fields,)*
+                }
+            });
 
             quote! {
                 impl<#(#user_field_generics,)*> #parameterized_selector_name {
                     #[doc = "Consume the selector and return the associated error"]
                     #[must_use]
+                    #cold_attributes
                     #visibility fn build<#(#original_generics_without_defaults,)*>(self) -> #parameterized_error_name
                     where
                         #(#extended_where_clauses),*
                     {
-                        #error_constructor_name {
-                            #construct_backtrace_field
-                            #(#transfer_user_fields,)*
-                        }
+                        #construct_error
                     }
 
                     #[doc = "Consume the selector and return a `Result` with the associated error"]
+                    #cold_attribute
                     #visibility fn fail<#(#original_generics_without_defaults,)* __T>(self) -> ::core::result::Result<__T, #parameterized_error_name>
                     where
                         #(#extended_where_clauses),*
@@ -202,6 +544,7 @@ pub mod context_selector {
             let extended_where_clauses = self.extended_where_clauses();
             let transfer_user_fields = self.transfer_user_fields();
             let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_implicit_fields = self.construct_implicit_fields_with_timing();
 
             let (source_ty, transfer_source_field) = match source_field {
                 Some(source_field) => {
@@ -211,6 +554,20 @@ pub mod context_selector {
                 None => (quote! { #crate_root::NoneError }, quote! {}),
             };
 
+            if let (true, Some(source_field)) = (self.dedup, source_field) {
+                return self.generate_dedup_into_error(source_field);
+            }
+
+            let construct_error = self.with_events(quote! {
+                #error_constructor_name {
+                    #transfer_source_field
+                    #construct_backtrace_field
+                    #(#construct_implicit_fields)*
+                    #(#transfer_user_fields),*
+                }
+            });
+            let into_error_methods = self.generate_into_error_methods(construct_error);
+
             quote! {
                 impl<#(#original_generics_without_defaults,)* #(#user_field_generics,)*> #crate_root::IntoError<#parameterized_error_name> for #parameterized_selector_name
                 where
@@ -219,13 +576,89 @@ pub mod context_selector {
                 {
                     type Source = #source_ty;
 
-                    fn into_error(self, error: Self::Source) -> #parameterized_error_name {
-                        #error_constructor_name {
-                            #transfer_source_field
-                            #construct_backtrace_field
-                            #(#transfer_user_fields),*
-                        }
-                    }
+                    #into_error_methods
+                }
+            }
+        }
+
+        fn generate_dedup_into_error(self, source_field: &crate::SourceField) -> TokenStream {
+            let crate_root = self.crate_root;
+            let error_constructor_name = self.error_constructor_name;
+            let original_generics_without_defaults = self.original_generics_without_defaults;
+            let parameterized_error_name = self.parameterized_error_name;
+            let parameterized_selector_name = self.parameterized_selector_name();
+            let user_field_generics = self.user_field_generics();
+            let extended_where_clauses = self.extended_where_clauses();
+
+            let source_ty = source_field.transformation.ty();
+            let source_member = &source_field.member;
+            let source_transformation = source_field.transformation.transformation();
+
+            let user_field_names = self.user_field_names();
+            let user_field_members: Vec<_> = self.user_fields.iter().map(|f| &f.member).collect();
+            let user_field_types: Vec<_> = self.user_fields.iter().map(|f| &f.ty).collect();
+            let selector_fields = self.user_fields.iter().enumerate().map(|(index, f)| {
+                if self.is_positional() {
+                    let index = syn::Index::from(index);
+                    quote! { self.#index }
+                } else {
+                    let name = &f.name;
+                    quote! { self.#name }
+                }
+            });
+            let existing_fields: Vec<_> = (0..self.user_fields.len())
+                .map(|i| format_ident!("__snafu_existing_{}", i))
+                .collect();
+
+            let is_duplicate = if existing_fields.is_empty() {
+                quote! { true }
+            } else {
+                quote! { #(*#existing_fields == #user_field_names)&&* }
+            };
+
+            // The duplicate is returned before any of the backtrace,
+            // implicit fields, or events of the new error are
+            // generated, as they would be thrown away.
+            let return_duplicate = quote! {
+                #[allow(unreachable_patterns)]
+                let __snafu_duplicate = match *__snafu_source {
+                    #error_constructor_name { #(#user_field_members: ref #existing_fields,)* .. } => #is_duplicate,
+                    _ => false,
+                };
+                if __snafu_duplicate {
+                    return *__snafu_source;
+                }
+            };
+
+            let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_implicit_fields = self.construct_implicit_fields_with_timing();
+            let construct_error = self.with_events(quote! {
+                #error_constructor_name {
+                    #source_member: __snafu_source,
+                    #construct_backtrace_field
+                    #(#construct_implicit_fields)*
+                    #(#user_field_members: #user_field_names),*
+                }
+            });
+            let into_error_methods = self.generate_into_error_methods(quote! {
+                let __snafu_source = (#source_transformation)(error);
+                #(let #user_field_names: #user_field_types = ::core::convert::Into::into(#selector_fields);)*
+
+                #return_duplicate
+
+                #construct_error
+            });
+
+            quote! {
+                impl<#(#original_generics_without_defaults,)* #(#user_field_generics,)*> #crate_root::IntoError<#parameterized_error_name> for #parameterized_selector_name
+                where
+                    #parameterized_error_name: #crate_root::Error + #crate_root::ErrorCompat,
+                    #(#user_field_types: ::core::cmp::PartialEq,)*
+                    #(#extended_where_clauses),*
+                {
+                    type Source = #source_ty;
+
+                    #into_error_methods
                 }
             }
         }
@@ -239,13 +672,17 @@ pub mod context_selector {
             let parameterized_error_name = self.parameterized_error_name;
             let error_constructor_name = self.error_constructor_name;
             let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_implicit_fields = self.construct_implicit_fields();
+            let construct_implicit_fields_from_details =
+                self.construct_implicit_fields_from_details();
+            let cold_attributes = self.cold_attributes();
 
             // testme: transform
 
             let (source_ty, transfer_source_field, empty_source_field) = match source_field {
                 Some(f) => {
                     let source_field_type = f.transformation.ty();
-                    let source_field_name = &f.name;
+                    let source_field_name = &f.member;
                     let source_transformation = f.transformation.transformation();
 
                     (
@@ -257,26 +694,70 @@ pub mod context_selector {
                 None => (quote! { #crate_root::NoneError }, None, None),
             };
 
-            let message_field_name = &message_field.name;
+            let message_field_name = &message_field.member;
+
+            let without_source = self.with_events(quote! {
+                #error_constructor_name {
+                    #empty_source_field
+                    #message_field_name: message,
+                    #construct_backtrace_field
+                    #(#construct_implicit_fields)*
+                }
+            });
+            let with_source = self.with_events(quote! {
+                #error_constructor_name {
+                    #transfer_source_field
+                    #message_field_name: message,
+                    #construct_backtrace_field
+                    #(#construct_implicit_fields)*
+                }
+            });
+            let without_source_and_details = self.with_events(quote! {
+                #error_constructor_name {
+                    #empty_source_field
+                    #message_field_name: message,
+                    #construct_backtrace_field
+                    #(#construct_implicit_fields_from_details)*
+                }
+            });
+            let with_source_and_details = self.with_events(quote! {
+                #error_constructor_name {
+                    #transfer_source_field
+                    #message_field_name: message,
+                    #construct_backtrace_field
+                    #(#construct_implicit_fields_from_details)*
+                }
+            });
 
             quote! {
                 impl #crate_root::FromString for #parameterized_error_name {
                     type Source = #source_ty;
 
+                    #cold_attributes
                     fn without_source(message: String) -> Self {
-                        #error_constructor_name {
-                            #empty_source_field
-                            #message_field_name: message,
-                            #construct_backtrace_field
-                        }
+                        #without_source
                     }
 
+                    #cold_attributes
                     fn with_source(error: Self::Source, message: String) -> Self {
-                        #error_constructor_name {
-                            #transfer_source_field
-                            #message_field_name: message,
-                            #construct_backtrace_field
-                        }
+                        #with_source
+                    }
+
+                    #cold_attributes
+                    fn without_source_and_details(
+                        message: String,
+                        __snafu_details: #crate_root::WhateverDetails,
+                    ) -> Self {
+                        #without_source_and_details
+                    }
+
+                    #cold_attributes
+                    fn with_source_and_details(
+                        error: Self::Source,
+                        message: String,
+                        __snafu_details: #crate_root::WhateverDetails,
+                    ) -> Self {
+                        #with_source_and_details
                     }
                 }
             }
@@ -286,22 +767,32 @@ pub mod context_selector {
             let parameterized_error_name = self.parameterized_error_name;
             let error_constructor_name = self.error_constructor_name;
             let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_implicit_fields = self.construct_implicit_fields();
             let original_generics_without_defaults = self.original_generics_without_defaults;
             let user_field_generics = self.user_field_generics();
             let where_clauses = self.where_clauses;
+            let event_where_clauses = self.event_where_clauses();
+            let cold_attributes = self.cold_attributes();
 
             let (source_field_type, transfer_source_field) = build_source_info(source_field);
 
+            let construct_error = self.with_events(quote! {
+                #error_constructor_name {
+                    #transfer_source_field
+                    #construct_backtrace_field
+                    #(#construct_implicit_fields)*
+                }
+            });
+
             quote! {
                 impl<#(#original_generics_without_defaults,)* #(#user_field_generics,)*> ::core::convert::From<#source_field_type> for #parameterized_error_name
                 where
-                    #(#where_clauses),*
+                    #(#where_clauses,)*
+                    #(#event_where_clauses),*
                 {
+                    #cold_attributes
                     fn from(error: #source_field_type) -> Self {
-                        #error_constructor_name {
-                            #transfer_source_field
-                            #construct_backtrace_field
-                        }
+                        #construct_error
                     }
                 }
             }
@@ -310,40 +801,74 @@ pub mod context_selector {
 
     // Assumes that the error is in a variable called "error"
     fn build_source_info(source_field: &crate::SourceField) -> (&syn::Type, TokenStream) {
-        let source_field_name = source_field.name();
+        let source_field_member = &source_field.member;
         let source_field_type = source_field.transformation.ty();
         let source_transformation = source_field.transformation.transformation();
 
         (
             source_field_type,
-            quote! { #source_field_name: (#source_transformation)(error), },
+            quote! { #source_field_member: (#source_transformation)(error), },
         )
     }
-}
-
-pub mod display {
-    use crate::{Field, SourceField};
-    use proc_macro2::TokenStream;
-    use quote::{quote, ToTokens};
 
-    struct StaticIdent(&'static str);
+    pub(crate) fn selector_ident(
+        selector_name: &proc_macro2::Ident,
+        selector_kind: &ContextSelectorKind,
+        strip_suffixes: &[String],
+    ) -> proc_macro2::Ident {
+        let name = selector_name.to_string();
+        let name = strip_suffixes
+            .iter()
+            .find_map(|suffix| strip_suffix(&name, suffix))
+            .unwrap_or(&name);
+        let suffix: &dyn IdentFragment = match selector_kind {
+            ContextSelectorKind::Context {
+                suffix: SuffixKind::Some(suffix),
+                ..
+            } => suffix,
+            ContextSelectorKind::Context {
+                suffix: SuffixKind::None,
+                ..
+            } => &"",
+            _ => &DEFAULT_SUFFIX,
+        };
+        format_ident!("{}{}", name, suffix, span = selector_name.span())
+    }
 
-    impl quote::ToTokens for StaticIdent {
-        fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-            proc_macro2::Ident::new(self.0, proc_macro2::Span::call_site()).to_tokens(tokens)
+    // `str::strip_suffix` requires Rust 1.45
+    fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+        if s.ends_with(suffix) {
+            Some(&s[..s.len() - suffix.len()])
+        } else {
+            None
         }
     }
 
-    const FORMATTER_ARG: StaticIdent = StaticIdent("__snafu_display_formatter");
+    fn join(names: &[&syn::Ident]) -> String {
+        names
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
 
-    pub(crate) struct Display<'a> {
+pub mod debug {
+    use super::StaticIdent;
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    const FORMATTER_ARG: StaticIdent = StaticIdent("__snafu_debug_formatter");
+
+    pub(crate) struct StableDebug<'a> {
         pub(crate) arms: &'a [TokenStream],
         pub(crate) original_generics: &'a [TokenStream],
         pub(crate) parameterized_error_name: &'a dyn ToTokens,
         pub(crate) where_clauses: &'a [TokenStream],
     }
 
-    impl ToTokens for Display<'_> {
+    impl ToTokens for StableDebug<'_> {
         fn to_tokens(&self, stream: &mut TokenStream) {
             let Self {
                 arms,
@@ -352,14 +877,13 @@ pub mod display {
                 where_clauses,
             } = *self;
 
-            let display_impl = quote! {
+            let debug_impl = quote! {
                 #[allow(single_use_lifetimes)]
-                impl<#(#original_generics),*> ::core::fmt::Display for #parameterized_error_name
+                impl<#(#original_generics),*> ::core::fmt::Debug for #parameterized_error_name
                 where
                     #(#where_clauses),*
                 {
                     fn fmt(&self, #FORMATTER_ARG: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                        #[allow(unused_variables)]
                         match *self {
                             #(#arms),*
                         }
@@ -367,58 +891,51 @@ pub mod display {
                 }
             };
 
-            stream.extend(display_impl);
+            stream.extend(debug_impl);
         }
     }
 
-    pub(crate) struct DisplayMatchArm<'a> {
-        pub(crate) backtrace_field: Option<&'a crate::Field>,
-        pub(crate) default_name: &'a dyn ToTokens,
-        pub(crate) display_format: Option<&'a dyn ToTokens>,
-        pub(crate) doc_comment: &'a str,
+    /// Writes the fields in the order they were declared, as
+    /// `Name`, `Name { a: .., b: .. }`, or `Name(.., ..)`. Each
+    /// value is written with `{:?}`, so formatter flags such as
+    /// `{:#?}` do not change the output.
+    pub(crate) struct StableDebugMatchArm<'a> {
+        pub(crate) field_container: &'a FieldContainer,
         pub(crate) pattern_ident: &'a dyn ToTokens,
-        pub(crate) selector_kind: &'a crate::ContextSelectorKind,
     }
 
-    impl ToTokens for DisplayMatchArm<'_> {
+    impl ToTokens for StableDebugMatchArm<'_> {
         fn to_tokens(&self, stream: &mut TokenStream) {
             let Self {
-                backtrace_field,
-                default_name,
-                display_format,
-                doc_comment,
+                field_container,
                 pattern_ident,
-                selector_kind,
             } = *self;
 
-            let user_fields = selector_kind.user_fields();
-            let source_field = selector_kind.source_field();
-            let message_field = selector_kind.message_field();
+            let name = field_container.name.to_string();
+            let fields = &field_container.fields;
 
-            let format = match (display_format, source_field) {
-                (Some(v), _) => quote! { #v },
-                (None, _) if !doc_comment.is_empty() => {
-                    quote! { #doc_comment }
-                }
-                (None, Some(f)) => {
-                    let field_name = &f.name;
-                    quote! { concat!(stringify!(#default_name), ": {}"), #field_name }
-                }
-                (None, None) => quote! { stringify!(#default_name)},
+            let format = if fields.is_empty() {
+                name
+            } else if fields.iter().all(|f| f.is_positional()) {
+                let values = vec!["{:?}"; fields.len()].join(", ");
+                format!("{}({})", name, values)
+            } else {
+                let values: Vec<_> = fields
+                    .iter()
+                    .map(|f| {
+                        let field_name = f.name().to_string();
+                        format!("{}: {{:?}}", field_name.trim_start_matches("r#"))
+                    })
+                    .collect();
+                format!("{} {{{{ {} }}}}", name, values.join(", "))
             };
 
-            let field_names = user_fields
-                .iter()
-                .chain(backtrace_field)
-                .chain(message_field)
-                .map(Field::name)
-                .chain(source_field.map(SourceField::name));
-
-            let field_names = quote! { #(ref #field_names),* };
+            let field_bindings = fields.iter().map(|f| f.binding());
+            let field_names = fields.iter().map(|f| f.name());
 
             let match_arm = quote! {
-                #pattern_ident { #field_names } => {
-                    write!(#FORMATTER_ARG, #format)
+                #pattern_ident { #(#field_bindings,)* } => {
+                    write!(#FORMATTER_ARG, #format, #(#field_names),*)
                 }
             };
 
@@ -427,36 +944,244 @@ pub mod display {
     }
 }
 
-pub mod error {
-    use crate::{FieldContainer, SourceField};
+pub mod display {
+    use super::StaticIdent;
+    use crate::{Field, SourceField};
     use proc_macro2::TokenStream;
     use quote::{quote, ToTokens};
 
-    pub(crate) struct Error<'a> {
+    const FORMATTER_ARG: StaticIdent = StaticIdent("__snafu_display_formatter");
+
+    pub(crate) struct Display<'a> {
         pub(crate) crate_root: &'a dyn ToTokens,
-        pub(crate) parameterized_error_name: &'a dyn ToTokens,
-        pub(crate) description_arms: &'a [TokenStream],
-        pub(crate) source_arms: &'a [TokenStream],
+        pub(crate) visibility: &'a dyn ToTokens,
+        pub(crate) arms: &'a [TokenStream],
+        /// The size of the buffer the message is formatted into
+        pub(crate) buffer: Option<usize>,
         pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
         pub(crate) where_clauses: &'a [TokenStream],
     }
 
-    impl ToTokens for Error<'_> {
+    impl ToTokens for Display<'_> {
         fn to_tokens(&self, stream: &mut TokenStream) {
             let Self {
                 crate_root,
-                parameterized_error_name,
-                description_arms,
-                source_arms,
+                visibility,
+                arms,
+                buffer,
                 original_generics,
+                parameterized_error_name,
                 where_clauses,
             } = *self;
 
-            let description_fn = quote! {
-                fn description(&self) -> &str {
-                    match *self {
-                        #(#description_arms)*
-                    }
+            let write_message = quote! {
+                #[allow(unused_variables)]
+                match *self {
+                    #(#arms),*
+                }
+            };
+
+            let (fmt_body, format_into_impl) = match buffer {
+                None => (write_message, quote! {}),
+                Some(size) => {
+                    let fmt_body = quote! {
+                        let mut __snafu_display_buffer = [0u8; #size];
+                        let __snafu_message = #crate_root::format_into(
+                            &mut __snafu_display_buffer,
+                            &#crate_root::display_fn(|#FORMATTER_ARG: &mut ::core::fmt::Formatter| {
+                                #write_message
+                            }),
+                        );
+                        #FORMATTER_ARG.write_str(__snafu_message)
+                    };
+
+                    let doc_string = format!(
+                        "Formats the message into the buffer, keeping at most the first {} bytes. Returns the text that fit.",
+                        size,
+                    );
+
+                    let format_into_impl = quote! {
+                        #[allow(single_use_lifetimes)]
+                        impl<#(#original_generics),*> #parameterized_error_name
+                        where
+                            #(#where_clauses),*
+                        {
+                            #[doc = #doc_string]
+                            #visibility fn format_into<'__snafu_buffer>(
+                                &self,
+                                buffer: &'__snafu_buffer mut [u8],
+                            ) -> &'__snafu_buffer str {
+                                #crate_root::format_into(buffer, self)
+                            }
+                        }
+                    };
+
+                    (fmt_body, format_into_impl)
+                }
+            };
+
+            let display_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::fmt::Display for #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    fn fmt(&self, #FORMATTER_ARG: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        #fmt_body
+                    }
+                }
+
+                #format_into_impl
+            };
+
+            stream.extend(display_impl);
+        }
+    }
+
+    pub(crate) struct FormatInternal<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for FormatInternal<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                arms,
+                original_generics,
+                parameterized_error_name,
+                where_clauses,
+            } = *self;
+
+            // Without any internal formats, the default method
+            // already uses the `Display` implementation.
+            let fmt_internal_fn = if arms.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    fn fmt_internal(&self, #FORMATTER_ARG: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        #[allow(unused_variables)]
+                        match *self {
+                            #(#arms,)*
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::fmt::Display::fmt(self, #FORMATTER_ARG),
+                        }
+                    }
+                }
+            };
+
+            let format_internal_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #crate_root::FormatInternal for #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    #fmt_internal_fn
+                }
+            };
+
+            stream.extend(format_internal_impl);
+        }
+    }
+
+    pub(crate) struct DisplayMatchArm<'a> {
+        pub(crate) backtrace_field: Option<&'a crate::Field>,
+        pub(crate) default_name: &'a dyn ToTokens,
+        pub(crate) display_format: Option<&'a dyn ToTokens>,
+        pub(crate) doc_comment: &'a str,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+        pub(crate) selector_kind: &'a crate::ContextSelectorKind,
+    }
+
+    impl ToTokens for DisplayMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                backtrace_field,
+                default_name,
+                display_format,
+                doc_comment,
+                pattern_ident,
+                selector_kind,
+            } = *self;
+
+            let user_fields = selector_kind.user_fields();
+            let source_field = selector_kind.source_field();
+            let message_field = selector_kind.message_field();
+
+            let format = match (display_format, source_field) {
+                (Some(v), _) => quote! { #v },
+                (None, _) if !doc_comment.is_empty() => {
+                    quote! { #doc_comment }
+                }
+                (None, Some(f)) => {
+                    let field_name = &f.name;
+                    quote! { concat!(stringify!(#default_name), ": {}"), #field_name }
+                }
+                (None, None) => quote! { stringify!(#default_name)},
+            };
+
+            let field_bindings = user_fields
+                .iter()
+                .chain(backtrace_field)
+                .chain(message_field)
+                .map(Field::binding)
+                .chain(source_field.map(SourceField::binding));
+
+            let field_names = quote! { #(#field_bindings,)* .. };
+
+            let write = match source_field {
+                Some(source_field) if selector_kind.is_flatten() => {
+                    let field_name = source_field.name();
+                    quote! { ::core::fmt::Display::fmt(#field_name, #FORMATTER_ARG) }
+                }
+                _ => quote! { write!(#FORMATTER_ARG, #format) },
+            };
+
+            let match_arm = quote! {
+                #pattern_ident { #field_names } => {
+                    #write
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+}
+
+pub mod error {
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    pub(crate) struct Error<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) description_arms: &'a [TokenStream],
+        pub(crate) source_arms: &'a [TokenStream],
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Error<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                parameterized_error_name,
+                description_arms,
+                source_arms,
+                original_generics,
+                where_clauses,
+            } = *self;
+
+            let description_fn = quote! {
+                fn description(&self) -> &str {
+                    match *self {
+                        #(#description_arms)*
+                    }
                 }
             };
 
@@ -479,10 +1204,12 @@ pub mod error {
                 }
             };
 
-            let std_backtrace_fn = if cfg!(feature = "unstable-backtraces-impl-std") {
+            let provide_fn = if cfg!(feature = "unstable-provider-api") {
                 Some(quote! {
-                    fn backtrace(&self) -> ::core::option::Option<&::std::backtrace::Backtrace> {
-                        #crate_root::ErrorCompat::backtrace(self)
+                    fn provide<'a>(&'a self, request: &mut ::core::error::Request<'a>) {
+                        if let ::core::option::Option::Some(backtrace) = #crate_root::ErrorCompat::backtrace(self) {
+                            request.provide_ref::<#crate_root::Backtrace>(backtrace);
+                        }
                     }
                 })
             } else {
@@ -499,7 +1226,7 @@ pub mod error {
                     #description_fn
                     #cause_fn
                     #source_fn
-                    #std_backtrace_fn
+                    #provide_fn
                 }
             };
 
@@ -523,14 +1250,19 @@ pub mod error {
 
             let arm = match source_field {
                 Some(source_field) => {
-                    let SourceField {
-                        name: field_name, ..
-                    } = source_field;
+                    let field_name = source_field.name();
+                    let field_binding = source_field.binding();
 
                     let convert_to_error_source = if selector_kind.is_whatever() {
                         quote! {
                             #field_name.as_ref().map(|e| e.as_error_source())
                         }
+                    } else if selector_kind.is_flatten() {
+                        // The wrapped error has already been reported
+                        // by our `Display` implementation.
+                        quote! {
+                            #field_name.as_error_source().source()
+                        }
                     } else {
                         quote! {
                             ::core::option::Option::Some(#field_name.as_error_source())
@@ -538,7 +1270,7 @@ pub mod error {
                     };
 
                     quote! {
-                        #pattern_ident { ref #field_name, .. } => {
+                        #pattern_ident { #field_binding, .. } => {
                             #convert_to_error_source
                         }
                     }
@@ -556,7 +1288,7 @@ pub mod error {
 }
 
 pub mod error_compat {
-    use crate::{Field, FieldContainer, SourceField};
+    use crate::FieldContainer;
     use proc_macro2::TokenStream;
     use quote::{quote, ToTokens};
 
@@ -564,6 +1296,9 @@ pub mod error_compat {
         pub(crate) crate_root: &'a dyn ToTokens,
         pub(crate) parameterized_error_name: &'a dyn ToTokens,
         pub(crate) backtrace_arms: &'a [TokenStream],
+        pub(crate) add_context_arms: &'a [TokenStream],
+        pub(crate) metadata_arms: &'a [TokenStream],
+        pub(crate) exit_code_arms: &'a [TokenStream],
         pub(crate) original_generics: &'a [TokenStream],
         pub(crate) where_clauses: &'a [TokenStream],
     }
@@ -574,6 +1309,9 @@ pub mod error_compat {
                 crate_root,
                 parameterized_error_name,
                 backtrace_arms,
+                add_context_arms,
+                metadata_arms,
+                exit_code_arms,
                 original_generics,
                 where_clauses,
             } = *self;
@@ -586,6 +1324,37 @@ pub mod error_compat {
                 }
             };
 
+            // These only exist when snafu's standard library support is
+            // enabled, which is required to have a `Metadata` field in
+            // the first place.
+            let metadata_fn = if metadata_arms.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    fn metadata(&self) -> ::core::option::Option<&#crate_root::Metadata> {
+                        match *self {
+                            #(#metadata_arms,)*
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+                }
+            };
+
+            let exit_code_fn = if exit_code_arms.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    fn exit_code(&self) -> ::core::option::Option<u8> {
+                        match *self {
+                            #(#exit_code_arms,)*
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+                }
+            };
+
             let error_compat_impl = quote! {
                 #[allow(single_use_lifetimes)]
                 impl<#(#original_generics),*> #crate_root::ErrorCompat for #parameterized_error_name
@@ -593,10 +1362,28 @@ pub mod error_compat {
                     #(#where_clauses),*
                 {
                     #backtrace_fn
+                    #metadata_fn
+                    #exit_code_fn
                 }
             };
 
             stream.extend(error_compat_impl);
+
+            if !add_context_arms.is_empty() {
+                stream.extend(quote! {
+                    #[allow(single_use_lifetimes)]
+                    impl<#(#original_generics),*> #crate_root::ErrorMetadata for #parameterized_error_name
+                    where
+                        #(#where_clauses),*
+                    {
+                        fn add_context(&mut self, key: &'static str, value: ::std::string::String) {
+                            match *self {
+                                #(#add_context_arms,)*
+                            }
+                        }
+                    }
+                });
+            }
         }
     }
 
@@ -621,19 +1408,17 @@ pub mod error_compat {
 
             let match_arm = match (selector_kind.source_field(), backtrace_field) {
                 (Some(source_field), _) if source_field.backtrace_delegate => {
-                    let SourceField {
-                        name: field_name, ..
-                    } = source_field;
+                    let field_name = source_field.name();
+                    let field_binding = source_field.binding();
                     quote! {
-                        #pattern_ident { ref #field_name, .. } => { #crate_root::ErrorCompat::backtrace(#field_name) }
+                        #pattern_ident { #field_binding, .. } => { #crate_root::ErrorCompat::backtrace(#field_name) }
                     }
                 }
                 (_, Some(backtrace_field)) => {
-                    let Field {
-                        name: field_name, ..
-                    } = backtrace_field;
+                    let field_name = backtrace_field.name();
+                    let field_binding = backtrace_field.binding();
                     quote! {
-                        #pattern_ident { ref #field_name, .. } => { #crate_root::GenerateBacktrace::as_backtrace(#field_name) }
+                        #pattern_ident { #field_binding, .. } => { #crate_root::GenerateBacktrace::as_backtrace(#field_name) }
                     }
                 }
                 _ => {
@@ -646,4 +1431,893 @@ pub mod error_compat {
             stream.extend(match_arm);
         }
     }
+
+    /// Variants without an exit code produce no arm and fall through
+    /// to the catch-all arm.
+    pub(crate) fn exit_code_match_arm(
+        field_container: &FieldContainer,
+        pattern_ident: &dyn ToTokens,
+    ) -> Option<TokenStream> {
+        field_container.exit_code.map(|code| {
+            quote! {
+                #pattern_ident { .. } => ::core::option::Option::Some(#code)
+            }
+        })
+    }
+
+    pub(crate) struct ErrorCompatMetadataMatchArm<'a> {
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    /// Variants without a metadata field produce no arm. `metadata`
+    /// falls through to the catch-all arm, while `add_context` is
+    /// only generated when every variant has an arm.
+    impl ErrorCompatMetadataMatchArm<'_> {
+        pub(crate) fn add_context(&self) -> Option<TokenStream> {
+            let pattern_ident = self.pattern_ident;
+
+            self.field_container.metadata_field.as_ref().map(
+                |crate::Field { name, member, .. }| {
+                    quote! {
+                        #pattern_ident { #member: ref mut #name, .. } => {
+                            #name.insert(key, value)
+                        }
+                    }
+                },
+            )
+        }
+
+        pub(crate) fn metadata(&self) -> Option<TokenStream> {
+            let pattern_ident = self.pattern_ident;
+
+            self.field_container.metadata_field.as_ref().map(
+                |crate::Field { name, member, .. }| {
+                    quote! {
+                        #pattern_ident { #member: ref #name, .. } => {
+                            ::core::option::Option::Some(#name)
+                        }
+                    }
+                },
+            )
+        }
+    }
+}
+
+pub mod grpc {
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    /// Generates the inherent `grpc_code` method and the conversion
+    /// into a `tonic::Status`.
+    pub(crate) struct Grpc<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+        pub(crate) visibility: &'a dyn ToTokens,
+        pub(crate) code_arms: &'a [TokenStream],
+        pub(crate) metadata_arms: &'a [TokenStream],
+        /// The types of the fields included in the metadata
+        pub(crate) metadata_types: &'a [&'a syn::Type],
+    }
+
+    impl ToTokens for Grpc<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                parameterized_error_name,
+                original_generics,
+                where_clauses,
+                visibility,
+                code_arms,
+                metadata_arms,
+                metadata_types,
+            } = *self;
+
+            let grpc_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    /// Returns the gRPC status code that a service
+                    /// should respond with when this error occurs.
+                    #visibility fn grpc_code(&self) -> #crate_root::__tonic::Code {
+                        match *self {
+                            #(#code_arms,)*
+                        }
+                    }
+                }
+
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::convert::From<#parameterized_error_name> for #crate_root::__tonic::Status
+                where
+                    #(#where_clauses,)*
+                    #parameterized_error_name: ::core::fmt::Display,
+                    #(#metadata_types: ::core::fmt::Display,)*
+                {
+                    fn from(error: #parameterized_error_name) -> Self {
+                        #[allow(unused_mut)]
+                        let mut metadata = #crate_root::__tonic::metadata::MetadataMap::new();
+
+                        match error {
+                            #(#metadata_arms,)*
+                            #[allow(unreachable_patterns)]
+                            _ => {}
+                        }
+
+                        #crate_root::__tonic::Status::with_metadata(
+                            error.grpc_code(),
+                            ::std::string::ToString::to_string(&error),
+                            metadata,
+                        )
+                    }
+                }
+            };
+
+            stream.extend(grpc_impl);
+        }
+    }
+
+    pub(crate) struct GrpcMatchArms<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl<'a> GrpcMatchArms<'a> {
+        /// Flattened variants use the code of their source unless
+        /// they have their own.
+        pub(crate) fn code(&self, default: &dyn ToTokens) -> TokenStream {
+            let Self {
+                crate_root,
+                field_container,
+                pattern_ident,
+            } = *self;
+            let selector_kind = &field_container.selector_kind;
+            let code = field_container.grpc.as_ref().and_then(|g| g.code.as_ref());
+
+            match (code, selector_kind.source_field()) {
+                (Some(code), _) => quote! {
+                    #pattern_ident { .. } => #crate_root::__tonic::Code::#code
+                },
+                (None, Some(source_field)) if selector_kind.is_flatten() => {
+                    let field_binding = source_field.binding();
+                    let field_name = source_field.name();
+                    quote! {
+                        #pattern_ident { #field_binding, .. } => #field_name.grpc_code()
+                    }
+                }
+                _ => quote! {
+                    #pattern_ident { .. } => #default
+                },
+            }
+        }
+
+        /// Each field is added using its `Display` implementation;
+        /// values that are not valid metadata are skipped.
+        pub(crate) fn metadata(&self) -> Option<TokenStream> {
+            let Self {
+                field_container,
+                pattern_ident,
+                ..
+            } = *self;
+
+            let fields: Vec<_> = field_container
+                .grpc
+                .as_ref()?
+                .metadata
+                .iter()
+                .flat_map(|name| field_container.fields.iter().find(|f| f.name == *name))
+                .collect();
+
+            if fields.is_empty() {
+                return None;
+            }
+
+            let bindings = fields.iter().map(|f| crate::binding(&f.member, &f.name));
+            let inserts = fields.iter().map(|f| {
+                let name = &f.name;
+                let key = name.to_string().trim_start_matches("r#").to_lowercase();
+                quote! {
+                    if let ::core::result::Result::Ok(value) =
+                        ::std::string::ToString::to_string(#name).parse()
+                    {
+                        metadata.insert(#key, value);
+                    }
+                }
+            });
+
+            Some(quote! {
+                #pattern_ident { #(#bindings,)* .. } => {
+                    #(#inserts)*
+                }
+            })
+        }
+    }
+
+    /// The types of the fields included in the metadata, which must
+    /// implement `Display`.
+    pub(crate) fn grpc_metadata_types(field_container: &FieldContainer) -> Vec<&syn::Type> {
+        field_container
+            .grpc
+            .iter()
+            .flat_map(|g| &g.metadata)
+            .flat_map(|name| field_container.fields.iter().find(|f| f.name == *name))
+            .map(|f| &f.ty)
+            .collect()
+    }
+}
+
+pub mod catalog {
+    use crate::{FieldContainer, HttpStatus as Status};
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+    use syn::{parse::Parser, punctuated::Punctuated, Expr, Lit};
+
+    /// Submits an enum variant or struct to the error catalog.
+    pub(crate) struct CatalogEntry<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) error_name: &'a syn::Ident,
+        /// `None` for structs
+        pub(crate) variant_name: Option<&'a syn::Ident>,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) skip_display: bool,
+        /// The HTTP status code of variants without one, when the
+        /// error has an `http_status` method
+        pub(crate) default_http_status: Option<u16>,
+        /// The C error code of variants without one, when the error
+        /// has an `error_code` method
+        pub(crate) default_c_code: Option<i32>,
+    }
+
+    impl CatalogEntry<'_> {
+        /// The format string as written in the source, mirroring the
+        /// fallbacks of the generated `Display` implementation.
+        fn message(&self) -> Option<String> {
+            let field_container = self.field_container;
+            let selector_kind = &field_container.selector_kind;
+
+            if self.skip_display || selector_kind.is_flatten() {
+                return None;
+            }
+
+            let name = unraw(&field_container.name);
+
+            let message = match &field_container.display_format {
+                Some(format) => format_string(format.to_token_stream()),
+                None if !field_container.doc_comment.is_empty() => {
+                    field_container.doc_comment.clone()
+                }
+                None if selector_kind.source_field().is_some() => format!("{}: {{}}", name),
+                None => name,
+            };
+
+            Some(message)
+        }
+
+        /// Flattened variants and those using the code of their
+        /// source do not have a code until the error is constructed.
+        fn http_status(&self) -> Option<u16> {
+            match self.field_container.http_status {
+                Some(Status::Code(code)) => Some(code),
+                Some(Status::Source) => None,
+                None if self.field_container.selector_kind.is_flatten() => None,
+                None => self.default_http_status,
+            }
+        }
+
+        fn c_code(&self) -> Option<i32> {
+            match self.field_container.c_code {
+                Some(code) => Some(code),
+                None if self.field_container.selector_kind.is_flatten() => None,
+                None => self.default_c_code,
+            }
+        }
+    }
+
+    impl ToTokens for CatalogEntry<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let crate_root = self.crate_root;
+            let error_name = unraw(self.error_name);
+            let variant_name = option(self.variant_name.map(unraw));
+            let message = option(self.message());
+            let doc = &self.field_container.doc_comment;
+            let exit_code = option(self.field_container.exit_code);
+            let http_status = option(self.http_status());
+            let c_code = option(self.c_code());
+
+            let entry = quote! {
+                #crate_root::__inventory::submit! {
+                    #crate_root::CatalogEntry::__new(
+                        ::core::module_path!(),
+                        #error_name,
+                        #variant_name,
+                        #message,
+                        #doc,
+                        #exit_code,
+                        #http_status,
+                        #c_code,
+                    )
+                }
+            };
+
+            stream.extend(entry);
+        }
+    }
+
+    fn unraw(ident: &syn::Ident) -> String {
+        ident.to_string().trim_start_matches("r#").to_owned()
+    }
+
+    fn option(value: Option<impl ToTokens>) -> TokenStream {
+        match value {
+            Some(value) => quote! { ::core::option::Option::Some(#value) },
+            None => quote! { ::core::option::Option::None },
+        }
+    }
+
+    /// Uses the format string when it is a literal, otherwise the
+    /// arguments exactly as written.
+    fn format_string(tokens: TokenStream) -> String {
+        let args = Punctuated::<Expr, syn::Token![,]>::parse_terminated.parse2(tokens.clone());
+
+        if let Ok(args) = args {
+            if let Some(Expr::Lit(expr)) = args.first() {
+                if let Lit::Str(s) = &expr.lit {
+                    return s.value();
+                }
+            }
+        }
+
+        tokens.to_string()
+    }
+}
+
+pub mod io_kind {
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    /// Generates the inherent `io_kind` method and the conversion
+    /// into a `std::io::Error`.
+    pub(crate) struct IoKind<'a> {
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+        pub(crate) visibility: &'a dyn ToTokens,
+        pub(crate) arms: &'a [TokenStream],
+    }
+
+    impl ToTokens for IoKind<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                parameterized_error_name,
+                original_generics,
+                where_clauses,
+                visibility,
+                arms,
+            } = *self;
+
+            let io_kind_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    /// Returns the kind of I/O error that this error
+                    /// is reported as when converted into a
+                    /// `std::io::Error`.
+                    #visibility fn io_kind(&self) -> ::std::io::ErrorKind {
+                        match *self {
+                            #(#arms,)*
+                        }
+                    }
+                }
+
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::convert::From<#parameterized_error_name> for ::std::io::Error
+                where
+                    #(#where_clauses,)*
+                    #parameterized_error_name: ::std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static,
+                {
+                    fn from(error: #parameterized_error_name) -> Self {
+                        ::std::io::Error::new(error.io_kind(), error)
+                    }
+                }
+            };
+
+            stream.extend(io_kind_impl);
+        }
+    }
+
+    /// Flattened variants use the kind of their source unless they
+    /// have their own.
+    pub(crate) fn io_kind_match_arm(
+        field_container: &FieldContainer,
+        pattern_ident: &dyn ToTokens,
+        default: &dyn ToTokens,
+    ) -> TokenStream {
+        let selector_kind = &field_container.selector_kind;
+
+        match (&field_container.io_kind, selector_kind.source_field()) {
+            (Some(kind), _) => quote! {
+                #pattern_ident { .. } => ::std::io::ErrorKind::#kind
+            },
+            (None, Some(source_field)) if selector_kind.is_flatten() => {
+                let field_binding = source_field.binding();
+                let field_name = source_field.name();
+                quote! {
+                    #pattern_ident { #field_binding, .. } => #field_name.io_kind()
+                }
+            }
+            _ => quote! {
+                #pattern_ident { .. } => #default
+            },
+        }
+    }
+}
+
+pub mod http_status {
+    use crate::{FieldContainer, HttpStatus as Status};
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    /// Generates the inherent `http_status` method, as well as
+    /// `http_status_code` when the `http` feature is enabled.
+    pub(crate) struct HttpStatus<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+        pub(crate) visibility: &'a dyn ToTokens,
+        /// The body of the `http_status` method
+        pub(crate) status: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for HttpStatus<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                parameterized_error_name,
+                original_generics,
+                where_clauses,
+                visibility,
+                status,
+            } = *self;
+
+            let status_code_fn = if cfg!(feature = "http") {
+                quote! {
+                    /// Returns the HTTP status code of this error as an
+                    /// `http::StatusCode`.
+                    #visibility fn http_status_code(&self) -> #crate_root::__http::StatusCode {
+                        #crate_root::__http::StatusCode::from_u16(self.http_status())
+                            .unwrap_or(#crate_root::__http::StatusCode::INTERNAL_SERVER_ERROR)
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            let http_status_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    /// Returns the HTTP status code that a web service
+                    /// should respond with when this error occurs.
+                    #visibility fn http_status(&self) -> u16 {
+                        #status
+                    }
+
+                    #status_code_fn
+                }
+            };
+
+            stream.extend(http_status_impl);
+        }
+    }
+
+    /// Flattened variants use the status code of their source unless
+    /// they have their own.
+    pub(crate) fn http_status_match_arm(
+        field_container: &FieldContainer,
+        pattern_ident: &dyn ToTokens,
+        default: u16,
+    ) -> TokenStream {
+        let selector_kind = &field_container.selector_kind;
+        let status = field_container.http_status.or_else(|| {
+            if selector_kind.is_flatten() {
+                Some(Status::Source)
+            } else {
+                None
+            }
+        });
+
+        match (status, selector_kind.source_field()) {
+            (Some(Status::Code(code)), _) => quote! {
+                #pattern_ident { .. } => #code
+            },
+            (Some(Status::Source), Some(source_field)) => {
+                let field_binding = source_field.binding();
+                let field_name = source_field.name();
+                quote! {
+                    #pattern_ident { #field_binding, .. } => #field_name.http_status()
+                }
+            }
+            _ => quote! {
+                #pattern_ident { .. } => #default
+            },
+        }
+    }
+}
+
+pub mod c_code {
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    /// Generates the inherent `error_code` method, as well as a
+    /// conversion into `FfiError` when the `ffi` feature is enabled.
+    pub(crate) struct CCode<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+        pub(crate) visibility: &'a dyn ToTokens,
+        /// The body of the `error_code` method
+        pub(crate) code: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for CCode<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                parameterized_error_name,
+                original_generics,
+                where_clauses,
+                visibility,
+                code,
+            } = *self;
+
+            let ffi_impl = if cfg!(feature = "ffi") {
+                quote! {
+                    #[allow(single_use_lifetimes)]
+                    impl<#(#original_generics),*> ::core::convert::From<#parameterized_error_name> for #crate_root::ffi::FfiError
+                    where
+                        #(#where_clauses,)*
+                        #parameterized_error_name: #crate_root::Error + #crate_root::ErrorCompat + 'static,
+                    {
+                        fn from(error: #parameterized_error_name) -> Self {
+                            let code = error.error_code();
+                            #crate_root::ffi::FfiError::new(code, error)
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            let c_code_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    /// Returns the code identifying this error to
+                    /// callers of a C API.
+                    #visibility fn error_code(&self) -> i32 {
+                        #code
+                    }
+                }
+
+                #ffi_impl
+            };
+
+            stream.extend(c_code_impl);
+        }
+    }
+
+    /// Flattened variants use the code of their source unless they
+    /// have their own.
+    pub(crate) fn c_code_match_arm(
+        field_container: &FieldContainer,
+        pattern_ident: &dyn ToTokens,
+        default: i32,
+    ) -> TokenStream {
+        let selector_kind = &field_container.selector_kind;
+
+        match (field_container.c_code, selector_kind.source_field()) {
+            (Some(code), _) => quote! {
+                #pattern_ident { .. } => #code
+            },
+            (None, Some(source_field)) if selector_kind.is_flatten() => {
+                let field_binding = source_field.binding();
+                let field_name = source_field.name();
+                quote! {
+                    #pattern_ident { #field_binding, .. } => #field_name.error_code()
+                }
+            }
+            _ => quote! {
+                #pattern_ident { .. } => #default
+            },
+        }
+    }
+}
+
+pub mod termination {
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    /// Implements `Termination` by printing the error as a `Report`
+    /// and exiting with the error's exit code. A `From`
+    /// implementation for `ExitCode` exposes the same exit code
+    /// without printing anything.
+    ///
+    /// Both traits require Rust 1.61, so these implementations are
+    /// only generated when the user asks for them.
+    pub(crate) struct Termination<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Termination<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                parameterized_error_name,
+                original_generics,
+                where_clauses,
+            } = *self;
+
+            let termination_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::std::process::Termination for #parameterized_error_name
+                where
+                    #(#where_clauses,)*
+                    Self: #crate_root::Error + #crate_root::ErrorCompat + 'static,
+                {
+                    fn report(self) -> ::std::process::ExitCode {
+                        let report = #crate_root::Report::from_error(self);
+                        let exit_code = report.exit_code();
+                        ::std::eprintln!("Error: {}", report);
+                        ::std::process::ExitCode::from(exit_code)
+                    }
+                }
+
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::convert::From<#parameterized_error_name> for ::std::process::ExitCode
+                where
+                    #(#where_clauses,)*
+                    #parameterized_error_name: #crate_root::ErrorCompat,
+                {
+                    fn from(error: #parameterized_error_name) -> Self {
+                        let exit_code = #crate_root::ErrorCompat::exit_code(&error);
+                        ::std::process::ExitCode::from(exit_code.unwrap_or(1))
+                    }
+                }
+            };
+
+            stream.extend(termination_impl);
+        }
+    }
+}
+
+pub mod wasm {
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    /// Converts the error into a `JsValue` holding a JavaScript
+    /// `Error` whose message is the error formatted as a `Report`,
+    /// so the entire chain of sources crosses the boundary.
+    pub(crate) struct Wasm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Wasm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                parameterized_error_name,
+                original_generics,
+                where_clauses,
+            } = *self;
+
+            let wasm_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::convert::From<#parameterized_error_name> for #crate_root::__wasm_bindgen::JsValue
+                where
+                    #(#where_clauses,)*
+                    #parameterized_error_name: #crate_root::Error + #crate_root::ErrorCompat + 'static,
+                {
+                    fn from(error: #parameterized_error_name) -> Self {
+                        let report = #crate_root::Report::from_error(error);
+                        let message = ::std::string::ToString::to_string(&report);
+                        #crate_root::__wasm_bindgen::JsError::new(&message).into()
+                    }
+                }
+            };
+
+            stream.extend(wasm_impl);
+        }
+    }
+}
+
+pub mod diagnostic {
+    use crate::{Field, FieldContainer, SourceField};
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    /// Implements `miette::Diagnostic`. Only the methods that have at
+    /// least one match arm are generated; the rest use the trait's
+    /// defaults.
+    pub(crate) struct Diagnostic<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) code_arms: &'a [TokenStream],
+        pub(crate) help_arms: &'a [TokenStream],
+        pub(crate) url_arms: &'a [TokenStream],
+        pub(crate) labels_arms: &'a [TokenStream],
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Diagnostic<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                parameterized_error_name,
+                code_arms,
+                help_arms,
+                url_arms,
+                labels_arms,
+                original_generics,
+                where_clauses,
+            } = *self;
+
+            let display_fn = |name: TokenStream, arms: &[TokenStream]| {
+                if arms.is_empty() {
+                    return quote! {};
+                }
+
+                quote! {
+                    fn #name<'__snafu_diagnostic>(
+                        &'__snafu_diagnostic self,
+                    ) -> ::core::option::Option<::std::boxed::Box<dyn ::core::fmt::Display + '__snafu_diagnostic>> {
+                        #[allow(unused_variables)]
+                        match *self {
+                            #(#arms,)*
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+                }
+            };
+
+            let code_fn = display_fn(quote! { code }, code_arms);
+            let help_fn = display_fn(quote! { help }, help_arms);
+            let url_fn = display_fn(quote! { url }, url_arms);
+
+            let labels_fn = if labels_arms.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    fn labels(
+                        &self,
+                    ) -> ::core::option::Option<::std::boxed::Box<dyn ::core::iter::Iterator<Item = #crate_root::__miette::LabeledSpan> + '_>> {
+                        #[allow(unused_variables)]
+                        match *self {
+                            #(#labels_arms,)*
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+                }
+            };
+
+            let diagnostic_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #crate_root::__miette::Diagnostic for #parameterized_error_name
+                where
+                    #(#where_clauses,)*
+                    Self: #crate_root::Error,
+                {
+                    #code_fn
+                    #help_fn
+                    #url_fn
+                    #labels_fn
+                }
+            };
+
+            stream.extend(diagnostic_impl);
+        }
+    }
+
+    /// Variants without the corresponding attribute produce no arm
+    /// and fall through to the catch-all arm.
+    pub(crate) struct DiagnosticMatchArms<'a> {
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl DiagnosticMatchArms<'_> {
+        pub(crate) fn code(&self) -> Option<TokenStream> {
+            self.display_arm(self.field_container.diagnostic.code.as_ref())
+        }
+
+        pub(crate) fn help(&self) -> Option<TokenStream> {
+            self.display_arm(self.field_container.diagnostic.help.as_ref())
+        }
+
+        pub(crate) fn url(&self) -> Option<TokenStream> {
+            self.display_arm(self.field_container.diagnostic.url.as_ref())
+        }
+
+        pub(crate) fn labels(&self, crate_root: &dyn ToTokens) -> Option<TokenStream> {
+            let labels = &self.field_container.diagnostic.labels;
+            if labels.is_empty() {
+                return None;
+            }
+
+            let pattern = self.pattern();
+            let labels = labels.iter().map(|(field, format)| {
+                let name = &field.name;
+                quote! {
+                    #crate_root::__miette::LabeledSpan::new_with_span(
+                        ::core::option::Option::Some(::std::format!(#format)),
+                        ::core::clone::Clone::clone(#name),
+                    )
+                }
+            });
+
+            Some(quote! {
+                #pattern => {
+                    let labels = ::std::vec![#(#labels),*];
+                    ::core::option::Option::Some(::std::boxed::Box::new(labels.into_iter()))
+                }
+            })
+        }
+
+        fn display_arm(&self, format: Option<&crate::UserInput>) -> Option<TokenStream> {
+            let format = format?;
+            let pattern = self.pattern();
+
+            Some(quote! {
+                #pattern => {
+                    ::core::option::Option::Some(::std::boxed::Box::new(::std::format!(#format)))
+                }
+            })
+        }
+
+        /// Binds the same fields that are available to `display`.
+        fn pattern(&self) -> TokenStream {
+            let Self {
+                field_container:
+                    FieldContainer {
+                        backtrace_field,
+                        selector_kind,
+                        ..
+                    },
+                pattern_ident,
+            } = *self;
+
+            let field_bindings = selector_kind
+                .user_fields()
+                .iter()
+                .chain(backtrace_field)
+                .chain(selector_kind.message_field())
+                .map(Field::binding)
+                .chain(selector_kind.source_field().map(SourceField::binding));
+
+            quote! { #pattern_ident { #(#field_bindings,)* .. } }
+        }
+    }
 }
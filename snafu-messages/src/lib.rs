@@ -0,0 +1,249 @@
+#![deny(missing_docs)]
+
+//! Extracts the user-facing messages of error types that use
+//! `#[derive(Snafu)]`, such as for building a message catalog for
+//! translators.
+//!
+//! The messages are found the same way that the derive macro finds
+//! them: from `#[snafu(display(...))]` or, when that is absent, from
+//! the summary of the doc comment.
+//!
+//! ```rust
+//! let file: syn::File = syn::parse_str(r#"
+//!     #[derive(Debug, Snafu)]
+//!     enum Error {
+//!         #[snafu(display("Could not open {}", path))]
+//!         Open { path: String },
+//!
+//!         /// The configuration is empty
+//!         Empty,
+//!     }
+//! "#).unwrap();
+//!
+//! let messages = snafu_messages::extract_file(&file);
+//!
+//! assert_eq!(messages[0].variant(), Some("Open"));
+//! assert_eq!(messages[0].template(), "Could not open {}");
+//! assert_eq!(messages[0].arguments(), ["path"]);
+//!
+//! assert_eq!(messages[1].variant(), Some("Empty"));
+//! assert_eq!(messages[1].template(), "The configuration is empty");
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Item, Lit, Meta, NestedMeta, Token,
+};
+
+#[doc(hidden)]
+pub mod parse;
+
+/// Where a message came from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MessageKind {
+    /// The message was provided by `#[snafu(display(...))]`.
+    Display,
+    /// The message is the summary of the doc comment.
+    DocComment,
+}
+
+/// The user-facing message of an error type or enum variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    type_name: String,
+    variant: Option<String>,
+    kind: MessageKind,
+    template: String,
+    arguments: Vec<String>,
+}
+
+impl Message {
+    /// The name of the error type.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The name of the enum variant, if the error type is an enum.
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_ref().map(String::as_str)
+    }
+
+    /// Where the message came from.
+    pub fn kind(&self) -> MessageKind {
+        self.kind
+    }
+
+    /// The format string of the message.
+    ///
+    /// When the display attribute does not start with a string
+    /// literal, this is the source code of the first argument.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// The source code of each argument following the format string.
+    pub fn arguments(&self) -> &[String] {
+        &self.arguments
+    }
+}
+
+/// Extracts the messages of a single error type.
+///
+/// The input is expected to be a type that uses `#[derive(Snafu)]`;
+/// this is not checked. Tuple structs delegate their messages to the
+/// wrapped error and so have none, as do enums that use
+/// `#[snafu(display(skip))]`.
+pub fn extract(input: &DeriveInput) -> Vec<Message> {
+    let type_name = input.ident.to_string();
+
+    match &input.data {
+        Data::Enum(_) if displays(&input.attrs).any(|d| d.is_skip()) => Vec::new(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|variant| {
+                message(&type_name, Some(variant.ident.to_string()), &variant.attrs)
+            })
+            .collect(),
+        Data::Struct(data) => match data.fields {
+            Fields::Unnamed(_) => Vec::new(),
+            _ => message(&type_name, None, &input.attrs)
+                .into_iter()
+                .collect(),
+        },
+        Data::Union(_) => Vec::new(),
+    }
+}
+
+/// Extracts the messages of every error type in the file that uses
+/// `#[derive(Snafu)]`, including those in inline modules.
+pub fn extract_file(file: &syn::File) -> Vec<Message> {
+    let mut messages = Vec::new();
+    extract_items(&file.items, &mut messages);
+    messages
+}
+
+fn extract_items(items: &[Item], messages: &mut Vec<Message>) {
+    for item in items {
+        let input: DeriveInput = match item {
+            Item::Enum(item) if derives_snafu(&item.attrs) => item.clone().into(),
+            Item::Struct(item) if derives_snafu(&item.attrs) => item.clone().into(),
+            Item::Mod(item) => {
+                if let Some((_, items)) = &item.content {
+                    extract_items(items, messages);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        messages.extend(extract(&input));
+    }
+}
+
+fn derives_snafu(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(meta) => meta
+                    .path()
+                    .segments
+                    .last()
+                    .map_or(false, |segment| segment.ident == "Snafu"),
+                _ => false,
+            }),
+            _ => false,
+        })
+}
+
+fn message(type_name: &str, variant: Option<String>, attrs: &[Attribute]) -> Option<Message> {
+    let new = |kind, template, arguments| Message {
+        type_name: type_name.to_string(),
+        variant: variant.clone(),
+        kind,
+        template,
+        arguments,
+    };
+
+    if let Some((template, arguments)) = display(attrs) {
+        return Some(new(MessageKind::Display, template, arguments));
+    }
+
+    let doc_comment = doc_comment(attrs);
+    if doc_comment.is_empty() {
+        None
+    } else {
+        Some(new(MessageKind::DocComment, doc_comment, Vec::new()))
+    }
+}
+
+fn display(attrs: &[Attribute]) -> Option<(String, Vec<String>)> {
+    let display = displays(attrs).find(|d| !d.is_skip() && !d.is_internal())?;
+
+    let mut exprs = display.into_exprs().into_iter().map(|expr| match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => s.value(),
+        expr => expr.to_token_stream().to_string(),
+    });
+
+    let template = exprs.next()?;
+    Some((template, exprs.collect()))
+}
+
+fn displays(attrs: &[Attribute]) -> impl Iterator<Item = parse::Display> + '_ {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("snafu"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<SnafuMeta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter_map(|meta| meta.display)
+}
+
+fn doc_comment(attrs: &[Attribute]) -> String {
+    let mut summary = parse::DocSummary::default();
+
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| syn::parse2::<parse::DocComment>(attr.tokens.clone()).ok());
+
+    for line in lines {
+        summary.push_line(&line.into_value());
+    }
+
+    summary.into_string()
+}
+
+/// A single item inside `#[snafu(...)]`, such as `display(...)` or
+/// `whatever`. Only `display` is parsed; everything else is skipped.
+struct SnafuMeta {
+    display: Option<parse::Display>,
+}
+
+impl Parse for SnafuMeta {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(parse::kw::display) {
+            return Ok(SnafuMeta {
+                display: Some(input.parse()?),
+            });
+        }
+
+        input.call(<syn::Ident as syn::ext::IdentExt>::parse_any)?;
+        if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            content.parse::<TokenStream>()?;
+        }
+        Ok(SnafuMeta { display: None })
+    }
+}
@@ -0,0 +1,145 @@
+//! Parsing of the attributes that determine an error's message.
+//!
+//! This is shared with `snafu-derive` so that the extracted messages
+//! cannot drift from the ones the derive macro generates. It is not
+//! part of the public API.
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    token, Expr, LitStr,
+};
+
+#[allow(missing_docs)]
+pub mod kw {
+    syn::custom_keyword!(display);
+}
+
+/// `display(...)` inside of `#[snafu(...)]`.
+pub struct Display {
+    display_token: kw::display,
+    paren_token: token::Paren,
+    args: Punctuated<Expr, token::Comma>,
+}
+
+impl Display {
+    /// `display(skip)` is never a valid format string, so it is
+    /// treated as a request to not implement `Display`.
+    pub fn is_skip(&self) -> bool {
+        match self.args.first() {
+            Some(Expr::Path(p)) if self.args.len() == 1 => p.path.is_ident("skip"),
+            _ => false,
+        }
+    }
+
+    /// `display(internal(...))` wraps a second format string used
+    /// for operator-facing output.
+    pub fn is_internal(&self) -> bool {
+        match self.args.first() {
+            Some(Expr::Call(c)) if self.args.len() == 1 => match &*c.func {
+                Expr::Path(p) => p.path.is_ident("internal"),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// The format string followed by its arguments.
+    pub fn into_exprs(self) -> Vec<Expr> {
+        self.args.into_iter().collect()
+    }
+
+    /// The format string and arguments inside of `internal(...)`.
+    pub fn into_internal_exprs(self) -> Vec<Expr> {
+        match self.args.into_iter().next() {
+            Some(Expr::Call(c)) => c.args.into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Parse for Display {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            display_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            args: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl ToTokens for Display {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.display_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.args.to_tokens(tokens);
+        });
+    }
+}
+
+/// The tokens of a `#[doc = "..."]` attribute following `doc`.
+pub struct DocComment {
+    eq_token: token::Eq,
+    str: LitStr,
+}
+
+impl DocComment {
+    /// The text of this line of the doc comment.
+    pub fn into_value(self) -> String {
+        self.str.value()
+    }
+}
+
+impl Parse for DocComment {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            eq_token: input.parse()?,
+            str: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for DocComment {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.eq_token.to_tokens(tokens);
+        self.str.to_tokens(tokens);
+    }
+}
+
+/// Builds the summary of a doc comment, one line at a time.
+#[derive(Default)]
+pub struct DocSummary {
+    summary: String,
+    complete: bool,
+}
+
+impl DocSummary {
+    /// Adds a line of the doc comment.
+    pub fn push_line(&mut self, line: &str) {
+        // We join all the doc comment lines with a space, but end
+        // once the summary is complete, which is indicated by an
+        // empty line.
+        if self.complete {
+            return;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            self.complete = true;
+        } else {
+            if !self.summary.is_empty() {
+                self.summary.push(' ');
+            }
+            self.summary.push_str(trimmed);
+        }
+    }
+
+    /// The summary, which is empty if there was no doc comment.
+    pub fn into_string(self) -> String {
+        self.summary
+    }
+}
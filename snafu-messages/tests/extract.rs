@@ -0,0 +1,157 @@
+use snafu_messages::{extract, extract_file, MessageKind};
+
+fn parse_file(source: &str) -> syn::File {
+    syn::parse_str(source).expect("Test source must parse")
+}
+
+#[test]
+fn display_attributes_are_extracted() {
+    let file = parse_file(
+        r#"
+        #[derive(Debug, Snafu)]
+        enum Error {
+            #[snafu(visibility(pub(crate)), display("Could not read {} ({})", path.display(), attempt))]
+            Read { path: PathBuf, attempt: u32 },
+
+            #[snafu(display(concat!("Could not ", "write")))]
+            Write,
+        }
+        "#,
+    );
+
+    let messages = extract_file(&file);
+    assert_eq!(messages.len(), 2);
+
+    assert_eq!(messages[0].type_name(), "Error");
+    assert_eq!(messages[0].variant(), Some("Read"));
+    assert_eq!(messages[0].kind(), MessageKind::Display);
+    assert_eq!(messages[0].template(), "Could not read {} ({})");
+    assert_eq!(messages[0].arguments(), ["path . display ()", "attempt"]);
+
+    assert_eq!(
+        messages[1].template(),
+        "concat ! (\"Could not \" , \"write\")"
+    );
+    assert!(messages[1].arguments().is_empty());
+}
+
+#[test]
+fn doc_comment_summaries_are_extracted() {
+    let file = parse_file(
+        r#"
+        #[derive(Debug, Snafu)]
+        enum Error {
+            /// The user
+            /// was not found
+            ///
+            /// This is not part of the message.
+            UserNotFound,
+
+            /// Ignored, as there is a display attribute
+            #[snafu(display("Out of memory"))]
+            OutOfMemory,
+
+            Undocumented,
+        }
+        "#,
+    );
+
+    let messages = extract_file(&file);
+    assert_eq!(messages.len(), 2);
+
+    assert_eq!(messages[0].variant(), Some("UserNotFound"));
+    assert_eq!(messages[0].kind(), MessageKind::DocComment);
+    assert_eq!(messages[0].template(), "The user was not found");
+
+    assert_eq!(messages[1].variant(), Some("OutOfMemory"));
+    assert_eq!(messages[1].kind(), MessageKind::Display);
+    assert_eq!(messages[1].template(), "Out of memory");
+}
+
+#[test]
+fn structs_are_extracted() {
+    let file = parse_file(
+        r#"
+        #[derive(Debug, snafu::Snafu)]
+        #[snafu(display("Invalid port {}", port))]
+        struct PortError { port: u16 }
+
+        #[derive(Debug, Snafu)]
+        struct OpaqueError(InnerError);
+        "#,
+    );
+
+    let messages = extract_file(&file);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].type_name(), "PortError");
+    assert_eq!(messages[0].variant(), None);
+    assert_eq!(messages[0].template(), "Invalid port {}");
+}
+
+#[test]
+fn only_types_deriving_snafu_are_extracted_from_files() {
+    let file = parse_file(
+        r#"
+        /// Not an error
+        #[derive(Debug)]
+        struct Config;
+
+        mod inner {
+            #[derive(Debug, Snafu)]
+            enum Error {
+                /// Inner failure
+                Failure,
+            }
+        }
+        "#,
+    );
+
+    let messages = extract_file(&file);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].template(), "Inner failure");
+}
+
+#[test]
+fn single_derive_inputs_can_be_extracted() {
+    let input: syn::DeriveInput = syn::parse_str(
+        r#"
+        enum Error {
+            #[snafu(context(suffix(false)), display("Timed out after {}s", seconds))]
+            Timeout { seconds: u64 },
+        }
+        "#,
+    )
+    .unwrap();
+
+    let messages = extract(&input);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].template(), "Timed out after {}s");
+}
+
+#[test]
+fn display_attributes_are_interpreted_like_the_derive() {
+    let file = parse_file(
+        r#"
+        #[derive(Debug, Snafu)]
+        enum Error {
+            /// Could not connect
+            #[snafu(display(internal("Connecting to {} failed", host)))]
+            Connect { host: String },
+        }
+
+        /// Not shown
+        #[derive(Debug, Snafu)]
+        #[snafu(display(skip))]
+        enum Undisplayed {
+            /// Not shown either
+            Variant,
+        }
+        "#,
+    );
+
+    let messages = extract_file(&file);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].variant(), Some("Connect"));
+    assert_eq!(messages[0].kind(), MessageKind::DocComment);
+    assert_eq!(messages[0].template(), "Could not connect");
+}
@@ -0,0 +1,178 @@
+//! Interoperability with the [`anyhow`][anyhow-crate] crate.
+//!
+//! Any SNAFU error that is `Send` and `Sync` can already be converted
+//! into an `anyhow::Error` using `?`. This module provides the other
+//! direction, allowing a codebase to move between the two crates one
+//! function at a time:
+//!
+//! - [`from_anyhow`] creates a [`Whatever`] or any other type using
+//!   [`#[snafu(whatever)]`](crate::Snafu#controlling-stringly-typed-errors).
+//! - [`AnyhowResultExt`] adds context to a `Result<T, anyhow::Error>`
+//!   using a context selector, the same as [`ResultExt::context`].
+//!
+//! ```rust
+//! # use anyhow_crate as anyhow;
+//! use snafu::{anyhow::AnyhowResultExt, prelude::*};
+//!
+//! #[derive(Debug, Snafu)]
+//! enum Error {
+//!     #[snafu(display("Could not load the configuration"))]
+//!     LoadConfiguration {
+//!         source: Box<dyn std::error::Error + Send + Sync>,
+//!     },
+//! }
+//!
+//! fn load_configuration() -> Result<String, Error> {
+//!     legacy_load().anyhow_context(LoadConfigurationSnafu)
+//! }
+//!
+//! fn legacy_load() -> anyhow::Result<String> {
+//!     anyhow::bail!("The file is empty")
+//! }
+//!
+//! let error = load_configuration().unwrap_err();
+//! assert_eq!(snafu::ChainCompat::new(&error).count(), 2);
+//! ```
+//!
+//! [anyhow-crate]: https://crates.io/crates/anyhow
+//! [`ResultExt::context`]: crate::ResultExt::context
+//! [`Whatever`]: crate::Whatever
+
+use crate::{Error, ErrorCompat, FromString, IntoError};
+use anyhow_crate::Error as AnyhowError;
+use std::{error, fmt};
+
+/// Creates a stringly-typed error from an `anyhow::Error`.
+///
+/// The message of the new error is the outermost message of the
+/// `anyhow::Error`. Any remaining errors in its chain become the
+/// source, wrapped in an [`AnyhowSource`].
+///
+/// ```rust
+/// # use anyhow_crate as anyhow;
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(whatever, display("{message}"))]
+/// struct Error {
+///     message: String,
+///     #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+///     source: Option<Box<dyn std::error::Error>>,
+/// }
+///
+/// impl From<anyhow::Error> for Error {
+///     fn from(error: anyhow::Error) -> Self {
+///         snafu::anyhow::from_anyhow(error)
+///     }
+/// }
+/// ```
+pub fn from_anyhow<E>(error: AnyhowError) -> E
+where
+    E: FromString,
+    AnyhowSource: Into<E::Source>,
+{
+    let message = error.to_string();
+
+    if error.source().is_none() {
+        E::without_source(message)
+    } else {
+        E::with_source(AnyhowSource(error).into(), message)
+    }
+}
+
+/// The sources of an `anyhow::Error`, without its outermost message.
+///
+/// This is used as the source of errors created by [`from_anyhow`] so
+/// that the outermost message is not reported twice.
+pub struct AnyhowSource(AnyhowError);
+
+impl AnyhowSource {
+    /// Returns a reference to the original error.
+    pub fn get_ref(&self) -> &AnyhowError {
+        &self.0
+    }
+
+    /// Returns the original error.
+    pub fn into_inner(self) -> AnyhowError {
+        self.0
+    }
+
+    fn first(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl fmt::Debug for AnyhowSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.first() {
+            Some(e) => fmt::Debug::fmt(e, f),
+            None => fmt::Debug::fmt(&self.0, f),
+        }
+    }
+}
+
+impl fmt::Display for AnyhowSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.first() {
+            Some(e) => fmt::Display::fmt(e, f),
+            None => fmt::Display::fmt(&self.0, f),
+        }
+    }
+}
+
+impl error::Error for AnyhowSource {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.first().and_then(error::Error::source)
+    }
+}
+
+/// Additions to a `Result` containing an `anyhow::Error`.
+///
+/// [`ResultExt::context`](crate::ResultExt::context) requires the
+/// source of the context selector to exactly match the error, which
+/// rules out the boxed trait objects that are typically used to hold
+/// an `anyhow::Error`. These methods convert the error into the
+/// selector's source type first.
+///
+/// The methods are prefixed with `anyhow_` so that they do not
+/// conflict with [`ResultExt`](crate::ResultExt) or
+/// `anyhow::Context`.
+pub trait AnyhowResultExt<T>: Sized {
+    /// Extend the `anyhow::Error` with additional context-sensitive
+    /// information.
+    fn anyhow_context<C, E>(self, context: C) -> Result<T, E>
+    where
+        C: IntoError<E>,
+        AnyhowError: Into<C::Source>,
+        E: Error + ErrorCompat;
+
+    /// Extend the `anyhow::Error` with lazily-generated
+    /// context-sensitive information.
+    fn with_anyhow_context<F, C, E>(self, context: F) -> Result<T, E>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E>,
+        AnyhowError: Into<C::Source>,
+        E: Error + ErrorCompat;
+}
+
+impl<T> AnyhowResultExt<T> for Result<T, AnyhowError> {
+    fn anyhow_context<C, E>(self, context: C) -> Result<T, E>
+    where
+        C: IntoError<E>,
+        AnyhowError: Into<C::Source>,
+        E: Error + ErrorCompat,
+    {
+        self.map_err(|error| context.into_error(error.into()))
+    }
+
+    fn with_anyhow_context<F, C, E>(self, context: F) -> Result<T, E>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E>,
+        AnyhowError: Into<C::Source>,
+        E: Error + ErrorCompat,
+    {
+        self.map_err(|error| context().into_error(error.into()))
+    }
+}
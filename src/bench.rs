@@ -0,0 +1,117 @@
+//! Measuring the cost of error types.
+//!
+//! How an error type is designed affects the cost of producing and
+//! reporting errors: each context field is another value to move, a
+//! backtrace may be captured on construction, and the message is
+//! formatted every time it is displayed. [`ErrorBenchmarks`] runs
+//! the same set of [Criterion] benchmarks against your own error
+//! types so that these costs can be compared between designs.
+//!
+//! This module is only available when the `bench` [feature flag] is
+//! enabled.
+//!
+//! ```rust,no_run
+//! # use criterion_crate as criterion;
+//! use criterion::{criterion_group, criterion_main, Criterion};
+//! use snafu::{bench::ErrorBenchmarks, prelude::*, Backtrace};
+//!
+//! #[derive(Debug, Snafu)]
+//! enum Error {
+//!     #[snafu(display("Could not parse {}", input))]
+//!     Parse {
+//!         input: String,
+//!         source: std::num::ParseIntError,
+//!         backtrace: Backtrace,
+//!     },
+//! }
+//!
+//! fn error_design(c: &mut Criterion) {
+//!     let error = "x".parse::<i32>().context(ParseSnafu { input: "x" }).unwrap_err();
+//!
+//!     ErrorBenchmarks::new(c, "parse")
+//!         .context(|| "x".parse::<i32>(), || ParseSnafu { input: "x" })
+//!         .backtrace::<Backtrace>()
+//!         .display(&error)
+//!         .finish();
+//! }
+//!
+//! criterion_group!(benches, error_design);
+//! criterion_main!(benches);
+//! ```
+//!
+//! [Criterion]: criterion_crate
+//! [feature flag]: crate::guide::feature_flags
+
+use crate::{Error, ErrorCompat, GenerateBacktrace, IntoError, ResultExt};
+use criterion_crate::{black_box, measurement::WallTime, BenchmarkGroup, Criterion};
+use std::fmt::{self, Write};
+
+/// A group of benchmarks for one error type.
+///
+/// Each benchmark is reported under the name of the group, such as
+/// `parse/context` and `parse/display`.
+pub struct ErrorBenchmarks<'a> {
+    group: BenchmarkGroup<'a, WallTime>,
+}
+
+impl<'a> ErrorBenchmarks<'a> {
+    /// Starts a new group of benchmarks with the given name.
+    pub fn new(criterion: &'a mut Criterion, name: &str) -> Self {
+        ErrorBenchmarks {
+            group: criterion.benchmark_group(name),
+        }
+    }
+
+    /// Measures applying a context selector to a failed result.
+    ///
+    /// Both closures are called on every iteration, so this includes
+    /// creating the underlying error, creating the context selector,
+    /// and any backtrace captured by the resulting error.
+    pub fn context<T, S, F, G, C, E>(mut self, result: F, context: G) -> Self
+    where
+        F: Fn() -> Result<T, S>,
+        G: Fn() -> C,
+        C: IntoError<E, Source = S>,
+        E: Error + ErrorCompat,
+    {
+        self.group.bench_function("context", |b| {
+            b.iter(|| black_box(result().with_context(&context)))
+        });
+        self
+    }
+
+    /// Measures generating a backtrace of the given type, such as
+    /// [`Backtrace`](crate::Backtrace) or `Option<Backtrace>`.
+    pub fn backtrace<B>(mut self) -> Self
+    where
+        B: GenerateBacktrace,
+    {
+        self.group
+            .bench_function("backtrace", |b| b.iter(|| black_box(B::generate())));
+        self
+    }
+
+    /// Measures formatting the error's message.
+    ///
+    /// The message is written to a reused buffer so that only the
+    /// cost of formatting is measured.
+    pub fn display<E>(mut self, error: &E) -> Self
+    where
+        E: fmt::Display + ?Sized,
+    {
+        let mut buffer = String::new();
+        self.group.bench_function("display", |b| {
+            b.iter(|| {
+                buffer.clear();
+                write!(buffer, "{}", black_box(error)).expect("Formatting an error failed");
+                black_box(buffer.len())
+            })
+        });
+        self
+    }
+
+    /// Completes the group of benchmarks.
+    pub fn finish(self) {
+        self.group.finish();
+    }
+}
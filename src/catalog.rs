@@ -0,0 +1,123 @@
+/// An error that was added to the catalog using
+/// [`#[snafu(catalog)]`](crate::Snafu#generating-an-error-catalog).
+///
+/// Each variant of an enum is a separate entry. The codes are those
+/// set by the `exit_code`, `http_status`, and `c_code` attributes,
+/// when they are known without constructing the error.
+///
+/// ```rust
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(catalog)]
+/// enum Error {
+///     /// The configuration file does not exist.
+///     #[snafu(display("Could not find {path}"), exit_code(66))]
+///     MissingConfig { path: String },
+/// }
+///
+/// let entry = snafu::catalog()
+///     .find(|e| e.variant() == Some("MissingConfig"))
+///     .unwrap();
+///
+/// assert_eq!(entry.error(), "Error");
+/// assert_eq!(entry.message(), Some("Could not find {path}"));
+/// assert_eq!(entry.doc(), "The configuration file does not exist.");
+/// assert_eq!(entry.exit_code(), Some(66));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CatalogEntry {
+    module_path: &'static str,
+    error: &'static str,
+    variant: Option<&'static str>,
+    message: Option<&'static str>,
+    doc: &'static str,
+    exit_code: Option<u8>,
+    http_status: Option<u16>,
+    c_code: Option<i32>,
+}
+
+impl CatalogEntry {
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    pub const fn __new(
+        module_path: &'static str,
+        error: &'static str,
+        variant: Option<&'static str>,
+        message: Option<&'static str>,
+        doc: &'static str,
+        exit_code: Option<u8>,
+        http_status: Option<u16>,
+        c_code: Option<i32>,
+    ) -> Self {
+        CatalogEntry {
+            module_path,
+            error,
+            variant,
+            message,
+            doc,
+            exit_code,
+            http_status,
+            c_code,
+        }
+    }
+
+    /// The path of the module the error is defined in.
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// The name of the error type.
+    pub fn error(&self) -> &'static str {
+        self.error
+    }
+
+    /// The name of the enum variant, or `None` for a struct.
+    pub fn variant(&self) -> Option<&'static str> {
+        self.variant
+    }
+
+    /// The `Display` format string as written in the source, before
+    /// any fields are substituted.
+    ///
+    /// Returns `None` when the message comes from another error,
+    /// such as for flattened variants, or when the error does not
+    /// implement `Display` itself.
+    pub fn message(&self) -> Option<&'static str> {
+        self.message
+    }
+
+    /// The doc comment of the variant or struct, which is empty when
+    /// there is none.
+    pub fn doc(&self) -> &'static str {
+        self.doc
+    }
+
+    /// The process exit code set by `#[snafu(exit_code)]`.
+    pub fn exit_code(&self) -> Option<u8> {
+        self.exit_code
+    }
+
+    /// The HTTP status code set by `#[snafu(http_status)]`.
+    pub fn http_status(&self) -> Option<u16> {
+        self.http_status
+    }
+
+    /// The C error code set by `#[snafu(c_code)]`.
+    pub fn c_code(&self) -> Option<i32> {
+        self.c_code
+    }
+}
+
+inventory_crate::collect!(CatalogEntry);
+
+/// Returns every error that was added to the catalog using
+/// [`#[snafu(catalog)]`](crate::Snafu#generating-an-error-catalog),
+/// in no particular order.
+///
+/// Errors from every crate linked into the program are included.
+/// This requires the `catalog` [feature
+/// flag](crate::guide::feature_flags).
+pub fn catalog() -> impl Iterator<Item = &'static CatalogEntry> {
+    inventory_crate::iter::<CatalogEntry>.into_iter()
+}
@@ -0,0 +1,92 @@
+use core::fmt;
+use tracing_crate::Metadata;
+
+/// A snapshot of the [`tracing`](tracing_crate) span that was current
+/// when an error was created.
+///
+/// When a field is declared with the type `snafu::CurrentSpan`, it is
+/// not part of the context selector and is filled in automatically.
+/// In addition to the ID recorded by [`SpanId`](crate::SpanId), this
+/// keeps the span's static metadata, such as its target, name, level,
+/// and the names of its fields, so the error can be correlated with
+/// the request that produced it without passing identifiers through
+/// every context selector.
+///
+/// The values of the span's fields are only available to the
+/// subscriber and are not captured.
+///
+/// This type is only available when the `tracing` [feature flag] is
+/// enabled.
+///
+/// ```rust
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not load user {}", id))]
+///     LoadUser { id: u32, span: snafu::CurrentSpan },
+/// }
+///
+/// let error = LoadUserSnafu { id: 42u32 }.build();
+///
+/// if let Error::LoadUser { span, .. } = &error {
+///     // No span is entered in this example
+///     assert_eq!(span.name(), None);
+/// }
+/// ```
+///
+/// [feature flag]: crate::guide::feature_flags
+#[derive(Debug, Copy, Clone)]
+pub struct CurrentSpan {
+    id: Option<u64>,
+    metadata: Option<&'static Metadata<'static>>,
+}
+
+impl CurrentSpan {
+    /// Records the current span, if any.
+    pub fn current() -> Self {
+        let span = tracing_crate::Span::current();
+        CurrentSpan {
+            id: span.id().map(|id| id.into_u64()),
+            metadata: span.metadata(),
+        }
+    }
+
+    /// The ID of the span. This is `None` when no span was entered or
+    /// the span was disabled.
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    /// The static metadata of the span, describing where it was
+    /// created and which fields it has.
+    pub fn metadata(&self) -> Option<&'static Metadata<'static>> {
+        self.metadata
+    }
+
+    /// The name of the span.
+    pub fn name(&self) -> Option<&'static str> {
+        self.metadata.map(|m| m.name())
+    }
+
+    /// The target of the span, usually the module it was created in.
+    pub fn target(&self) -> Option<&'static str> {
+        self.metadata.map(|m| m.target())
+    }
+}
+
+impl Default for CurrentSpan {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+/// Shows the span as `target: name`.
+impl fmt::Display for CurrentSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.metadata {
+            Some(m) => write!(f, "{}: {}", m.target(), m.name()),
+            None => f.write_str("none"),
+        }
+    }
+}
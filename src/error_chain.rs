@@ -26,3 +26,106 @@ impl<'a> Iterator for ChainCompat<'a> {
         }
     }
 }
+
+/// Returns the number of errors in the chain, starting with the
+/// given error and continuing with recursive calls to
+/// [`Error::source`](std::error::Error::source).
+///
+/// Deep chains are hard to read when reported; this can be used in
+/// tests or debug assertions to keep them at a comprehensible depth.
+///
+/// ```rust
+/// use snafu::{prelude::*, chain_len};
+///
+/// #[derive(Debug, Snafu)]
+/// struct LeafError;
+///
+/// #[derive(Debug, Snafu)]
+/// struct OuterError {
+///     source: LeafError,
+/// }
+///
+/// let error = Err::<(), _>(LeafSnafu.build())
+///     .context(OuterSnafu)
+///     .unwrap_err();
+///
+/// assert_eq!(chain_len(&error), 2);
+/// ```
+pub fn chain_len(error: &dyn std::error::Error) -> usize {
+    ChainCompat::new(error).count()
+}
+
+/// An iterator over an error and its sources that removes repeated
+/// text from each error's message.
+///
+/// Errors often include the message of their source in their own
+/// message. When every error in the chain is shown, such as in a
+/// report, this produces output like `Could not read config: No such
+/// file: No such file`. If an error's message ends with the message
+/// of its source, that part is removed along with any separating
+/// `:`.
+///
+/// Each item is the error, its cleaned message, and whether the
+/// message was changed.
+///
+/// ```rust
+/// use snafu::{prelude::*, CleanedErrorText};
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("Could not read config: {}", source))]
+/// struct ConfigError {
+///     source: std::io::Error,
+/// }
+///
+/// let io = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file");
+/// let error = Err::<(), _>(io).context(ConfigSnafu).unwrap_err();
+///
+/// let messages: Vec<_> = CleanedErrorText::new(&error)
+///     .map(|(_, message, _)| message)
+///     .collect();
+/// assert_eq!(messages, ["Could not read config", "No such file"]);
+/// ```
+pub struct CleanedErrorText<'a> {
+    chain: ChainCompat<'a>,
+    current: Option<(&'a dyn std::error::Error, String)>,
+}
+
+impl<'a> CleanedErrorText<'a> {
+    /// Creates a new cleaned error text iterator.
+    pub fn new(error: &'a dyn std::error::Error) -> Self {
+        let mut chain = ChainCompat::new(error);
+        let current = chain.next().map(|e| (e, e.to_string()));
+        CleanedErrorText { chain, current }
+    }
+}
+
+impl<'a> Iterator for CleanedErrorText<'a> {
+    type Item = (&'a dyn std::error::Error, String, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (error, mut message) = self.current.take()?;
+        self.current = self.chain.next().map(|e| (e, e.to_string()));
+
+        let cleaned = match &self.current {
+            Some((_, source_message)) => remove_source_message(&mut message, source_message),
+            None => false,
+        };
+
+        Some((error, message, cleaned))
+    }
+}
+
+fn remove_source_message(message: &mut String, source_message: &str) -> bool {
+    if source_message.is_empty() || !message.ends_with(source_message) {
+        return false;
+    }
+
+    let without_source = &message[..message.len() - source_message.len()];
+    let len = without_source
+        .trim_end()
+        .trim_end_matches(':')
+        .trim_end()
+        .len();
+    message.truncate(len);
+    true
+}
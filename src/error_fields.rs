@@ -0,0 +1,60 @@
+use core::fmt;
+
+/// Access to the context fields of an error as key-value pairs.
+///
+/// Logging frameworks can use this to emit an error as a structured
+/// record instead of a single formatted string. Placing
+/// [`#[snafu(fields)]`](crate::Snafu#exporting-context-fields) on an
+/// enum implements this trait, visiting the context fields of the
+/// current variant in the order they are declared.
+///
+/// ```rust
+/// use snafu::{prelude::*, ErrorFields};
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(fields)]
+/// enum Error {
+///     #[snafu(display("User {id} could not be loaded"))]
+///     LoadUser { id: u32, shard: String },
+/// }
+///
+/// let error = LoadUserSnafu { id: 42u32, shard: "eu-1" }.build();
+///
+/// let mut pairs = Vec::new();
+/// error.visit_fields(&mut |key, value| pairs.push(format!("{}={}", key, value)));
+/// assert_eq!(pairs, ["id=42", "shard=eu-1"]);
+/// ```
+pub trait ErrorFields {
+    /// Calls the visitor with the name and value of each context
+    /// field.
+    fn visit_fields(&self, visitor: &mut dyn FnMut(&'static str, &dyn fmt::Display));
+}
+
+impl<E> ErrorFields for &E
+where
+    E: ErrorFields + ?Sized,
+{
+    fn visit_fields(&self, visitor: &mut dyn FnMut(&'static str, &dyn fmt::Display)) {
+        (**self).visit_fields(visitor)
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<E> ErrorFields for Box<E>
+where
+    E: ErrorFields + ?Sized,
+{
+    fn visit_fields(&self, visitor: &mut dyn FnMut(&'static str, &dyn fmt::Display)) {
+        (**self).visit_fields(visitor)
+    }
+}
+
+/// Visits the key-value pairs provided to [`whatever!`](crate::whatever).
+#[cfg(any(feature = "std", test))]
+impl ErrorFields for crate::Whatever {
+    fn visit_fields(&self, visitor: &mut dyn FnMut(&'static str, &dyn fmt::Display)) {
+        for (key, value) in self.fields() {
+            visitor(key, value);
+        }
+    }
+}
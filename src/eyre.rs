@@ -0,0 +1,179 @@
+//! Interoperability with the [`eyre`][eyre-crate] crate.
+//!
+//! Any SNAFU error that is `Send` and `Sync` can be converted into an
+//! `eyre::Report` using `?`, which keeps the chain of sources. Wrapping
+//! the error in an [`EyreCompat`] first also keeps the error's
+//! backtrace reachable from the report. In the other direction, an
+//! [`EyreSource`] allows an `eyre::Report` to be used as the source
+//! of a SNAFU error.
+//!
+//! ```rust
+//! # use eyre_crate as eyre;
+//! use snafu::{eyre::EyreSource, prelude::*};
+//!
+//! #[derive(Debug, Snafu)]
+//! enum Error {
+//!     #[snafu(display("Could not load the plugin"))]
+//!     LoadPlugin {
+//!         #[snafu(source(from(eyre::Report, EyreSource::new)))]
+//!         source: EyreSource,
+//!     },
+//! }
+//!
+//! fn load_plugin() -> Result<(), Error> {
+//!     legacy_load().context(LoadPluginSnafu)
+//! }
+//!
+//! fn legacy_load() -> eyre::Result<()> {
+//! #   eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).ok();
+//!     eyre::bail!("The plugin is missing")
+//! }
+//!
+//! let error = load_plugin().unwrap_err();
+//! assert_eq!(snafu::ChainCompat::new(&error).count(), 2);
+//! ```
+//!
+//! [eyre-crate]: https://crates.io/crates/eyre
+
+use crate::{Backtrace, Error, ErrorCompat};
+use core::fmt;
+use eyre_crate::Report;
+
+/// Adapts a SNAFU error for conversion into an `eyre::Report`.
+///
+/// The sources of the error are exposed unchanged. Because the report
+/// owns the adapter, the error's backtrace can be retrieved from the
+/// report using `downcast_ref`.
+///
+/// ```rust
+/// # use eyre_crate as eyre;
+/// use snafu::{eyre::EyreCompat, prelude::*};
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("Could not save the document"))]
+/// struct SaveError {
+///     backtrace: snafu::Backtrace,
+/// }
+///
+/// fn entrypoint() -> eyre::Result<()> {
+/// #   eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).ok();
+///     SaveSnafu.fail().map_err(EyreCompat::new)?;
+///     Ok(())
+/// }
+///
+/// let report = entrypoint().unwrap_err();
+/// let error = report.downcast_ref::<EyreCompat<SaveError>>().unwrap();
+/// assert!(error.backtrace().is_some());
+/// ```
+pub struct EyreCompat<E>(E);
+
+impl<E> EyreCompat<E>
+where
+    E: Error + ErrorCompat,
+{
+    /// Wraps the error.
+    pub fn new(error: E) -> Self {
+        EyreCompat(error)
+    }
+
+    /// Returns the backtrace of the wrapped error, if it has one.
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        ErrorCompat::backtrace(&self.0)
+    }
+
+    /// Returns a reference to the wrapped error.
+    pub fn get_ref(&self) -> &E {
+        &self.0
+    }
+
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+impl<E> From<E> for EyreCompat<E>
+where
+    E: Error + ErrorCompat,
+{
+    fn from(error: E) -> Self {
+        EyreCompat::new(error)
+    }
+}
+
+impl<E> fmt::Debug for EyreCompat<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<E> fmt::Display for EyreCompat<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<E> Error for EyreCompat<E>
+where
+    E: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Allows an `eyre::Report` to be the source of a SNAFU error.
+///
+/// `eyre::Report` does not implement `Error` itself. This wrapper
+/// does, exposing the report's message and chain of sources. The
+/// report is kept intact, so its `Debug` output still includes
+/// everything added by the installed handler, such as sections and
+/// backtraces.
+pub struct EyreSource(Report);
+
+impl EyreSource {
+    /// Wraps the report.
+    pub fn new(report: Report) -> Self {
+        EyreSource(report)
+    }
+
+    /// Returns a reference to the wrapped report.
+    pub fn get_ref(&self) -> &Report {
+        &self.0
+    }
+
+    /// Returns the wrapped report.
+    pub fn into_inner(self) -> Report {
+        self.0
+    }
+}
+
+impl From<Report> for EyreSource {
+    fn from(report: Report) -> Self {
+        EyreSource::new(report)
+    }
+}
+
+impl fmt::Debug for EyreSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for EyreSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for EyreSource {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
@@ -0,0 +1,229 @@
+//! Interoperability with the [`failure`][failure-crate] crate.
+//!
+//! Any SNAFU error that is `Send` and `Sync` already implements
+//! `failure::Fail` through that crate's blanket implementation for
+//! `std::error::Error`. However, that implementation does not expose
+//! the underlying causes. Wrapping the error in a [`FailCompat`]
+//! exposes them, allowing SNAFU errors to be adopted incrementally
+//! in code that still reports errors using `failure`.
+//!
+//! ```rust
+//! # use failure_crate as failure;
+//! use snafu::{failure::FailCompat, ResultExt, Snafu};
+//!
+//! #[derive(Debug, Snafu)]
+//! enum Error {
+//!     #[snafu(display("Could not read the configuration"))]
+//!     ReadConfiguration { source: std::io::Error },
+//! }
+//!
+//! fn read_configuration() -> Result<String, Error> {
+//!     std::fs::read_to_string("/this/does/not/exist").context(ReadConfigurationSnafu)
+//! }
+//!
+//! fn legacy_entrypoint() -> Result<String, failure::Error> {
+//!     let config = read_configuration().map_err(FailCompat::new)?;
+//!     Ok(config)
+//! }
+//!
+//! let error = legacy_entrypoint().unwrap_err();
+//! assert_eq!(error.iter_chain().count(), 2);
+//! ```
+//!
+//! [failure-crate]: https://crates.io/crates/failure
+
+use crate::{Error, ErrorCompat};
+use core::fmt;
+use failure_crate::{Backtrace, Fail};
+use std::sync::Arc;
+
+/// Adapts a SNAFU error to the `failure::Fail` trait, exposing the
+/// error's sources as the chain of causes.
+///
+/// Each cause is a [`FailSource`] that refers to the source of the
+/// wrapped error, so it can be downcast to the original type:
+///
+/// ```rust
+/// # use failure_crate as failure;
+/// use failure::Fail;
+/// use snafu::{failure::{FailCompat, FailSource}, prelude::*};
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("The disk is full"))]
+/// struct DiskFullError;
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("Could not save the document"))]
+/// struct SaveError { source: DiskFullError }
+///
+/// let error = FailCompat::new(DiskFullSnafu.fail::<()>().context(SaveSnafu).unwrap_err());
+/// let cause = error.cause().unwrap();
+/// let source = cause.downcast_ref::<FailSource<SaveError>>().unwrap();
+/// assert!(source.downcast_ref::<DiskFullError>().is_some());
+/// ```
+///
+/// A `failure::Backtrace` can only be captured from the current stack;
+/// it cannot be converted from the backtrace of the wrapped error.
+/// When the wrapped error has a backtrace, one is captured when the
+/// adapter is created, so [`Fail::backtrace`] shows where the error
+/// was wrapped. The backtrace of the wrapped error remains available
+/// through [`ErrorCompat::backtrace`] on [`FailCompat::get_ref`].
+pub struct FailCompat<E> {
+    error: Arc<E>,
+    cause: Option<Box<FailSource<E>>>,
+    backtrace: Option<Backtrace>,
+}
+
+impl<E> FailCompat<E>
+where
+    E: Error + ErrorCompat + 'static,
+{
+    /// Wraps the error.
+    pub fn new(error: E) -> Self {
+        let backtrace = ErrorCompat::backtrace(&error).map(|_| Backtrace::new());
+        let error = Arc::new(error);
+        let cause = FailSource::chain(&error);
+
+        FailCompat {
+            error,
+            cause,
+            backtrace,
+        }
+    }
+
+    /// Returns a reference to the wrapped error.
+    pub fn get_ref(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> E {
+        // The causes hold the only other references to the error.
+        drop(self.cause);
+        match Arc::try_unwrap(self.error) {
+            Ok(error) => error,
+            Err(_) => unreachable!("The causes of the error have been dropped"),
+        }
+    }
+}
+
+impl<E> From<E> for FailCompat<E>
+where
+    E: Error + ErrorCompat + 'static,
+{
+    fn from(error: E) -> Self {
+        FailCompat::new(error)
+    }
+}
+
+impl<E> fmt::Debug for FailCompat<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.error, f)
+    }
+}
+
+impl<E> fmt::Display for FailCompat<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<E> Fail for FailCompat<E>
+where
+    E: Error + ErrorCompat + Send + Sync + 'static,
+{
+    fn cause(&self) -> Option<&dyn Fail> {
+        self.cause.as_ref().map(|c| &**c as &dyn Fail)
+    }
+
+    fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_ref()
+    }
+}
+
+/// One of the sources of the error wrapped by a [`FailCompat`].
+///
+/// Displays and formats as the source it refers to. Use
+/// [`get_ref`](FailSource::get_ref) or
+/// [`downcast_ref`](FailSource::downcast_ref) to reach the source
+/// itself.
+pub struct FailSource<E> {
+    error: Arc<E>,
+    depth: usize,
+    cause: Option<Box<FailSource<E>>>,
+}
+
+impl<E> FailSource<E>
+where
+    E: Error + 'static,
+{
+    fn chain(error: &Arc<E>) -> Option<Box<Self>> {
+        let mut depth = 0;
+        let mut source = error.source();
+        while let Some(s) = source {
+            depth += 1;
+            source = s.source();
+        }
+
+        (1..=depth).rev().fold(None, |cause, depth| {
+            Some(Box::new(FailSource {
+                error: Arc::clone(error),
+                depth,
+                cause,
+            }))
+        })
+    }
+
+    /// Returns a reference to the source.
+    pub fn get_ref(&self) -> &(dyn Error + 'static) {
+        let mut error: &(dyn Error + 'static) = &*self.error;
+        for _ in 0..self.depth {
+            error = match error.source() {
+                Some(source) => source,
+                None => break,
+            };
+        }
+        error
+    }
+
+    /// Returns a reference to the source if it is of type `T`.
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: Error + 'static,
+    {
+        self.get_ref().downcast_ref()
+    }
+}
+
+impl<E> fmt::Debug for FailSource<E>
+where
+    E: Error + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.get_ref(), f)
+    }
+}
+
+impl<E> fmt::Display for FailSource<E>
+where
+    E: Error + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.get_ref(), f)
+    }
+}
+
+impl<E> Fail for FailSource<E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn cause(&self) -> Option<&dyn Fail> {
+        self.cause.as_ref().map(|c| &**c as &dyn Fail)
+    }
+}
@@ -0,0 +1,90 @@
+//! Exposing errors through `extern "C"` APIs.
+//!
+//! Errors using
+//! [`#[snafu(c_code = ...)]`](crate::Snafu#exposing-errors-to-c) can be
+//! converted into an [`FfiError`], a `#[repr(C)]` struct holding the
+//! error's code and its message, including the messages of its
+//! sources.
+//!
+//! ```rust
+//! use snafu::{ffi::FfiError, prelude::*};
+//!
+//! #[derive(Debug, Snafu)]
+//! enum Error {
+//!     #[snafu(display("The file {path} does not exist"), c_code = 2)]
+//!     MissingFile { path: String },
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn open_document(out_error: *mut FfiError) -> bool {
+//!     let result = MissingFileSnafu { path: "a.txt" }.fail::<()>();
+//!     match result {
+//!         Ok(()) => true,
+//!         Err(e) => {
+//!             unsafe { out_error.write(e.into()) };
+//!             false
+//!         }
+//!     }
+//! }
+//!
+//! #[no_mangle]
+//! pub unsafe extern "C" fn free_error(error: FfiError) {
+//!     error.free();
+//! }
+//! # let mut error = std::mem::MaybeUninit::uninit();
+//! # assert!(!open_document(error.as_mut_ptr()));
+//! # let error = unsafe { error.assume_init() };
+//! # assert_eq!(error.code, 2);
+//! # unsafe { free_error(error) };
+//! ```
+
+use crate::{Error, ErrorCompat, Report};
+use std::{ffi::CString, os::raw::c_char};
+
+/// A flat view of an error that can be passed to C.
+///
+/// The message is a nul-terminated UTF-8 string owned by Rust. It
+/// must be released by passing this value back to Rust and calling
+/// [`free`](FfiError::free); releasing it with C's `free` is
+/// undefined behavior.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FfiError {
+    /// The code identifying the error.
+    pub code: i32,
+    /// The error and its sources, formatted by [`Report`].
+    pub message: *mut c_char,
+}
+
+impl FfiError {
+    /// Creates a flat view of the error using the given code.
+    ///
+    /// Any nul bytes in the message are replaced with the Unicode
+    /// replacement character.
+    pub fn new<E>(code: i32, error: E) -> Self
+    where
+        E: Error + ErrorCompat + 'static,
+    {
+        let message = Report::from_error(error)
+            .to_string()
+            .replace('\0', "\u{FFFD}");
+        let message = CString::new(message).expect("All nul bytes were replaced");
+
+        FfiError {
+            code,
+            message: message.into_raw(),
+        }
+    }
+
+    /// Releases the message.
+    ///
+    /// # Safety
+    ///
+    /// The value must have been created by [`FfiError::new`] and its
+    /// message must not have already been released.
+    pub unsafe fn free(self) {
+        if !self.message.is_null() {
+            drop(CString::from_raw(self.message));
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::{fmt, slice};
+
+/// A typed value of one of the [`Fields`] of an error.
+///
+/// Values are created from booleans, integers, floating point
+/// numbers, and strings using [`From`]. Integers compare equal
+/// regardless of whether they are stored as signed or unsigned, and
+/// floating point numbers compare equal when their bits are equal.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate", untagged)
+)]
+#[cfg_attr(
+    feature = "schemars",
+    derive(schemars_crate::JsonSchema),
+    schemars(crate = "schemars_crate", untagged)
+)]
+pub enum FieldValue {
+    /// A boolean
+    Bool(bool),
+    /// An unsigned integer
+    U64(u64),
+    /// A signed integer
+    I64(i64),
+    /// A floating point number
+    F64(f64),
+    /// A string, such as a value formatted with `%` or `?` in
+    /// [`whatever!`](crate::whatever)
+    String(String),
+}
+
+impl PartialEq for FieldValue {
+    fn eq(&self, other: &Self) -> bool {
+        use self::FieldValue::*;
+
+        match (self, other) {
+            (Bool(a), Bool(b)) => a == b,
+            (U64(a), U64(b)) => a == b,
+            (I64(a), I64(b)) => a == b,
+            (U64(a), I64(b)) | (I64(b), U64(a)) => *b >= 0 && *b as u64 == *a,
+            (F64(a), F64(b)) => a.to_bits() == b.to_bits(),
+            (String(a), String(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FieldValue {}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Bool(v) => v.fmt(f),
+            FieldValue::U64(v) => v.fmt(f),
+            FieldValue::I64(v) => v.fmt(f),
+            FieldValue::F64(v) => v.fmt(f),
+            FieldValue::String(v) => v.fmt(f),
+        }
+    }
+}
+
+macro_rules! field_value_from {
+    ($variant:ident($target:ty): $($source:ty),*) => {
+        $(
+            impl From<$source> for FieldValue {
+                fn from(value: $source) -> Self {
+                    FieldValue::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+field_value_from!(U64(u64): u8, u16, u32, u64, usize);
+field_value_from!(I64(i64): i8, i16, i32, i64, isize);
+field_value_from!(F64(f64): f32, f64);
+
+impl From<bool> for FieldValue {
+    fn from(value: bool) -> Self {
+        FieldValue::Bool(value)
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(value: String) -> Self {
+        FieldValue::String(value)
+    }
+}
+
+impl From<&str> for FieldValue {
+    fn from(value: &str) -> Self {
+        FieldValue::String(value.to_owned())
+    }
+}
+
+/// A small, ordered collection of typed key-value pairs provided when
+/// an error was created.
+///
+/// The [`whatever!`](crate::whatever) macro accepts `key = value`
+/// pairs after the format arguments. They are available from
+/// [`Whatever::fields`](crate::Whatever::fields) and from fields of
+/// the type `snafu::Fields` in stringly-typed errors.
+///
+/// ```rust
+/// use snafu::{prelude::*, FieldValue, Whatever};
+///
+/// fn connect(address: std::net::Ipv4Addr, attempt: u32) -> Result<(), Whatever> {
+///     whatever!("Failed to connect"; host = %address, attempt = attempt);
+/// }
+///
+/// let error = connect([127, 0, 0, 1].into(), 3).unwrap_err();
+/// let fields = error.fields();
+/// assert_eq!(fields.get("host"), Some(&FieldValue::from("127.0.0.1")));
+/// assert_eq!(fields.get("attempt"), Some(&FieldValue::from(3u32)));
+/// assert_eq!(fields.to_string(), "host: 127.0.0.1, attempt: 3");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fields {
+    entries: Vec<(&'static str, FieldValue)>,
+}
+
+impl Fields {
+    /// Creates an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key-value pair. If the key is already present, its value
+    /// is replaced but it keeps its original position.
+    pub fn insert(&mut self, key: &'static str, value: impl Into<FieldValue>) {
+        let value = value.into();
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+
+    /// The value associated with the key, if any.
+    pub fn get(&self, key: &str) -> Option<&FieldValue> {
+        self.entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// The number of key-value pairs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the key-value pairs in the order they were first
+    /// inserted.
+    pub fn iter(&self) -> FieldsIter<'_> {
+        FieldsIter(self.entries.iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a Fields {
+    type Item = (&'static str, &'a FieldValue);
+    type IntoIter = FieldsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Shows the key-value pairs as `key: value`, separated by commas.
+impl fmt::Display for Fields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the key-value pairs of [`Fields`].
+pub struct FieldsIter<'a>(slice::Iter<'a, (&'static str, FieldValue)>);
+
+impl<'a> Iterator for FieldsIter<'a> {
+    type Item = (&'static str, &'a FieldValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl fmt::Debug for FieldsIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FieldsIter").finish()
+    }
+}
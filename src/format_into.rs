@@ -0,0 +1,73 @@
+use core::fmt::{self, Write};
+
+/// Formats the value into the buffer without allocating, returning
+/// the text that fit.
+///
+/// Output that does not fit is discarded. The text is truncated at a
+/// character boundary, so it is always valid UTF-8.
+///
+/// ```rust
+/// let mut buffer = [0; 8];
+/// let text = snafu::format_into(&mut buffer, &"Sensor 7 failed");
+/// assert_eq!(text, "Sensor 7");
+/// ```
+pub fn format_into<'a, T>(buffer: &'a mut [u8], value: &T) -> &'a str
+where
+    T: fmt::Display + ?Sized,
+{
+    let mut writer = TruncatingWriter { buffer, len: 0 };
+    // An error only means that the buffer is full
+    let _ = write!(writer, "{}", value);
+
+    let TruncatingWriter { buffer, len } = writer;
+    core::str::from_utf8(&buffer[..len]).unwrap_or("")
+}
+
+struct TruncatingWriter<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for TruncatingWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let available = self.buffer.len() - self.len;
+
+        let (s, result) = if s.len() <= available {
+            (s, Ok(()))
+        } else {
+            let mut end = available;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            (&s[..end], Err(fmt::Error))
+        };
+
+        self.buffer[self.len..][..s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        result
+    }
+}
+
+/// Formats using a closure.
+///
+/// Used by `#[snafu(display_buffer(N))]` to format a message into a
+/// buffer before writing it to the real formatter.
+#[doc(hidden)]
+pub fn display_fn<F>(f: F) -> DisplayFn<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    DisplayFn(f)
+}
+
+#[doc(hidden)]
+pub struct DisplayFn<F>(F);
+
+impl<F> fmt::Display for DisplayFn<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
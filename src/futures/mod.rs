@@ -1,15 +1,23 @@
-//! Additions to the [`TryFuture`] and [`TryStream`] traits.
+//! Additions to the [`TryFuture`], [`TryStream`], and [`Sink`] traits
+//! and to [`Poll`](core::task::Poll)-wrapped results.
 //!
 //! This module is only available when the `futures` [feature flag] is
 //! enabled.
 //!
 //! [`TryFuture`]: futures_core_crate::TryFuture
 //! [`TryStream`]: futures_core_crate::TryStream
+//! [`Sink`]: futures_sink_crate::Sink
 //! [feature flag]: crate::guide::feature_flags
 
+pub mod poll;
+pub mod sink;
 pub mod try_future;
 pub mod try_stream;
 
+#[doc(inline)]
+pub use self::poll::{PollExt, PollOptionExt};
+#[doc(inline)]
+pub use self::sink::SinkExt;
 #[doc(inline)]
 pub use self::try_future::TryFutureExt;
 #[doc(inline)]
@@ -0,0 +1,252 @@
+//! Additions to [`Poll`]-wrapped results.
+//!
+//! Manual implementations of [`Future`](core::future::Future),
+//! [`Stream`](futures_core_crate::Stream), and similar traits produce
+//! a `Poll<Result<T, E>>` or `Poll<Option<Result<T, E>>>`. These
+//! traits allow adding context to the error without first matching
+//! on the [`Poll`].
+
+use crate::{Error, ErrorCompat, FromString, IntoError, ResultExt};
+use core::task::Poll;
+
+/// Additions to [`Poll<Result<T, E>>`](Poll).
+pub trait PollExt<T, E>: Sized {
+    /// Extend a [`Poll`]-wrapped [`Result`]'s error with additional
+    /// context-sensitive information.
+    ///
+    /// ```rust
+    /// use snafu::{futures::PollExt, Snafu};
+    /// use std::task::Poll;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Reading { source: std::io::Error },
+    /// }
+    ///
+    /// fn poll_example() -> Poll<Result<usize, Error>> {
+    ///     poll_another().context(ReadingSnafu)
+    /// }
+    ///
+    /// fn poll_another() -> Poll<std::io::Result<usize>> {
+    ///     /* ... */
+    /// # Poll::Ready(Ok(42))
+    /// }
+    /// ```
+    ///
+    /// A [`Poll::Pending`] value is returned unchanged.
+    fn context<C, E2>(self, context: C) -> Poll<Result<T, E2>>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
+    /// Extend a [`Poll`]-wrapped [`Result`]'s error with
+    /// lazily-generated context-sensitive information.
+    ///
+    /// The closure is only called when the value is
+    /// `Poll::Ready(Err(_))`.
+    fn with_context<F, C, E2>(self, context: F) -> Poll<Result<T, E2>>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
+    /// Extend a [`Poll`]-wrapped [`Result`]'s error with information
+    /// from a string.
+    ///
+    /// The target error type must implement [`FromString`] by using
+    /// the
+    /// [`#[snafu(whatever)]`][crate::Snafu#controlling-stringly-typed-errors]
+    /// attribute. The premade [`Whatever`](crate::Whatever) type is also available.
+    ///
+    /// ```rust
+    /// use snafu::{futures::PollExt, Whatever};
+    /// use std::task::Poll;
+    ///
+    /// fn poll_example() -> Poll<Result<usize, Whatever>> {
+    ///     poll_another().whatever_context("Could not read the header")
+    /// }
+    ///
+    /// fn poll_another() -> Poll<std::io::Result<usize>> {
+    ///     /* ... */
+    /// # Poll::Ready(Ok(42))
+    /// }
+    /// ```
+    fn whatever_context<S, E2>(self, context: S) -> Poll<Result<T, E2>>
+    where
+        S: Into<String>,
+        E2: FromString,
+        E: Into<E2::Source>;
+
+    /// Extend a [`Poll`]-wrapped [`Result`]'s error with information
+    /// from a lazily-generated string.
+    ///
+    /// The target error type must implement [`FromString`] by using
+    /// the
+    /// [`#[snafu(whatever)]`][crate::Snafu#controlling-stringly-typed-errors]
+    /// attribute. The premade [`Whatever`](crate::Whatever) type is also available.
+    fn with_whatever_context<F, S, E2>(self, context: F) -> Poll<Result<T, E2>>
+    where
+        F: FnOnce(&E) -> S,
+        S: Into<String>,
+        E2: FromString,
+        E: Into<E2::Source>;
+}
+
+impl<T, E> PollExt<T, E> for Poll<Result<T, E>> {
+    fn context<C, E2>(self, context: C) -> Poll<Result<T, E2>>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        self.map(|r| r.context(context))
+    }
+
+    fn with_context<F, C, E2>(self, context: F) -> Poll<Result<T, E2>>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        self.map(|r| r.with_context(context))
+    }
+
+    fn whatever_context<S, E2>(self, context: S) -> Poll<Result<T, E2>>
+    where
+        S: Into<String>,
+        E2: FromString,
+        E: Into<E2::Source>,
+    {
+        self.map(|r| r.map_err(|e| FromString::with_source(e.into(), context.into())))
+    }
+
+    fn with_whatever_context<F, S, E2>(self, context: F) -> Poll<Result<T, E2>>
+    where
+        F: FnOnce(&E) -> S,
+        S: Into<String>,
+        E2: FromString,
+        E: Into<E2::Source>,
+    {
+        self.map(|r| {
+            r.map_err(|e| {
+                let context = context(&e);
+                FromString::with_source(e.into(), context.into())
+            })
+        })
+    }
+}
+
+/// Additions to [`Poll<Option<Result<T, E>>>`](Poll), as returned by
+/// [`Stream::poll_next`](futures_core_crate::Stream::poll_next).
+pub trait PollOptionExt<T, E>: Sized {
+    /// Extend a [`Poll`]-wrapped stream item's error with additional
+    /// context-sensitive information.
+    ///
+    /// ```rust
+    /// use snafu::{futures::PollOptionExt, Snafu};
+    /// use std::task::Poll;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Reading { source: std::io::Error },
+    /// }
+    ///
+    /// fn poll_next_example() -> Poll<Option<Result<usize, Error>>> {
+    ///     poll_next_another().context(ReadingSnafu)
+    /// }
+    ///
+    /// fn poll_next_another() -> Poll<Option<std::io::Result<usize>>> {
+    ///     /* ... */
+    /// # Poll::Ready(None)
+    /// }
+    /// ```
+    ///
+    /// [`Poll::Pending`] and `Poll::Ready(None)` values are returned
+    /// unchanged.
+    fn context<C, E2>(self, context: C) -> Poll<Option<Result<T, E2>>>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
+    /// Extend a [`Poll`]-wrapped stream item's error with
+    /// lazily-generated context-sensitive information.
+    ///
+    /// The closure is only called when the value is
+    /// `Poll::Ready(Some(Err(_)))`.
+    fn with_context<F, C, E2>(self, context: F) -> Poll<Option<Result<T, E2>>>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
+    /// Extend a [`Poll`]-wrapped stream item's error with information
+    /// from a string.
+    ///
+    /// The target error type must implement [`FromString`] by using
+    /// the
+    /// [`#[snafu(whatever)]`][crate::Snafu#controlling-stringly-typed-errors]
+    /// attribute. The premade [`Whatever`](crate::Whatever) type is also available.
+    fn whatever_context<S, E2>(self, context: S) -> Poll<Option<Result<T, E2>>>
+    where
+        S: Into<String>,
+        E2: FromString,
+        E: Into<E2::Source>;
+
+    /// Extend a [`Poll`]-wrapped stream item's error with information
+    /// from a lazily-generated string.
+    ///
+    /// The target error type must implement [`FromString`] by using
+    /// the
+    /// [`#[snafu(whatever)]`][crate::Snafu#controlling-stringly-typed-errors]
+    /// attribute. The premade [`Whatever`](crate::Whatever) type is also available.
+    fn with_whatever_context<F, S, E2>(self, context: F) -> Poll<Option<Result<T, E2>>>
+    where
+        F: FnOnce(&E) -> S,
+        S: Into<String>,
+        E2: FromString,
+        E: Into<E2::Source>;
+}
+
+impl<T, E> PollOptionExt<T, E> for Poll<Option<Result<T, E>>> {
+    fn context<C, E2>(self, context: C) -> Poll<Option<Result<T, E2>>>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        self.map(|o| o.map(|r| r.context(context)))
+    }
+
+    fn with_context<F, C, E2>(self, context: F) -> Poll<Option<Result<T, E2>>>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        self.map(|o| o.map(|r| r.with_context(context)))
+    }
+
+    fn whatever_context<S, E2>(self, context: S) -> Poll<Option<Result<T, E2>>>
+    where
+        S: Into<String>,
+        E2: FromString,
+        E: Into<E2::Source>,
+    {
+        self.map(|o| o.map(|r| r.map_err(|e| FromString::with_source(e.into(), context.into()))))
+    }
+
+    fn with_whatever_context<F, S, E2>(self, context: F) -> Poll<Option<Result<T, E2>>>
+    where
+        F: FnOnce(&E) -> S,
+        S: Into<String>,
+        E2: FromString,
+        E: Into<E2::Source>,
+    {
+        self.map(|o| {
+            o.map(|r| {
+                r.map_err(|e| {
+                    let context = context(&e);
+                    FromString::with_source(e.into(), context.into())
+                })
+            })
+        })
+    }
+}
@@ -0,0 +1,255 @@
+//! Additions to the [`Sink`] trait.
+//!
+//! [`Sink`]: futures_sink_crate::Sink
+
+use crate::{Error, ErrorCompat, IntoError};
+use core::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+use futures_core_crate::stream::Stream;
+use futures_sink_crate::Sink;
+use pin_project::pin_project;
+
+/// Additions to [`Sink`].
+///
+/// The methods are prefixed with `sink_` so that they do not conflict
+/// with [`TryStreamExt`](crate::futures::TryStreamExt) for types that
+/// are both a stream and a sink, such as a socket.
+pub trait SinkExt<Item>: Sink<Item> + Sized {
+    /// Extend a [`Sink`]'s error with additional context-sensitive
+    /// information.
+    ///
+    /// ```rust
+    /// # use futures_crate as futures;
+    /// use futures::Sink;
+    /// # use futures::sink;
+    /// use snafu::{futures::SinkExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Publishing {
+    ///         topic: String,
+    ///         source: ApiError,
+    ///     },
+    /// }
+    ///
+    /// fn example() -> impl Sink<i32, Error = Error> {
+    ///     stock_prices().sink_context(PublishingSnafu { topic: "stocks" })
+    /// }
+    ///
+    /// # type ApiError = Box<dyn std::error::Error>;
+    /// fn stock_prices() -> impl Sink<i32, Error = ApiError> {
+    ///     /* ... */
+    /// # futures::SinkExt::sink_map_err(sink::drain(), |e| match e {})
+    /// }
+    /// ```
+    ///
+    /// Note that the context selector will call [`Into::into`] on
+    /// each field, so the types are not required to exactly match.
+    fn sink_context<C, E>(self, context: C) -> SinkContext<Self, C, E>
+    where
+        C: IntoError<E, Source = Self::Error> + Clone,
+        E: Error + ErrorCompat;
+
+    /// Extend a [`Sink`]'s error with lazily-generated
+    /// context-sensitive information.
+    ///
+    /// ```rust
+    /// # use futures_crate as futures;
+    /// use futures::Sink;
+    /// # use futures::sink;
+    /// use snafu::{futures::SinkExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Publishing {
+    ///         topic: String,
+    ///         source: ApiError,
+    ///     },
+    /// }
+    ///
+    /// fn example() -> impl Sink<i32, Error = Error> {
+    ///     stock_prices().with_sink_context(|| PublishingSnafu {
+    ///         topic: format!("stocks-{}", 42),
+    ///     })
+    /// }
+    ///
+    /// # type ApiError = Box<dyn std::error::Error>;
+    /// fn stock_prices() -> impl Sink<i32, Error = ApiError> {
+    ///     /* ... */
+    /// # futures::SinkExt::sink_map_err(sink::drain(), |e| match e {})
+    /// }
+    /// ```
+    ///
+    /// Note that this *may not* be needed in many cases because the
+    /// context selector will call [`Into::into`] on each field.
+    fn with_sink_context<F, C, E>(self, context: F) -> WithSinkContext<Self, F, E>
+    where
+        F: FnMut() -> C,
+        C: IntoError<E, Source = Self::Error>,
+        E: Error + ErrorCompat;
+}
+
+impl<Si, Item> SinkExt<Item> for Si
+where
+    Si: Sink<Item>,
+{
+    fn sink_context<C, E>(self, context: C) -> SinkContext<Self, C, E>
+    where
+        C: IntoError<E, Source = Self::Error> + Clone,
+        E: Error + ErrorCompat,
+    {
+        SinkContext {
+            inner: self,
+            context,
+            _e: PhantomData,
+        }
+    }
+
+    fn with_sink_context<F, C, E>(self, context: F) -> WithSinkContext<Self, F, E>
+    where
+        F: FnMut() -> C,
+        C: IntoError<E, Source = Self::Error>,
+        E: Error + ErrorCompat,
+    {
+        WithSinkContext {
+            inner: self,
+            context,
+            _e: PhantomData,
+        }
+    }
+}
+
+/// Sink for the [`sink_context`](SinkExt::sink_context) combinator.
+///
+/// See the [`SinkExt::sink_context`] method for more details.
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "sinks do nothing unless polled"]
+pub struct SinkContext<Si, C, E> {
+    #[pin]
+    inner: Si,
+    context: C,
+    _e: PhantomData<fn() -> E>,
+}
+
+impl<Si, Item, C, E> Sink<Item> for SinkContext<Si, C, E>
+where
+    Si: Sink<Item>,
+    C: IntoError<E, Source = Si::Error> + Clone,
+    E: Error + ErrorCompat,
+{
+    type Error = E;
+
+    fn poll_ready(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Result<(), E>> {
+        let this = self.project();
+        let context = this.context;
+        this.inner
+            .poll_ready(ctx)
+            .map_err(|error| context.clone().into_error(error))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), E> {
+        let this = self.project();
+        let context = this.context;
+        this.inner
+            .start_send(item)
+            .map_err(|error| context.clone().into_error(error))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Result<(), E>> {
+        let this = self.project();
+        let context = this.context;
+        this.inner
+            .poll_flush(ctx)
+            .map_err(|error| context.clone().into_error(error))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Result<(), E>> {
+        let this = self.project();
+        let context = this.context;
+        this.inner
+            .poll_close(ctx)
+            .map_err(|error| context.clone().into_error(error))
+    }
+}
+
+impl<Si, C, E> Stream for SinkContext<Si, C, E>
+where
+    Si: Stream,
+{
+    type Item = Si::Item;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Option<Self::Item>> {
+        self.project().inner.poll_next(ctx)
+    }
+}
+
+/// Sink for the [`with_sink_context`](SinkExt::with_sink_context)
+/// combinator.
+///
+/// See the [`SinkExt::with_sink_context`] method for more details.
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "sinks do nothing unless polled"]
+pub struct WithSinkContext<Si, F, E> {
+    #[pin]
+    inner: Si,
+    context: F,
+    _e: PhantomData<fn() -> E>,
+}
+
+impl<Si, Item, F, C, E> Sink<Item> for WithSinkContext<Si, F, E>
+where
+    Si: Sink<Item>,
+    F: FnMut() -> C,
+    C: IntoError<E, Source = Si::Error>,
+    E: Error + ErrorCompat,
+{
+    type Error = E;
+
+    fn poll_ready(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Result<(), E>> {
+        let this = self.project();
+        let context = this.context;
+        this.inner
+            .poll_ready(ctx)
+            .map_err(|error| context().into_error(error))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), E> {
+        let this = self.project();
+        let context = this.context;
+        this.inner
+            .start_send(item)
+            .map_err(|error| context().into_error(error))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Result<(), E>> {
+        let this = self.project();
+        let context = this.context;
+        this.inner
+            .poll_flush(ctx)
+            .map_err(|error| context().into_error(error))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Result<(), E>> {
+        let this = self.project();
+        let context = this.context;
+        this.inner
+            .poll_close(ctx)
+            .map_err(|error| context().into_error(error))
+    }
+}
+
+impl<Si, F, E> Stream for WithSinkContext<Si, F, E>
+where
+    Si: Stream,
+{
+    type Item = Si::Item;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Option<Self::Item>> {
+        self.project().inner.poll_next(ctx)
+    }
+}
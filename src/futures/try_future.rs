@@ -112,7 +112,7 @@ pub trait TryFutureExt: TryFuture + Sized {
     ///     api_function().whatever_context("The API failed")
     /// }
     ///
-    /// # type ApiError = Box<dyn std::error::Error>;
+    /// # type ApiError = Box<dyn std::error::Error + Send + Sync>;
     /// fn api_function() -> impl TryFuture<Ok = i32, Error = ApiError> {
     ///     /* ... */
     /// # futures::future::ok(42)
@@ -142,7 +142,7 @@ pub trait TryFutureExt: TryFuture + Sized {
     ///     })
     /// }
     ///
-    /// # type ApiError = Box<dyn std::error::Error>;
+    /// # type ApiError = Box<dyn std::error::Error + Send + Sync>;
     /// fn api_function(arg: &'static str) -> impl TryFuture<Ok = i32, Error = ApiError> {
     ///     /* ... */
     /// # futures::future::ok(42)
@@ -153,6 +153,41 @@ pub trait TryFutureExt: TryFuture + Sized {
         F: FnOnce(&Self::Error) -> S,
         S: Into<String>,
         E: FromString;
+
+    /// Extend a [`TryFuture`]'s error with additional context-sensitive
+    /// information, recording how long the future ran before failing
+    /// in any [`Timing`](crate::Timing) fields of the error.
+    ///
+    /// The time is measured from when the future is first polled.
+    ///
+    /// ```rust
+    /// # use futures_crate as futures;
+    /// use futures::future::TryFuture;
+    /// use snafu::{futures::TryFutureExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Querying {
+    ///         source: ApiError,
+    ///         timing: snafu::Timing,
+    ///     },
+    /// }
+    ///
+    /// fn example() -> impl TryFuture<Ok = i32, Error = Error> {
+    ///     query().timed_context(QueryingSnafu)
+    /// }
+    ///
+    /// # type ApiError = Box<dyn std::error::Error>;
+    /// fn query() -> impl TryFuture<Ok = i32, Error = ApiError> {
+    ///     /* ... */
+    /// # futures::future::ok(42)
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    fn timed_context<C, E>(self, context: C) -> TimedContext<Self, C, E>
+    where
+        C: IntoError<E, Source = Self::Error>,
+        E: Error + ErrorCompat;
 }
 
 impl<Fut> TryFutureExt for Fut
@@ -208,6 +243,20 @@ where
             _e: PhantomData,
         }
     }
+
+    #[cfg(feature = "std")]
+    fn timed_context<C, E>(self, context: C) -> TimedContext<Self, C, E>
+    where
+        C: IntoError<E, Source = Self::Error>,
+        E: Error + ErrorCompat,
+    {
+        TimedContext {
+            inner: self,
+            context: Some(context),
+            start: None,
+            _e: PhantomData,
+        }
+    }
 }
 
 /// Future for the [`context`](TryFutureExt::context) combinator.
@@ -220,7 +269,7 @@ pub struct Context<Fut, C, E> {
     #[pin]
     inner: Fut,
     context: Option<C>,
-    _e: PhantomData<E>,
+    _e: PhantomData<fn() -> E>,
 }
 
 impl<Fut, C, E> Future for Context<Fut, C, E>
@@ -255,7 +304,7 @@ pub struct WithContext<Fut, F, E> {
     #[pin]
     inner: Fut,
     context: Option<F>,
-    _e: PhantomData<E>,
+    _e: PhantomData<fn() -> E>,
 }
 
 impl<Fut, F, C, E> Future for WithContext<Fut, F, E>
@@ -294,7 +343,7 @@ pub struct WhateverContext<Fut, S, E> {
     #[pin]
     inner: Fut,
     context: Option<S>,
-    _e: PhantomData<E>,
+    _e: PhantomData<fn() -> E>,
 }
 
 impl<Fut, S, E> Future for WhateverContext<Fut, S, E>
@@ -333,7 +382,7 @@ pub struct WithWhateverContext<Fut, F, E> {
     #[pin]
     inner: Fut,
     context: Option<F>,
-    _e: PhantomData<E>,
+    _e: PhantomData<fn() -> E>,
 }
 
 impl<Fut, F, S, E> Future for WithWhateverContext<Fut, F, E>
@@ -360,3 +409,43 @@ where
         })
     }
 }
+
+/// Future for the [`timed_context`](TryFutureExt::timed_context)
+/// combinator.
+///
+/// See the [`TryFutureExt::timed_context`] method for more details.
+#[cfg(feature = "std")]
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct TimedContext<Fut, C, E> {
+    #[pin]
+    inner: Fut,
+    context: Option<C>,
+    start: Option<std::time::Instant>,
+    _e: PhantomData<fn() -> E>,
+}
+
+#[cfg(feature = "std")]
+impl<Fut, C, E> Future for TimedContext<Fut, C, E>
+where
+    Fut: TryFuture,
+    C: IntoError<E, Source = Fut::Error>,
+    E: Error + ErrorCompat,
+{
+    type Output = Result<Fut::Ok, E>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut TaskContext) -> Poll<Self::Output> {
+        let this = self.project();
+        let inner = this.inner;
+        let context = this.context;
+        let start = *this.start.get_or_insert_with(std::time::Instant::now);
+
+        inner.try_poll(ctx).map_err(|error| {
+            let context = context
+                .take()
+                .expect("Cannot poll TimedContext after it resolves");
+            crate::timing::into_error_with_elapsed(context, error, start.elapsed())
+        })
+    }
+}
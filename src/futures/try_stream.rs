@@ -114,7 +114,7 @@ pub trait TryStreamExt: TryStream + Sized {
     ///     stock_prices().whatever_context("Couldn't get stock prices")
     /// }
     ///
-    /// # type ApiError = Box<dyn std::error::Error>;
+    /// # type ApiError = Box<dyn std::error::Error + Send + Sync>;
     /// fn stock_prices() -> impl TryStream<Ok = i32, Error = ApiError> {
     ///     /* ... */
     /// # stream::empty()
@@ -145,7 +145,7 @@ pub trait TryStreamExt: TryStream + Sized {
     ///     })
     /// }
     ///
-    /// # type ApiError = Box<dyn std::error::Error>;
+    /// # type ApiError = Box<dyn std::error::Error + Send + Sync>;
     /// fn stock_prices(symbol: &'static str) -> impl TryStream<Ok = i32, Error = ApiError> {
     ///     /* ... */
     /// # stream::empty()
@@ -223,7 +223,7 @@ pub struct Context<St, C, E> {
     #[pin]
     inner: St,
     context: C,
-    _e: PhantomData<E>,
+    _e: PhantomData<fn() -> E>,
 }
 
 impl<St, C, E> Stream for Context<St, C, E>
@@ -261,7 +261,7 @@ pub struct WithContext<St, F, E> {
     #[pin]
     inner: St,
     context: F,
-    _e: PhantomData<E>,
+    _e: PhantomData<fn() -> E>,
 }
 
 impl<St, F, C, E> Stream for WithContext<St, F, E>
@@ -302,7 +302,7 @@ pub struct WhateverContext<St, S, E> {
     #[pin]
     inner: St,
     context: S,
-    _e: PhantomData<E>,
+    _e: PhantomData<fn() -> E>,
 }
 
 impl<St, S, E> Stream for WhateverContext<St, S, E>
@@ -344,7 +344,7 @@ pub struct WithWhateverContext<St, F, E> {
     #[pin]
     inner: St,
     context: F,
-    _e: PhantomData<E>,
+    _e: PhantomData<fn() -> E>,
 }
 
 impl<St, F, S, E> Stream for WithWhateverContext<St, F, E>
@@ -1,6 +1,9 @@
 #![deny(missing_docs)]
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
-#![cfg_attr(feature = "unstable-backtraces-impl-std", feature(backtrace))]
+#![cfg_attr(
+    feature = "unstable-provider-api",
+    feature(error_generic_member_access, error_reporter)
+)]
 
 //! # SNAFU
 //!
@@ -14,11 +17,15 @@
 //! - [Custom error types](Snafu)
 //!   - Including a conversion path from turnkey errors
 //! - [Backtraces](Backtrace)
+//! - [Reports](Report) of an error and its sources for `fn main`
 //! - Extension traits for
 //!   - [`Results`](ResultExt)
 //!   - [`Options`](OptionExt)
 #![cfg_attr(feature = "futures", doc = "   - [`Futures`](futures::TryFutureExt)")]
 #![cfg_attr(feature = "futures", doc = "   - [`Streams`](futures::TryStreamExt)")]
+#![cfg_attr(feature = "futures", doc = "   - [`Sinks`](futures::SinkExt)")]
+#![cfg_attr(feature = "futures", doc = "   - [`Poll`s](futures::PollExt)")]
+//! - A [prelude](prelude) that imports the most common items at once
 //! - Suitable for libraries and applications
 //! - `no-std` compatibility
 //! - Generic types and lifetimes
@@ -31,7 +38,7 @@
 //! [`Whatever`][] type and the [`whatever!`][] macro:
 //!
 //! ```rust
-//! use snafu::{Whatever, whatever};
+//! use snafu::{prelude::*, Whatever};
 //!
 //! fn is_valid_id(id: u16) -> Result<(), Whatever> {
 //!     if id < 10 {
@@ -44,7 +51,7 @@
 //! You can also use it to wrap any other error:
 //!
 //! ```rust
-//! use snafu::{ResultExt, Whatever, whatever};
+//! use snafu::{prelude::*, Whatever};
 //!
 //! fn read_config_file(path: &str) -> Result<String, Whatever> {
 //!     std::fs::read_to_string(path)
@@ -56,7 +63,7 @@
 //! [`Backtrace`][] for every error:
 //!
 //! ```rust
-//! use snafu::{ErrorCompat, ResultExt, Whatever};
+//! use snafu::{prelude::*, ErrorCompat, Whatever};
 //!
 //! fn main() {
 //! # fn returns_an_error() -> Result<(), Whatever> { Ok(()) }
@@ -70,6 +77,22 @@
 //! }
 //! ```
 //!
+//! Returning a [`Report`][] from `main` shows the error, each of its
+//! sources, and the backtrace when the program exits:
+//!
+//! ```rust,no_run
+//! use snafu::{prelude::*, Report, Whatever};
+//!
+//! fn main() -> Result<(), Report<Whatever>> {
+//!     start_server()?;
+//!     Ok(())
+//! }
+//!
+//! fn start_server() -> Result<(), Whatever> {
+//!     whatever!("Could not start the server");
+//! }
+//! ```
+//!
 //! ## Custom error types
 //!
 //! Many projects will hit limitations of the `Whatever` type. When
@@ -84,7 +107,7 @@
 //! ergonomic error creation:
 //!
 //! ```rust
-//! use snafu::{ensure, Snafu};
+//! use snafu::prelude::*;
 //!
 //! #[derive(Debug, Snafu)]
 //! #[snafu(display("ID may not be less than 10, but it was {}", id))]
@@ -103,7 +126,7 @@
 //! extension method:
 //!
 //! ```rust
-//! use snafu::{ResultExt, Snafu};
+//! use snafu::prelude::*;
 //!
 //! #[derive(Debug, Snafu)]
 //! #[snafu(display("Could not read file {}", path))]
@@ -129,7 +152,7 @@
 //! macro to provide ergonomic error creation:
 //!
 //! ```rust
-//! use snafu::{ensure, Snafu};
+//! use snafu::prelude::*;
 //!
 //! #[derive(Debug, Snafu)]
 //! enum Error {
@@ -148,7 +171,7 @@
 //! extension method:
 //!
 //! ```rust
-//! use snafu::{ResultExt, Snafu};
+//! use snafu::prelude::*;
 //!
 //! #[derive(Debug, Snafu)]
 //! enum Error {
@@ -169,7 +192,7 @@
 //! [`Whatever`][] and are moving to a custom error type:
 //!
 //! ```rust
-//! use snafu::{ensure, whatever, Snafu};
+//! use snafu::prelude::*;
 //!
 //! #[derive(Debug, Snafu)]
 //! enum Error {
@@ -191,6 +214,10 @@
 //! }
 //! ```
 
+// Allows the derive to recognize types such as `snafu::Fields` when
+// they are used in this crate.
+extern crate self as snafu;
+
 #[cfg(all(
     not(feature = "backtraces"),
     not(feature = "backtraces-impl-backtrace-crate"),
@@ -223,19 +250,239 @@ pub use backtrace::Backtrace;
 #[cfg(feature = "unstable-backtraces-impl-std")]
 pub use std::backtrace::Backtrace;
 
+// Criterion requires a newer compiler than the rest of the crate
+#[cfg(feature = "bench")]
+#[clippy::msrv = "1.64.0"]
+pub mod bench;
+
+#[cfg(feature = "anyhow")]
+pub mod anyhow;
+
+#[cfg(feature = "eyre")]
+pub mod eyre;
+
+#[cfg(feature = "failure")]
+pub mod failure;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+// OpenTelemetry requires a newer compiler than the rest of the crate
+#[cfg(feature = "opentelemetry")]
+#[clippy::msrv = "1.75.0"]
+pub mod opentelemetry;
+
+#[cfg(feature = "sentry")]
+pub mod sentry;
+
+// The `windows` crates require a newer compiler than the rest of the crate
+#[cfg(feature = "windows")]
+#[clippy::msrv = "1.82.0"]
+pub mod windows;
+
+// Futures require a newer compiler than the rest of the crate
 #[cfg(feature = "futures")]
+#[clippy::msrv = "1.39.0"]
 pub mod futures;
 
+// Tokio requires a newer compiler than the rest of the crate
+#[cfg(feature = "tokio")]
+#[clippy::msrv = "1.46.0"]
+pub mod tokio;
+
+#[cfg(feature = "std")]
+pub mod path;
+
 #[cfg(feature = "std")]
 mod error_chain;
 #[cfg(feature = "std")]
 pub use crate::error_chain::*;
 
+#[cfg(feature = "std")]
+mod multi_error;
+#[cfg(feature = "std")]
+pub use crate::multi_error::*;
+
+mod location;
+pub use crate::location::*;
+
+mod error_fields;
+pub use crate::error_fields::*;
+
+mod format_into;
+pub use crate::format_into::*;
+
+mod trace_cell;
+pub use crate::trace_cell::*;
+
+// Inventory requires a newer compiler than the rest of the crate
+#[cfg(feature = "catalog")]
+#[clippy::msrv = "1.68.0"]
+mod catalog;
+#[cfg(feature = "catalog")]
+pub use crate::catalog::*;
+
+#[cfg(feature = "std")]
+mod fields;
+#[cfg(feature = "std")]
+pub use crate::fields::*;
+
+#[cfg(feature = "std")]
+mod metadata;
+#[cfg(feature = "std")]
+pub use crate::metadata::*;
+
+#[cfg(feature = "std")]
+pub mod report;
+#[cfg(feature = "std")]
+pub use crate::report::Report;
+
+#[cfg(feature = "std")]
+mod retryable;
+#[cfg(feature = "std")]
+pub use crate::retryable::*;
+
+#[cfg(feature = "std")]
+mod serializable;
+#[cfg(feature = "std")]
+pub use crate::serializable::*;
+
+// Used by `#[snafu(serialize)]`
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde_crate as __serde;
+
+// Used by `#[snafu(code)]`, `#[snafu(help)]`, `#[snafu(url)]`, and
+// `#[snafu(label)]`
+#[cfg(feature = "miette")]
+#[doc(hidden)]
+pub use miette_crate as __miette;
+
+// Used by `#[snafu(catalog)]`
+#[cfg(feature = "catalog")]
+#[doc(hidden)]
+pub use inventory_crate as __inventory;
+
+// Used by `#[snafu(http_status)]`
+#[cfg(feature = "http")]
+#[doc(hidden)]
+pub use http_crate as __http;
+
+// Used by `#[snafu(grpc)]`
+#[cfg(feature = "tonic")]
+#[doc(hidden)]
+pub use tonic_crate as __tonic;
+
+// Used by `#[snafu(opentelemetry)]`
+#[cfg(feature = "opentelemetry")]
+#[doc(hidden)]
+pub use opentelemetry_crate as __opentelemetry;
+
+// Used by `#[snafu(wasm)]`
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+pub use wasm_bindgen_crate as __wasm_bindgen;
+
+mod source_conversion;
+pub use crate::source_conversion::*;
+
+#[cfg(feature = "tracing")]
+mod current_span;
+#[cfg(feature = "tracing")]
+pub use crate::current_span::*;
+
+#[cfg(feature = "tracing")]
+mod span_id;
+#[cfg(feature = "tracing")]
+pub use crate::span_id::*;
+
+// Used by `#[snafu(tracing)]`
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing_crate as __tracing;
+
+// Used by `#[snafu(log)]`
+#[cfg(feature = "log")]
+#[doc(hidden)]
+pub use log_crate as __log;
+
+// Used by `#[snafu(metric)]`
+#[cfg(feature = "metrics")]
+#[doc(hidden)]
+pub use metrics_crate as __metrics;
+
+#[cfg(feature = "std")]
+mod timing;
+#[cfg(feature = "std")]
+pub use crate::timing::*;
+
 doc_comment::doc_comment! {
     include_str!("Snafu.md"),
     pub use snafu_derive::Snafu;
 }
 
+/// Converts an error returned from `main` into a [`Report`].
+///
+/// Apply this attribute to a `main` function that returns
+/// `Result<(), E>`. When it returns an error, the error is shown
+/// using [`Report`] and the process exits with the report's [exit
+/// code](Report::exit_code), without changing the function's return
+/// type.
+///
+/// ```rust,no_run
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not read the configuration"), exit_code(78))]
+///     ReadConfig { source: std::io::Error },
+/// }
+///
+/// #[snafu::report]
+/// fn main() -> Result<(), Error> {
+///     let config = std::fs::read_to_string("config.toml").context(ReadConfigSnafu)?;
+///     println!("{}", config);
+///     Ok(())
+/// }
+/// ```
+///
+/// `async` functions are also supported and can be combined with
+/// the attribute of your async runtime:
+///
+/// ```rust,ignore
+/// #[tokio::main]
+/// #[snafu::report]
+/// async fn main() -> Result<(), Error> {
+///     // ...
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub use snafu_derive::report;
+
+/// Traits and macros used by most projects. Add `use
+/// snafu::prelude::*` to your code to quickly get started with
+/// SNAFU.
+///
+/// The extension traits are imported anonymously so that their
+/// methods are available without their names conflicting with any
+/// of your own.
+pub mod prelude {
+    pub use crate::{
+        ensure, ensure_code, FormatInternal as _, OptionExt as _, ResultExt as _, Snafu,
+    };
+
+    #[cfg(any(feature = "std", test))]
+    pub use crate::whatever;
+
+    #[cfg(feature = "std")]
+    pub use crate::ResultIteratorExt as _;
+
+    #[cfg(feature = "futures")]
+    pub use crate::futures::{
+        PollExt as _, PollOptionExt as _, SinkExt as _, TryFutureExt as _, TryStreamExt as _,
+    };
+}
+
 macro_rules! generate_guide {
     (pub mod $name:ident; $($rest:tt)*) => {
         generate_guide!(@gen ".", pub mod $name { } $($rest)*);
@@ -302,9 +549,13 @@ doc_comment::doctest!("../README.md", readme_tests);
 #[doc(hidden)]
 pub use std::error::Error;
 
-#[cfg(not(any(feature = "std", test)))]
+#[cfg(all(not(any(feature = "std", test)), feature = "rust_1_81"))]
+#[doc(hidden)]
+pub use core::error::Error;
+
+#[cfg(not(any(feature = "std", test, feature = "rust_1_81")))]
 mod no_std_error;
-#[cfg(not(any(feature = "std", test)))]
+#[cfg(not(any(feature = "std", test, feature = "rust_1_81")))]
 #[doc(hidden)]
 pub use no_std_error::Error;
 
@@ -337,6 +588,69 @@ macro_rules! ensure {
     };
 }
 
+/// Ensure a condition is true. If it is not, return from the function
+/// with an error built from a fieldless code.
+///
+/// Unlike [`ensure!`], the error does not need to implement
+/// [`Error`](crate::Error) and is only constructed using
+/// [`FromCode`], so no formatting code is required. This is useful for
+/// guard clauses on constrained targets.
+///
+/// ```rust
+/// use snafu::{ensure_code, FromCode};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ErrorCode {
+///     Empty,
+///     TooLong,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Error {
+///     code: ErrorCode,
+/// }
+///
+/// impl FromCode<ErrorCode> for Error {
+///     fn from_code(code: ErrorCode) -> Self {
+///         Error { code }
+///     }
+/// }
+///
+/// fn example(name: &str) -> Result<(), Error> {
+///     ensure_code!(!name.is_empty(), ErrorCode::Empty);
+///     ensure_code!(name.len() <= 16, ErrorCode::TooLong);
+///     Ok(())
+/// }
+///
+/// assert_eq!(example(""), Err(Error { code: ErrorCode::Empty }));
+/// ```
+///
+/// The code itself can also be used as the error type.
+///
+/// ```rust
+/// use snafu::ensure_code;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ErrorCode {
+///     Empty,
+/// }
+///
+/// fn example(name: &str) -> Result<(), ErrorCode> {
+///     ensure_code!(!name.is_empty(), ErrorCode::Empty);
+///     Ok(())
+/// }
+///
+/// assert_eq!(example(""), Err(ErrorCode::Empty));
+/// ```
+#[macro_export]
+macro_rules! ensure_code {
+    ($predicate:expr, $code:expr $(,)?) => {
+        if !$predicate {
+            return ::core::result::Result::Err($crate::FromCode::from_code($code));
+        }
+    };
+}
+
 /// Instantiate and return a stringly-typed error message.
 ///
 /// This can be used with the provided [`Whatever`][] type or with a
@@ -398,31 +712,130 @@ macro_rules! ensure {
 ///     whatever!("The programmer forgot to implement this...");
 /// }
 /// ```
+///
+/// # With key-value pairs
+///
+/// Any `key = value` pairs after a `;` that ends the format arguments
+/// are passed to the error along with the location of the macro call;
+/// see [`WhateverDetails`]. The value is converted to a typed
+/// [`FieldValue`] using [`From`]. As with the `tracing` crate, prefix
+/// the value with `%` to format it using
+/// [`Display`](core::fmt::Display) or with `?` to format it using
+/// [`Debug`](core::fmt::Debug) instead.
+///
+/// A `name = value` before the `;` remains a named argument of the
+/// format string.
+///
+/// The pairs are available from [`Whatever::fields`] and are stored
+/// in fields of the type `snafu::Fields` or, converted to strings,
+/// `snafu::Metadata`. The location is stored in fields of the type
+/// `snafu::Location`.
+///
+/// ```rust
+/// use snafu::{FieldValue, Snafu, whatever};
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(whatever, display("Error was: {}", message))]
+/// struct Error {
+///     message: String,
+///     fields: snafu::Fields,
+/// }
+///
+/// fn withdraw(account_id: &str, amount: u8) -> Result<u8, Error> {
+///     let path = std::path::Path::new("/accounts");
+///     whatever!(
+///         "Insufficient funds in {}", account_id;
+///         amount = amount, account = %account_id, path = ?path,
+///     );
+/// }
+///
+/// let error = withdraw("1234", 50).unwrap_err();
+/// assert_eq!(error.fields.get("amount"), Some(&FieldValue::U64(50)));
+/// assert_eq!(error.fields.get("account"), Some(&FieldValue::from("1234")));
+/// assert_eq!(error.fields.get("path"), Some(&FieldValue::from(r#""/accounts""#)));
+/// ```
 #[macro_export]
 #[cfg(any(feature = "std", test))]
 macro_rules! whatever {
-    ($fmt:literal$(, $($arg:expr),* $(,)?)?) => {
-        return core::result::Result::Err({
-            $crate::FromString::without_source(
-                format!($fmt$(, $($arg),*)*),
-            )
-        });
+    ($fmt:literal $($rest:tt)*) => {
+        return core::result::Result::Err(
+            $crate::__whatever_arguments!([] [$fmt] $($rest)*)
+        );
     };
-    ($source:expr, $fmt:literal$(, $($arg:expr),* $(,)?)*) => {
+    ($source:expr, $fmt:literal $($rest:tt)*) => {
         match $source {
             core::result::Result::Ok(v) => v,
             core::result::Result::Err(e) => {
-                return core::result::Result::Err({
-                    $crate::FromString::with_source(
-                        core::convert::Into::into(e),
-                        format!($fmt$(, $($arg),*)*),
-                    )
-                });
+                return core::result::Result::Err(
+                    $crate::__whatever_arguments!([e] [$fmt] $($rest)*)
+                );
             }
         }
     };
 }
 
+/// Collects the format arguments of [`whatever!`] up to the `;` that
+/// starts the `key = value` pairs, then builds the error.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(any(feature = "std", test))]
+macro_rules! __whatever_arguments {
+    ([$($e:ident)?] [$($fmt:tt)*]) => {
+        $crate::__whatever_fields!([$($e)?] [$($fmt)*] [])
+    };
+    ([$($e:ident)?] [$($fmt:tt)*] ; $($fields:tt)*) => {
+        $crate::__whatever_fields!([$($e)?] [$($fmt)*] [] $($fields)*)
+    };
+    ([$($e:ident)?] [$($fmt:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__whatever_arguments!([$($e)?] [$($fmt)* $next] $($rest)*)
+    };
+}
+
+/// Converts the `key = value` pairs of [`whatever!`], then builds the
+/// error.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(any(feature = "std", test))]
+macro_rules! __whatever_fields {
+    ([] [$($fmt:tt)*] [$(($key:ident, $value:expr))*]) => {{
+        let details = $crate::__whatever_details!($(($key, $value))*);
+        $crate::FromString::without_source_and_details(format!($($fmt)*), details)
+    }};
+    ([$e:ident] [$($fmt:tt)*] [$(($key:ident, $value:expr))*]) => {{
+        // Format the message before converting the source so that
+        // the converted source is not held across any `.await` in
+        // the arguments
+        let message = format!($($fmt)*);
+        let details = $crate::__whatever_details!($(($key, $value))*);
+        $crate::FromString::with_source_and_details(core::convert::Into::into($e), message, details)
+    }};
+    ([$($e:ident)?] [$($fmt:tt)*] [$($kv:tt)*] $key:ident = % $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__whatever_fields!(
+            [$($e)?] [$($fmt)*] [$($kv)* ($key, $crate::FieldValue::String(format!("{}", $value)))] $($($rest)*)?
+        )
+    };
+    ([$($e:ident)?] [$($fmt:tt)*] [$($kv:tt)*] $key:ident = ? $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__whatever_fields!(
+            [$($e)?] [$($fmt)*] [$($kv)* ($key, $crate::FieldValue::String(format!("{:?}", $value)))] $($($rest)*)?
+        )
+    };
+    ([$($e:ident)?] [$($fmt:tt)*] [$($kv:tt)*] $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__whatever_fields!(
+            [$($e)?] [$($fmt)*] [$($kv)* ($key, $crate::FieldValue::from($value))] $($($rest)*)?
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(any(feature = "std", test))]
+macro_rules! __whatever_details {
+    ($(($key:ident, $value:expr))*) => {
+        $crate::WhateverDetails::new($crate::Location::new(file!(), line!(), column!()))
+            $(.with(stringify!($key), $value))*
+    };
+}
+
 /// Additions to [`Result`](std::result::Result).
 pub trait ResultExt<T, E>: Sized {
     /// Extend a [`Result`]'s error with additional context-sensitive information.
@@ -578,6 +991,162 @@ pub trait ResultExt<T, E>: Sized {
         E2: FromString,
         E: Into<E2::Source>;
 
+    /// Attach a key-value pair to a [`Result`]'s error using
+    /// [`ErrorMetadata::add_context`]. The value is only formatted
+    /// in case of error. Errors that cannot hold the pair do not
+    /// implement [`ErrorMetadata`] and are rejected at compile time.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, ErrorCompat};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     #[snafu(display("Could not load user {}", id))]
+    ///     LoadUser { id: u32, metadata: snafu::Metadata },
+    /// }
+    ///
+    /// fn load_user(id: u32) -> Result<(), Error> {
+    ///     LoadUserSnafu { id }.fail()
+    /// }
+    ///
+    /// let err = load_user(42).add_context("request_id", 7).unwrap_err();
+    /// let metadata = err.metadata().unwrap();
+    /// assert_eq!(metadata.get("request_id"), Some("7"));
+    /// ```
+    #[cfg(feature = "std")]
+    fn add_context<V>(self, key: &'static str, value: V) -> Result<T, E>
+    where
+        V: core::fmt::Display,
+        E: ErrorMetadata;
+
+    /// Convert a [`Result`]'s error into another error type using
+    /// [`Into`].
+    ///
+    /// This is equivalent to `map_err(Into::into)` but allows naming
+    /// the target type when it cannot be inferred. Variants marked
+    /// with
+    /// [`#[snafu(context(false))]`][Snafu#disabling-the-context-selector]
+    /// generate the needed [`From`] implementation.
+    ///
+    /// ```rust
+    /// use snafu::prelude::*;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum StorageError {
+    ///     DiskFull,
+    /// }
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum ServiceError {
+    ///     #[snafu(context(false))]
+    ///     Storage { source: StorageError },
+    /// }
+    ///
+    /// fn save() -> Result<(), StorageError> {
+    ///     DiskFullSnafu.fail()
+    /// }
+    ///
+    /// let err = save().map_err_into::<ServiceError>().unwrap_err();
+    /// assert!(matches!(err, ServiceError::Storage { .. }));
+    /// ```
+    fn map_err_into<E2>(self) -> Result<T, E2>
+    where
+        E: Into<E2>;
+
+    /// Convert a [`Result`]'s error into a boxed trait object that
+    /// is `Send` and `Sync`.
+    ///
+    /// This is useful when the concrete error type is not important
+    /// to the caller, such as when collecting errors from several
+    /// libraries or when returning a [`Box`] as the source of another
+    /// error.
+    ///
+    /// ```rust
+    /// use snafu::prelude::*;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// struct ConfigError {
+    ///     source: Box<dyn std::error::Error + Send + Sync>,
+    /// }
+    ///
+    /// fn read_config() -> Result<String, ConfigError> {
+    ///     std::fs::read_to_string("config.toml").boxed().context(ConfigSnafu)
+    /// }
+    /// ```
+    #[cfg(any(feature = "std", test))]
+    fn boxed<'a>(self) -> Result<T, Box<dyn Error + Send + Sync + 'a>>
+    where
+        E: Error + Send + Sync + 'a;
+
+    /// Convert a [`Result`]'s error into a boxed trait object.
+    ///
+    /// Unlike [`boxed`][Self::boxed], the error does not need to be
+    /// `Send` or `Sync`.
+    ///
+    /// ```rust
+    /// use snafu::prelude::*;
+    /// use std::rc::Rc;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// struct LocalError {
+    ///     shared: Rc<String>,
+    /// }
+    ///
+    /// fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let shared = Rc::new(String::from("not thread-safe"));
+    ///     LocalSnafu { shared }.fail().boxed_local()
+    /// }
+    ///
+    /// assert!(example().is_err());
+    /// ```
+    #[cfg(any(feature = "std", test))]
+    fn boxed_local<'a>(self) -> Result<T, Box<dyn Error + 'a>>
+    where
+        E: Error + 'a;
+
+    /// Inspect a [`Result`]'s error and either recover from it with a
+    /// fallback value or extend it with additional context-sensitive
+    /// information.
+    ///
+    /// The closure is only called when the result is an error. It
+    /// receives the error by reference and returns a [`Recovery`]
+    /// describing what to do with it.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Recovery};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum ConfigError {
+    ///     Missing,
+    ///     Malformed { line: usize },
+    /// }
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     LoadingConfig { path: String, source: ConfigError },
+    /// }
+    ///
+    /// fn load_config(path: &str) -> Result<String, Error> {
+    ///     read_config(path).recover_with(|e| match e {
+    ///         ConfigError::Missing => Recovery::Value(String::from("defaults")),
+    ///         _ => Recovery::Context(LoadingConfigSnafu { path }),
+    ///     })
+    /// }
+    ///
+    /// fn read_config(path: &str) -> Result<String, ConfigError> {
+    ///     /* ... */
+    /// # if path == "missing" { MissingSnafu.fail() } else { MalformedSnafu { line: 3usize }.fail() }
+    /// }
+    ///
+    /// assert_eq!(load_config("missing").unwrap(), "defaults");
+    /// assert!(load_config("broken").is_err());
+    /// ```
+    fn recover_with<F, C, E2>(self, recovery: F) -> Result<T, E2>
+    where
+        F: FnOnce(&E) -> Recovery<T, C>,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
     #[doc(hidden)]
     #[deprecated(since = "0.4.0", note = "use ResultExt::context instead")]
     fn eager_context<C, E2>(self, context: C) -> Result<T, E2>
@@ -644,16 +1213,73 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
             FromString::with_source(e.into(), context.into())
         })
     }
-}
 
-/// A temporary error type used when converting an [`Option`][] into a
-/// [`Result`][]
-///
-/// [`Option`]: std::option::Option
-/// [`Result`]: std::result::Result
-pub struct NoneError;
+    #[cfg(feature = "std")]
+    fn add_context<V>(self, key: &'static str, value: V) -> Result<T, E>
+    where
+        V: core::fmt::Display,
+        E: ErrorMetadata,
+    {
+        self.map_err(|mut e| {
+            e.add_context(key, value.to_string());
+            e
+        })
+    }
 
-/// Additions to [`Option`](std::option::Option).
+    fn map_err_into<E2>(self) -> Result<T, E2>
+    where
+        E: Into<E2>,
+    {
+        self.map_err(Into::into)
+    }
+
+    #[cfg(any(feature = "std", test))]
+    fn boxed<'a>(self) -> Result<T, Box<dyn Error + Send + Sync + 'a>>
+    where
+        E: Error + Send + Sync + 'a,
+    {
+        self.map_err(|e| Box::new(e) as _)
+    }
+
+    #[cfg(any(feature = "std", test))]
+    fn boxed_local<'a>(self) -> Result<T, Box<dyn Error + 'a>>
+    where
+        E: Error + 'a,
+    {
+        self.map_err(|e| Box::new(e) as _)
+    }
+
+    fn recover_with<F, C, E2>(self, recovery: F) -> Result<T, E2>
+    where
+        F: FnOnce(&E) -> Recovery<T, C>,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        self.or_else(|error| match recovery(&error) {
+            Recovery::Value(value) => Ok(value),
+            Recovery::Context(context) => Err(context.into_error(error)),
+        })
+    }
+}
+
+/// The outcome of inspecting an error with
+/// [`ResultExt::recover_with`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Recovery<T, C> {
+    /// Recover from the error by using this value instead
+    Value(T),
+    /// Extend the error using this context selector
+    Context(C),
+}
+
+/// A temporary error type used when converting an [`Option`][] into a
+/// [`Result`][]
+///
+/// [`Option`]: std::option::Option
+/// [`Result`]: std::result::Result
+pub struct NoneError;
+
+/// Additions to [`Option`](std::option::Option).
 pub trait OptionExt<T>: Sized {
     /// Convert an [`Option`][] into a [`Result`][] with additional
     /// context-sensitive information.
@@ -812,7 +1438,7 @@ pub trait OptionExt<T>: Sized {
         C: IntoError<E, Source = NoneError>,
         E: Error + ErrorCompat,
     {
-        self.context(context).map_err(Into::into)
+        self.context(context)
     }
 
     #[doc(hidden)]
@@ -823,7 +1449,7 @@ pub trait OptionExt<T>: Sized {
         C: IntoError<E, Source = NoneError>,
         E: Error + ErrorCompat,
     {
-        self.with_context(context).map_err(Into::into)
+        self.with_context(context)
     }
 }
 
@@ -896,31 +1522,299 @@ pub trait ErrorCompat {
     /// To omit the current error and only traverse its sources,
     /// use `skip(1)`.
     #[cfg(feature = "std")]
-    fn iter_chain(&self) -> ChainCompat
+    fn iter_chain(&self) -> ChainCompat<'_>
     where
-        Self: AsErrorSource,
+        Self: AsErrorSource + Sized,
     {
         ChainCompat::new(self.as_error_source())
     }
+
+    /// Returns the first error in the chain of sources that has the
+    /// type `T`, skipping the current error.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, ErrorCompat};
+    /// use std::io;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// struct LoadError {
+    ///     source: ConfigError,
+    /// }
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// struct ConfigError {
+    ///     source: io::Error,
+    /// }
+    ///
+    /// let io = io::Error::new(io::ErrorKind::NotFound, "config.toml");
+    /// let error = Err::<(), _>(io)
+    ///     .context(ConfigSnafu)
+    ///     .context(LoadSnafu)
+    ///     .unwrap_err();
+    ///
+    /// let io = error.find_source::<io::Error>().unwrap();
+    /// assert_eq!(io.kind(), io::ErrorKind::NotFound);
+    /// ```
+    #[cfg(feature = "std")]
+    fn find_source<T>(&self) -> Option<&T>
+    where
+        Self: AsErrorSource + Sized,
+        T: Error + 'static,
+    {
+        let mut source = self.as_error_source().source();
+        while let Some(error) = source {
+            if let Some(error) = error.downcast_ref() {
+                return Some(error);
+            }
+            source = error.source();
+        }
+        None
+    }
+
+    /// Returns the [`ErrorKind`](std::io::ErrorKind) of the first
+    /// [`io::Error`](std::io::Error) in the chain of sources, skipping
+    /// the current error.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, ErrorCompat};
+    /// use std::io;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     #[snafu(display("Could not read {path}"))]
+    ///     Read { path: String, source: io::Error },
+    /// }
+    ///
+    /// let error = std::fs::read("/this/does/not/exist")
+    ///     .context(ReadSnafu { path: "/this/does/not/exist" })
+    ///     .unwrap_err();
+    ///
+    /// match error.io_error_kind() {
+    ///     Some(io::ErrorKind::NotFound) => { /* Create the file */ }
+    ///     Some(io::ErrorKind::PermissionDenied) => { /* Ask for access */ }
+    ///     _ => { /* Give up */ }
+    /// }
+    /// # assert_eq!(error.io_error_kind(), Some(io::ErrorKind::NotFound));
+    /// ```
+    #[cfg(feature = "std")]
+    fn io_error_kind(&self) -> Option<std::io::ErrorKind>
+    where
+        Self: AsErrorSource + Sized,
+    {
+        self.find_source::<std::io::Error>()
+            .map(std::io::Error::kind)
+    }
+
+    /// Returns the raw operating system error code (such as `errno`
+    /// on Unix) of the first [`io::Error`](std::io::Error) in the
+    /// chain of sources, skipping the current error.
+    ///
+    /// Returns `None` if there is no such source or if it was not
+    /// created from an operating system error.
+    #[cfg(feature = "std")]
+    fn raw_os_error(&self) -> Option<i32>
+    where
+        Self: AsErrorSource + Sized,
+    {
+        self.find_source::<std::io::Error>()
+            .and_then(std::io::Error::raw_os_error)
+    }
+
+    /// Returns the Windows `HRESULT` of the first error in the chain
+    /// of sources that has one, skipping the current error.
+    ///
+    /// See [`windows::hresult`] for the errors that provide an
+    /// `HRESULT`.
+    #[cfg(feature = "windows")]
+    fn hresult(&self) -> Option<windows_result_crate::HRESULT>
+    where
+        Self: AsErrorSource + Sized,
+    {
+        self.as_error_source()
+            .source()
+            .and_then(crate::windows::hresult)
+    }
+
+    /// Returns the key-value pairs stored in the error's [`Metadata`]
+    /// field, if it has one. Pairs are added using
+    /// [`ErrorMetadata::add_context`].
+    #[cfg(feature = "std")]
+    fn metadata(&self) -> Option<&Metadata> {
+        None
+    }
+
+    /// Returns the process exit code that should be used when this
+    /// error ends the program, as set by the
+    /// [`#[snafu(exit_code)]`](Snafu#controlling-exit-codes)
+    /// attribute.
+    ///
+    /// [`Report`] uses this code, falling back to `1` when none is
+    /// set.
+    fn exit_code(&self) -> Option<u8> {
+        None
+    }
 }
 
-impl<'a, E> ErrorCompat for &'a E
+impl<E> ErrorCompat for &E
 where
-    E: ErrorCompat,
+    E: ErrorCompat + ?Sized,
 {
     fn backtrace(&self) -> Option<&Backtrace> {
         (**self).backtrace()
     }
+
+    #[cfg(feature = "std")]
+    fn metadata(&self) -> Option<&Metadata> {
+        (**self).metadata()
+    }
+
+    fn exit_code(&self) -> Option<u8> {
+        (**self).exit_code()
+    }
 }
 
 #[cfg(any(feature = "std", test))]
 impl<E> ErrorCompat for Box<E>
 where
-    E: ErrorCompat,
+    E: ErrorCompat + ?Sized,
 {
     fn backtrace(&self) -> Option<&Backtrace> {
         (**self).backtrace()
     }
+
+    #[cfg(feature = "std")]
+    fn metadata(&self) -> Option<&Metadata> {
+        (**self).metadata()
+    }
+
+    fn exit_code(&self) -> Option<u8> {
+        (**self).exit_code()
+    }
+}
+
+/// An error that also implements [`ErrorCompat`], suitable for use
+/// as a trait object.
+///
+/// Reporting code can accept a `&dyn CompatError` or a `Box<dyn
+/// CompatError>` instead of being generic over every concrete error
+/// type. This trait is implemented for all errors that implement
+/// both traits.
+///
+/// ```rust
+/// use snafu::{prelude::*, Backtrace, ChainCompat, CompatError, ErrorCompat};
+///
+/// #[derive(Debug, Snafu)]
+/// struct ConfigError {
+///     backtrace: Backtrace,
+/// }
+///
+/// fn report(error: &dyn CompatError) -> usize {
+///     let has_backtrace = ErrorCompat::backtrace(error).is_some();
+///     assert!(has_backtrace);
+///     ChainCompat::new(error.as_error()).count()
+/// }
+///
+/// let error: Box<dyn CompatError> = Box::new(ConfigSnafu.build());
+/// assert_eq!(report(&*error), 1);
+/// ```
+pub trait CompatError: Error + ErrorCompat {
+    /// Returns the error as an [`Error`] trait object.
+    fn as_error(&self) -> &(dyn Error + 'static);
+
+    /// Returns the error as an [`ErrorCompat`] trait object.
+    fn as_error_compat(&self) -> &dyn ErrorCompat;
+}
+
+impl<E> CompatError for E
+where
+    E: Error + ErrorCompat + 'static,
+{
+    fn as_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+
+    fn as_error_compat(&self) -> &dyn ErrorCompat {
+        self
+    }
+}
+
+/// A second, operator-facing message for an error.
+///
+/// The [`Display`](core::fmt::Display) implementation of an error is
+/// often shown to end users and should not reveal internal details.
+/// Errors deriving [`Snafu`] implement this trait, using the format
+/// given by
+/// [`#[snafu(display(internal(...)))]`][Snafu#showing-a-different-message-to-operators]
+/// when present and the regular message otherwise. Use
+/// [`display_internal`][Self::display_internal] to format it, such as
+/// when writing logs.
+///
+/// ```rust
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("Could not sign in"))]
+/// #[snafu(display(internal("Password hash mismatch for user {}", user_id)))]
+/// struct LoginError {
+///     user_id: u32,
+/// }
+///
+/// let error = LoginSnafu { user_id: 42u32 }.build();
+/// assert_eq!(error.to_string(), "Could not sign in");
+/// assert_eq!(
+///     error.display_internal().to_string(),
+///     "Password hash mismatch for user 42",
+/// );
+/// ```
+pub trait FormatInternal: core::fmt::Display {
+    /// Formats the operator-facing message. Defaults to the
+    /// [`Display`](core::fmt::Display) implementation.
+    fn fmt_internal(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+
+    /// Returns a value whose [`Display`](core::fmt::Display)
+    /// implementation shows the operator-facing message.
+    fn display_internal(&self) -> DisplayInternal<'_, Self>
+    where
+        Self: Sized,
+    {
+        DisplayInternal(self)
+    }
+}
+
+impl<E> FormatInternal for &E
+where
+    E: FormatInternal + ?Sized,
+{
+    fn fmt_internal(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt_internal(f)
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<E> FormatInternal for Box<E>
+where
+    E: FormatInternal + ?Sized,
+{
+    fn fmt_internal(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt_internal(f)
+    }
+}
+
+/// Shows the operator-facing message of an error.
+///
+/// Created by [`FormatInternal::display_internal`].
+#[derive(Debug)]
+pub struct DisplayInternal<'a, E: ?Sized>(&'a E);
+
+impl<E> core::fmt::Display for DisplayInternal<'_, E>
+where
+    E: FormatInternal + ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt_internal(f)
+    }
 }
 
 /// Converts the receiver into an [`Error`][] trait object, suitable
@@ -1019,6 +1913,131 @@ where
 
     /// Combine the information to produce the error
     fn into_error(self, source: Self::Source) -> E;
+
+    /// Like [`into_error`](IntoError::into_error), also recording how
+    /// long the failing operation ran in any [`Timing`] fields of the
+    /// error. Used by [`timed_context`].
+    #[cfg(feature = "std")]
+    fn into_error_with_timing(self, source: Self::Source, timing: Timing) -> E
+    where
+        Self: Sized,
+    {
+        let _ = timing;
+        self.into_error(source)
+    }
+}
+
+/// An object-safe form of [`IntoError`], allowing context selectors
+/// of different types to be stored and applied later.
+///
+/// Every context selector implements this trait. Storing a selector
+/// beyond the current scope requires the values given to it to be
+/// owned, such as a `String` instead of a borrowed `&str`. A boxed
+/// selector can be used anywhere a context selector is expected.
+///
+/// ```rust
+/// use snafu::{prelude::*, DelayedContext};
+/// use std::{collections::HashMap, io};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not read {}", path))]
+///     Read { path: String, source: io::Error },
+///
+///     #[snafu(display("Could not write {} bytes", len))]
+///     Write { len: usize, source: io::Error },
+/// }
+///
+/// type Pending = HashMap<u32, Box<dyn DelayedContext<io::Error, Error>>>;
+///
+/// let mut pending = Pending::new();
+/// pending.insert(1, Box::new(ReadSnafu { path: String::from("/tmp/a") }));
+/// pending.insert(2, Box::new(WriteSnafu { len: 42_usize }));
+///
+/// // Later, when the result of operation 2 arrives...
+/// let result: io::Result<()> = Err(io::ErrorKind::BrokenPipe.into());
+/// let context = pending.remove(&2).expect("Operation was not pending");
+/// let error = result.context(context).unwrap_err();
+/// assert_eq!(error.to_string(), "Could not write 42 bytes");
+/// ```
+#[cfg(any(feature = "std", test))]
+pub trait DelayedContext<S, E>
+where
+    E: Error + ErrorCompat,
+{
+    /// Combine the information to produce the error
+    fn into_error_boxed(self: Box<Self>, source: S) -> E;
+
+    /// Like [`into_error_boxed`](DelayedContext::into_error_boxed),
+    /// also recording how long the failing operation ran.
+    #[cfg(feature = "std")]
+    fn into_error_boxed_with_timing(self: Box<Self>, source: S, timing: Timing) -> E;
+}
+
+#[cfg(any(feature = "std", test))]
+impl<C, S, E> DelayedContext<S, E> for C
+where
+    C: IntoError<E, Source = S>,
+    E: Error + ErrorCompat,
+{
+    fn into_error_boxed(self: Box<Self>, source: S) -> E {
+        (*self).into_error(source)
+    }
+
+    #[cfg(feature = "std")]
+    fn into_error_boxed_with_timing(self: Box<Self>, source: S, timing: Timing) -> E {
+        (*self).into_error_with_timing(source, timing)
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<'a, S, E> IntoError<E> for Box<dyn DelayedContext<S, E> + 'a>
+where
+    E: Error + ErrorCompat,
+{
+    type Source = S;
+
+    fn into_error(self, source: S) -> E {
+        self.into_error_boxed(source)
+    }
+
+    #[cfg(feature = "std")]
+    fn into_error_with_timing(self, source: S, timing: Timing) -> E {
+        self.into_error_boxed_with_timing(source, timing)
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<'a, S, E> IntoError<E> for Box<dyn DelayedContext<S, E> + Send + 'a>
+where
+    E: Error + ErrorCompat,
+{
+    type Source = S;
+
+    fn into_error(self, source: S) -> E {
+        self.into_error_boxed(source)
+    }
+
+    #[cfg(feature = "std")]
+    fn into_error_with_timing(self, source: S, timing: Timing) -> E {
+        self.into_error_boxed_with_timing(source, timing)
+    }
+}
+
+/// Builds an error from a fieldless code, as used by
+/// [`ensure_code!`].
+///
+/// Every type can be built from itself, so a code can be used
+/// directly as an error.
+pub trait FromCode<C> {
+    /// Create the error corresponding to the code
+    fn from_code(code: C) -> Self;
+}
+
+impl<C> FromCode<C> for C {
+    fn from_code(code: C) -> Self {
+        code
+    }
 }
 
 /// Takes a string message and builds the corresponding error.
@@ -1035,6 +2054,86 @@ pub trait FromString {
 
     /// Wrap an existing error with the given string
     fn with_source(source: Self::Source, message: String) -> Self;
+
+    /// Create a brand new error from the given string and the
+    /// details captured by [`whatever!`].
+    ///
+    /// The default implementation ignores the details.
+    fn without_source_and_details(message: String, details: WhateverDetails) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = details;
+        Self::without_source(message)
+    }
+
+    /// Wrap an existing error with the given string and the details
+    /// captured by [`whatever!`].
+    ///
+    /// The default implementation ignores the details.
+    fn with_source_and_details(
+        source: Self::Source,
+        message: String,
+        details: WhateverDetails,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = details;
+        Self::with_source(source, message)
+    }
+}
+
+/// Information about an error created by [`whatever!`] beyond its
+/// message and source.
+///
+/// Errors using
+/// [`#[snafu(whatever)]`](Snafu#controlling-stringly-typed-errors)
+/// receive these details when they have fields of the types
+/// `snafu::Location`, `snafu::Fields`, or `snafu::Metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg(any(feature = "std", test))]
+pub struct WhateverDetails {
+    location: Location,
+    fields: Fields,
+}
+
+#[cfg(any(feature = "std", test))]
+impl WhateverDetails {
+    /// Creates details for an error created at the given location
+    /// without any key-value pairs.
+    pub fn new(location: Location) -> Self {
+        WhateverDetails {
+            location,
+            fields: Fields::new(),
+        }
+    }
+
+    /// Adds a key-value pair.
+    pub fn with(mut self, key: &'static str, value: impl Into<FieldValue>) -> Self {
+        self.fields.insert(key, value);
+        self
+    }
+
+    /// Where the error was created.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// The key-value pairs provided when the error was created.
+    pub fn fields(&self) -> &Fields {
+        &self.fields
+    }
+
+    /// The key-value pairs provided when the error was created, with
+    /// each value converted to a string.
+    pub fn metadata(&self) -> Metadata {
+        let mut metadata = Metadata::new();
+        for (key, value) in &self.fields {
+            metadata.insert(key, value.to_string());
+        }
+        metadata
+    }
 }
 
 /// Construct a backtrace, allowing it to be optional.
@@ -1087,6 +2186,21 @@ impl GenerateBacktrace for Option<Backtrace> {
     }
 }
 
+/// Share a backtrace between clones of an error.
+#[cfg(any(feature = "std", test))]
+impl<T> GenerateBacktrace for std::sync::Arc<T>
+where
+    T: GenerateBacktrace,
+{
+    fn generate() -> Self {
+        std::sync::Arc::new(T::generate())
+    }
+
+    fn as_backtrace(&self) -> Option<&Backtrace> {
+        (**self).as_backtrace()
+    }
+}
+
 #[cfg(feature = "backtraces-impl-backtrace-crate")]
 impl GenerateBacktrace for Backtrace {
     fn generate() -> Self {
@@ -1152,20 +2266,44 @@ impl GenerateBacktrace for Backtrace {
 ///
 /// When the standard library stabilizes backtrace support, this
 /// behavior may change.
+///
+/// Only errors that are `Send` and `Sync` can be used as the source,
+/// allowing `Whatever` itself to be sent between threads. To accept
+/// any error instead, create a custom error type using
+/// [`#[snafu(whatever)]`](Snafu#controlling-stringly-typed-errors).
 #[derive(Debug, Snafu)]
 #[snafu(crate_root(crate))]
 #[snafu(whatever)]
 #[snafu(display("{}", message))]
 #[cfg(any(feature = "std", test))]
 pub struct Whatever {
-    #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
-    source: Option<Box<dyn std::error::Error>>,
+    #[snafu(source(from(Box<dyn std::error::Error + Send + Sync>, Some)))]
+    source: Option<Box<dyn std::error::Error + Send + Sync>>,
     message: String,
+    fields: snafu::Fields,
     backtrace: Backtrace,
 }
 
 #[cfg(any(feature = "std", test))]
 impl Whatever {
+    /// The key-value pairs provided to [`whatever!`] when this error
+    /// was created.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, FieldValue, Whatever};
+    ///
+    /// fn connect(attempt: u8) -> Result<(), Whatever> {
+    ///     whatever!("Failed to connect"; attempt = attempt, retry = true);
+    /// }
+    ///
+    /// let error = connect(3).unwrap_err();
+    /// assert_eq!(error.fields().get("attempt"), Some(&FieldValue::U64(3)));
+    /// assert_eq!(error.fields().get("retry"), Some(&FieldValue::Bool(true)));
+    /// ```
+    pub fn fields(&self) -> &Fields {
+        &self.fields
+    }
+
     /// Gets the backtrace from the deepest `Whatever` error. If none
     /// of the underlying errors are `Whatever`, returns the backtrace
     /// from when this instance was created.
@@ -1182,4 +2320,28 @@ impl Whatever {
 
         Some(best_backtrace)
     }
+
+    /// Gets the [`ErrorKind`](std::io::ErrorKind) of the first
+    /// [`io::Error`](std::io::Error) among the underlying errors,
+    /// allowing callers to react to specific I/O failures without
+    /// downcasting.
+    ///
+    /// This is the same as [`ErrorCompat::io_error_kind`], available
+    /// without importing the trait.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Whatever};
+    /// use std::io;
+    ///
+    /// fn load() -> Result<String, Whatever> {
+    ///     std::fs::read_to_string("/this/does/not/exist")
+    ///         .whatever_context("Could not load the configuration")
+    /// }
+    ///
+    /// let e = load().unwrap_err();
+    /// assert_eq!(e.io_error_kind(), Some(io::ErrorKind::NotFound));
+    /// ```
+    pub fn io_error_kind(&self) -> Option<std::io::ErrorKind> {
+        ErrorCompat::io_error_kind(self)
+    }
 }
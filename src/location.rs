@@ -0,0 +1,73 @@
+use core::fmt;
+
+/// A position in the source code where an error was created.
+///
+/// When a field is declared with the type `snafu::Location`, it is
+/// not part of the context selector. The [`whatever!`](crate::whatever)
+/// macro fills it in with the position of the macro call; other ways
+/// of constructing the error cannot know the calling position and
+/// use [`Location::unknown`]. Only errors marked with
+/// `#[snafu(whatever)]` may have a location field.
+///
+/// When the type is imported or comes from a renamed crate, mark the
+/// field with `#[snafu(implicit)]`.
+///
+/// ```rust
+/// use snafu::{prelude::*, Location};
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(whatever, display("{}", message))]
+/// struct Error {
+///     message: String,
+///     location: snafu::Location,
+/// }
+///
+/// fn example() -> Result<(), Error> {
+///     whatever!("Something went wrong");
+/// }
+///
+/// let error = example().unwrap_err();
+/// assert_eq!(error.location.file, file!());
+/// assert_ne!(error.location, Location::unknown());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    /// The file where the error was created
+    pub file: &'static str,
+    /// The line where the error was created
+    pub line: u32,
+    /// The column where the error was created
+    pub column: u32,
+}
+
+impl Location {
+    /// Creates a location from its parts, as returned by the
+    /// [`file!`], [`line!`], and [`column!`] macros.
+    pub fn new(file: &'static str, line: u32, column: u32) -> Self {
+        Location { file, line, column }
+    }
+
+    /// The location used when the position is not known.
+    pub fn unknown() -> Self {
+        Location::new("<unknown>", 0, 0)
+    }
+
+    /// Returns true if this is the [unknown](Location::unknown)
+    /// location.
+    pub fn is_unknown(&self) -> bool {
+        *self == Location::unknown()
+    }
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location::unknown()
+    }
+}
+
+/// Shows the location as `file:line:column`.
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
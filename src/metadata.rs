@@ -0,0 +1,143 @@
+use std::{fmt, slice};
+
+/// A small, ordered collection of key-value pairs that can be added to
+/// an error after it has been constructed.
+///
+/// When a field is declared with the type `snafu::Metadata`, it is
+/// not part of the context selector; it starts empty and is filled
+/// using [`ErrorMetadata::add_context`].
+///
+/// ```rust
+/// use snafu::{prelude::*, ErrorMetadata};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not load user {}", id))]
+///     LoadUser { id: u32, metadata: snafu::Metadata },
+/// }
+///
+/// let mut error = LoadUserSnafu { id: 42u32 }.build();
+/// error.add_context("request_id", "abc-123".to_string());
+///
+/// if let Error::LoadUser { metadata, .. } = &error {
+///     assert_eq!(metadata.get("request_id"), Some("abc-123"));
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    entries: Vec<(&'static str, String)>,
+}
+
+impl Metadata {
+    /// Creates an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key-value pair. If the key is already present, its value
+    /// is replaced but it keeps its original position.
+    pub fn insert(&mut self, key: &'static str, value: String) {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+
+    /// The value associated with the key, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The number of key-value pairs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the key-value pairs in the order they were first
+    /// inserted.
+    pub fn iter(&self) -> MetadataIter<'_> {
+        MetadataIter(self.entries.iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a Metadata {
+    type Item = (&'static str, &'a str);
+    type IntoIter = MetadataIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Shows the key-value pairs as `key: value`, separated by commas.
+impl fmt::Display for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the key-value pairs of a [`Metadata`].
+pub struct MetadataIter<'a>(slice::Iter<'a, (&'static str, String)>);
+
+impl<'a> Iterator for MetadataIter<'a> {
+    type Item = (&'static str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (*k, v.as_str()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl fmt::Debug for MetadataIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MetadataIter").finish()
+    }
+}
+
+/// Errors that key-value pairs can be attached to after they have
+/// been constructed.
+///
+/// `#[derive(Snafu)]` implements this trait for structs with a
+/// [`Metadata`] field and for enums where every variant has one, so
+/// a pair is never discarded. The pairs can be read back using
+/// [`ErrorCompat::metadata`](crate::ErrorCompat::metadata).
+pub trait ErrorMetadata {
+    /// Attaches a key-value pair to the error, such as a request ID
+    /// that is only known to a caller further up the stack.
+    fn add_context(&mut self, key: &'static str, value: String);
+}
+
+impl<E> ErrorMetadata for &mut E
+where
+    E: ErrorMetadata + ?Sized,
+{
+    fn add_context(&mut self, key: &'static str, value: String) {
+        (**self).add_context(key, value)
+    }
+}
+
+impl<E> ErrorMetadata for Box<E>
+where
+    E: ErrorMetadata + ?Sized,
+{
+    fn add_context(&mut self, key: &'static str, value: String) {
+        (**self).add_context(key, value)
+    }
+}
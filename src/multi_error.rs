@@ -0,0 +1,190 @@
+use crate::{ErrorCompat, IntoError};
+use std::{fmt, vec};
+
+/// Additions to iterators of [`Result`](std::result::Result)s.
+pub trait ResultIteratorExt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Consumes the iterator, separating the successful values from
+    /// the errors. Each error is recorded along with the index of the
+    /// item that produced it.
+    ///
+    /// ```rust
+    /// use snafu::{ResultIteratorExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// #[snafu(display("{} is not even", value))]
+    /// struct NotEvenError {
+    ///     value: i32,
+    /// }
+    ///
+    /// fn check(value: i32) -> Result<i32, NotEvenError> {
+    ///     if value % 2 == 0 {
+    ///         Ok(value)
+    ///     } else {
+    ///         NotEvenSnafu { value }.fail()
+    ///     }
+    /// }
+    ///
+    /// let (evens, errors) = vec![2, 3, 4, 5].into_iter().map(check).collect_results();
+    /// assert_eq!(evens, [2, 4]);
+    /// assert_eq!(errors.len(), 2);
+    ///
+    /// let indices: Vec<_> = errors.iter().map(|(index, _)| index).collect();
+    /// assert_eq!(indices, [1, 3]);
+    /// ```
+    fn collect_results(self) -> (Vec<T>, MultiError<E>) {
+        let mut values = Vec::new();
+        let mut errors = MultiError::new();
+
+        for (index, result) in self.enumerate() {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push(index, error),
+            }
+        }
+
+        (values, errors)
+    }
+
+    /// Consumes the iterator, separating the successful values from
+    /// the errors. The closure is called with the index of each item
+    /// that failed and must return a context selector, which is then
+    /// used to wrap the error.
+    ///
+    /// ```rust
+    /// use snafu::{ResultIteratorExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     #[snafu(display("Could not parse line {}", line))]
+    ///     ParseLine {
+    ///         line: usize,
+    ///         source: std::num::ParseIntError,
+    ///     },
+    /// }
+    ///
+    /// let input = "1\ntwo\n3";
+    /// let (numbers, errors) = input
+    ///     .lines()
+    ///     .map(str::parse::<i32>)
+    ///     .collect_results_with_context(|index| ParseLineSnafu { line: index + 1 });
+    ///
+    /// assert_eq!(numbers, [1, 3]);
+    /// assert_eq!(errors.to_string(), "1 error occurred");
+    ///
+    /// let (_, error) = errors.iter().next().unwrap();
+    /// assert_eq!(error.to_string(), "Could not parse line 2");
+    /// ```
+    fn collect_results_with_context<F, C, E2>(self, mut context: F) -> (Vec<T>, MultiError<E2>)
+    where
+        F: FnMut(usize) -> C,
+        C: IntoError<E2, Source = E>,
+        E2: std::error::Error + ErrorCompat,
+    {
+        let mut values = Vec::new();
+        let mut errors = MultiError::new();
+
+        for (index, result) in self.enumerate() {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push(index, context(index).into_error(error)),
+            }
+        }
+
+        (values, errors)
+    }
+}
+
+impl<I, T, E> ResultIteratorExt<T, E> for I where I: Iterator<Item = Result<T, E>> {}
+
+/// A collection of errors, each tagged with the index of the item
+/// that produced it.
+///
+/// When used as an error, the first error in the collection is
+/// reported as the source.
+///
+/// Usually created by [`ResultIteratorExt::collect_results`].
+#[derive(Debug)]
+pub struct MultiError<E> {
+    errors: Vec<(usize, E)>,
+}
+
+impl<E> MultiError<E> {
+    /// Creates an empty collection of errors.
+    pub fn new() -> Self {
+        MultiError { errors: Vec::new() }
+    }
+
+    /// Records the error produced by the item at `index`.
+    pub fn push(&mut self, index: usize, error: E) {
+        self.errors.push((index, error));
+    }
+
+    /// The number of errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns true if there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Iterates over the errors along with the index of the item that
+    /// produced each one.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &E)> {
+        self.errors.iter().map(|(index, error)| (*index, error))
+    }
+
+    /// Returns `Ok` if there are no errors, otherwise returns the
+    /// collection itself as the error.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Returns the errors along with the index of the item that
+    /// produced each one.
+    pub fn into_inner(self) -> Vec<(usize, E)> {
+        self.errors
+    }
+}
+
+impl<E> Default for MultiError<E> {
+    fn default() -> Self {
+        MultiError::new()
+    }
+}
+
+impl<E> IntoIterator for MultiError<E> {
+    type Item = (usize, E);
+    type IntoIter = vec::IntoIter<(usize, E)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<E> fmt::Display for MultiError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.len() {
+            1 => write!(f, "1 error occurred"),
+            n => write!(f, "{} errors occurred", n),
+        }
+    }
+}
+
+impl<E> std::error::Error for MultiError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.errors
+            .first()
+            .map(|(_, error)| error as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl<E> ErrorCompat for MultiError<E> {}
@@ -0,0 +1,65 @@
+//! Recording errors on [OpenTelemetry][opentelemetry-crate] spans.
+//!
+//! [`record_error`] marks the active span as failed and describes the
+//! error using the `exception.message` and `exception.type`
+//! attributes from OpenTelemetry's semantic conventions. The
+//! [`#[snafu(opentelemetry)]`](crate::Snafu#recording-errors-on-opentelemetry-spans)
+//! attribute does the same whenever an error is constructed, using the
+//! name of the variant as the type and including selected context
+//! fields.
+//!
+//! ```rust
+//! use snafu::prelude::*;
+//!
+//! #[derive(Debug, Snafu)]
+//! #[snafu(display("The cache is cold"))]
+//! struct ColdCacheError;
+//!
+//! fn handle_request() {
+//!     if let Err(e) = ColdCacheSnafu.fail::<()>() {
+//!         snafu::opentelemetry::record_error(&e);
+//!     }
+//! }
+//! # handle_request();
+//! ```
+//!
+//! [opentelemetry-crate]: https://crates.io/crates/opentelemetry
+
+use core::fmt;
+use opentelemetry_crate::{
+    trace::{get_active_span, Status},
+    KeyValue,
+};
+
+/// Records the error on the active span, using the name of the
+/// error's type as the `exception.type` attribute.
+pub fn record_error<E>(error: &E)
+where
+    E: fmt::Display + ?Sized,
+{
+    record_error_as(error, core::any::type_name::<E>(), None)
+}
+
+/// Records the error on the active span with the given
+/// `exception.type` and additional attributes.
+///
+/// The span's status is set to an error described by the error's
+/// message, which is also recorded as the `exception.message`
+/// attribute. Nothing is recorded when there is no active span.
+pub fn record_error_as<E, I>(error: &E, exception_type: &'static str, attributes: I)
+where
+    E: fmt::Display + ?Sized,
+    I: IntoIterator<Item = KeyValue>,
+{
+    get_active_span(|span| {
+        if !span.is_recording() {
+            return;
+        }
+
+        let message = error.to_string();
+        span.set_status(Status::error(message.clone()));
+        span.set_attribute(KeyValue::new("exception.message", message));
+        span.set_attribute(KeyValue::new("exception.type", exception_type));
+        span.set_attributes(attributes);
+    })
+}
@@ -0,0 +1,117 @@
+//! Formatting paths in error messages.
+//!
+//! Paths are one of the most common pieces of context attached to an
+//! error. [`Path::display`] replaces anything that is not valid UTF-8
+//! with U+FFFD (�), so two different paths can produce the same
+//! message. [`display`] keeps those parts readable by escaping them
+//! instead, while valid paths are shown unchanged.
+//!
+//! ```rust
+//! use snafu::{path, prelude::*};
+//! use std::path::PathBuf;
+//!
+//! #[derive(Debug, Snafu)]
+//! #[snafu(display("Could not read {}", path::display(path)))]
+//! struct ReadError {
+//!     path: PathBuf,
+//!     source: std::io::Error,
+//! }
+//!
+//! fn read(path: &std::path::Path) -> Result<String, ReadError> {
+//!     std::fs::read_to_string(path).context(ReadSnafu { path })
+//! }
+//! ```
+//!
+//! Context selectors convert their fields using [`Into`], so a
+//! `PathBuf` field accepts a `&Path`, a `&str`, or a `String`. Paths
+//! from the [camino] crate implement `AsRef<Path>` and can be passed to
+//! [`display`] directly.
+//!
+//! [camino]: https://docs.rs/camino
+
+use std::{fmt, path::Path};
+
+/// Returns a value that formats the path for an error message.
+///
+/// Parts of the path that are not valid Unicode are shown as escapes:
+/// each byte as `\xFF` on Unix and each unpaired surrogate as
+/// `\u{D800}` on Windows. On other platforms, they are replaced as
+/// with [`Path::display`].
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// let path = Path::new("/etc/hosts");
+/// assert_eq!(snafu::path::display(path).to_string(), "/etc/hosts");
+/// ```
+pub fn display<P>(path: &P) -> Display<'_>
+where
+    P: AsRef<Path> + ?Sized,
+{
+    Display {
+        path: path.as_ref(),
+    }
+}
+
+/// Formats a path for an error message.
+///
+/// Created by [`display`].
+#[derive(Debug, Copy, Clone)]
+pub struct Display<'a> {
+    path: &'a Path,
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path.to_str() {
+            Some(s) => f.write_str(s),
+            None => write_escaped(self.path, f),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn write_escaped(path: &Path, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut bytes = path.as_os_str().as_bytes();
+
+    while !bytes.is_empty() {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => return f.write_str(valid),
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                // The prefix was just validated
+                f.write_str(std::str::from_utf8(valid).unwrap_or_default())?;
+
+                let invalid_len = e.error_len().unwrap_or(rest.len());
+                let (invalid, rest) = rest.split_at(invalid_len);
+                for b in invalid {
+                    write!(f, "\\x{:02X}", b)?;
+                }
+                bytes = rest;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn write_escaped(path: &Path, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use std::os::windows::ffi::OsStrExt;
+
+    for c in std::char::decode_utf16(path.as_os_str().encode_wide()) {
+        match c {
+            Ok(c) => write!(f, "{}", c)?,
+            Err(e) => write!(f, "\\u{{{:X}}}", e.unpaired_surrogate())?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn write_escaped(path: &Path, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Display::fmt(&path.display(), f)
+}
@@ -0,0 +1,888 @@
+//! Reporting errors to the user.
+//!
+//! See [`Report`] for details. Applications can change how every
+//! report is shown by [installing](install) a [`ReportRenderer`].
+
+use crate::{Backtrace, CleanedErrorText, ErrorCompat, Metadata, Snafu, Whatever};
+use std::{
+    env,
+    error::Error,
+    fmt, iter, ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Decides whether an error in the chain is shown by a [`Report`].
+///
+/// The function receives each error along with its depth in the
+/// chain, where the outermost error has a depth of 0.
+pub type ReportFilter = fn(&(dyn Error + 'static), usize) -> bool;
+
+/// Formats an error and its chain of sources for a person to read.
+///
+/// `Report` implements [`Debug`](fmt::Debug) by showing the message of
+/// each error in the chain instead of the structure of the error
+/// type. This makes it suitable as the error type of `main`:
+///
+/// ```rust,no_run
+/// use snafu::{prelude::*, Report};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not load the configuration from {}", path))]
+///     LoadConfig { path: String, source: std::io::Error },
+/// }
+///
+/// fn main() -> Result<(), Report<Error>> {
+///     let path = "config.toml";
+///     std::fs::read_to_string(path).context(LoadConfigSnafu { path })?;
+///     Ok(())
+/// }
+/// ```
+///
+/// Which prints something like this when the file is missing:
+///
+/// ```text
+/// Error: Could not load the configuration from config.toml
+/// Caused by: No such file or directory (os error 2)
+/// ```
+///
+/// When a message ends with the message of its source, the repeated
+/// text is removed using [`CleanedErrorText`]. Key-value pairs from
+/// the error's [`Metadata`] and from any [`Whatever`] in the chain are
+/// shown after the message of the corresponding error.
+///
+/// ## Backtraces
+///
+/// When backtraces are enabled and the error has one, it is shown
+/// last, but only if the `SNAFU_BACKTRACE` environment variable, or
+/// `RUST_BACKTRACE` when that is unset, is set to a value other than
+/// `0`. This keeps backtraces out of the output seen by users while
+/// letting developers turn them on. [`Report::show_backtrace`]
+/// overrides the environment.
+///
+/// ## Colors
+///
+/// With the `color` feature, the [`DefaultRenderer`] styles the
+/// report using ANSI escape codes when standard error is a terminal
+/// and the `NO_COLOR` environment variable is unset or empty.
+/// [`Report::colorize`] overrides this detection.
+///
+/// ## Exit codes
+///
+/// Returning a `Result` from `main` always exits with the code 1 on
+/// failure. [`Report::exit`] instead exits with the code set by
+/// [`#[snafu(exit_code)]`](crate::Snafu#controlling-exit-codes). With
+/// the `rust_1_61` feature, `Report` implements
+/// [`Termination`](std::process::Termination), so `main` can return
+/// it directly.
+///
+/// ## Hiding errors
+///
+/// Some errors in a chain, such as those added by middleware, are
+/// not useful to the person reading the report. A [`ReportFilter`]
+/// hides them. Formatting with the alternate flag (`{:#}` or
+/// `{:#?}`) ignores the filter, showing every error for debugging.
+///
+/// ```rust
+/// use snafu::{prelude::*, Report};
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("Middleware failed"))]
+/// struct MiddlewareError {
+///     source: std::io::Error,
+/// }
+///
+/// let io = std::io::Error::new(std::io::ErrorKind::Other, "Disk is full");
+/// let error = Err::<(), _>(io).context(MiddlewareSnafu).unwrap_err();
+///
+/// let report = Report::from_error(error)
+///     .with_filter(|e, _depth| !e.is::<MiddlewareError>());
+///
+/// assert_eq!(report.to_string(), "Disk is full");
+/// assert_eq!(
+///     format!("{:#}", report),
+///     "Middleware failed\nCaused by: Disk is full",
+/// );
+/// ```
+pub struct Report<E> {
+    result: Result<(), E>,
+    filter: Option<ReportFilter>,
+    show_backtrace: Option<bool>,
+    #[cfg(feature = "color")]
+    colorize: Option<bool>,
+}
+
+impl<E> Report<E> {
+    fn new(result: Result<(), E>) -> Self {
+        Report {
+            result,
+            filter: None,
+            show_backtrace: None,
+            #[cfg(feature = "color")]
+            colorize: None,
+        }
+    }
+
+    /// Creates a report for the error.
+    pub fn from_error(error: E) -> Self {
+        Report::new(Err(error))
+    }
+
+    /// Creates a report without an error; it renders as nothing.
+    pub fn ok() -> Self {
+        Report::new(Ok(()))
+    }
+
+    /// Runs the function, creating a report from its result.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Report, Whatever};
+    ///
+    /// let report = Report::capture(|| -> Result<(), Whatever> {
+    ///     whatever!("Something went wrong");
+    /// });
+    ///
+    /// assert_eq!(report.to_string(), "Something went wrong");
+    /// ```
+    pub fn capture(body: impl FnOnce() -> Result<(), E>) -> Self {
+        Report::new(body())
+    }
+
+    /// Hides the errors in the chain for which the filter returns
+    /// `false`, unless the report is formatted with the alternate
+    /// flag.
+    pub fn with_filter(mut self, filter: ReportFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Shows or hides the error's backtrace regardless of the
+    /// `SNAFU_BACKTRACE` and `RUST_BACKTRACE` environment variables.
+    pub fn show_backtrace(mut self, show: bool) -> Self {
+        self.show_backtrace = Some(show);
+        self
+    }
+
+    /// Enables or disables colors regardless of whether standard
+    /// error is a terminal and of the `NO_COLOR` environment variable.
+    #[cfg(feature = "color")]
+    pub fn colorize(mut self, colorize: bool) -> Self {
+        self.colorize = Some(colorize);
+        self
+    }
+
+    /// The error being reported, if any.
+    pub fn error(&self) -> Option<&E> {
+        self.result.as_ref().err()
+    }
+
+    /// Converts the report back into the result it was created from.
+    pub fn into_result(self) -> Result<(), E> {
+        self.result
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ErrorCompat,
+{
+    /// The code the process should exit with: `0` without an error,
+    /// otherwise the error's [`ErrorCompat::exit_code`] or `1` if it
+    /// has none.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Report};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     #[snafu(exit_code(66))]
+    ///     MissingInput,
+    ///     Unknown,
+    /// }
+    ///
+    /// assert_eq!(Report::from_error(MissingInputSnafu.build()).exit_code(), 66);
+    /// assert_eq!(Report::from_error(UnknownSnafu.build()).exit_code(), 1);
+    /// assert_eq!(Report::<Error>::ok().exit_code(), 0);
+    /// ```
+    pub fn exit_code(&self) -> u8 {
+        match &self.result {
+            Ok(()) => 0,
+            Err(e) => e.exit_code().unwrap_or(1),
+        }
+    }
+}
+
+impl<E> Report<E>
+where
+    E: Error + ErrorCompat + 'static,
+{
+    /// Prints the report to standard error and exits the process with
+    /// the report's [exit code](Report::exit_code).
+    ///
+    /// ```rust,no_run
+    /// use snafu::{prelude::*, Report, Whatever};
+    ///
+    /// fn main() {
+    ///     Report::capture(run).exit();
+    /// }
+    ///
+    /// fn run() -> Result<(), Whatever> {
+    ///     whatever!("Could not start the server");
+    /// }
+    /// ```
+    pub fn exit(self) -> ! {
+        let code = self.print_to_stderr();
+        std::process::exit(code.into())
+    }
+
+    fn print_to_stderr(&self) -> u8 {
+        if self.result.is_err() {
+            eprintln!("Error: {}", self);
+        }
+        self.exit_code()
+    }
+}
+
+/// Allows `main` to return a `Report`, exiting with the report's
+/// [exit code](Report::exit_code).
+///
+/// ```rust,no_run
+/// use snafu::{prelude::*, Report, Whatever};
+///
+/// fn main() -> Report<Whatever> {
+///     Report::capture(|| {
+///         whatever!("Could not start the server");
+///     })
+/// }
+/// ```
+#[cfg(feature = "rust_1_61")]
+#[allow(clippy::incompatible_msrv)] // Only available with the `rust_1_61` feature
+impl<E> std::process::Termination for Report<E>
+where
+    E: Error + ErrorCompat + 'static,
+{
+    fn report(self) -> std::process::ExitCode {
+        std::process::ExitCode::from(self.print_to_stderr())
+    }
+}
+
+impl<E> From<E> for Report<E>
+where
+    E: Error,
+{
+    fn from(error: E) -> Self {
+        Report::from_error(error)
+    }
+}
+
+#[cfg(feature = "unstable-provider-api")]
+impl<E> Report<E>
+where
+    E: Error,
+{
+    /// Converts into the standard library's
+    /// [`Report`](std::error::Report), or `None` if there is no error.
+    ///
+    /// The standard library's report shows each source on its own
+    /// line and finds the backtrace using the provider API. Whether the
+    /// backtrace is shown is decided as for this report; filters and
+    /// colors are not supported.
+    ///
+    /// ```rust
+    /// #![feature(error_generic_member_access)]
+    /// use snafu::{prelude::*, Report};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// #[snafu(display("Could not save the document"))]
+    /// struct SaveError {
+    ///     source: std::io::Error,
+    /// }
+    ///
+    /// let io = std::io::Error::new(std::io::ErrorKind::Other, "Disk is full");
+    /// let error = Err::<(), _>(io).context(SaveSnafu).unwrap_err();
+    ///
+    /// let report = Report::from_error(error).show_backtrace(false);
+    /// let report = report.into_std_report().unwrap();
+    ///
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "Could not save the document\n\nCaused by:\n      Disk is full",
+    /// );
+    /// ```
+    pub fn into_std_report(self) -> Option<std::error::Report<E>> {
+        let error = self.result.err()?;
+        let show_backtrace = self.show_backtrace.unwrap_or_else(backtrace_requested);
+
+        Some(
+            std::error::Report::new(error)
+                .pretty(true)
+                .show_backtrace(show_backtrace),
+        )
+    }
+}
+
+impl<E> Report<E>
+where
+    E: Error + ErrorCompat + 'static,
+{
+    /// Writes the report to any destination that accepts text, such as
+    /// a log file or a dialog box, using the same rendering as
+    /// `main`.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Report};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// #[snafu(display("Could not save the document"))]
+    /// struct SaveError {
+    ///     source: std::io::Error,
+    /// }
+    ///
+    /// let io = std::io::Error::new(std::io::ErrorKind::Other, "Disk is full");
+    /// let error = Err::<(), _>(io).context(SaveSnafu).unwrap_err();
+    ///
+    /// let mut dialog = String::from("Saving failed.\n\n");
+    /// Report::from_error(error).write_to(&mut dialog)?;
+    ///
+    /// assert_eq!(
+    ///     dialog,
+    ///     "Saving failed.\n\nCould not save the document\nCaused by: Disk is full",
+    /// );
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn write_to<W>(&self, w: &mut W) -> fmt::Result
+    where
+        W: fmt::Write + ?Sized,
+    {
+        write!(w, "{}", self)
+    }
+
+    /// Shows the report on multiple lines, as printed by `main`.
+    ///
+    /// This is the same as the [`Display`](fmt::Display)
+    /// implementation and is the counterpart of
+    /// [`to_compact_string`](Report::to_compact_string).
+    pub fn to_string_pretty(&self) -> String {
+        self.to_string()
+    }
+
+    /// Shows the report on a single line, such as `Could not save:
+    /// Disk is full`, which is suitable for log messages.
+    ///
+    /// The messages are separated by `: ` and the backtrace is not
+    /// included. An installed [`ReportRenderer`] can change this by
+    /// checking for [`ReportFormat::Compact`].
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Report};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// #[snafu(display("Could not save the document"))]
+    /// struct SaveError {
+    ///     source: std::io::Error,
+    /// }
+    ///
+    /// let io = std::io::Error::new(std::io::ErrorKind::Other, "Disk is full");
+    /// let error = Err::<(), _>(io).context(SaveSnafu).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     Report::from_error(error).to_compact_string(),
+    ///     "Could not save the document: Disk is full",
+    /// );
+    /// ```
+    pub fn to_compact_string(&self) -> String {
+        struct Compact<'a, E>(&'a Report<E>);
+
+        impl<E> fmt::Display for Compact<'_, E>
+        where
+            E: Error + ErrorCompat + 'static,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.render(f, ReportFormat::Compact)
+            }
+        }
+
+        Compact(self).to_string()
+    }
+
+    fn render(&self, f: &mut fmt::Formatter<'_>, format: ReportFormat) -> fmt::Result {
+        let error = match &self.result {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+
+        let filter = if f.alternate() { None } else { self.filter };
+
+        let chain = iter::successors(Some(error as &(dyn Error + 'static)), |&e| e.source());
+        let mut messages: Vec<_> = CleanedErrorText::new(error)
+            .zip(chain)
+            .enumerate()
+            .filter(|(depth, (_, e))| filter.map_or(true, |filter| filter(*e, *depth)))
+            .map(|(depth, ((_, text, _), e))| ReportMessage {
+                depth,
+                annotations: annotations(e, depth, error.metadata()),
+                text,
+            })
+            .collect();
+
+        // The outermost error is shown even if it is filtered so that
+        // the report is never empty.
+        if messages.is_empty() {
+            if let Some((_, text, _)) = CleanedErrorText::new(error).next() {
+                messages.push(ReportMessage {
+                    depth: 0,
+                    annotations: annotations(error, 0, error.metadata()),
+                    text,
+                });
+            }
+        }
+
+        #[cfg(any(
+            feature = "backtraces",
+            feature = "backtraces-impl-backtrace-crate",
+            feature = "unstable-backtraces-impl-std",
+        ))]
+        let backtrace = {
+            let show_backtrace = self.show_backtrace.unwrap_or_else(backtrace_requested);
+            ErrorCompat::backtrace(error).filter(|_| show_backtrace)
+        };
+        #[cfg(not(any(
+            feature = "backtraces",
+            feature = "backtraces-impl-backtrace-crate",
+            feature = "unstable-backtraces-impl-std",
+        )))]
+        let backtrace = None;
+
+        #[cfg(feature = "color")]
+        let color = self.colorize.unwrap_or_else(color_supported);
+        #[cfg(not(feature = "color"))]
+        let color = false;
+
+        let parts = ReportParts {
+            error,
+            messages,
+            backtrace,
+            color,
+            format,
+        };
+
+        renderer().render(&parts, f)
+    }
+}
+
+impl<E> fmt::Display for Report<E>
+where
+    E: Error + ErrorCompat + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f, ReportFormat::Full)
+    }
+}
+
+/// Shows the same output as [`Display`](fmt::Display) so that a
+/// `Report` returned from `main` is readable.
+impl<E> fmt::Debug for Report<E>
+where
+    E: Error + ErrorCompat + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(any(
+    feature = "backtraces",
+    feature = "backtraces-impl-backtrace-crate",
+    feature = "unstable-backtraces-impl-std",
+    feature = "unstable-provider-api",
+))]
+fn backtrace_requested() -> bool {
+    std::env::var_os("SNAFU_BACKTRACE")
+        .or_else(|| std::env::var_os("RUST_BACKTRACE"))
+        .map_or(false, |v| v != "0")
+}
+
+#[cfg(feature = "color")]
+fn color_supported() -> bool {
+    let disabled = std::env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty());
+    !disabled && atty::is(atty::Stream::Stderr)
+}
+
+// Only the outermost error's metadata is reachable; sources are only
+// known as `dyn Error`.
+fn annotations(
+    error: &(dyn Error + 'static),
+    depth: usize,
+    metadata: Option<&Metadata>,
+) -> Option<String> {
+    let metadata = metadata.filter(|m| depth == 0 && !m.is_empty());
+    let fields = error
+        .downcast_ref::<Whatever>()
+        .map(Whatever::fields)
+        .filter(|fields| !fields.is_empty());
+
+    match (metadata, fields) {
+        (Some(metadata), Some(fields)) => Some(format!("{}, {}", metadata, fields)),
+        (Some(metadata), None) => Some(metadata.to_string()),
+        (None, Some(fields)) => Some(fields.to_string()),
+        (None, None) => None,
+    }
+}
+
+/// The parts of a [`Report`] that a [`ReportRenderer`] arranges into
+/// the final output.
+///
+/// The messages have already been cleaned and filtered as described
+/// in [`Report`].
+pub struct ReportParts<'a> {
+    error: &'a (dyn Error + 'static),
+    messages: Vec<ReportMessage>,
+    backtrace: Option<&'a Backtrace>,
+    color: bool,
+    format: ReportFormat,
+}
+
+impl<'a> ReportParts<'a> {
+    /// The outermost error being reported.
+    pub fn error(&self) -> &'a (dyn Error + 'static) {
+        self.error
+    }
+
+    /// The message of each error that should be shown, starting with
+    /// the outermost error. There is always at least one message.
+    pub fn messages(&self) -> &[ReportMessage] {
+        &self.messages
+    }
+
+    /// The backtrace of the outermost error, if it has one and it
+    /// should be shown.
+    pub fn backtrace(&self) -> Option<&'a Backtrace> {
+        self.backtrace
+    }
+
+    /// Whether the report should be styled using ANSI escape codes.
+    /// This is always `false` without the `color` feature.
+    pub fn color(&self) -> bool {
+        self.color
+    }
+
+    /// The format that was requested for the report.
+    pub fn format(&self) -> ReportFormat {
+        self.format
+    }
+}
+
+/// The format requested from a [`ReportRenderer`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ReportFormat {
+    /// The multi-line format for people, used by the
+    /// [`Display`](fmt::Display) and [`Debug`](fmt::Debug)
+    /// implementations of [`Report`].
+    Full,
+    /// The single-line format for log messages, used by
+    /// [`Report::to_compact_string`].
+    Compact,
+}
+
+/// The message of one error in a [`ReportParts`].
+///
+/// Its [`Display`](fmt::Display) implementation shows the text
+/// followed by the annotations in brackets, if there are any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportMessage {
+    depth: usize,
+    text: String,
+    annotations: Option<String>,
+}
+
+impl ReportMessage {
+    /// The depth of the error in the chain, where the outermost error
+    /// has a depth of 0.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The error's message, without the repeated message of its
+    /// source.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The error's key-value pairs, formatted as `key: value`
+    /// separated by commas.
+    pub fn annotations(&self) -> Option<&str> {
+        self.annotations.as_ref().map(String::as_str)
+    }
+}
+
+impl fmt::Display for ReportMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)?;
+        if let Some(annotations) = &self.annotations {
+            write!(f, " [{}]", annotations)?;
+        }
+        Ok(())
+    }
+}
+
+/// Arranges the parts of a [`Report`] into the final output.
+///
+/// Applications can [`install`] a renderer to change how every
+/// `Report` is shown, such as adding a footer asking users to file a
+/// bug. Closures with the same signature as [`render`](Self::render)
+/// are also renderers.
+///
+/// ```rust
+/// use snafu::report::{self, DefaultRenderer, ReportParts, ReportRenderer};
+/// use std::fmt;
+///
+/// struct WithFooter;
+///
+/// impl ReportRenderer for WithFooter {
+///     fn render(&self, parts: &ReportParts<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         DefaultRenderer.render(parts, f)?;
+///         write!(f, "\n\nPlease file a bug at https://example.com/bugs")
+///     }
+/// }
+///
+/// report::install(WithFooter).expect("Only installed once");
+/// ```
+pub trait ReportRenderer: Send + Sync + 'static {
+    /// Writes the report.
+    fn render(&self, parts: &ReportParts<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl<F> ReportRenderer for F
+where
+    F: Fn(&ReportParts<'_>, &mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync + 'static,
+{
+    fn render(&self, parts: &ReportParts<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self(parts, f)
+    }
+}
+
+/// The renderer used when no other renderer has been installed.
+///
+/// The first message is shown on its own, each following message is
+/// prefixed by `Caused by:`, and the backtrace is shown last. In the
+/// [compact format](ReportFormat::Compact), the messages are
+/// separated by `: ` and nothing else is shown.
+///
+/// When [colors](ReportParts::color) are enabled, the first message
+/// is bold and red, `Caused by:` is dimmed, and the function names
+/// in the backtrace are highlighted.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DefaultRenderer;
+
+impl ReportRenderer for DefaultRenderer {
+    fn render(&self, parts: &ReportParts<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if parts.format() == ReportFormat::Compact {
+            for (i, message) in parts.messages().iter().enumerate() {
+                if i != 0 {
+                    f.write_str(": ")?;
+                }
+                write!(f, "{}", message)?;
+            }
+            return Ok(());
+        }
+
+        let color = parts.color();
+
+        let mut messages = parts.messages().iter();
+        if let Some(message) = messages.next() {
+            write!(f, "{}", Paint(BOLD_RED, message, color))?;
+        }
+        for message in messages {
+            write!(f, "\n{} {}", Paint(DIMMED, "Caused by:", color), message)?;
+        }
+
+        if let Some(backtrace) = parts.backtrace() {
+            // The backtrace crate's type only implements `Debug`
+            #[cfg(feature = "backtraces-impl-backtrace-crate")]
+            let backtrace = format!("{:?}", backtrace);
+            #[cfg(not(feature = "backtraces-impl-backtrace-crate"))]
+            let backtrace = backtrace.to_string();
+
+            write!(f, "\n\nBacktrace:\n")?;
+            write_backtrace(f, &backtrace, color)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A renderer for command-line tools that want users to file useful
+/// bug reports when the program exits with an error.
+///
+/// The report is shown by [`DefaultRenderer`], followed by a block
+/// containing the name and version of the tool, the operating system,
+/// and the chain of errors. The block is plain text wrapped in a
+/// Markdown code fence so it can be pasted directly into an issue. The
+/// [compact format](ReportFormat::Compact) is left unchanged.
+///
+/// ```rust,no_run
+/// use snafu::{report::{self, BugReportRenderer}, Report, Whatever};
+///
+/// fn main() -> Result<(), Report<Whatever>> {
+///     let renderer = BugReportRenderer::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+///     report::install(renderer).expect("No other renderer is installed");
+///
+///     // ...
+/// # Ok(())
+/// }
+/// ```
+///
+/// Which prints something like this:
+///
+/// ````text
+/// Error: Could not save the document
+/// Caused by: Disk is full
+///
+/// Please include the following when reporting this problem:
+///
+/// ```text
+/// name: my-tool
+/// version: 1.2.3
+/// os: linux (x86_64)
+/// error: Could not save the document
+/// caused by: Disk is full
+/// ```
+/// ````
+#[derive(Debug, Clone)]
+pub struct BugReportRenderer {
+    name: String,
+    version: String,
+}
+
+impl BugReportRenderer {
+    /// Creates a renderer for the tool with the given name and
+    /// version, usually `env!("CARGO_PKG_NAME")` and
+    /// `env!("CARGO_PKG_VERSION")`.
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        BugReportRenderer {
+            name: name.into(),
+            version: version.into(),
+        }
+    }
+}
+
+impl ReportRenderer for BugReportRenderer {
+    fn render(&self, parts: &ReportParts<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DefaultRenderer.render(parts, f)?;
+
+        if parts.format() == ReportFormat::Compact {
+            return Ok(());
+        }
+
+        write!(
+            f,
+            "\n\nPlease include the following when reporting this problem:\n\n"
+        )?;
+        writeln!(f, "```text")?;
+        writeln!(f, "name: {}", self.name)?;
+        writeln!(f, "version: {}", self.version)?;
+        writeln!(f, "os: {} ({})", env::consts::OS, env::consts::ARCH)?;
+        for (i, message) in parts.messages().iter().enumerate() {
+            let label = if i == 0 { "error" } else { "caused by" };
+            writeln!(f, "{}: {}", label, message)?;
+        }
+        f.write_str("```")
+    }
+}
+
+const BOLD_RED: &str = "1;31";
+const DIMMED: &str = "2";
+const CYAN: &str = "36";
+
+/// Wraps the value in an ANSI style when enabled.
+struct Paint<T>(&'static str, T, bool);
+
+impl<T> fmt::Display for Paint<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Paint(style, value, enabled) = self;
+        if *enabled {
+            write!(f, "\x1b[{}m{}\x1b[0m", style, value)
+        } else {
+            value.fmt(f)
+        }
+    }
+}
+
+// Frames look like `  12: path::to::function`, optionally followed by
+// a line like `      at src/main.rs:4:5`.
+fn write_backtrace(f: &mut fmt::Formatter<'_>, backtrace: &str, color: bool) -> fmt::Result {
+    if !color {
+        return f.write_str(backtrace);
+    }
+
+    for (i, line) in backtrace.split('\n').enumerate() {
+        if i != 0 {
+            f.write_str("\n")?;
+        }
+
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        let frame = trimmed
+            .find(": ")
+            .filter(|&i| i != 0 && trimmed[..i].bytes().all(|b| b.is_ascii_digit()));
+
+        if trimmed.starts_with("at ") {
+            write!(f, "{}{}", indent, Paint(DIMMED, trimmed, color))?;
+        } else if let Some(end) = frame {
+            let (number, function) = trimmed.split_at(end + 2);
+            write!(f, "{}{}{}", indent, number, Paint(CYAN, function, color))?;
+        } else {
+            f.write_str(line)?;
+        }
+    }
+
+    Ok(())
+}
+
+// The installed renderer is leaked so that it can be used for the
+// rest of the program without synchronization.
+static RENDERER: AtomicPtr<Box<dyn ReportRenderer>> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets the renderer used by every [`Report`] for the rest of the
+/// program.
+///
+/// Only one renderer can be installed; later attempts return an
+/// error and leave the first renderer in place.
+pub fn install(renderer: impl ReportRenderer) -> Result<(), InstallError> {
+    let renderer: Box<Box<dyn ReportRenderer>> = Box::new(Box::new(renderer));
+    let renderer = Box::into_raw(renderer);
+
+    match RENDERER.compare_exchange(
+        ptr::null_mut(),
+        renderer,
+        Ordering::AcqRel,
+        Ordering::Acquire,
+    ) {
+        Ok(_) => Ok(()),
+        Err(_) => {
+            // Safety: The pointer was created above and was not stored.
+            drop(unsafe { Box::from_raw(renderer) });
+            InstallSnafu.fail()
+        }
+    }
+}
+
+fn renderer() -> &'static dyn ReportRenderer {
+    let renderer = RENDERER.load(Ordering::Acquire);
+    if renderer.is_null() {
+        &DefaultRenderer
+    } else {
+        // Safety: Installed renderers are never freed.
+        unsafe { &**renderer }
+    }
+}
+
+/// The error returned when [`install`] is called after a renderer
+/// has already been installed.
+#[derive(Debug, Snafu)]
+#[snafu(crate_root(crate))]
+#[snafu(display("A report renderer has already been installed"))]
+pub struct InstallError;
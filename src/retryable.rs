@@ -0,0 +1,187 @@
+use crate::{ErrorCompat, ErrorMetadata, Metadata};
+use std::{collections::VecDeque, fmt};
+
+/// The number of recent messages kept by [`Retryable::new`].
+const DEFAULT_CAPACITY: usize = 8;
+
+/// Collects the failures of an operation that is attempted multiple
+/// times.
+///
+/// The error from the first attempt is kept as-is and is reported as
+/// the source. Only the messages of the errors from subsequent
+/// attempts are kept, and only the most recent of those, so large
+/// errors do not need to be cloned or stored for every attempt.
+///
+/// A `Retryable` can be cloned when the error can be. To share the
+/// first error between clones instead of copying it, collect
+/// `Arc<E>`.
+///
+/// ```rust
+/// use snafu::{prelude::*, Retryable};
+/// use std::error::Error;
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("Connection attempt {} was refused", attempt))]
+/// struct ConnectError {
+///     attempt: usize,
+/// }
+///
+/// fn connect(attempt: usize) -> Result<(), ConnectError> {
+///     ConnectSnafu { attempt }.fail()
+/// }
+///
+/// fn connect_with_retries() -> Result<(), Retryable<ConnectError>> {
+///     let mut failures: Option<Retryable<_>> = None;
+///
+///     for attempt in 1..=5 {
+///         match connect(attempt) {
+///             Ok(()) => return Ok(()),
+///             Err(e) => match &mut failures {
+///                 Some(failures) => failures.push(e),
+///                 None => failures = Some(Retryable::new(e)),
+///             },
+///         }
+///     }
+///
+///     Err(failures.expect("At least one attempt was made"))
+/// }
+///
+/// let error = connect_with_retries().unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Failed after 5 attempts; last: Connection attempt 5 was refused",
+/// );
+/// assert_eq!(
+///     error.source().unwrap().to_string(),
+///     "Connection attempt 1 was refused",
+/// );
+/// ```
+#[derive(Clone)]
+pub struct Retryable<E> {
+    first: E,
+    attempts: usize,
+    recent: VecDeque<String>,
+    capacity: usize,
+}
+
+impl<E> Retryable<E> {
+    /// Starts collecting failures with the error from the first
+    /// attempt.
+    pub fn new(first: E) -> Self {
+        Self::with_capacity(first, DEFAULT_CAPACITY)
+    }
+
+    /// Starts collecting failures with the error from the first
+    /// attempt, keeping at most `capacity` messages from subsequent
+    /// attempts.
+    pub fn with_capacity(first: E, capacity: usize) -> Self {
+        Retryable {
+            first,
+            attempts: 1,
+            recent: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Records the failure of another attempt. Only the error's
+    /// message is kept; if the capacity has been reached, the oldest
+    /// message is discarded.
+    pub fn push(&mut self, error: impl fmt::Display) {
+        self.attempts += 1;
+
+        if self.capacity == 0 {
+            return;
+        }
+        if self.recent.len() == self.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(error.to_string());
+    }
+
+    /// The total number of failed attempts, including the first.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// The error from the first attempt.
+    pub fn first(&self) -> &E {
+        &self.first
+    }
+
+    /// The messages of the most recent attempts after the first,
+    /// oldest first.
+    pub fn recent_messages(&self) -> impl Iterator<Item = &str> {
+        self.recent.iter().map(String::as_str)
+    }
+
+    /// The message of the last attempt, if there was more than one
+    /// attempt and messages are being kept.
+    pub fn last_message(&self) -> Option<&str> {
+        self.recent.back().map(String::as_str)
+    }
+
+    /// Returns the error from the first attempt.
+    pub fn into_first(self) -> E {
+        self.first
+    }
+}
+
+impl<E> fmt::Debug for Retryable<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Retryable")
+            .field("first", &self.first)
+            .field("attempts", &self.attempts)
+            .field("recent", &self.recent)
+            .finish()
+    }
+}
+
+// The first error is the source, so it is not repeated here.
+impl<E> fmt::Display for Retryable<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.attempts, self.last_message()) {
+            (1, _) => write!(f, "Failed after 1 attempt"),
+            (n, Some(last)) => write!(f, "Failed after {} attempts; last: {}", n, last),
+            (n, None) => write!(f, "Failed after {} attempts", n),
+        }
+    }
+}
+
+impl<E> std::error::Error for Retryable<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.first)
+    }
+}
+
+impl<E> ErrorCompat for Retryable<E>
+where
+    E: ErrorCompat,
+{
+    fn backtrace(&self) -> Option<&crate::Backtrace> {
+        ErrorCompat::backtrace(&self.first)
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        ErrorCompat::metadata(&self.first)
+    }
+
+    fn exit_code(&self) -> Option<u8> {
+        ErrorCompat::exit_code(&self.first)
+    }
+}
+
+/// Key-value pairs are attached to the error from the first attempt.
+impl<E> ErrorMetadata for Retryable<E>
+where
+    E: ErrorMetadata,
+{
+    fn add_context(&mut self, key: &'static str, value: String) {
+        self.first.add_context(key, value)
+    }
+}
@@ -0,0 +1,201 @@
+//! Reporting errors to [Sentry][sentry-crate].
+//!
+//! Sentry shows each error in an event's chain of sources as a
+//! separate exception. Converting a SNAFU error with
+//! [`event_from_error`] keeps that chain and adds the error's
+//! backtrace, instead of flattening everything into a single message.
+//! Errors using
+//! [`#[snafu(serialize)]`](crate::Snafu#serializing-errors) can also
+//! include the name of the variant and the context fields by using
+//! [`event_from_serializable`].
+//!
+//! [`SentryResultExt`] adds context to a `Result` and reports the new
+//! error in one step:
+//!
+//! ```rust
+//! use snafu::{prelude::*, sentry::SentryResultExt};
+//!
+//! #[derive(Debug, Snafu)]
+//! enum Error {
+//!     #[snafu(display("Could not load the configuration from {path}"))]
+//!     LoadConfiguration {
+//!         path: String,
+//!         source: std::io::Error,
+//!     },
+//! }
+//!
+//! fn load_configuration(path: &str) -> Result<String, Error> {
+//!     std::fs::read_to_string(path).capture_context(LoadConfigurationSnafu { path })
+//! }
+//! # assert!(load_configuration("/does/not/exist").is_err());
+//! ```
+//!
+//! [sentry-crate]: https://crates.io/crates/sentry
+
+use crate::{Error, ErrorCompat, FieldValue, IntoError, SerializableError};
+use sentry_core_crate::{
+    protocol::{Event, Exception, Level, Value},
+    types::Uuid,
+};
+
+/// Creates a Sentry event from the error.
+///
+/// Each error in the chain of sources becomes an exception, with the
+/// type of each error parsed from its `Debug` output. The backtrace,
+/// when the error has one, is added to the event's extra data.
+pub fn event_from_error<E>(error: &E) -> Event<'static>
+where
+    E: Error + ErrorCompat + ?Sized,
+{
+    let mut event = sentry_core_crate::event_from_error(error);
+
+    if let Some(backtrace) = ErrorCompat::backtrace(error) {
+        event
+            .extra
+            .insert("backtrace".into(), backtrace.to_string().into());
+    }
+
+    event
+}
+
+/// Creates a Sentry event from a snapshot of an error.
+///
+/// The name of the variant, when known, is used as the type of the
+/// outermost exception. The code is added as the `code` tag, and the
+/// fields and backtrace are added to the event's extra data.
+///
+/// ```rust
+/// use snafu::{sentry::event_from_serializable, SerializableError};
+///
+/// let error = SerializableError::new("Could not find user 42")
+///     .with_variant("UserNotFound")
+///     .with_field("id", 42u32);
+/// let event = event_from_serializable(&error);
+///
+/// assert_eq!(event.exception[0].ty, "UserNotFound");
+/// assert_eq!(event.extra["id"], 42);
+/// ```
+pub fn event_from_serializable(error: &SerializableError) -> Event<'static> {
+    let sources = error.chain().iter().rev().map(|message| Exception {
+        ty: "Error".into(),
+        value: Some(message.clone()),
+        ..Default::default()
+    });
+
+    let outermost = Exception {
+        ty: error.variant().unwrap_or("Error").into(),
+        value: Some(error.message().into()),
+        ..Default::default()
+    };
+
+    let exceptions: Vec<_> = sources.chain(Some(outermost)).collect();
+
+    let mut event = Event {
+        exception: exceptions.into(),
+        level: Level::Error,
+        ..Default::default()
+    };
+
+    if let Some(code) = error.code() {
+        event.tags.insert("code".into(), code.into());
+    }
+
+    for (key, value) in error.fields() {
+        event.extra.insert(key.clone(), field_value(value));
+    }
+
+    if let Some(backtrace) = error.backtrace() {
+        event.extra.insert("backtrace".into(), backtrace.into());
+    }
+
+    event
+}
+
+fn field_value(value: &FieldValue) -> Value {
+    match value {
+        FieldValue::Bool(v) => (*v).into(),
+        FieldValue::U64(v) => (*v).into(),
+        FieldValue::I64(v) => (*v).into(),
+        FieldValue::F64(v) => (*v).into(),
+        FieldValue::String(v) => v.clone().into(),
+    }
+}
+
+/// Reports the error to the current Sentry hub, as created by
+/// [`event_from_error`].
+pub fn capture_error<E>(error: &E) -> Uuid
+where
+    E: Error + ErrorCompat + ?Sized,
+{
+    sentry_core_crate::capture_event(event_from_error(error))
+}
+
+/// Additions to `Result` for reporting errors to Sentry.
+///
+/// Errors are reported using [`capture_error`] and then returned
+/// unchanged, so the caller can still handle them.
+pub trait SentryResultExt<T, E>: Sized {
+    /// Reports the error to Sentry.
+    fn capture_err(self) -> Self
+    where
+        E: Error + ErrorCompat;
+
+    /// Extend the error with additional context-sensitive
+    /// information, as [`ResultExt::context`], and report the new
+    /// error to Sentry.
+    ///
+    /// [`ResultExt::context`]: crate::ResultExt::context
+    fn capture_context<C, E2>(self, context: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
+    /// Extend the error with lazily-generated context-sensitive
+    /// information, as [`ResultExt::with_context`], and report the
+    /// new error to Sentry.
+    ///
+    /// [`ResultExt::with_context`]: crate::ResultExt::with_context
+    fn with_capture_context<F, C, E2>(self, context: F) -> Result<T, E2>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+}
+
+impl<T, E> SentryResultExt<T, E> for Result<T, E> {
+    fn capture_err(self) -> Self
+    where
+        E: Error + ErrorCompat,
+    {
+        self.map_err(|error| {
+            capture_error(&error);
+            error
+        })
+    }
+
+    fn capture_context<C, E2>(self, context: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        self.map_err(|error| {
+            let error = context.into_error(error);
+            capture_error(&error);
+            error
+        })
+    }
+
+    fn with_capture_context<F, C, E2>(self, context: F) -> Result<T, E2>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        self.map_err(|error| {
+            let context = context();
+            let error = context.into_error(error);
+            capture_error(&error);
+            error
+        })
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{Backtrace, ChainCompat, FieldValue, Fields};
+use std::{collections::BTreeMap, error::Error, fmt};
+
+/// An owned snapshot of an error and its chain of sources, suitable
+/// for sending to another process.
+///
+/// The snapshot implements [`Error`], and its
+/// [`source`](Error::source) returns the messages of the original
+/// sources, so an error received from another process can be used
+/// like any other error, such as by wrapping it in a
+/// [`Report`](crate::Report) or using it as the source of a local
+/// error.
+///
+/// When the `serde` [feature flag] is enabled, this type implements
+/// `Serialize` and `Deserialize`. When the `schemars` feature flag is
+/// enabled, it also implements `JsonSchema`, allowing APIs that
+/// return errors in this form to publish a schema for them.
+///
+/// ```rust
+/// use snafu::{prelude::*, SerializableError};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not find user {}", id))]
+///     UserNotFound { id: u32, source: std::io::Error },
+/// }
+///
+/// let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+/// let error = Err::<(), _>(io_error)
+///     .context(UserNotFoundSnafu { id: 42u32 })
+///     .unwrap_err();
+///
+/// let serializable = SerializableError::from_error(&error)
+///     .with_variant("UserNotFound")
+///     .with_code("E0404");
+///
+/// assert_eq!(serializable.message(), "Could not find user 42");
+/// assert_eq!(serializable.chain(), ["no such file"]);
+/// ```
+///
+/// [feature flag]: crate::guide::feature_flags
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[cfg_attr(
+    feature = "schemars",
+    derive(schemars_crate::JsonSchema),
+    schemars(crate = "schemars_crate")
+)]
+pub struct SerializableError {
+    /// The name of the variant that produced the error, if known.
+    #[cfg_attr(feature = "serde", serde(default))]
+    variant: Option<String>,
+    /// An application-defined code identifying the error, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    code: Option<String>,
+    /// The error's message.
+    message: String,
+    /// The messages of the error's sources, outermost first.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<String>"))]
+    chain: Chain,
+    /// Typed key-value pairs describing the error.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    fields: BTreeMap<String, FieldValue>,
+    /// The error's backtrace, if it was recorded.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    backtrace: Option<String>,
+}
+
+impl SerializableError {
+    /// Creates a snapshot with the given message and no sources.
+    pub fn new(message: impl Into<String>) -> Self {
+        SerializableError {
+            variant: None,
+            code: None,
+            message: message.into(),
+            chain: Chain::default(),
+            fields: BTreeMap::new(),
+            backtrace: None,
+        }
+    }
+
+    /// Creates a snapshot of the error's message and the messages of
+    /// all of its sources.
+    pub fn from_error(error: &dyn Error) -> Self {
+        let mut messages = ChainCompat::new(error).map(ToString::to_string);
+        let message = messages.next().unwrap_or_default();
+
+        SerializableError {
+            variant: None,
+            code: None,
+            message,
+            chain: Chain::new(messages.collect()),
+            fields: BTreeMap::new(),
+            backtrace: None,
+        }
+    }
+
+    /// Records the name of the variant that produced the error.
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Records an application-defined code identifying the error.
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Records typed key-value pairs, such as those provided to
+    /// [`whatever!`](crate::whatever).
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, FieldValue, SerializableError, Whatever};
+    ///
+    /// fn connect() -> Result<(), Whatever> {
+    ///     whatever!("Failed to connect"; attempt = 3);
+    /// }
+    ///
+    /// let error = connect().unwrap_err();
+    /// let serializable = SerializableError::from_error(&error).with_fields(error.fields());
+    /// assert_eq!(serializable.fields()["attempt"], FieldValue::I64(3));
+    /// ```
+    pub fn with_fields(mut self, fields: &Fields) -> Self {
+        self.fields.extend(
+            fields
+                .iter()
+                .map(|(key, value)| (key.to_owned(), value.clone())),
+        );
+        self
+    }
+
+    /// Records a single typed key-value pair.
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Into<FieldValue>) -> Self {
+        self.fields.insert(key.into(), value.into());
+        self
+    }
+
+    /// Records the error's backtrace as text.
+    pub fn with_backtrace(mut self, backtrace: &Backtrace) -> Self {
+        // The backtrace crate's type only implements `Debug`
+        #[cfg(feature = "backtraces-impl-backtrace-crate")]
+        let backtrace = format!("{:?}", backtrace);
+        #[cfg(not(feature = "backtraces-impl-backtrace-crate"))]
+        let backtrace = backtrace.to_string();
+
+        self.backtrace = Some(backtrace);
+        self
+    }
+
+    /// The name of the variant that produced the error, if known.
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_ref().map(String::as_str)
+    }
+
+    /// The application-defined code identifying the error, if any.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_ref().map(String::as_str)
+    }
+
+    /// The error's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The messages of the error's sources, outermost first.
+    pub fn chain(&self) -> &[String] {
+        &self.chain.messages
+    }
+
+    /// Typed key-value pairs describing the error, ordered by key.
+    pub fn fields(&self) -> &BTreeMap<String, FieldValue> {
+        &self.fields
+    }
+
+    /// The error's backtrace, if it was recorded.
+    pub fn backtrace(&self) -> Option<&str> {
+        self.backtrace.as_ref().map(String::as_str)
+    }
+}
+
+impl fmt::Display for SerializableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl Error for SerializableError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.chain.source()
+    }
+}
+
+/// The messages of the sources along with the errors returned by
+/// `source`, which are rebuilt whenever the messages change.
+#[derive(Debug, Clone, Default)]
+struct Chain {
+    messages: Vec<String>,
+    head: Option<Box<Cause>>,
+}
+
+impl Chain {
+    fn new(messages: Vec<String>) -> Self {
+        let head = messages.iter().rev().fold(None, |source, message| {
+            Some(Box::new(Cause {
+                message: message.clone(),
+                source,
+            }))
+        });
+
+        Chain { messages, head }
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.head.as_ref().map(|c| &**c as &(dyn Error + 'static))
+    }
+}
+
+impl PartialEq for Chain {
+    fn eq(&self, other: &Self) -> bool {
+        self.messages == other.messages
+    }
+}
+
+impl Eq for Chain {}
+
+#[cfg(feature = "serde")]
+impl serde_crate::Serialize for Chain {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde_crate::Serializer,
+    {
+        serde_crate::Serialize::serialize(&self.messages, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde_crate::Deserialize<'de> for Chain {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde_crate::Deserializer<'de>,
+    {
+        serde_crate::Deserialize::deserialize(deserializer).map(Chain::new)
+    }
+}
+
+/// A source of a [`SerializableError`], which only has a message.
+#[derive(Debug, Clone)]
+struct Cause {
+    message: String,
+    source: Option<Box<Cause>>,
+}
+
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl Error for Cause {
+    fn description(&self) -> &str {
+        &self.message
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|c| &**c as &(dyn Error + 'static))
+    }
+}
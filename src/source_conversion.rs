@@ -0,0 +1,42 @@
+/// A source type accepted by a variant of an error, as listed by
+/// [`#[snafu(report_conversions)]`][crate::Snafu#reporting-source-conversions].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SourceConversion {
+    /// The type of the source, as written in the error definition
+    pub source_type: &'static str,
+    /// The name of the variant accepting the source
+    pub variant: &'static str,
+}
+
+/// Panics if any source type is accepted by more than one variant.
+///
+/// This is intended to be called from a test with the conversions
+/// listed by
+/// [`#[snafu(report_conversions)]`][crate::Snafu#reporting-source-conversions].
+///
+/// ```rust,should_panic
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(report_conversions)]
+/// enum Error {
+///     Read { source: std::io::Error },
+///     Write { source: std::io::Error },
+/// }
+///
+/// snafu::assert_unambiguous_conversions(Error::SNAFU_CONVERSIONS);
+/// ```
+pub fn assert_unambiguous_conversions(conversions: &[SourceConversion]) {
+    for (i, conversion) in conversions.iter().enumerate() {
+        let duplicate = conversions[i + 1..]
+            .iter()
+            .find(|other| other.source_type == conversion.source_type);
+
+        if let Some(duplicate) = duplicate {
+            panic!(
+                "The source type `{}` is accepted by both the `{}` and `{}` variants",
+                conversion.source_type, conversion.variant, duplicate.variant,
+            );
+        }
+    }
+}
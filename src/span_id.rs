@@ -0,0 +1,62 @@
+use core::fmt;
+
+/// The ID of the [`tracing`](tracing_crate) span that was current when
+/// an error was created.
+///
+/// When a field is declared with the type `snafu::SpanId`, it is not
+/// part of the context selector and is filled in automatically. Only
+/// the numeric ID is recorded, so this is much cheaper than capturing
+/// the entire span and its parents, allowing errors to be joined with
+/// the logs of the same span in a tracing backend.
+///
+/// This type is only available when the `tracing` [feature flag] is
+/// enabled.
+///
+/// ```rust
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not load user {}", id))]
+///     LoadUser { id: u32, span_id: snafu::SpanId },
+/// }
+///
+/// let error = LoadUserSnafu { id: 42u32 }.build();
+///
+/// if let Error::LoadUser { span_id, .. } = &error {
+///     // No span is entered in this example
+///     assert_eq!(span_id.get(), None);
+/// }
+/// ```
+///
+/// [feature flag]: crate::guide::feature_flags
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SpanId(Option<u64>);
+
+impl SpanId {
+    /// Records the ID of the current span, if any.
+    pub fn current() -> Self {
+        SpanId(tracing_crate::Span::current().id().map(|id| id.into_u64()))
+    }
+
+    /// The recorded ID. This is `None` when no span was entered or
+    /// the span was disabled.
+    pub fn get(&self) -> Option<u64> {
+        self.0
+    }
+}
+
+impl Default for SpanId {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl fmt::Display for SpanId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(id) => id.fmt(f),
+            None => f.write_str("none"),
+        }
+    }
+}
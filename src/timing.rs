@@ -0,0 +1,84 @@
+use crate::{Error, ErrorCompat, IntoError};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// How long the failing operation ran before it produced an error.
+///
+/// When a field is declared with the type `snafu::Timing`, it is not
+/// part of the context selector. When the error is created by
+/// [`timed_context`] or
+/// [`TryFutureExt::timed_context`](crate::futures::TryFutureExt::timed_context),
+/// it holds the time between the start of the operation and its
+/// failure. Errors created any other way do not know when the
+/// operation started and have no elapsed time.
+///
+/// ```rust
+/// use snafu::prelude::*;
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not query the database"))]
+///     Query {
+///         source: std::io::Error,
+///         timing: snafu::Timing,
+///     },
+/// }
+///
+/// fn query() -> Result<(), std::io::Error> {
+///     std::thread::sleep(Duration::from_millis(5));
+///     Err(std::io::ErrorKind::TimedOut.into())
+/// }
+///
+/// let error = snafu::timed_context(QuerySnafu, query).unwrap_err();
+///
+/// if let Error::Query { timing, .. } = &error {
+///     assert!(timing.elapsed().unwrap() >= Duration::from_millis(5));
+/// }
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Timing(Option<Duration>);
+
+impl Timing {
+    /// How long the operation ran, if known.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.0
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(elapsed) => write!(f, "{:?}", elapsed),
+            None => f.write_str("unknown"),
+        }
+    }
+}
+
+/// Runs an operation, extending its error with a context selector
+/// and recording how long it ran in any [`Timing`] fields of the
+/// resulting error.
+///
+/// See [`Timing`] for an example.
+pub fn timed_context<F, T, E, C, E2>(context: C, operation: F) -> Result<T, E2>
+where
+    F: FnOnce() -> Result<T, E>,
+    C: IntoError<E2, Source = E>,
+    E2: Error + ErrorCompat,
+{
+    let start = Instant::now();
+    operation().map_err(|error| into_error_with_elapsed(context, error, start.elapsed()))
+}
+
+/// Creates the error with the elapsed time in its timing fields.
+/// Errors created by the operation itself have already been created
+/// and are unaffected.
+pub(crate) fn into_error_with_elapsed<C, E, E2>(context: C, error: E, elapsed: Duration) -> E2
+where
+    C: IntoError<E2, Source = E>,
+    E2: Error + ErrorCompat,
+{
+    context.into_error_with_timing(error, Timing(Some(elapsed)))
+}
@@ -0,0 +1,235 @@
+//! Recording where asynchronous tasks were spawned and adding
+//! context to operations that time out.
+//!
+//! When a background task panics or is cancelled, the error returned
+//! by awaiting its `JoinHandle` does not say where the task came
+//! from. Spawning the task using [`spawn`] or [`spawn_blocking`]
+//! records the location of the call, which is then reported by the
+//! resulting [`TaskError`].
+//!
+//! This module is only available when the `tokio` [feature flag] is
+//! enabled.
+//!
+//! ```rust
+//! # use tokio_crate as tokio;
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let handle = snafu::tokio::spawn(async {
+//!     panic!("Oh no");
+//! });
+//!
+//! let error = handle.await.unwrap_err();
+//! assert!(error.is_panic());
+//! println!("{}", error); // The task spawned at src/main.rs:4:14 failed
+//! # });
+//! ```
+//!
+//! An operation that must complete within a limited time can be run
+//! using [`with_deadline`], which wraps both the operation's error
+//! and the deadline elapsing using the same context selector.
+//!
+//! [feature flag]: crate::guide::feature_flags
+
+use crate::{Error, ErrorCompat, IntoError, ResultExt, Snafu};
+use core::{
+    future::Future,
+    panic::Location,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio_crate::task::{JoinError, JoinHandle};
+
+/// Spawns a new asynchronous task, recording the location of the
+/// caller.
+///
+/// See [`tokio::spawn`](tokio_crate::spawn) for details about the
+/// task itself.
+#[track_caller]
+pub fn spawn<F>(future: F) -> SpawnedHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let location = Location::caller();
+    SpawnedHandle {
+        handle: tokio_crate::spawn(future),
+        location,
+    }
+}
+
+/// Runs the provided closure on a thread where blocking is
+/// acceptable, recording the location of the caller.
+///
+/// See [`tokio::task::spawn_blocking`](tokio_crate::task::spawn_blocking)
+/// for details about the task itself.
+#[track_caller]
+pub fn spawn_blocking<F, R>(f: F) -> SpawnedHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let location = Location::caller();
+    SpawnedHandle {
+        handle: tokio_crate::task::spawn_blocking(f),
+        location,
+    }
+}
+
+/// An owned permission to join on a task, along with the location
+/// where the task was spawned.
+///
+/// Awaiting the handle produces the task's output or a [`TaskError`].
+#[derive(Debug)]
+pub struct SpawnedHandle<T> {
+    handle: JoinHandle<T>,
+    location: &'static Location<'static>,
+}
+
+impl<T> SpawnedHandle<T> {
+    /// The location where the task was spawned.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Aborts the task. Awaiting the handle afterwards will produce a
+    /// cancellation error unless the task has already completed.
+    pub fn abort(&self) {
+        self.handle.abort()
+    }
+
+    /// Returns the underlying `JoinHandle`, discarding the location.
+    pub fn into_inner(self) -> JoinHandle<T> {
+        self.handle
+    }
+}
+
+impl<T> Future for SpawnedHandle<T> {
+    type Output = Result<T, TaskError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let location = self.location;
+        Pin::new(&mut self.handle)
+            .poll(cx)
+            .map(|r| r.context(TaskSnafu { location }))
+    }
+}
+
+/// The error returned when a task spawned using [`spawn`] or
+/// [`spawn_blocking`] panics or is cancelled.
+#[derive(Debug, Snafu)]
+#[snafu(crate_root(crate))]
+#[snafu(display("The task spawned at {location} failed"))]
+pub struct TaskError {
+    source: JoinError,
+    location: &'static Location<'static>,
+}
+
+impl TaskError {
+    /// The location where the failed task was spawned.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns true if the task failed because it panicked.
+    pub fn is_panic(&self) -> bool {
+        self.source.is_panic()
+    }
+
+    /// Returns true if the task failed because it was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.source.is_cancelled()
+    }
+
+    /// Returns the underlying `JoinError`, discarding the location.
+    pub fn into_inner(self) -> JoinError {
+        self.source
+    }
+}
+
+/// Runs the future, failing if it does not complete within the
+/// duration.
+///
+/// An error produced by the future and the deadline elapsing are both
+/// wrapped using the context selector, with a [`DeadlineError`] as
+/// the source.
+///
+/// ```rust
+/// # use tokio_crate as tokio;
+/// use snafu::{prelude::*, tokio::DeadlineError};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not fetch the price of {}", symbol))]
+///     FetchPrice {
+///         symbol: String,
+///         source: DeadlineError<std::io::Error>,
+///     },
+/// }
+///
+/// async fn fetch_price(symbol: &str) -> Result<u32, Error> {
+///     let fetch = async {
+///         tokio::time::sleep(Duration::from_secs(60)).await;
+///         Ok(42)
+///     };
+///
+///     snafu::tokio::with_deadline(
+///         Duration::from_millis(10),
+///         FetchPriceSnafu { symbol },
+///         fetch,
+///     )
+///     .await
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap().block_on(async {
+/// let error = fetch_price("ACME").await.unwrap_err();
+/// assert!(matches!(
+///     error,
+///     Error::FetchPrice { ref source, .. } if source.is_elapsed(),
+/// ));
+/// # });
+/// ```
+pub async fn with_deadline<F, T, S, C, E>(duration: Duration, context: C, future: F) -> Result<T, E>
+where
+    F: Future<Output = Result<T, S>>,
+    C: IntoError<E, Source = DeadlineError<S>>,
+    E: Error + ErrorCompat,
+{
+    let source = match tokio_crate::time::timeout(duration, future).await {
+        Ok(Ok(v)) => return Ok(v),
+        Ok(Err(source)) => DeadlineError::Failed { source },
+        Err(_) => DeadlineError::Elapsed { duration },
+    };
+
+    Err(context.into_error(source))
+}
+
+/// The error returned when an operation run using [`with_deadline`]
+/// fails or does not complete in time.
+#[derive(Debug, Snafu)]
+#[snafu(crate_root(crate))]
+pub enum DeadlineError<E> {
+    /// The operation did not complete in time.
+    #[snafu(display("The deadline of {duration:?} elapsed"))]
+    Elapsed {
+        /// How long the operation was allowed to run
+        duration: Duration,
+    },
+
+    /// The operation completed with an error.
+    #[snafu(display("The operation failed before the deadline"))]
+    Failed {
+        /// The error produced by the operation
+        source: E,
+    },
+}
+
+impl<E> DeadlineError<E> {
+    /// Returns true if the operation did not complete in time.
+    pub fn is_elapsed(&self) -> bool {
+        match self {
+            DeadlineError::Elapsed { .. } => true,
+            DeadlineError::Failed { .. } => false,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use core::{
+    cmp, fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Records return addresses into the slice, returning how many were
+/// recorded.
+///
+/// See [`TraceCell::set_hook`].
+pub type TraceHook = fn(&mut [usize]) -> usize;
+
+/// The installed [`TraceHook`] as an address, or zero when there is
+/// none. Only loads and stores are used so that this works on targets
+/// without atomic compare-and-swap.
+static HOOK: AtomicUsize = AtomicUsize::new(0);
+
+/// A lightweight alternative to a [`Backtrace`](crate::Backtrace)
+/// that records a small, fixed number of return addresses without
+/// allocating.
+///
+/// Capturing a full backtrace is often impossible on embedded
+/// targets. A `TraceCell` can be used as the type of a backtrace
+/// field instead, and is filled in when the error is created by the
+/// context selector. The addresses can be symbolized later using the
+/// binary's debug information.
+///
+/// The addresses are recorded by the hook installed using
+/// [`TraceCell::set_hook`], which typically walks the stack using
+/// frame pointers or reads the link register. Without a hook, the
+/// addresses are recorded using the `backtrace` crate when the
+/// `backtraces` [feature flag](crate::guide::feature_flags) is
+/// enabled, otherwise nothing is recorded.
+///
+/// Because a `TraceCell` is not a `Backtrace`,
+/// [`ErrorCompat::backtrace`](crate::ErrorCompat::backtrace) returns
+/// `None` for errors using it.
+///
+/// ```rust
+/// use snafu::{prelude::*, TraceCell};
+///
+/// // Selecting the type lets the same error definitions be used on
+/// // the host and on the target.
+/// #[cfg(not(target_os = "none"))]
+/// type Trace = Option<snafu::Backtrace>;
+/// #[cfg(target_os = "none")]
+/// type Trace = TraceCell;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("The sensor did not respond"))]
+///     Timeout {
+///         #[snafu(backtrace)]
+///         trace: TraceCell,
+///     },
+///
+///     #[snafu(display("The flash write failed"))]
+///     Flash { backtrace: Trace },
+/// }
+///
+/// fn fake_hook(addresses: &mut [usize]) -> usize {
+///     addresses[0] = 0x0800_1234;
+///     addresses[1] = 0x0800_5678;
+///     2
+/// }
+///
+/// TraceCell::set_hook(fake_hook);
+///
+/// match TimeoutSnafu.build() {
+///     Error::Timeout { trace } => assert_eq!(trace.addresses(), [0x0800_1234, 0x0800_5678]),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct TraceCell {
+    addresses: [usize; TraceCell::CAPACITY],
+    len: usize,
+}
+
+impl TraceCell {
+    /// The maximum number of return addresses that are recorded.
+    pub const CAPACITY: usize = 8;
+
+    /// Installs the hook used to record return addresses, replacing
+    /// any previous hook.
+    pub fn set_hook(hook: TraceHook) {
+        HOOK.store(hook as usize, Ordering::SeqCst);
+    }
+
+    /// Records the current return addresses.
+    pub fn capture() -> Self {
+        let mut addresses = [0; TraceCell::CAPACITY];
+        let len = cmp::min(capture_into(&mut addresses), TraceCell::CAPACITY);
+
+        TraceCell { addresses, len }
+    }
+
+    /// A trace without any return addresses.
+    pub fn empty() -> Self {
+        TraceCell {
+            addresses: [0; TraceCell::CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// The recorded return addresses, innermost first.
+    pub fn addresses(&self) -> &[usize] {
+        &self.addresses[..self.len]
+    }
+
+    /// Returns true if no return addresses were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Default for TraceCell {
+    fn default() -> Self {
+        TraceCell::empty()
+    }
+}
+
+impl fmt::Debug for TraceCell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        for address in self.addresses() {
+            list.entry(&format_args!("{:#x}", address));
+        }
+        list.finish()
+    }
+}
+
+/// Each return address is written on its own line, innermost first.
+impl fmt::Display for TraceCell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, address) in self.addresses().iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:>4}: {:#x}", i, address)?;
+        }
+        Ok(())
+    }
+}
+
+impl crate::GenerateBacktrace for TraceCell {
+    fn generate() -> Self {
+        TraceCell::capture()
+    }
+
+    fn as_backtrace(&self) -> Option<&crate::Backtrace> {
+        None
+    }
+}
+
+fn capture_into(addresses: &mut [usize]) -> usize {
+    let hook = HOOK.load(Ordering::SeqCst);
+    if hook != 0 {
+        // Safety: the only non-zero values stored are `TraceHook`s
+        let hook: TraceHook = unsafe { core::mem::transmute(hook) };
+        return hook(addresses);
+    }
+
+    capture_without_hook(addresses)
+}
+
+#[cfg(feature = "backtraces")]
+fn capture_without_hook(addresses: &mut [usize]) -> usize {
+    let mut len = 0;
+    backtrace::trace(|frame| {
+        addresses[len] = frame.ip() as usize;
+        len += 1;
+        len < addresses.len()
+    });
+    len
+}
+
+#[cfg(not(feature = "backtraces"))]
+fn capture_without_hook(_addresses: &mut [usize]) -> usize {
+    0
+}
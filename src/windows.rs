@@ -0,0 +1,155 @@
+//! Finding Windows [`HRESULT`] error codes in errors.
+//!
+//! Windows APIs called through the [`windows`][windows-crate] crate
+//! fail with a `windows::core::Error`, which wraps an `HRESULT`. Tools
+//! that must behave differently depending on that code usually want
+//! it long after the error has been wrapped in context. Using
+//! [`WindowsError`] as the source of an error keeps the code
+//! accessible, and [`hresult`] or
+//! [`ErrorCompat::hresult`](crate::ErrorCompat::hresult) find it
+//! anywhere in the chain of sources. On Windows, the raw OS error
+//! code of an [`io::Error`](std::io::Error) is treated as a Win32
+//! error code.
+//!
+//! ```rust
+//! # mod windows { pub use windows_result_crate as core; }
+//! use snafu::{prelude::*, windows::WindowsError, ErrorCompat};
+//! use windows::core::HRESULT;
+//!
+//! const ERROR_ACCESS_DENIED: u32 = 5;
+//!
+//! #[derive(Debug, Snafu)]
+//! enum Error {
+//!     #[snafu(display("Could not open the registry key {key}"))]
+//!     OpenKey {
+//!         key: String,
+//!         #[snafu(source(from(windows::core::Error, WindowsError::from)))]
+//!         source: WindowsError,
+//!     },
+//! }
+//!
+//! fn open_key(key: &str) -> Result<(), Error> {
+//!     let code = HRESULT::from_win32(ERROR_ACCESS_DENIED);
+//!     Err(windows::core::Error::from_hresult(code)).context(OpenKeySnafu { key })
+//! }
+//!
+//! let error = open_key(r"HKLM\SOFTWARE\Example").unwrap_err();
+//! assert_eq!(error.hresult(), Some(HRESULT::from_win32(ERROR_ACCESS_DENIED)));
+//!
+//! let Error::OpenKey { source, .. } = &error;
+//! assert_eq!(source.win32_error(), Some(ERROR_ACCESS_DENIED));
+//! ```
+//!
+//! This module is only available when the `windows` [feature flag] is
+//! enabled.
+//!
+//! [windows-crate]: https://crates.io/crates/windows
+//! [feature flag]: crate::guide::feature_flags
+
+use crate::Error;
+use core::fmt;
+use windows_result_crate::HRESULT;
+
+/// A `windows::core::Error` used as the source of another error.
+///
+/// `windows::core::Error` can be converted into this type using
+/// `#[snafu(source(from(windows::core::Error, WindowsError::from)))]`.
+/// It has the same `Display` output as the original error, and adds
+/// accessors for the error code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowsError(windows_result_crate::Error);
+
+impl WindowsError {
+    /// The `HRESULT` describing the error.
+    pub fn code(&self) -> HRESULT {
+        self.0.code()
+    }
+
+    /// The Win32 error code that the `HRESULT` was created from, if
+    /// it was created from one.
+    pub fn win32_error(&self) -> Option<u32> {
+        win32_error(self.code())
+    }
+
+    /// The original `windows::core::Error`.
+    pub fn get_ref(&self) -> &windows_result_crate::Error {
+        &self.0
+    }
+
+    /// Returns the original `windows::core::Error`.
+    pub fn into_inner(self) -> windows_result_crate::Error {
+        self.0
+    }
+}
+
+impl From<windows_result_crate::Error> for WindowsError {
+    fn from(other: windows_result_crate::Error) -> Self {
+        WindowsError(other)
+    }
+}
+
+impl From<HRESULT> for WindowsError {
+    fn from(other: HRESULT) -> Self {
+        WindowsError(windows_result_crate::Error::from_hresult(other))
+    }
+}
+
+impl fmt::Display for WindowsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for WindowsError {}
+
+/// Returns the `HRESULT` of the first error in the chain of sources
+/// that has one, starting with `error` itself.
+///
+/// `windows::core::Error` and [`WindowsError`] provide an `HRESULT`.
+/// On Windows, an [`io::Error`](std::io::Error) with a raw OS error
+/// code provides the `HRESULT` for that Win32 error code.
+pub fn hresult(error: &(dyn Error + 'static)) -> Option<HRESULT> {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if let Some(code) = hresult_of(error) {
+            return Some(code);
+        }
+        source = error.source();
+    }
+    None
+}
+
+/// Returns the Win32 error code of a failure `HRESULT` created using
+/// `HRESULT::from_win32`.
+pub fn win32_error(code: HRESULT) -> Option<u32> {
+    // Failures from `FACILITY_WIN32` keep the Win32 error code in the
+    // lower 16 bits
+    let code = code.0 as u32;
+    if code & 0xFFFF_0000 == 0x8007_0000 {
+        Some(code & 0xFFFF)
+    } else {
+        None
+    }
+}
+
+fn hresult_of(error: &(dyn Error + 'static)) -> Option<HRESULT> {
+    if let Some(e) = error.downcast_ref::<WindowsError>() {
+        return Some(e.code());
+    }
+    if let Some(e) = error.downcast_ref::<windows_result_crate::Error>() {
+        return Some(e.code());
+    }
+    io_hresult(error)
+}
+
+#[cfg(windows)]
+fn io_hresult(error: &(dyn Error + 'static)) -> Option<HRESULT> {
+    let e = error.downcast_ref::<std::io::Error>()?;
+    let code = e.raw_os_error()?;
+    Some(HRESULT::from_win32(code as u32))
+}
+
+#[cfg(not(windows))]
+fn io_hresult(_error: &(dyn Error + 'static)) -> Option<HRESULT> {
+    None
+}
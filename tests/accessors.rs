@@ -0,0 +1,88 @@
+use snafu::{ResultExt, Snafu};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+#[snafu(accessors)]
+enum Error {
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    ParseConfig {
+        path: PathBuf,
+        line: usize,
+    },
+    Unauthorized {
+        user: String,
+        roles: Vec<String>,
+    },
+    Shutdown,
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error>();
+}
+
+#[test]
+fn accessors_return_the_field_when_present() {
+    let e = std::fs::read("/this/does/not/exist")
+        .context(ReadFileSnafu {
+            path: "/this/does/not/exist",
+        })
+        .unwrap_err();
+    assert_eq!(e.path(), Some(Path::new("/this/does/not/exist")));
+    assert_eq!(e.line(), None);
+    assert_eq!(e.user(), None);
+
+    let e = ParseConfigSnafu {
+        path: "config.toml",
+        line: 3usize,
+    }
+    .build();
+    assert_eq!(e.path(), Some(Path::new("config.toml")));
+    assert_eq!(e.line(), Some(&3));
+
+    let e = UnauthorizedSnafu {
+        user: "alice",
+        roles: vec!["reader".to_string()],
+    }
+    .build();
+    assert_eq!(e.path(), None);
+    assert_eq!(e.user(), Some("alice"));
+    assert_eq!(e.roles(), Some(&["reader".to_string()][..]));
+}
+
+#[test]
+fn accessors_return_none_for_variants_without_fields() {
+    let e = ShutdownSnafu.build();
+    assert_eq!(e.path(), None);
+    assert_eq!(e.line(), None);
+    assert_eq!(e.user(), None);
+    assert_eq!(e.roles(), None);
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(accessors)]
+    enum Error<'a, T>
+    where
+        T: std::fmt::Debug,
+    {
+        Invalid { value: T, name: &'a str },
+    }
+
+    #[test]
+    fn accessors_are_available_for_generic_errors() {
+        let e: Error<'_, i32> = InvalidSnafu {
+            value: 7,
+            name: "seven",
+        }
+        .build();
+        assert_eq!(e.value(), Some(&7));
+        assert_eq!(e.name(), Some(&"seven"));
+    }
+}
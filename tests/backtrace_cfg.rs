@@ -0,0 +1,50 @@
+use snafu::{prelude::*, Backtrace, ErrorCompat};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Enabled {
+        #[cfg(test)]
+        backtrace: Backtrace,
+    },
+
+    Disabled {
+        #[cfg(not(test))]
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Disabled with {}", value))]
+    DisabledWithFields {
+        value: i32,
+        #[cfg(not(test))]
+        backtrace: Backtrace,
+    },
+}
+
+#[derive(Debug, Snafu)]
+struct StructError {
+    #[cfg(not(test))]
+    #[snafu(backtrace)]
+    trace: Backtrace,
+}
+
+#[test]
+fn enabled_backtrace_fields_are_captured() {
+    let e = EnabledSnafu.build();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
+
+#[test]
+fn disabled_backtrace_fields_are_omitted() {
+    let e = DisabledSnafu.build();
+    assert!(ErrorCompat::backtrace(&e).is_none());
+
+    let e = DisabledWithFieldsSnafu { value: 42 }.build();
+    assert!(ErrorCompat::backtrace(&e).is_none());
+    assert_eq!(e.to_string(), "Disabled with 42");
+}
+
+#[test]
+fn disabled_backtrace_fields_are_omitted_from_structs() {
+    let e = StructSnafu.build();
+    assert!(ErrorCompat::backtrace(&e).is_none());
+}
@@ -0,0 +1,57 @@
+use snafu::{prelude::*, ChainCompat};
+
+#[derive(Debug, Snafu)]
+enum Error<'a> {
+    #[snafu(display("Unexpected token {}", token))]
+    Unexpected {
+        token: &'a str,
+    },
+
+    Empty,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Unknown keyword {}", keyword))]
+struct KeywordError<'a> {
+    keyword: &'a str,
+}
+
+fn parse(input: &str) -> Result<char, Error<'_>> {
+    ensure!(!input.is_empty(), EmptySnafu);
+    input
+        .chars()
+        .next()
+        .filter(|c| c.is_numeric())
+        .context(UnexpectedSnafu { token: input })
+}
+
+fn check_error<E: std::error::Error>(_: &E) {}
+
+#[test]
+fn borrowed_errors_implement_error() {
+    let input = String::from("abc");
+    let e = parse(&input).unwrap_err();
+    check_error(&e);
+    assert_eq!(e.to_string(), "Unexpected token abc");
+
+    let e = KeywordSnafu {
+        keyword: &input[..],
+    }
+    .build();
+    check_error(&e);
+    assert_eq!(e.to_string(), "Unknown keyword abc");
+}
+
+#[test]
+fn borrowed_errors_can_be_boxed() {
+    let input = String::from("");
+    let e: Box<dyn std::error::Error + '_> = Box::new(parse(&input).unwrap_err());
+    assert_eq!(e.to_string(), "Empty");
+}
+
+#[test]
+fn borrowed_errors_can_be_iterated() {
+    let input = String::from("abc");
+    let e = parse(&input).unwrap_err();
+    assert_eq!(ChainCompat::new(&e).count(), 1);
+}
@@ -0,0 +1,26 @@
+use snafu::prelude::*;
+use std::error::Error as StdError;
+
+#[derive(Debug, Snafu)]
+struct LeafError;
+
+#[derive(Debug, Snafu)]
+struct OuterError {
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+#[test]
+fn boxed_composes_with_context() {
+    let r: Result<(), LeafError> = LeafSnafu.fail();
+    let e = r.boxed().context(OuterSnafu).unwrap_err();
+
+    let source = e.source().expect("Must have a source");
+    assert!(source.is::<LeafError>());
+}
+
+#[test]
+fn boxed_local_does_not_require_send() {
+    let r: Result<i32, LeafError> = Ok(1);
+    let r: Result<i32, Box<dyn StdError>> = r.boxed_local();
+    assert_eq!(r.unwrap(), 1);
+}
@@ -35,6 +35,7 @@ mod trait_object_send_sync {
 }
 
 #[derive(Debug, Snafu)]
+#[allow(clippy::enum_variant_names)]
 enum Error {
     TraitObject {
         user_id: i32,
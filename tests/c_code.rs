@@ -0,0 +1,98 @@
+use snafu::prelude::*;
+
+mod database {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(c_code = 20)]
+        Unavailable,
+
+        #[snafu(c_code = -21)]
+        Conflict { key: String },
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(c_code = 1)]
+enum Error {
+    #[snafu(c_code = 2)]
+    MissingUser {
+        id: u32,
+    },
+
+    InvalidName {
+        name: String,
+    },
+
+    #[snafu(flatten)]
+    Database {
+        source: database::Error,
+    },
+
+    #[snafu(c_code = 3)]
+    Upstream {
+        source: database::Error,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum WithoutDefault {
+    #[snafu(c_code = 42)]
+    Answer,
+
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(c_code = 13)]
+struct UnauthorizedError {
+    user: String,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(c_code = 99)]
+struct OpaqueError(Error);
+
+#[test]
+fn variants_use_their_code() {
+    assert_eq!(MissingUserSnafu { id: 1u32 }.build().error_code(), 2);
+    assert_eq!(
+        database::ConflictSnafu { key: "id" }.build().error_code(),
+        -21
+    );
+}
+
+#[test]
+fn variants_without_a_code_use_the_enum_default() {
+    assert_eq!(InvalidNameSnafu { name: "" }.build().error_code(), 1);
+}
+
+#[test]
+fn variants_without_a_code_or_default_use_negative_one() {
+    assert_eq!(AnswerSnafu.build().error_code(), 42);
+    assert_eq!(UnknownSnafu.build().error_code(), -1);
+}
+
+#[test]
+fn flattened_variants_use_the_code_of_their_source() {
+    let e = Error::from(database::UnavailableSnafu.build());
+    assert_eq!(e.error_code(), 20);
+}
+
+#[test]
+fn variants_with_a_code_ignore_their_source() {
+    let e = Err::<(), _>(database::UnavailableSnafu.build())
+        .context(UpstreamSnafu)
+        .unwrap_err();
+    assert_eq!(e.error_code(), 3);
+}
+
+#[test]
+fn structs_use_their_code() {
+    assert_eq!(UnauthorizedSnafu { user: "alice" }.build().error_code(), 13);
+
+    let e = OpaqueError(MissingUserSnafu { id: 1u32 }.build());
+    assert_eq!(e.error_code(), 99);
+}
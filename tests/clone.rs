@@ -0,0 +1,67 @@
+use snafu::{prelude::*, Backtrace, ErrorCompat};
+use std::sync::Arc;
+
+type SharedError = Arc<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Clone, Snafu)]
+enum Error {
+    #[snafu(display("Could not fetch {}", url))]
+    Fetch {
+        url: String,
+        source: SharedError,
+    },
+
+    Io {
+        #[snafu(source(from(std::io::Error, Arc::new)))]
+        source: Arc<std::io::Error>,
+    },
+
+    Missing {
+        backtrace: Arc<Backtrace>,
+    },
+
+    MaybeMissing {
+        backtrace: Arc<Option<Backtrace>>,
+    },
+}
+
+fn io_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, "boom")
+}
+
+#[test]
+fn shared_trait_object_sources_are_reported() {
+    let source: SharedError = Arc::new(io_error());
+    let e = Err::<(), _>(source)
+        .context(FetchSnafu { url: "/index" })
+        .unwrap_err();
+    let e = e.clone();
+
+    let chain: Vec<_> = e.iter_chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["Could not fetch /index", "boom"]);
+}
+
+#[test]
+fn sources_can_be_converted_into_shared_sources() {
+    let e = Err::<(), _>(io_error()).context(IoSnafu).unwrap_err();
+    let clone = e.clone();
+
+    match (e, clone) {
+        (Error::Io { source: a }, Error::Io { source: b }) => assert!(Arc::ptr_eq(&a, &b)),
+        _ => panic!("Expected the Io variant"),
+    }
+}
+
+#[test]
+fn backtraces_can_be_shared() {
+    let e = MissingSnafu.build();
+    let clone = e.clone();
+    assert!(ErrorCompat::backtrace(&clone).is_some());
+
+    let e = MaybeMissingSnafu.build();
+    let clone = e.clone();
+    assert_eq!(
+        ErrorCompat::backtrace(&e).is_some(),
+        ErrorCompat::backtrace(&clone).is_some(),
+    );
+}
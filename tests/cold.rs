@@ -0,0 +1,72 @@
+use snafu::{prelude::*, FromString};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Value {value} is out of range"))]
+    OutOfRange { value: u32 },
+
+    #[snafu(display("Could not parse the value"), cold(false))]
+    Parse { source: std::num::ParseIntError },
+
+    #[snafu(context(false))]
+    Io { source: std::io::Error },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(cold(false))]
+enum HotError {
+    #[snafu(display("The queue is full"))]
+    Full,
+
+    #[snafu(display("The queue is closed"), cold)]
+    Closed,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(whatever, display("{message}"))]
+struct WhateverError {
+    message: String,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The buffer is empty"), cold(false))]
+struct EmptyError;
+
+fn check(value: u32) -> Result<u32, Error> {
+    ensure!(value < 10, OutOfRangeSnafu { value });
+    Ok(value)
+}
+
+fn parse(text: &str) -> Result<u32, Error> {
+    let value = text.parse().context(ParseSnafu)?;
+    check(value)
+}
+
+#[test]
+fn cold_construction_paths_create_errors() {
+    assert_eq!(check(3).unwrap(), 3);
+    assert_eq!(
+        check(30).unwrap_err().to_string(),
+        "Value 30 is out of range",
+    );
+
+    let e: Error = std::io::Error::from(std::io::ErrorKind::Other).into();
+    match e {
+        Error::Io { .. } => {}
+        other => panic!("Unexpected error: {:?}", other),
+    }
+
+    let e = WhateverError::without_source("Something went wrong".to_string());
+    assert_eq!(e.to_string(), "Something went wrong");
+}
+
+#[test]
+fn hot_construction_paths_create_errors() {
+    assert_eq!(
+        parse("x").unwrap_err().to_string(),
+        "Could not parse the value",
+    );
+    assert_eq!(FullSnafu.build().to_string(), "The queue is full");
+    assert_eq!(ClosedSnafu.build().to_string(), "The queue is closed");
+    assert_eq!(EmptySnafu.build().to_string(), "The buffer is empty");
+}
@@ -0,0 +1,54 @@
+use snafu::{prelude::*, Backtrace, ChainCompat, CompatError, ErrorCompat};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Leaf { backtrace: Backtrace },
+
+    Outer { source: InnerError },
+}
+
+#[derive(Debug, Snafu)]
+struct InnerError;
+
+fn chain_len(error: &dyn CompatError) -> usize {
+    ChainCompat::new(error.as_error()).count()
+}
+
+#[test]
+fn error_compat_is_object_safe() {
+    let e = LeafSnafu.build();
+    let e: &dyn ErrorCompat = &e;
+    assert!(e.backtrace().is_some());
+}
+
+#[test]
+fn boxed_error_compat_trait_objects_implement_error_compat() {
+    let e: Box<dyn ErrorCompat> = Box::new(LeafSnafu.build());
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
+
+#[test]
+fn different_errors_can_be_reported_through_one_trait_object() {
+    let errors: Vec<Box<dyn CompatError>> = vec![
+        Box::new(LeafSnafu.build()),
+        Box::new(InnerSnafu.fail::<()>().context(OuterSnafu).unwrap_err()),
+        Box::new(InnerSnafu.build()),
+    ];
+
+    let has_backtrace: Vec<_> = errors
+        .iter()
+        .map(|e| ErrorCompat::backtrace(e).is_some())
+        .collect();
+    assert_eq!(has_backtrace, [true, false, false]);
+
+    let lengths: Vec<_> = errors.iter().map(|e| chain_len(&**e)).collect();
+    assert_eq!(lengths, [1, 2, 1]);
+}
+
+#[test]
+fn trait_objects_can_be_converted() {
+    let e = LeafSnafu.build();
+    let e: &dyn CompatError = &e;
+    assert_eq!(e.as_error().to_string(), "Leaf");
+    assert!(e.as_error_compat().backtrace().is_some());
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "rust_1_61")]
+
+use snafu::prelude::*;
+use std::{fmt::Debug, io};
+
+#[derive(Debug, Snafu)]
+#[snafu(kind, predicates, accessors)]
+enum Error {
+    #[snafu(display("The request timed out after {seconds}s"))]
+    Timeout {
+        seconds: u32,
+    },
+
+    #[snafu(display("Could not read the configuration"))]
+    Read {
+        #[snafu(source(accessor(source_io)))]
+        source: io::Error,
+    },
+
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(kind, predicates, accessors)]
+enum GenericError<T>
+where
+    T: Debug + Copy,
+{
+    Value { value: T },
+}
+
+const TIMEOUT: Error = Error::Timeout { seconds: 30 };
+const VALUE: GenericError<u8> = GenericError::Value { value: 7 };
+
+const TIMEOUT_KIND: ErrorKind = TIMEOUT.kind();
+const TIMEOUT_IS_UNKNOWN: bool = TIMEOUT.is_unknown();
+const TIMEOUT_SECONDS: Option<&u32> = TIMEOUT.seconds();
+const VALUE_IS_VALUE: bool = VALUE.is_value();
+
+#[test]
+fn kinds_and_predicates_are_const() {
+    assert_eq!(TIMEOUT_KIND, ErrorKind::Timeout);
+    assert!(!TIMEOUT_IS_UNKNOWN);
+    assert!(VALUE_IS_VALUE);
+}
+
+#[test]
+fn accessors_are_const() {
+    assert_eq!(TIMEOUT_SECONDS, Some(&30));
+
+    const fn has_io_source(e: &Error) -> bool {
+        e.source_io().is_some()
+    }
+    assert!(!has_io_source(&Error::Unknown));
+
+    let e = Err::<(), _>(io::Error::from(io::ErrorKind::NotFound))
+        .context(ReadSnafu)
+        .unwrap_err();
+    assert!(has_io_source(&e));
+}
@@ -0,0 +1,117 @@
+#![allow(dead_code)]
+
+use snafu::{ResultExt, Snafu};
+use std::fmt::Debug;
+
+#[derive(Debug, Snafu)]
+enum Error<const N: usize> {
+    #[snafu(display("Length {} exceeds the capacity of {}", len, N))]
+    Overflow {
+        len: usize,
+    },
+
+    Truncated {
+        data: [u8; N],
+    },
+
+    Inner {
+        source: BufferError<N>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+struct BufferError<const N: usize> {
+    len: usize,
+}
+
+#[derive(Debug, Snafu)]
+struct OpaqueError<const N: usize>(Error<N>);
+
+fn overflow<const N: usize>(len: usize) -> Result<(), Error<N>> {
+    OverflowSnafu { len }.fail()
+}
+
+fn inner<const N: usize>() -> Result<(), Error<N>> {
+    BufferSnafu { len: 1usize }.fail().context(InnerSnafu)
+}
+
+fn opaque<const N: usize>() -> Result<(), OpaqueError<N>> {
+    overflow(N + 1)?;
+    Ok(())
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error<4>>();
+    check::<BufferError<4>>();
+    check::<OpaqueError<4>>();
+}
+
+#[test]
+fn const_parameters_can_be_used_in_display() {
+    let e = overflow::<4>(5).unwrap_err();
+    assert_eq!(e.to_string(), "Length 5 exceeds the capacity of 4");
+}
+
+#[test]
+fn const_parameters_can_be_used_in_fields() {
+    let e: Error<2> = TruncatedSnafu { data: [1u8, 2] }.build();
+    assert!(matches!(e, Error::Truncated { data: [1, 2] }));
+}
+
+#[test]
+fn const_parameters_can_be_used_in_sources() {
+    let e = inner::<8>().unwrap_err();
+    assert!(matches!(e, Error::Inner { .. }));
+}
+
+#[test]
+fn const_parameters_can_be_used_in_opaque_errors() {
+    let e = opaque::<3>().unwrap_err();
+    assert_eq!(e.to_string(), "Length 4 exceeds the capacity of 3");
+}
+
+mod mixed {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(kind, predicates, accessors)]
+    enum Error<'a, T, const N: usize>
+    where
+        T: Debug,
+    {
+        Invalid { value: T, name: &'a str },
+    }
+
+    #[test]
+    fn lifetimes_types_and_consts_can_be_combined() {
+        let e: Error<'_, i32, 4> = InvalidSnafu {
+            value: 1,
+            name: "one",
+        }
+        .build();
+        assert!(e.is_invalid());
+        assert_eq!(e.kind(), ErrorKind::Invalid);
+        assert_eq!(e.value(), Some(&1));
+    }
+}
+
+mod defaults {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    enum Error<const N: usize = 4> {
+        Overflow { len: usize },
+    }
+
+    #[derive(Debug, Snafu)]
+    struct OpaqueError<const N: usize = 4>(Error<N>);
+
+    #[test]
+    fn const_parameters_can_have_defaults() {
+        fn check<T: std::error::Error>() {}
+        check::<Error>();
+        check::<OpaqueError>();
+    }
+}
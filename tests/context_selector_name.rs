@@ -1,6 +1,7 @@
 use snafu::Snafu;
 
 #[derive(Debug, Snafu)]
+#[allow(clippy::enum_variant_names)]
 enum Error {
     #[snafu(context(suffix(Moo)))]
     Alpha,
@@ -32,3 +33,44 @@ fn implements_error() {
     trimming_usage().unwrap_err();
     no_suffix_usage().unwrap_err();
 }
+
+mod strip_suffix {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(strip_suffix(Error, Failure))]
+    #[allow(clippy::enum_variant_names)]
+    enum Error {
+        ReadError,
+        WriteFailure,
+        Timeout,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(strip_suffix())]
+    #[allow(clippy::enum_variant_names)]
+    enum Unstripped {
+        ReadError,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(strip_suffix(Failure))]
+    struct ConnectFailure;
+
+    #[test]
+    fn strips_each_listed_word() {
+        let _: Error = ReadSnafu.build();
+        let _: Error = WriteSnafu.build();
+        let _: Error = TimeoutSnafu.build();
+    }
+
+    #[test]
+    fn an_empty_list_disables_stripping() {
+        let _: Unstripped = ReadErrorSnafu.build();
+    }
+
+    #[test]
+    fn strips_from_named_structs() {
+        let _: ConnectFailure = ConnectSnafu.build();
+    }
+}
@@ -0,0 +1,124 @@
+use snafu::prelude::*;
+use std::path::PathBuf;
+
+mod monolith {
+    use snafu::{prelude::*, Backtrace};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum MonolithError {
+        ReadConfig {
+            path: PathBuf,
+            source: std::io::Error,
+        },
+        ParseConfig {
+            line: u32,
+            backtrace: Backtrace,
+        },
+        Missing,
+        Positional(u8, bool),
+    }
+}
+
+mod config {
+    use super::monolith::MonolithError;
+    use snafu::{prelude::*, Backtrace};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(convert(from(
+        MonolithError,
+        ReadConfig => Read,
+        ParseConfig => Parse,
+        Missing,
+        Positional,
+    )))]
+    pub enum ConfigError {
+        Read {
+            path: PathBuf,
+            source: Box<std::io::Error>,
+        },
+        Parse {
+            line: u64,
+            backtrace: Backtrace,
+        },
+        Missing,
+        Positional(u16, bool),
+    }
+}
+
+mod generic {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    pub enum Source<T>
+    where
+        T: std::fmt::Debug + std::fmt::Display,
+    {
+        Value { value: T },
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(convert(from(Source<T>, Value => Wrapped)))]
+    pub enum Target<T>
+    where
+        T: std::fmt::Debug + std::fmt::Display,
+    {
+        Wrapped { value: T },
+    }
+}
+
+use config::ConfigError;
+use monolith::*;
+
+#[test]
+fn variants_are_mapped_by_name() {
+    let io = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+    let e = Err::<(), _>(io)
+        .context(ReadConfigSnafu { path: "a.toml" })
+        .unwrap_err();
+
+    match ConfigError::from(e) {
+        ConfigError::Read { path, source } => {
+            assert_eq!(path, PathBuf::from("a.toml"));
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        other => panic!("Unexpected variant {:?}", other),
+    }
+}
+
+#[test]
+fn fields_are_converted_with_into() {
+    let e: ConfigError = ParseConfigSnafu { line: 3u32 }.build().into();
+    assert!(matches!(e, ConfigError::Parse { line: 3, .. }));
+
+    let e: ConfigError = MonolithError::Positional(4, true).into();
+    assert!(matches!(e, ConfigError::Positional(4, true)));
+}
+
+#[test]
+fn variants_with_the_same_name_can_be_listed_alone() {
+    let e: ConfigError = MissingSnafu.build().into();
+    assert!(matches!(e, ConfigError::Missing));
+}
+
+#[test]
+fn question_mark_converts_between_enums() {
+    fn inner() -> Result<(), MonolithError> {
+        MissingSnafu.fail()
+    }
+
+    fn outer() -> Result<(), ConfigError> {
+        inner()?;
+        Ok(())
+    }
+
+    assert!(matches!(outer(), Err(ConfigError::Missing)));
+}
+
+#[test]
+fn generic_enums_can_be_converted() {
+    let e: generic::Target<i32> = generic::Source::Value { value: 1 }.into();
+    assert!(matches!(e, generic::Target::Wrapped { value: 1 }));
+}
@@ -0,0 +1,79 @@
+use snafu::prelude::*;
+
+#[derive(Snafu)]
+#[snafu(debug(stable))]
+enum Error {
+    #[snafu(display("Could not open {}", path))]
+    Open {
+        path: String,
+        source: InnerError,
+    },
+
+    Mismatch {
+        r#type: &'static str,
+        count: u8,
+    },
+
+    Wrapped(InnerError),
+
+    Unit,
+}
+
+#[derive(Snafu)]
+#[snafu(debug(stable))]
+struct InnerError {
+    id: u32,
+}
+
+#[derive(Snafu)]
+#[snafu(debug(stable))]
+enum GenericError<T> {
+    Value { value: T },
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error>();
+    check::<InnerError>();
+}
+
+#[test]
+fn named_fields_are_written_in_declaration_order() {
+    let e = MismatchSnafu {
+        r#type: "user",
+        count: 3u8,
+    }
+    .build();
+    assert_eq!(format!("{:?}", e), r#"Mismatch { type: "user", count: 3 }"#);
+}
+
+#[test]
+fn sources_use_their_own_debug_format() {
+    let e = Err::<(), _>(InnerSnafu { id: 7u32 }.build())
+        .context(OpenSnafu { path: "/tmp" })
+        .unwrap_err();
+    assert_eq!(
+        format!("{:?}", e),
+        r#"Open { path: "/tmp", source: InnerError { id: 7 } }"#,
+    );
+}
+
+#[test]
+fn tuple_and_unit_variants() {
+    let e = Error::Wrapped(InnerSnafu { id: 7u32 }.build());
+    assert_eq!(format!("{:?}", e), "Wrapped(InnerError { id: 7 })");
+    assert_eq!(format!("{:?}", UnitSnafu.build()), "Unit");
+}
+
+#[test]
+fn alternate_flag_does_not_change_the_output() {
+    let e = InnerSnafu { id: 7u32 }.build();
+    assert_eq!(format!("{:#?}", e), format!("{:?}", e));
+}
+
+#[test]
+fn generic_type_parameters_must_implement_debug() {
+    let e: GenericError<Vec<u8>> = ValueSnafu { value: vec![1, 2] }.build();
+    assert_eq!(format!("{:?}", e), "Value { value: [1, 2] }");
+}
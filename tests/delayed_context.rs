@@ -0,0 +1,50 @@
+use snafu::{prelude::*, DelayedContext};
+use std::{collections::VecDeque, io, thread};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not read {}", path))]
+    Read { path: String, source: io::Error },
+
+    #[snafu(display("Could not write {} bytes", len))]
+    Write { len: usize, source: io::Error },
+}
+
+type Context = Box<dyn DelayedContext<io::Error, Error> + Send>;
+
+fn failure() -> io::Result<()> {
+    Err(io::ErrorKind::Other.into())
+}
+
+#[test]
+fn selectors_of_different_types_can_be_queued() {
+    let mut pending: VecDeque<Context> = VecDeque::new();
+    pending.push_back(Box::new(ReadSnafu { path: "/tmp/a" }));
+    pending.push_back(Box::new(WriteSnafu { len: 42_usize }));
+
+    let read = failure().context(pending.pop_front().unwrap()).unwrap_err();
+    assert!(matches!(read, Error::Read { ref path, .. } if path == "/tmp/a"));
+
+    let write = failure().context(pending.pop_front().unwrap()).unwrap_err();
+    assert!(matches!(write, Error::Write { len: 42, .. }));
+}
+
+#[test]
+fn boxed_selectors_can_be_sent_between_threads() {
+    let context: Context = Box::new(ReadSnafu {
+        path: String::from("/tmp/b"),
+    });
+
+    let error = thread::spawn(move || failure().context(context))
+        .join()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Could not read /tmp/b");
+}
+
+#[test]
+fn boxed_selectors_can_be_used_lazily() {
+    let context: Context = Box::new(WriteSnafu { len: 1_usize });
+    let error = failure().with_context(|| context).unwrap_err();
+    assert_eq!(error.to_string(), "Could not write 1 bytes");
+}
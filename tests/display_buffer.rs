@@ -0,0 +1,85 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(display_buffer(16))]
+enum Error {
+    #[snafu(display("Sensor {id} failed"))]
+    Sensor { id: u8 },
+
+    #[snafu(display("The device at {address} did not respond to any request"))]
+    Timeout { address: u16 },
+
+    #[snafu(display("Température trop élevée"))]
+    Temperature,
+
+    #[snafu(flatten)]
+    Bus { source: BusError },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The bus is busy for {} cycles", cycles))]
+struct BusError {
+    cycles: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_buffer(8), display("Value {value} is out of range"))]
+struct RangeError<T: std::fmt::Display> {
+    value: T,
+}
+
+#[test]
+fn messages_that_fit_are_unchanged() {
+    assert_eq!(
+        SensorSnafu { id: 7u8 }.build().to_string(),
+        "Sensor 7 failed"
+    );
+}
+
+#[test]
+fn long_messages_are_truncated() {
+    let e = TimeoutSnafu { address: 42u16 }.build();
+    assert_eq!(e.to_string(), "The device at 42");
+}
+
+#[test]
+fn truncation_keeps_whole_characters() {
+    // "Température trop" is 17 bytes, so the last character that
+    // fits ends on byte 16.
+    assert_eq!(TemperatureSnafu.build().to_string(), "Température tro");
+}
+
+#[test]
+fn flattened_messages_are_truncated() {
+    let e = Error::from(BusSnafu { cycles: 3u32 }.build());
+    assert_eq!(e.to_string(), "The bus is busy ");
+}
+
+#[test]
+fn format_into_uses_the_given_buffer() {
+    let e = TimeoutSnafu { address: 42u16 }.build();
+
+    let mut buffer = [0; 64];
+    assert_eq!(e.format_into(&mut buffer), "The device at 42");
+
+    let mut buffer = [0; 4];
+    assert_eq!(e.format_into(&mut buffer), "The ");
+}
+
+#[test]
+fn structs_are_truncated() {
+    let e: RangeError<i32> = RangeSnafu { value: 1000 }.build();
+    assert_eq!(e.to_string(), "Value 10");
+
+    let mut buffer = [0; 3];
+    assert_eq!(e.format_into(&mut buffer), "Val");
+}
+
+#[test]
+fn format_into_accepts_any_display() {
+    let mut buffer = [0; 5];
+    assert_eq!(snafu::format_into(&mut buffer, &"ab€de"), "ab€");
+
+    let mut buffer = [];
+    assert_eq!(snafu::format_into(&mut buffer, &1234), "");
+}
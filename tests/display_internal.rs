@@ -0,0 +1,88 @@
+use snafu::{prelude::*, FormatInternal};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not sign in"))]
+    #[snafu(display(internal("Password hash mismatch for user {}", user_id)))]
+    Login { user_id: u32 },
+
+    #[snafu(display("The database is unavailable"))]
+    #[snafu(display(internal("Connection to {host} failed: {source}")))]
+    Database {
+        host: String,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Not found"))]
+    NotFound,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Request failed"))]
+#[snafu(display(internal("Request {} failed", id)))]
+struct RequestError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+struct OpaqueError(Error);
+
+#[derive(Debug, Snafu)]
+#[snafu(display(internal("Generic value {}", value)))]
+struct GenericError<T>
+where
+    T: std::fmt::Display,
+{
+    value: T,
+}
+
+#[test]
+fn variants_use_the_internal_format() {
+    let e = LoginSnafu { user_id: 42u32 }.build();
+    assert_eq!(e.to_string(), "Could not sign in");
+    assert_eq!(
+        e.display_internal().to_string(),
+        "Password hash mismatch for user 42"
+    );
+}
+
+#[test]
+fn internal_format_can_reference_fields_by_name() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "refused");
+    let e = Err::<(), _>(io)
+        .context(DatabaseSnafu { host: "db1" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "The database is unavailable");
+    assert_eq!(
+        e.display_internal().to_string(),
+        "Connection to db1 failed: refused"
+    );
+}
+
+#[test]
+fn variants_without_an_internal_format_use_display() {
+    let e = NotFoundSnafu.build();
+    assert_eq!(e.display_internal().to_string(), "Not found");
+}
+
+#[test]
+fn structs_use_the_internal_format() {
+    let e = RequestSnafu { id: 7u32 }.build();
+    assert_eq!(e.to_string(), "Request failed");
+    assert_eq!(e.display_internal().to_string(), "Request 7 failed");
+
+    let e: GenericError<i32> = GenericSnafu { value: 1 }.build();
+    assert_eq!(e.display_internal().to_string(), "Generic value 1");
+}
+
+#[test]
+fn opaque_errors_use_display() {
+    let e = OpaqueError::from(LoginSnafu { user_id: 1u32 }.build());
+    assert_eq!(e.display_internal().to_string(), "Could not sign in");
+}
+
+#[test]
+fn trait_objects_use_the_internal_format() {
+    let e: Box<dyn FormatInternal> = Box::new(RequestSnafu { id: 7u32 }.build());
+    assert_eq!(e.display_internal().to_string(), "Request 7 failed");
+}
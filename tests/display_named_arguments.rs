@@ -0,0 +1,106 @@
+use snafu::Snafu;
+
+const MAX_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("{host}:{port}"))]
+    InOrder { host: String, port: u16 },
+
+    #[snafu(display("Port {port} of {host}"))]
+    Reordered { host: String, port: u16 },
+
+    #[snafu(display("{name}, {name}, wherefore art thou {name}?"))]
+    Repeated { name: String },
+
+    #[snafu(display("{user} ({user:?}) in {}", place))]
+    MixedWithPositional { user: String, place: String },
+
+    #[snafu(display("{id:>width$}", width = 6))]
+    WithExplicitNamedArgument { id: u32 },
+
+    #[snafu(display("{id:>width$}|"))]
+    WithNamedWidth { id: u32, width: usize },
+
+    #[snafu(display("{user}", user = user.to_uppercase()))]
+    ExplicitNamedArgumentWins { user: String },
+
+    #[snafu(display("{{literal}} braces around {value}"))]
+    EscapedBraces { value: i32 },
+
+    #[snafu(display("Gave up after {attempt} of {}", MAX_ATTEMPTS))]
+    WithConstant { attempt: u32 },
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error>();
+}
+
+#[test]
+fn named_arguments_in_declaration_order() {
+    let e = InOrderSnafu {
+        host: "example.com",
+        port: 80u16,
+    }
+    .build();
+    assert_eq!(e.to_string(), "example.com:80");
+}
+
+#[test]
+fn named_arguments_can_be_reordered() {
+    let e = ReorderedSnafu {
+        host: "example.com",
+        port: 80u16,
+    }
+    .build();
+    assert_eq!(e.to_string(), "Port 80 of example.com");
+}
+
+#[test]
+fn named_arguments_can_be_repeated() {
+    let e = RepeatedSnafu { name: "Romeo" }.build();
+    assert_eq!(e.to_string(), "Romeo, Romeo, wherefore art thou Romeo?");
+}
+
+#[test]
+fn named_arguments_can_be_mixed_with_positional_arguments() {
+    let e = MixedWithPositionalSnafu {
+        user: "alice",
+        place: "Wonderland",
+    }
+    .build();
+    assert_eq!(e.to_string(), r#"alice ("alice") in Wonderland"#);
+}
+
+#[test]
+fn explicit_named_arguments_are_preserved() {
+    let e = WithExplicitNamedArgumentSnafu { id: 42u32 }.build();
+    assert_eq!(e.to_string(), "    42");
+
+    let e = ExplicitNamedArgumentWinsSnafu { user: "bob" }.build();
+    assert_eq!(e.to_string(), "BOB");
+}
+
+#[test]
+fn fields_can_be_named_width_arguments() {
+    let e = WithNamedWidthSnafu {
+        id: 7u32,
+        width: 3usize,
+    }
+    .build();
+    assert_eq!(e.to_string(), "  7|");
+}
+
+#[test]
+fn escaped_braces_are_not_arguments() {
+    let e = EscapedBracesSnafu { value: 1 }.build();
+    assert_eq!(e.to_string(), "{literal} braces around 1");
+}
+
+#[test]
+fn constants_can_still_be_used() {
+    let e = WithConstantSnafu { attempt: 3u32 }.build();
+    assert_eq!(e.to_string(), "Gave up after 3 of 3");
+}
@@ -0,0 +1,98 @@
+#![allow(dead_code)]
+
+use snafu::prelude::*;
+
+const LIMIT: usize = 10;
+static UNIT: &str = "bytes";
+
+fn describe(size: usize) -> String {
+    format!("{} {}", size, UNIT)
+}
+
+mod limits {
+    pub const MAXIMUM: usize = 100;
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Size {} exceeds the limit of {}", describe(*size), LIMIT))]
+    TooLarge { size: usize },
+
+    #[snafu(display("Size must be below {} {}", limits::MAXIMUM, UNIT))]
+    WayTooLarge,
+
+    #[snafu(display("Expected {} {}", Self::EXPECTED, UNIT))]
+    Unexpected,
+
+    #[snafu(display("Size {size} exceeds {limit}", limit = LIMIT))]
+    Named { size: usize },
+}
+
+impl Error {
+    const EXPECTED: usize = 42;
+}
+
+macro_rules! generate_error {
+    ($name:ident, $limit:expr) => {
+        mod $name {
+            use snafu::prelude::*;
+
+            const GENERATED_LIMIT: usize = $limit;
+
+            fn unit() -> &'static str {
+                super::UNIT
+            }
+
+            #[derive(Debug, Snafu)]
+            #[snafu(visibility(pub(super)))]
+            pub(super) enum Error {
+                #[snafu(display("Size {} exceeds {} {}", size, GENERATED_LIMIT, unit()))]
+                TooLarge { size: usize },
+
+                #[snafu(display("Size exceeds {}", super::limits::MAXIMUM))]
+                WayTooLarge,
+            }
+        }
+    };
+}
+
+generate_error!(generated, 5);
+
+#[test]
+fn can_reference_constants_statics_and_functions() {
+    assert_eq!(
+        TooLargeSnafu { size: 20usize }.build().to_string(),
+        "Size 20 bytes exceeds the limit of 10",
+    );
+}
+
+#[test]
+fn can_reference_items_by_path() {
+    assert_eq!(
+        WayTooLargeSnafu.build().to_string(),
+        "Size must be below 100 bytes",
+    );
+    assert_eq!(UnexpectedSnafu.build().to_string(), "Expected 42 bytes");
+}
+
+#[test]
+fn can_reference_constants_as_named_arguments() {
+    assert_eq!(
+        NamedSnafu { size: 11usize }.build().to_string(),
+        "Size 11 exceeds 10",
+    );
+}
+
+#[test]
+fn can_reference_items_in_a_generated_module() {
+    assert_eq!(
+        generated::TooLargeSnafu { size: 7usize }
+            .build()
+            .to_string(),
+        "Size 7 exceeds 5 bytes",
+    );
+    assert_eq!(
+        generated::WayTooLargeSnafu.build().to_string(),
+        "Size exceeds 100",
+    );
+}
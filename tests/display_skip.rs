@@ -0,0 +1,91 @@
+use snafu::prelude::*;
+use std::fmt;
+
+#[derive(Debug, Snafu)]
+#[snafu(display(skip))]
+enum Error {
+    Parse { line: usize, column: usize },
+    Io { source: std::io::Error },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse { line, column } => {
+                write!(f, "Syntax error")?;
+                if *line > 0 {
+                    write!(f, " on line {}", line)?;
+                }
+                write!(f, ", column {}", column)
+            }
+            Error::Io { .. } => write!(f, "Input could not be read"),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display(skip))]
+struct StructError {
+    value: i32,
+}
+
+impl fmt::Display for StructError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handwritten {}", self.value)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display(skip))]
+struct OpaqueError(Error);
+
+impl fmt::Display for OpaqueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Opaque: {}", self.0)
+    }
+}
+
+#[test]
+fn enums_use_the_handwritten_display() {
+    let e = ParseSnafu {
+        line: 3usize,
+        column: 7usize,
+    }
+    .build();
+    assert_eq!(e.to_string(), "Syntax error on line 3, column 7");
+
+    let e = ParseSnafu {
+        line: 0usize,
+        column: 7usize,
+    }
+    .build();
+    assert_eq!(e.to_string(), "Syntax error, column 7");
+}
+
+#[test]
+fn the_rest_of_the_implementation_is_still_generated() {
+    use std::error::Error as _;
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "boom");
+    let e = Err::<(), _>(io).context(IoSnafu).unwrap_err();
+    assert_eq!(e.to_string(), "Input could not be read");
+    assert_eq!(e.source().unwrap().to_string(), "boom");
+}
+
+#[test]
+fn structs_use_the_handwritten_display() {
+    let e = StructSnafu { value: 1 }.build();
+    assert_eq!(e.to_string(), "Handwritten 1");
+}
+
+#[test]
+fn opaque_structs_use_the_handwritten_display() {
+    let e = OpaqueError::from(
+        ParseSnafu {
+            line: 1usize,
+            column: 2usize,
+        }
+        .build(),
+    );
+    assert_eq!(e.to_string(), "Opaque: Syntax error on line 1, column 2");
+}
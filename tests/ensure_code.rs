@@ -0,0 +1,66 @@
+#![no_std]
+
+use snafu::{ensure_code, FromCode};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ErrorCode {
+    InvalidInput,
+    TooLarge,
+}
+
+#[derive(Debug, PartialEq)]
+struct Error {
+    code: ErrorCode,
+    attempt: u8,
+}
+
+impl FromCode<ErrorCode> for Error {
+    fn from_code(code: ErrorCode) -> Self {
+        Error { code, attempt: 1 }
+    }
+}
+
+fn check(value: u8) -> Result<u8, Error> {
+    ensure_code!(value > 0, ErrorCode::InvalidInput);
+    ensure_code!(value < 100, ErrorCode::TooLarge,);
+    Ok(value)
+}
+
+fn check_code(value: u8) -> Result<u8, ErrorCode> {
+    ensure_code!(value > 0, ErrorCode::InvalidInput);
+    Ok(value)
+}
+
+#[test]
+fn returns_the_error_built_from_the_code() {
+    assert_eq!(check(42), Ok(42));
+    assert_eq!(
+        check(0),
+        Err(Error {
+            code: ErrorCode::InvalidInput,
+            attempt: 1,
+        }),
+    );
+    assert_eq!(check(200).map_err(|e| e.code), Err(ErrorCode::TooLarge));
+}
+
+#[test]
+fn the_code_can_be_the_error() {
+    assert_eq!(check_code(1), Ok(1));
+    assert_eq!(check_code(0), Err(ErrorCode::InvalidInput));
+}
+
+mod prelude {
+    use super::ErrorCode;
+    use snafu::prelude::*;
+
+    fn check(value: u8) -> Result<(), ErrorCode> {
+        ensure_code!(value > 0, ErrorCode::InvalidInput);
+        Ok(())
+    }
+
+    #[test]
+    fn is_in_the_prelude() {
+        assert_eq!(check(0), Err(ErrorCode::InvalidInput));
+    }
+}
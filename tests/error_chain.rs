@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use snafu::{ChainCompat, Snafu};
 use std::fmt::Debug;
 
@@ -36,9 +38,9 @@ fn chain_compat_iterates() {
 
     let errors: Vec<_> = ChainCompat::new(&error).collect();
 
-    assert_eq_debug(&errors[0], &error);
-    assert_eq_debug(&errors[1], &middle_error);
-    assert_eq_debug(&errors[2], &bottom_error);
+    assert_eq_debug(errors[0], &error);
+    assert_eq_debug(errors[1], &middle_error);
+    assert_eq_debug(errors[2], &bottom_error);
 }
 
 #[test]
@@ -51,7 +53,84 @@ fn errorcompat_chain_iterates() {
 
     let errors: Vec<_> = ErrorCompat::iter_chain(&error).collect();
 
-    assert_eq_debug(&errors[0], &error);
-    assert_eq_debug(&errors[1], &middle_error);
-    assert_eq_debug(&errors[2], &bottom_error);
+    assert_eq_debug(errors[0], &error);
+    assert_eq_debug(errors[1], &middle_error);
+    assert_eq_debug(errors[2], &bottom_error);
+}
+
+#[test]
+fn chain_len_counts_every_error() {
+    use snafu::{chain_len, IntoError};
+
+    let bottom_error = InvalidUserSnafu { user_id: 12 }.build();
+    assert_eq!(chain_len(&bottom_error), 1);
+
+    let middle_error = CheckUserSnafu.into_error(bottom_error);
+    let error = AccessControlSnafu.into_error(middle_error);
+    assert_eq!(chain_len(&error), 3);
+}
+
+#[test]
+fn chain_compat_iterates_deep_chains() {
+    use snafu::IntoError;
+
+    #[derive(Debug, Snafu)]
+    enum Nested {
+        Leaf,
+        Wrapped { source: Box<Nested> },
+    }
+
+    let mut error = LeafSnafu.build();
+    for _ in 0..1_000 {
+        error = WrappedSnafu.into_error(Box::new(error));
+    }
+
+    assert_eq!(ChainCompat::new(&error).count(), 1_001);
+}
+
+mod cleaned_error_text {
+    use snafu::{prelude::*, CleanedErrorText};
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Could not read config: {}", source))]
+    struct RepeatingError {
+        source: MiddleError,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Could not open file"))]
+    struct MiddleError {
+        source: LeafError,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("No such file"))]
+    struct LeafError;
+
+    fn messages(error: &dyn std::error::Error) -> Vec<(String, bool)> {
+        CleanedErrorText::new(error)
+            .map(|(_, message, cleaned)| (message, cleaned))
+            .collect()
+    }
+
+    #[test]
+    fn repeated_source_messages_are_removed() {
+        let error = RepeatingError {
+            source: MiddleError { source: LeafError },
+        };
+
+        assert_eq!(
+            messages(&error),
+            [
+                ("Could not read config".to_string(), true),
+                ("Could not open file".to_string(), false),
+                ("No such file".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_errors_are_unchanged() {
+        assert_eq!(messages(&LeafError), [("No such file".to_string(), false)]);
+    }
 }
@@ -0,0 +1,95 @@
+use snafu::{prelude::*, ErrorFields};
+
+#[derive(Debug, Snafu)]
+#[snafu(fields)]
+enum Error {
+    ReadFile {
+        path: String,
+        attempt: u8,
+        source: std::io::Error,
+    },
+    Unauthorized {
+        user: String,
+    },
+    Shutdown,
+    #[snafu(context(false))]
+    Forwarded {
+        source: std::fmt::Error,
+    },
+    Positional(u32, bool),
+    Raw {
+        r#type: String,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(fields)]
+enum GenericError<T> {
+    Value { value: T },
+}
+
+fn collect(error: &dyn ErrorFields) -> Vec<(&'static str, String)> {
+    let mut pairs = Vec::new();
+    error.visit_fields(&mut |key, value| pairs.push((key, value.to_string())));
+    pairs
+}
+
+#[test]
+fn visits_context_fields_in_order() {
+    let e = std::fs::read("/this/does/not/exist")
+        .context(ReadFileSnafu {
+            path: "/this/does/not/exist",
+            attempt: 3u8,
+        })
+        .unwrap_err();
+    assert_eq!(
+        collect(&e),
+        [
+            ("path", "/this/does/not/exist".to_string()),
+            ("attempt", "3".to_string()),
+        ],
+    );
+
+    let e = UnauthorizedSnafu { user: "alice" }.build();
+    assert_eq!(collect(&e), [("user", "alice".to_string())]);
+}
+
+#[test]
+fn variants_without_context_fields_visit_nothing() {
+    assert!(collect(&ShutdownSnafu.build()).is_empty());
+    assert!(collect(&Error::from(std::fmt::Error)).is_empty());
+}
+
+#[test]
+fn positional_and_raw_fields_use_their_names() {
+    let e = PositionalSnafu(7u32, true).build();
+    assert_eq!(
+        collect(&e),
+        [("_0", "7".to_string()), ("_1", "true".to_string())],
+    );
+
+    let e = RawSnafu { r#type: "admin" }.build();
+    assert_eq!(collect(&e), [("type", "admin".to_string())]);
+}
+
+#[test]
+fn generic_fields_are_visited() {
+    fn check<E: ErrorFields>(_: &E) {}
+
+    let e: GenericError<i32> = ValueSnafu { value: -1 }.build();
+    check(&e);
+    assert_eq!(collect(&e), [("value", "-1".to_string())]);
+}
+
+#[test]
+fn whatever_visits_its_key_value_pairs() {
+    fn connect(attempt: u8) -> Result<(), snafu::Whatever> {
+        whatever!("Failed to connect"; attempt = attempt, retry = true);
+    }
+
+    let e = connect(2).unwrap_err();
+    assert_eq!(
+        collect(&e),
+        [("attempt", "2".to_string()), ("retry", "true".to_string())],
+    );
+}
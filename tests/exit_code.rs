@@ -0,0 +1,59 @@
+use snafu::{prelude::*, ErrorCompat};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(exit_code(66))]
+    MissingInput {
+        path: String,
+    },
+
+    #[snafu(exit_code(74))]
+    Write {
+        source: std::io::Error,
+    },
+
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(exit_code(78))]
+struct ConfigError {
+    key: String,
+}
+
+#[derive(Debug, Snafu)]
+struct OpaqueError(Error);
+
+#[test]
+fn variants_use_their_exit_code() {
+    let e = MissingInputSnafu { path: "input.txt" }.build();
+    assert_eq!(e.exit_code(), Some(66));
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "Disk is full");
+    let e = Err::<(), _>(io).context(WriteSnafu).unwrap_err();
+    assert_eq!(e.exit_code(), Some(74));
+}
+
+#[test]
+fn variants_without_an_exit_code_have_none() {
+    assert_eq!(UnknownSnafu.build().exit_code(), None);
+}
+
+#[test]
+fn structs_use_their_exit_code() {
+    let e = ConfigSnafu { key: "port" }.build();
+    assert_eq!(e.exit_code(), Some(78));
+}
+
+#[test]
+fn opaque_errors_use_the_exit_code_of_the_inner_error() {
+    let e = OpaqueError(MissingInputSnafu { path: "input.txt" }.build());
+    assert_eq!(e.exit_code(), Some(66));
+}
+
+#[test]
+fn references_and_boxes_use_the_exit_code_of_the_error() {
+    let e = ConfigSnafu { key: "port" }.build();
+    assert_eq!(ErrorCompat::exit_code(&&e), Some(78));
+    assert_eq!(ErrorCompat::exit_code(&Box::new(e)), Some(78));
+}
@@ -0,0 +1,49 @@
+use snafu::{prelude::*, ErrorCompat};
+use std::io;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Load { source: ConfigError },
+    Missing,
+}
+
+#[derive(Debug, Snafu)]
+enum ConfigError {
+    Read { source: io::Error },
+    Parse { source: std::num::ParseIntError },
+}
+
+fn load(io_error: bool) -> Error {
+    let config = if io_error {
+        let io = io::Error::new(io::ErrorKind::NotFound, "config.toml");
+        Err::<(), _>(io).context(ReadSnafu).unwrap_err()
+    } else {
+        let parse = "x".parse::<i32>().unwrap_err();
+        Err::<(), _>(parse).context(ParseSnafu).unwrap_err()
+    };
+    Err::<(), _>(config).context(LoadSnafu).unwrap_err()
+}
+
+#[test]
+fn finds_the_first_source_of_the_type() {
+    let error = load(true);
+
+    let io = error
+        .find_source::<io::Error>()
+        .expect("Must find the I/O error");
+    assert_eq!(io.kind(), io::ErrorKind::NotFound);
+
+    assert!(error.find_source::<ConfigError>().is_some());
+}
+
+#[test]
+fn skips_the_current_error() {
+    let error = load(true);
+    assert!(error.find_source::<Error>().is_none());
+}
+
+#[test]
+fn returns_none_when_no_source_matches() {
+    assert!(load(false).find_source::<io::Error>().is_none());
+    assert!(MissingSnafu.build().find_source::<io::Error>().is_none());
+}
@@ -0,0 +1,100 @@
+use snafu::{prelude::*, Backtrace, ErrorCompat};
+use std::error::Error as _;
+
+mod database {
+    use snafu::{prelude::*, Backtrace};
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(display("Could not connect to {}", host))]
+        Connect {
+            host: String,
+            source: std::io::Error,
+            backtrace: Backtrace,
+        },
+
+        #[snafu(display("The table {} is missing", table))]
+        MissingTable { table: String },
+    }
+}
+
+mod network {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(display("The peer hung up"))]
+        HungUp,
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(flatten)]
+    Database { source: database::Error },
+
+    #[snafu(flatten)]
+    Network(network::Error),
+
+    #[snafu(display("Invalid configuration"))]
+    Config,
+}
+
+fn connect() -> Result<(), database::Error> {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "connection refused");
+    Err(io).context(database::ConnectSnafu { host: "db.example" })
+}
+
+fn run() -> Result<(), Error> {
+    connect()?;
+    Ok(())
+}
+
+#[test]
+fn display_is_that_of_the_inner_error() {
+    let e = run().unwrap_err();
+    assert_eq!(e.to_string(), "Could not connect to db.example");
+
+    let e = Error::from(network::HungUpSnafu.build());
+    assert_eq!(e.to_string(), "The peer hung up");
+
+    assert_eq!(ConfigSnafu.build().to_string(), "Invalid configuration");
+}
+
+#[test]
+fn source_is_that_of_the_inner_error() {
+    let e = run().unwrap_err();
+    let source = e.source().expect("Must have a source");
+    assert_eq!(source.to_string(), "connection refused");
+
+    let e = Error::from(database::MissingTableSnafu { table: "users" }.build());
+    assert!(e.source().is_none());
+}
+
+#[test]
+fn messages_are_not_repeated_in_the_chain() {
+    let e = run().unwrap_err();
+    let messages: Vec<_> = e.iter_chain().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        ["Could not connect to db.example", "connection refused"]
+    );
+}
+
+#[test]
+fn backtrace_is_that_of_the_inner_error() {
+    let e = run().unwrap_err();
+    let inner = match &e {
+        Error::Database { source } => source,
+        _ => panic!("Expected a database error"),
+    };
+
+    let outer_backtrace: Option<&Backtrace> = ErrorCompat::backtrace(&e);
+    let inner_backtrace: Option<&Backtrace> = ErrorCompat::backtrace(inner);
+    assert_eq!(
+        outer_backtrace.map(|b| b as *const Backtrace),
+        inner_backtrace.map(|b| b as *const Backtrace),
+    );
+}
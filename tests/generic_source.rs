@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+
+use snafu::{prelude::*, Backtrace, ErrorCompat};
+use std::io;
+
+#[derive(Debug, Snafu)]
+enum Error<E> {
+    Layer {
+        source: E,
+    },
+
+    #[snafu(display("Could not open {}", path))]
+    Open {
+        path: String,
+        source: Box<E>,
+    },
+
+    Converted {
+        #[snafu(source(from(String, MessageError::new)))]
+        source: MessageError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("{}", message))]
+struct MessageError {
+    message: String,
+}
+
+impl MessageError {
+    fn new(message: String) -> Self {
+        MessageError { message }
+    }
+}
+
+#[derive(Debug, Snafu)]
+struct WrapperError<E> {
+    source: E,
+}
+
+#[derive(Debug, Snafu)]
+enum DelegatingError<E> {
+    Delegate {
+        #[snafu(backtrace)]
+        source: E,
+    },
+}
+
+#[derive(Debug, Snafu)]
+struct InnerError {
+    backtrace: Backtrace,
+}
+
+fn io_failure() -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Other, "disk on fire"))
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error<io::Error>>();
+    check::<WrapperError<io::Error>>();
+    check::<DelegatingError<InnerError>>();
+}
+
+#[test]
+fn sources_can_be_generic() {
+    let e: Error<io::Error> = io_failure().context(LayerSnafu).unwrap_err();
+    assert_eq!(e.to_string(), "Layer: disk on fire");
+
+    let source = std::error::Error::source(&e).expect("Must have a source");
+    assert_eq!(source.to_string(), "disk on fire");
+}
+
+#[test]
+fn boxed_sources_can_be_generic() {
+    let e: Error<io::Error> = io_failure()
+        .map_err(Box::new)
+        .context(OpenSnafu { path: "/tmp" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Could not open /tmp");
+
+    let chain: Vec<_> = e.iter_chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["Could not open /tmp", "disk on fire"]);
+}
+
+#[test]
+fn non_generic_sources_are_unaffected() {
+    let e: Error<io::Error> = Err::<(), _>(String::from("bad"))
+        .context(ConvertedSnafu)
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Converted: bad");
+}
+
+#[test]
+fn structs_can_have_generic_sources() {
+    let e: WrapperError<io::Error> = io_failure().context(WrapperSnafu).unwrap_err();
+    assert_eq!(e.to_string(), "WrapperError: disk on fire");
+}
+
+#[test]
+fn backtraces_can_be_delegated_to_generic_sources() {
+    let e = InnerSnafu.fail::<()>().context(DelegateSnafu).unwrap_err();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
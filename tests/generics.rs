@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use snafu::{Backtrace, ResultExt, Snafu};
 
 type BoxError = Box<dyn std::error::Error>;
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 mod default_with_lifetime {
     use snafu::{AsErrorSource, Snafu};
     use std::fmt::{Debug, Display};
@@ -0,0 +1,130 @@
+use snafu::prelude::*;
+
+mod database {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(http_status(503))]
+        Unavailable,
+
+        #[snafu(http_status(409))]
+        Conflict { key: String },
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status(400))]
+enum Error {
+    #[snafu(http_status(404))]
+    MissingUser {
+        id: u32,
+    },
+
+    InvalidName {
+        name: String,
+    },
+
+    #[snafu(flatten)]
+    Database {
+        source: database::Error,
+    },
+
+    #[snafu(http_status(502))]
+    Upstream {
+        source: database::Error,
+    },
+
+    #[snafu(http_status)]
+    Boxed {
+        source: Box<database::Error>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum WithoutDefault {
+    #[snafu(http_status(418))]
+    Teapot,
+
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status(401))]
+struct UnauthorizedError {
+    user: String,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status)]
+struct WrappedError {
+    source: database::Error,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status)]
+struct OpaqueError(Error);
+
+#[derive(Debug, Snafu)]
+#[snafu(http_status(500))]
+struct FixedOpaqueError(Error);
+
+#[test]
+fn variants_use_their_status() {
+    assert_eq!(MissingUserSnafu { id: 1u32 }.build().http_status(), 404);
+}
+
+#[test]
+fn variants_without_a_status_use_the_enum_default() {
+    assert_eq!(InvalidNameSnafu { name: "" }.build().http_status(), 400);
+}
+
+#[test]
+fn variants_without_a_status_or_default_are_internal_server_errors() {
+    assert_eq!(TeapotSnafu.build().http_status(), 418);
+    assert_eq!(UnknownSnafu.build().http_status(), 500);
+}
+
+#[test]
+fn flattened_variants_use_the_status_of_their_source() {
+    let e = Error::from(database::UnavailableSnafu.build());
+    assert_eq!(e.http_status(), 503);
+}
+
+#[test]
+fn variants_can_use_the_status_of_their_source() {
+    let source = Box::new(database::ConflictSnafu { key: "id" }.build());
+    let e = Err::<(), _>(source).context(BoxedSnafu).unwrap_err();
+    assert_eq!(e.http_status(), 409);
+}
+
+#[test]
+fn variants_with_a_status_ignore_their_source() {
+    let e = Err::<(), _>(database::UnavailableSnafu.build())
+        .context(UpstreamSnafu)
+        .unwrap_err();
+    assert_eq!(e.http_status(), 502);
+}
+
+#[test]
+fn structs_use_their_status() {
+    assert_eq!(
+        UnauthorizedSnafu { user: "alice" }.build().http_status(),
+        401
+    );
+
+    let e = Err::<(), _>(database::UnavailableSnafu.build())
+        .context(WrappedSnafu)
+        .unwrap_err();
+    assert_eq!(e.http_status(), 503);
+}
+
+#[test]
+fn opaque_errors_can_use_the_status_of_the_inner_error() {
+    let e = OpaqueError(MissingUserSnafu { id: 1u32 }.build());
+    assert_eq!(e.http_status(), 404);
+
+    let e = FixedOpaqueError(MissingUserSnafu { id: 1u32 }.build());
+    assert_eq!(e.http_status(), 500);
+}
@@ -0,0 +1,131 @@
+use snafu::{prelude::*, ErrorCompat, IntoError};
+use std::io;
+
+mod storage {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(io_kind(TimedOut))]
+        Timeout,
+
+        #[snafu(io_kind(AlreadyExists))]
+        Duplicate { key: String },
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(io_kind(InvalidInput))]
+enum Error {
+    #[snafu(io_kind(NotFound))]
+    MissingUser {
+        id: u32,
+    },
+
+    InvalidName {
+        name: String,
+    },
+
+    #[snafu(flatten)]
+    Storage {
+        source: storage::Error,
+    },
+
+    #[snafu(io_kind(Interrupted))]
+    Retried {
+        source: storage::Error,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum WithoutDefault {
+    #[snafu(io_kind(PermissionDenied))]
+    Forbidden,
+
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(io_kind(InvalidData))]
+struct UnsupportedError {
+    feature: String,
+}
+
+#[derive(Debug, Snafu)]
+enum LoadError {
+    Read { source: io::Error },
+
+    Parse { source: Error },
+}
+
+#[test]
+fn variants_use_their_kind() {
+    let e = MissingUserSnafu { id: 1u32 }.build();
+    assert_eq!(e.io_kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn variants_without_a_kind_use_the_enum_default() {
+    let e = InvalidNameSnafu { name: "" }.build();
+    assert_eq!(e.io_kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn variants_without_a_kind_or_default_are_other() {
+    assert_eq!(
+        ForbiddenSnafu.build().io_kind(),
+        io::ErrorKind::PermissionDenied
+    );
+    assert_eq!(UnknownSnafu.build().io_kind(), io::ErrorKind::Other);
+}
+
+#[test]
+fn flattened_variants_use_the_kind_of_their_source() {
+    let e = Error::from(storage::TimeoutSnafu.build());
+    assert_eq!(e.io_kind(), io::ErrorKind::TimedOut);
+
+    let e = Err::<(), _>(storage::DuplicateSnafu { key: "id" }.build())
+        .context(RetriedSnafu)
+        .unwrap_err();
+    assert_eq!(e.io_kind(), io::ErrorKind::Interrupted);
+}
+
+#[test]
+fn structs_use_their_kind() {
+    let e = UnsupportedSnafu { feature: "mmap" }.build();
+    assert_eq!(e.io_kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn errors_convert_into_io_errors() {
+    let e = io::Error::from(MissingUserSnafu { id: 1u32 }.build());
+    assert_eq!(e.kind(), io::ErrorKind::NotFound);
+
+    let inner = e.get_ref().and_then(|e| e.downcast_ref::<Error>());
+    assert!(matches!(inner, Some(Error::MissingUser { id: 1 })));
+}
+
+#[test]
+fn io_error_kind_finds_the_io_error_in_the_chain() {
+    let e = Err::<(), _>(io::Error::from(io::ErrorKind::PermissionDenied))
+        .context(ReadSnafu)
+        .unwrap_err();
+    assert_eq!(e.io_error_kind(), Some(io::ErrorKind::PermissionDenied));
+    assert_eq!(e.raw_os_error(), None);
+}
+
+#[test]
+fn io_error_kind_is_none_without_an_io_error() {
+    let e = ParseSnafu.into_error(InvalidNameSnafu { name: "" }.build());
+    assert_eq!(e.io_error_kind(), None);
+    assert_eq!(e.raw_os_error(), None);
+}
+
+#[test]
+fn raw_os_error_finds_the_os_error_in_the_chain() {
+    let e = Err::<(), _>(io::Error::from_raw_os_error(2))
+        .context(ReadSnafu)
+        .unwrap_err();
+    assert_eq!(e.raw_os_error(), Some(2));
+}
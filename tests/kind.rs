@@ -0,0 +1,103 @@
+use snafu::{ResultExt, Snafu};
+use std::collections::HashSet;
+
+#[derive(Debug, Snafu)]
+#[snafu(kind)]
+enum Error {
+    Timeout { seconds: u64 },
+    Refused { source: std::io::Error },
+    Shutdown,
+}
+
+fn refused() -> Result<(), Error> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::ConnectionRefused,
+        "refused",
+    ))
+    .context(RefusedSnafu)
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error>();
+}
+
+#[test]
+fn kind_matches_the_variant() {
+    assert_eq!(
+        TimeoutSnafu { seconds: 5u64 }.build().kind(),
+        ErrorKind::Timeout
+    );
+    assert_eq!(refused().unwrap_err().kind(), ErrorKind::Refused);
+    assert_eq!(ShutdownSnafu.build().kind(), ErrorKind::Shutdown);
+}
+
+#[test]
+fn kind_implements_common_traits() {
+    fn check<T: std::fmt::Debug + Copy + Eq + std::hash::Hash>() {}
+    check::<ErrorKind>();
+
+    let kinds: HashSet<_> = vec![ErrorKind::Timeout, ErrorKind::Timeout, ErrorKind::Shutdown]
+        .into_iter()
+        .collect();
+    assert_eq!(kinds.len(), 2);
+}
+
+mod visibility {
+    pub mod inner {
+        use snafu::Snafu;
+
+        #[derive(Debug, Snafu)]
+        #[snafu(kind, visibility(pub(crate)))]
+        pub enum PublicError {
+            Boom,
+        }
+    }
+
+    #[test]
+    fn kind_has_the_visibility_of_the_enum() {
+        let e = inner::BoomSnafu.build();
+        assert_eq!(e.kind(), inner::PublicErrorKind::Boom);
+    }
+}
+
+mod generics {
+    use snafu::Snafu;
+    use std::fmt::Display;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(kind)]
+    enum Error<'a, T>
+    where
+        T: Display,
+    {
+        Invalid { value: T, name: &'a str },
+    }
+
+    #[test]
+    fn kind_is_available_for_generic_errors() {
+        let e: Error<'_, i32> = InvalidSnafu {
+            value: 42,
+            name: "answer",
+        }
+        .build();
+        assert_eq!(e.kind(), ErrorKind::Invalid);
+    }
+}
+
+mod empty {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(kind)]
+    enum Error {}
+
+    #[test]
+    fn kind_is_available_for_empty_errors() {
+        fn check(e: Error) -> ErrorKind {
+            e.kind()
+        }
+        let _ = check;
+    }
+}
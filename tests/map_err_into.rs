@@ -0,0 +1,46 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+enum StorageError {
+    DiskFull,
+}
+
+#[derive(Debug, Snafu)]
+enum NetworkError {
+    Timeout,
+}
+
+#[derive(Debug, Snafu)]
+enum ServiceError {
+    #[snafu(context(false))]
+    Storage { source: StorageError },
+
+    #[snafu(context(false))]
+    Network { source: NetworkError },
+}
+
+fn save() -> Result<(), StorageError> {
+    DiskFullSnafu.fail()
+}
+
+fn fetch() -> Result<(), NetworkError> {
+    TimeoutSnafu.fail()
+}
+
+#[test]
+fn converts_between_sibling_errors() {
+    let e = save().map_err_into::<ServiceError>().unwrap_err();
+    assert!(matches!(e, ServiceError::Storage { .. }));
+
+    let e = fetch().map_err_into::<ServiceError>().unwrap_err();
+    assert!(matches!(e, ServiceError::Network { .. }));
+}
+
+#[test]
+fn target_can_be_inferred() {
+    fn run() -> Result<(), ServiceError> {
+        save().map_err_into()
+    }
+
+    assert!(matches!(run(), Err(ServiceError::Storage { .. })));
+}
@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+
+use snafu::{prelude::*, ErrorCompat, ErrorMetadata, Metadata, Retryable};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not load user {}", id))]
+    LoadUser { id: u32, metadata: snafu::Metadata },
+
+    Database {
+        source: std::io::Error,
+        metadata: snafu::Metadata,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum PartialError {
+    Enriched { metadata: snafu::Metadata },
+
+    Unenriched,
+}
+
+#[derive(Debug, Snafu)]
+struct StructError {
+    metadata: snafu::Metadata,
+}
+
+#[derive(Debug, Snafu)]
+struct UnrelatedMetadata {
+    metadata: std::fs::Metadata,
+}
+
+fn metadata(e: &Error) -> &Metadata {
+    match e {
+        Error::LoadUser { metadata, .. } | Error::Database { metadata, .. } => metadata,
+    }
+}
+
+#[test]
+fn metadata_is_not_part_of_the_selector() {
+    let e = LoadUserSnafu { id: 42u32 }.build();
+    assert_eq!(e.to_string(), "Could not load user 42");
+    assert!(metadata(&e).is_empty());
+}
+
+#[test]
+fn context_can_be_added_after_construction() {
+    let mut e = LoadUserSnafu { id: 42u32 }.build();
+    e.add_context("request_id", "abc-123".to_string());
+    e.add_context("attempt", "2".to_string());
+
+    let pairs: Vec<_> = metadata(&e).iter().collect();
+    assert_eq!(pairs, [("request_id", "abc-123"), ("attempt", "2")]);
+}
+
+#[test]
+fn context_is_filled_in_with_a_source() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "boom");
+    let mut e = Err::<(), _>(io).context(DatabaseSnafu).unwrap_err();
+    e.add_context("table", "users".to_string());
+    assert_eq!(metadata(&e).get("table"), Some("users"));
+}
+
+#[test]
+fn context_can_be_added_through_a_trait_object() {
+    let mut e: Box<dyn ErrorMetadata> = Box::new(LoadUserSnafu { id: 1u32 }.build());
+    e.add_context("request_id", "abc-123".to_string());
+}
+
+#[test]
+fn boxed_errors_forward_metadata() {
+    let mut e = Box::new(LoadUserSnafu { id: 1u32 }.build());
+    e.add_context("request_id", "abc-123".to_string());
+
+    let metadata = ErrorCompat::metadata(&e).expect("Must have metadata");
+    assert_eq!(metadata.get("request_id"), Some("abc-123"));
+}
+
+#[test]
+fn retryable_errors_forward_metadata_to_the_first_error() {
+    let mut e = Retryable::new(LoadUserSnafu { id: 1u32 }.build());
+    e.push("Attempt 2 failed");
+    e.add_context("request_id", "abc-123".to_string());
+
+    let metadata = ErrorCompat::metadata(&e).expect("Must have metadata");
+    assert_eq!(metadata.get("request_id"), Some("abc-123"));
+    assert_eq!(ErrorCompat::metadata(e.first()), Some(metadata));
+}
+
+#[test]
+fn context_can_be_added_to_structs() {
+    let mut e = StructSnafu.build();
+    e.add_context("request_id", "abc-123".to_string());
+    assert_eq!(e.metadata.get("request_id"), Some("abc-123"));
+}
+
+#[test]
+fn inserting_an_existing_key_keeps_its_position() {
+    let mut metadata = Metadata::new();
+    metadata.insert("a", "1".to_string());
+    metadata.insert("b", "2".to_string());
+    metadata.insert("a", "3".to_string());
+
+    let pairs: Vec<_> = metadata.iter().collect();
+    assert_eq!(pairs, [("a", "3"), ("b", "2")]);
+}
+
+#[test]
+fn metadata_is_available_through_error_compat() {
+    let mut e = LoadUserSnafu { id: 42u32 }.build();
+    e.add_context("request_id", "abc-123".to_string());
+
+    let metadata = ErrorCompat::metadata(&e).expect("Must have metadata");
+    assert_eq!(metadata.get("request_id"), Some("abc-123"));
+
+    assert!(ErrorCompat::metadata(&EnrichedSnafu.build()).is_some());
+    assert!(ErrorCompat::metadata(&UnenrichedSnafu.build()).is_none());
+    assert!(ErrorCompat::metadata(&StructSnafu.build()).is_some());
+}
+
+#[test]
+fn context_can_be_added_to_a_result() {
+    fn load_user(id: u32) -> Result<(), Error> {
+        LoadUserSnafu { id }.fail()
+    }
+
+    let e = load_user(42)
+        .add_context("request_id", "abc-123")
+        .add_context("attempt", 2)
+        .unwrap_err();
+    let metadata = ErrorCompat::metadata(&e).expect("Must have metadata");
+    assert_eq!(metadata.to_string(), "request_id: abc-123, attempt: 2");
+}
+
+#[test]
+fn context_value_is_not_formatted_for_ok() {
+    struct Unformattable;
+
+    impl std::fmt::Display for Unformattable {
+        fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            panic!("This value must not be formatted");
+        }
+    }
+
+    let r: Result<i32, Error> = Ok(1);
+    assert_eq!(r.add_context("value", Unformattable).unwrap(), 1);
+}
@@ -0,0 +1,93 @@
+use snafu::{ErrorCompat, MultiError, ResultExt, ResultIteratorExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Item {} is invalid", index))]
+    InvalidItem { index: usize, source: ItemError },
+
+    #[snafu(display("The batch failed"))]
+    Batch { source: MultiError<Error> },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("{} is negative", value))]
+struct ItemError {
+    value: i32,
+}
+
+fn validate(value: i32) -> Result<i32, ItemError> {
+    snafu::ensure!(value >= 0, ItemSnafu { value });
+    Ok(value)
+}
+
+fn process(items: Vec<i32>) -> Result<Vec<i32>, Error> {
+    let (values, errors) = items
+        .into_iter()
+        .map(validate)
+        .collect_results_with_context(|index| InvalidItemSnafu { index });
+    errors.into_result().context(BatchSnafu)?;
+    Ok(values)
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<MultiError<Error>>();
+}
+
+#[test]
+fn successful_batches_return_all_values() {
+    assert_eq!(process(vec![1, 2, 3]).unwrap(), [1, 2, 3]);
+}
+
+#[test]
+fn failed_batches_report_every_failure() {
+    let error = process(vec![1, -2, 3, -4]).unwrap_err();
+
+    let errors = match error {
+        Error::Batch { source } => source,
+        other => panic!("Unexpected error {:?}", other),
+    };
+    assert_eq!(errors.to_string(), "2 errors occurred");
+
+    let messages: Vec<_> = errors
+        .into_iter()
+        .map(|(index, e)| (index, e.to_string()))
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            (1, "Item 1 is invalid".to_string()),
+            (3, "Item 3 is invalid".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn first_error_is_the_source() {
+    let error = process(vec![-1, -2]).unwrap_err();
+    let chain: Vec<_> = error.iter_chain().map(ToString::to_string).collect();
+    assert_eq!(
+        chain,
+        [
+            "The batch failed",
+            "2 errors occurred",
+            "Item 0 is invalid",
+            "-1 is negative",
+        ]
+    );
+}
+
+#[test]
+fn results_without_context_keep_the_original_errors() {
+    let (values, errors) = vec![Ok(1), Err("bad"), Ok(3)].into_iter().collect_results();
+    assert_eq!(values, [1, 3]);
+    assert_eq!(errors.into_inner(), [(1, "bad")]);
+}
+
+#[test]
+fn empty_collections_are_ok() {
+    let errors = MultiError::<Error>::default();
+    assert!(errors.is_empty());
+    assert!(errors.into_result().is_ok());
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use ::snafu as real_snafu;
 use real_snafu::{ensure, Snafu};
 
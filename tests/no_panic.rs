@@ -0,0 +1,52 @@
+use snafu::{prelude::*, ErrorCompat};
+use std::io;
+
+#[derive(Debug, Snafu)]
+#[snafu(no_panic, kind, predicates, display_buffer(32), http_status(500))]
+enum Error {
+    #[snafu(
+        display("Could not read sector {sector}"),
+        exit_code(74),
+        io_kind(NotFound)
+    )]
+    ReadSector { sector: u32, source: io::Error },
+
+    #[snafu(display("The device is busy"), http_status(503))]
+    Busy,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(no_panic, display("The watchdog fired after {ticks} ticks"))]
+struct WatchdogError {
+    ticks: u64,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(no_panic)]
+struct OpaqueError(Error);
+
+#[test]
+fn compatible_attributes_are_generated() {
+    let e = Err::<(), _>(io::Error::from(io::ErrorKind::Other))
+        .context(ReadSectorSnafu { sector: 7u32 })
+        .unwrap_err();
+
+    assert_eq!(e.to_string(), "Could not read sector 7");
+    assert_eq!(e.kind(), ErrorKind::ReadSector);
+    assert!(e.is_read_sector());
+    assert_eq!(e.exit_code(), Some(74));
+    assert_eq!(e.io_kind(), io::ErrorKind::NotFound);
+    assert_eq!(BusySnafu.build().http_status(), 503);
+
+    let mut buffer = [0; 8];
+    assert_eq!(e.format_into(&mut buffer), "Could no");
+}
+
+#[test]
+fn structs_can_be_panic_free() {
+    let e = WatchdogSnafu { ticks: 3u64 }.build();
+    assert_eq!(e.to_string(), "The watchdog fired after 3 ticks");
+
+    let e = OpaqueError::from(BusySnafu.build());
+    assert_eq!(e.to_string(), "The device is busy");
+}
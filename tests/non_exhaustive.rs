@@ -0,0 +1,52 @@
+use snafu::{prelude::*, Backtrace, ErrorCompat};
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+#[snafu(kind, predicates, accessors, visibility(pub))]
+pub enum Error {
+    #[non_exhaustive]
+    #[snafu(display("Could not open {}", path))]
+    Open {
+        path: String,
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+
+    #[non_exhaustive]
+    Empty,
+
+    #[non_exhaustive]
+    Positional(std::num::ParseIntError),
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+#[snafu(display("Invalid port {}", port))]
+pub struct PortError {
+    pub port: u16,
+}
+
+#[test]
+fn non_exhaustive_variants_can_be_constructed_with_selectors() {
+    let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+    let e = Err::<(), _>(io_error)
+        .context(OpenSnafu { path: "a.txt" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Could not open a.txt");
+    assert!(ErrorCompat::backtrace(&e).is_some());
+    assert!(e.is_open());
+    assert_eq!(e.kind(), ErrorKind::Open);
+    assert_eq!(e.path(), Some("a.txt"));
+
+    let e = EmptySnafu.build();
+    assert_eq!(e.to_string(), "Empty");
+
+    let e = "x".parse::<u8>().context(PositionalSnafu).unwrap_err();
+    assert!(e.is_positional());
+}
+
+#[test]
+fn non_exhaustive_structs_can_be_constructed_with_selectors() {
+    let e = PortSnafu { port: 0u16 }.build();
+    assert_eq!(e.to_string(), "Invalid port 0");
+}
@@ -0,0 +1,50 @@
+use snafu::{path, prelude::*};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Could not read {}", path::display(path)))]
+struct ReadError {
+    path: PathBuf,
+}
+
+#[test]
+fn valid_paths_are_shown_unchanged() {
+    let e = ReadSnafu { path: "/etc/hosts" }.build();
+    assert_eq!(e.to_string(), "Could not read /etc/hosts");
+}
+
+#[test]
+fn selectors_accept_borrowed_paths() {
+    let e = ReadSnafu {
+        path: Path::new("relative/file.txt"),
+    }
+    .build();
+    assert_eq!(e.path, Path::new("relative/file.txt"));
+}
+
+#[cfg(unix)]
+#[test]
+fn invalid_bytes_are_escaped() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let path = Path::new(OsStr::from_bytes(b"/tmp/caf\xE9/\xFF\xFEx"));
+    assert_eq!(path::display(path).to_string(), r"/tmp/caf\xE9/\xFF\xFEx");
+}
+
+#[cfg(unix)]
+#[test]
+fn truncated_sequences_are_escaped() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let path = Path::new(OsStr::from_bytes(b"end\xE2\x82"));
+    assert_eq!(path::display(path).to_string(), r"end\xE2\x82");
+}
+
+#[cfg(windows)]
+#[test]
+fn unpaired_surrogates_are_escaped() {
+    use std::{ffi::OsString, os::windows::ffi::OsStringExt};
+
+    let path = OsString::from_wide(&[0x61, 0xD800, 0x62]);
+    assert_eq!(path::display(&path).to_string(), r"a\u{D800}b");
+}
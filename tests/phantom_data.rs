@@ -0,0 +1,84 @@
+#![allow(dead_code)]
+
+use snafu::{prelude::*, FromString};
+use std::marker::PhantomData;
+
+#[derive(Debug)]
+struct Meters;
+
+#[derive(Debug, Snafu)]
+enum Error<U> {
+    #[snafu(display("Distance {} is out of range", distance))]
+    OutOfRange {
+        distance: f64,
+        unit: PhantomData<U>,
+    },
+
+    Overflow {
+        marker: std::marker::PhantomData<U>,
+    },
+
+    Parse {
+        source: std::num::ParseFloatError,
+        unit: PhantomData<U>,
+    },
+
+    #[snafu(context(false))]
+    Io {
+        source: std::io::Error,
+        unit: PhantomData<U>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(whatever, display("{}", message))]
+struct WhateverError {
+    message: String,
+    marker: PhantomData<fn()>,
+}
+
+#[derive(Debug, Snafu)]
+struct StructError<U> {
+    value: i32,
+    unit: PhantomData<U>,
+}
+
+#[test]
+fn phantom_data_is_not_part_of_the_selector() {
+    let e: Error<Meters> = OutOfRangeSnafu { distance: 2.5 }.build();
+    assert_eq!(e.to_string(), "Distance 2.5 is out of range");
+}
+
+#[test]
+fn phantom_data_can_be_the_only_field() {
+    let e: Error<Meters> = OverflowSnafu.build();
+    assert_eq!(e.to_string(), "Overflow");
+}
+
+#[test]
+fn phantom_data_is_filled_in_with_a_source() {
+    let e: Error<Meters> = "x".parse::<f64>().context(ParseSnafu).unwrap_err();
+    assert!(matches!(e, Error::Parse { .. }));
+}
+
+#[test]
+fn phantom_data_is_filled_in_without_context() {
+    fn io() -> Result<(), Error<Meters>> {
+        Err(std::io::Error::new(std::io::ErrorKind::Other, "boom"))?;
+        Ok(())
+    }
+
+    assert!(matches!(io(), Err(Error::Io { .. })));
+}
+
+#[test]
+fn phantom_data_is_filled_in_for_whatever() {
+    let e = WhateverError::without_source("oops".to_string());
+    assert_eq!(e.to_string(), "oops");
+}
+
+#[test]
+fn phantom_data_is_filled_in_for_structs() {
+    let e: StructError<Meters> = StructSnafu { value: 1 }.build();
+    assert_eq!(e.value, 1);
+}
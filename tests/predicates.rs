@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(predicates)]
+enum Error {
+    NotFound { path: String },
+    PermissionDenied,
+    HTTPTimeout,
+    Retry2Later,
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error>();
+}
+
+#[test]
+fn predicates_match_their_variant() {
+    let e = NotFoundSnafu { path: "/etc" }.build();
+    assert!(e.is_not_found());
+    assert!(!e.is_permission_denied());
+
+    let e = PermissionDeniedSnafu.build();
+    assert!(!e.is_not_found());
+    assert!(e.is_permission_denied());
+}
+
+#[test]
+fn predicate_names_are_snake_case() {
+    assert!(HTTPTimeoutSnafu.build().is_http_timeout());
+    assert!(Retry2LaterSnafu.build().is_retry2_later());
+}
+
+mod single_variant {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(predicates, kind)]
+    enum Error {
+        Only,
+    }
+
+    #[test]
+    fn predicates_can_be_combined_with_kind() {
+        let e = OnlySnafu.build();
+        assert!(e.is_only());
+        assert_eq!(e.kind(), ErrorKind::Only);
+    }
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(predicates)]
+    enum Error<T>
+    where
+        T: std::fmt::Debug,
+    {
+        Invalid { value: T },
+        Missing,
+    }
+
+    #[test]
+    fn predicates_are_available_for_generic_errors() {
+        let e: Error<i32> = InvalidSnafu { value: 1 }.build();
+        assert!(e.is_invalid());
+        assert!(!e.is_missing());
+    }
+}
@@ -0,0 +1,53 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not open {}", filename))]
+    Open {
+        filename: String,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("The value was missing"))]
+    Missing,
+
+    #[snafu(display("{} is too small", value))]
+    TooSmall { value: i32 },
+}
+
+fn open() -> Result<Vec<u8>, Error> {
+    std::fs::read("/this/does/not/exist").context(OpenSnafu {
+        filename: "/this/does/not/exist",
+    })
+}
+
+fn missing() -> Result<i32, Error> {
+    None.context(MissingSnafu)
+}
+
+fn validate(value: i32) -> Result<i32, Error> {
+    ensure!(value >= 0, TooSmallSnafu { value });
+    Ok(value)
+}
+
+fn stringly_typed() -> Result<(), snafu::Whatever> {
+    whatever!("Oh no")
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error>();
+}
+
+#[test]
+fn extension_traits_and_macros_are_available() {
+    assert!(open().is_err());
+    assert!(missing().is_err());
+    assert!(validate(-1).is_err());
+    assert!(stringly_typed().is_err());
+
+    let (values, errors) = vec![1, -1].into_iter().map(validate).collect_results();
+    assert_eq!(values, [1]);
+    assert_eq!(errors.len(), 1);
+}
@@ -17,6 +17,18 @@ fn implements_error() {
     check::<Whatever>();
 }
 
+#[test]
+fn is_send_and_sync() {
+    fn check<T: Send + Sync>() {}
+    check::<Whatever>();
+
+    let e = underlying(false)
+        .whatever_context::<_, Whatever>("Outer")
+        .unwrap_err();
+    let e = std::thread::spawn(move || e).join().unwrap();
+    assert_eq!(e.to_string(), "Outer");
+}
+
 #[test]
 fn does_not_need_a_cause() {
     use std::error::Error as _;
@@ -105,3 +117,88 @@ fn has_a_backtrace() {
     let bt = ErrorCompat::backtrace(&e).expect("Must have a backtrace");
     assert_eq!("disabled backtrace", bt.to_string());
 }
+
+#[test]
+fn io_error_kind_is_found_among_the_sources() {
+    use std::io;
+
+    fn read() -> Result<()> {
+        let e = io::Error::new(io::ErrorKind::PermissionDenied, "no access");
+        Err(e).whatever_context("Could not read")
+    }
+
+    fn load() -> Result<()> {
+        read().whatever_context("Could not load")
+    }
+
+    let e = load().unwrap_err();
+    assert_eq!(e.io_error_kind(), Some(io::ErrorKind::PermissionDenied));
+
+    let e = underlying(false)
+        .whatever_context::<_, Whatever>("Not I/O")
+        .unwrap_err();
+    assert_eq!(e.io_error_kind(), None);
+}
+
+#[test]
+fn has_typed_fields() {
+    use snafu::FieldValue;
+
+    fn exercise(port: u16) -> Result<(), Whatever> {
+        let host = std::net::Ipv4Addr::LOCALHOST;
+        whatever!(
+            "Could not connect to port {}",
+            port;
+            host = %host,
+            port = port,
+            offset = -1,
+            ratio = 0.5,
+            secure = false,
+            name = "local",
+            path = ?"/",
+        );
+    }
+
+    let e = exercise(80).unwrap_err();
+    assert_eq!(e.to_string(), "Could not connect to port 80");
+
+    let fields: Vec<_> = e.fields().iter().collect();
+    assert_eq!(
+        fields,
+        [
+            ("host", &FieldValue::String("127.0.0.1".into())),
+            ("port", &FieldValue::U64(80)),
+            ("offset", &FieldValue::I64(-1)),
+            ("ratio", &FieldValue::F64(0.5)),
+            ("secure", &FieldValue::Bool(false)),
+            ("name", &FieldValue::String("local".into())),
+            ("path", &FieldValue::String(r#""/""#.into())),
+        ],
+    );
+}
+
+#[test]
+fn has_typed_fields_when_wrapping_a_cause() {
+    fn exercise() -> Result<Vec<u8>, Whatever> {
+        let contents = whatever!(
+            std::fs::read("/this/does/not/exist"),
+            "Could not read {}",
+            "the file";
+            attempt = 2u8,
+        );
+        Ok(contents)
+    }
+
+    let e = exercise().unwrap_err();
+    assert_eq!(e.to_string(), "Could not read the file");
+    assert_eq!(e.fields().to_string(), "attempt: 2");
+    assert!(std::error::Error::source(&e).is_some());
+}
+
+#[test]
+fn fields_are_empty_without_key_value_pairs() {
+    let e = std::fs::read("/this/does/not/exist")
+        .whatever_context::<_, Whatever>("Could not read")
+        .unwrap_err();
+    assert!(e.fields().is_empty());
+}
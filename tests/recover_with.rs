@@ -0,0 +1,55 @@
+use snafu::{prelude::*, Recovery};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Missing,
+    Malformed { line: usize },
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Loading { path: String, source: InnerError },
+}
+
+fn load(path: &str) -> Result<u8, InnerError> {
+    match path {
+        "missing" => MissingSnafu.fail(),
+        "malformed" => MalformedSnafu { line: 7usize }.fail(),
+        _ => Ok(42),
+    }
+}
+
+fn load_with_default(path: &str) -> Result<u8, Error> {
+    load(path).recover_with(|e| match e {
+        InnerError::Missing => Recovery::Value(0),
+        _ => Recovery::Context(LoadingSnafu { path }),
+    })
+}
+
+#[test]
+fn success_is_unchanged() {
+    assert_eq!(load_with_default("present").unwrap(), 42);
+}
+
+#[test]
+fn can_recover_with_a_value() {
+    assert_eq!(load_with_default("missing").unwrap(), 0);
+}
+
+#[test]
+fn can_add_context_to_the_original_error() {
+    let e = load_with_default("malformed").unwrap_err();
+    match e {
+        Error::Loading { path, source } => {
+            assert_eq!(path, "malformed");
+            assert!(matches!(source, InnerError::Malformed { line: 7 }));
+        }
+    }
+}
+
+#[test]
+fn closure_is_not_called_on_success() {
+    let result: Result<u8, Error> = Ok::<_, InnerError>(1)
+        .recover_with(|_| -> Recovery<_, LoadingSnafu<&str>> { panic!("Should not be called") });
+    assert_eq!(result.unwrap(), 1);
+}
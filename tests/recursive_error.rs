@@ -46,3 +46,190 @@ fn implements_error() {
     check::<Error>();
     re_private().unwrap_err();
 }
+
+mod backtrace_delegation {
+    use snafu::{prelude::*, Backtrace, ErrorCompat};
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Leaf {
+            backtrace: Backtrace,
+        },
+
+        Nested {
+            #[snafu(backtrace)]
+            source: Box<Error>,
+        },
+    }
+
+    fn eval(depth: usize) -> Result<(), Error> {
+        if depth == 0 {
+            return LeafSnafu.fail();
+        }
+        eval(depth - 1).map_err(Box::new).context(NestedSnafu)
+    }
+
+    #[test]
+    fn backtraces_are_delegated_through_boxed_self() {
+        let e = eval(3).unwrap_err();
+        assert!(ErrorCompat::backtrace(&e).is_some());
+        assert_eq!(e.iter_chain().count(), 4);
+    }
+}
+
+mod generic {
+    use snafu::{prelude::*, Backtrace, ErrorCompat};
+    use std::fmt::{Debug, Display};
+
+    #[derive(Debug, Snafu)]
+    enum Error<T>
+    where
+        T: Debug + Display + 'static,
+    {
+        #[snafu(display("Bad value {}", value))]
+        Leaf { value: T, backtrace: Backtrace },
+
+        Nested {
+            #[snafu(backtrace)]
+            source: Box<Error<T>>,
+        },
+
+        NestedSelf {
+            #[snafu(source(from(Error<T>, Box::new)), backtrace)]
+            source: Box<Self>,
+        },
+    }
+
+    fn eval(depth: usize) -> Result<(), Error<i32>> {
+        match depth {
+            0 => LeafSnafu { value: 42 }.fail(),
+            1 => eval(depth - 1).context(NestedSelfSnafu),
+            _ => eval(depth - 1).map_err(Box::new).context(NestedSnafu),
+        }
+    }
+
+    #[test]
+    fn generic_errors_can_have_boxed_self_sources() {
+        let e = eval(3).unwrap_err();
+        assert!(ErrorCompat::backtrace(&e).is_some());
+
+        let chain: Vec<_> = e.iter_chain().map(ToString::to_string).collect();
+        assert_eq!(
+            chain,
+            [
+                "Nested: Nested: NestedSelf: Bad value 42",
+                "Nested: NestedSelf: Bad value 42",
+                "NestedSelf: Bad value 42",
+                "Bad value 42",
+            ],
+        );
+    }
+}
+
+mod dedup {
+    use snafu::{prelude::*, ErrorCompat};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Snafu)]
+    #[snafu(dedup)]
+    enum Error {
+        Leaf,
+
+        #[snafu(display("Evaluating {}", name))]
+        Evaluate {
+            name: String,
+            source: Box<Error>,
+        },
+
+        Retry {
+            #[snafu(source(from(Error, Box::new)))]
+            source: Box<Self>,
+        },
+
+        NotBoxed {
+            source: std::io::Error,
+        },
+    }
+
+    fn evaluate(names: &[&str]) -> Result<(), Error> {
+        match names.split_first() {
+            None => LeafSnafu.fail(),
+            Some((name, rest)) => evaluate(rest)
+                .map_err(Box::new)
+                .context(EvaluateSnafu { name: *name }),
+        }
+    }
+
+    fn chain(e: &Error) -> Vec<String> {
+        e.iter_chain().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn identical_consecutive_contexts_are_not_repeated() {
+        let e = evaluate(&["a", "a", "a"]).unwrap_err();
+        assert_eq!(chain(&e), ["Evaluating a", "Leaf"]);
+    }
+
+    #[test]
+    fn different_contexts_are_kept() {
+        let e = evaluate(&["a", "b", "a"]).unwrap_err();
+        assert_eq!(
+            chain(&e),
+            ["Evaluating a", "Evaluating b", "Evaluating a", "Leaf"],
+        );
+    }
+
+    #[test]
+    fn contexts_without_fields_are_not_repeated() {
+        let e = Err::<(), _>(LeafSnafu.build())
+            .context(RetrySnafu)
+            .context(RetrySnafu)
+            .unwrap_err();
+        assert_eq!(e.iter_chain().count(), 2);
+    }
+
+    #[test]
+    fn other_sources_are_wrapped_as_usual() {
+        let io = std::io::Error::new(std::io::ErrorKind::Other, "boom");
+        let e = Err::<(), _>(io).context(NotBoxedSnafu).unwrap_err();
+        assert!(matches!(e, Error::NotBoxed { .. }));
+    }
+
+    static GENERATED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug)]
+    struct Generated;
+
+    impl Default for Generated {
+        fn default() -> Self {
+            GENERATED.fetch_add(1, Ordering::SeqCst);
+            Generated
+        }
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(dedup)]
+    #[allow(dead_code)]
+    enum CountingError {
+        Base,
+
+        Wrap {
+            name: String,
+            source: Box<CountingError>,
+            #[snafu(implicit)]
+            generated: Generated,
+        },
+    }
+
+    #[test]
+    fn duplicates_do_not_generate_implicit_data() {
+        let wrap = |e| Err::<(), _>(Box::new(e)).context(WrapSnafu { name: "a" });
+
+        let e = wrap(BaseSnafu.build()).unwrap_err();
+        assert_eq!(GENERATED.load(Ordering::SeqCst), 1);
+
+        let e = wrap(e).unwrap_err();
+        assert_eq!(e.iter_chain().count(), 2);
+        assert_eq!(GENERATED.load(Ordering::SeqCst), 1);
+    }
+}
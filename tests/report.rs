@@ -0,0 +1,248 @@
+use snafu::{prelude::*, ErrorMetadata, Report, Whatever};
+use std::io;
+
+mod chain {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(display("Could not load the configuration"))]
+        LoadConfig { source: ParseError },
+
+        #[snafu(display("Could not start: {}", source))]
+        Start { source: io::Error },
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    #[snafu(display("Line {} is invalid", line))]
+    pub struct ParseError {
+        pub line: u32,
+        pub source: io::Error,
+    }
+}
+
+mod enriched {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    #[snafu(display("Could not load user {}", id))]
+    pub struct LoadUserError {
+        pub id: u32,
+        pub source: Whatever,
+        pub metadata: snafu::Metadata,
+    }
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "Disk is full")
+}
+
+fn load_config() -> Result<(), chain::Error> {
+    Err(io_error())
+        .context(chain::ParseSnafu { line: 3u32 })
+        .context(chain::LoadConfigSnafu)
+}
+
+#[test]
+fn shows_each_error_in_the_chain() {
+    let report = Report::from_error(load_config().unwrap_err());
+    assert_eq!(
+        report.to_string(),
+        "Could not load the configuration\nCaused by: Line 3 is invalid\nCaused by: Disk is full",
+    );
+}
+
+#[test]
+fn debug_output_matches_display_output() {
+    let report = Report::from_error(load_config().unwrap_err());
+    assert_eq!(format!("{:?}", report), report.to_string());
+}
+
+#[test]
+fn repeated_source_messages_are_removed() {
+    let error = Err::<(), _>(io_error())
+        .context(chain::StartSnafu)
+        .unwrap_err();
+    let report = Report::from_error(error);
+    assert_eq!(
+        report.to_string(),
+        "Could not start\nCaused by: Disk is full"
+    );
+}
+
+#[test]
+fn filtered_errors_are_hidden_unless_alternate() {
+    let report = Report::from_error(load_config().unwrap_err())
+        .with_filter(|e, _| !e.is::<chain::ParseError>());
+    assert_eq!(
+        report.to_string(),
+        "Could not load the configuration\nCaused by: Disk is full",
+    );
+    assert_eq!(
+        format!("{:#?}", report),
+        "Could not load the configuration\nCaused by: Line 3 is invalid\nCaused by: Disk is full",
+    );
+}
+
+#[test]
+fn filter_receives_the_depth() {
+    let report = Report::from_error(load_config().unwrap_err()).with_filter(|_, depth| depth != 0);
+    assert_eq!(
+        report.to_string(),
+        "Line 3 is invalid\nCaused by: Disk is full",
+    );
+}
+
+#[test]
+fn outermost_error_is_shown_when_everything_is_filtered() {
+    let report = Report::from_error(load_config().unwrap_err()).with_filter(|_, _| false);
+    assert_eq!(report.to_string(), "Could not load the configuration");
+}
+
+#[test]
+fn metadata_and_whatever_fields_are_shown() {
+    fn lookup() -> Result<(), Whatever> {
+        whatever!("Database timed out"; table = "users", attempt = 2u32)
+    }
+
+    let mut error = lookup()
+        .context(enriched::LoadUserSnafu { id: 42u32 })
+        .unwrap_err();
+    ErrorMetadata::add_context(&mut error, "request_id", "abc-123".to_string());
+
+    let report = Report::from_error(error);
+    assert_eq!(
+        report.to_string(),
+        "Could not load user 42 [request_id: abc-123]\n\
+         Caused by: Database timed out [table: users, attempt: 2]",
+    );
+}
+
+#[test]
+fn can_be_created_with_the_question_mark_operator() {
+    fn example() -> Result<(), Report<chain::Error>> {
+        load_config()?;
+        Ok(())
+    }
+
+    let report = example().unwrap_err();
+    assert!(report.error().is_some());
+    assert!(report.into_result().is_err());
+}
+
+#[test]
+fn captured_success_renders_nothing() {
+    let report = Report::capture(|| -> Result<(), Whatever> { Ok(()) });
+    assert!(report.error().is_none());
+    assert_eq!(report.to_string(), "");
+    assert_eq!(Report::<Whatever>::ok().to_string(), "");
+}
+
+mod exit_code {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(exit_code(74))]
+        Write {
+            source: io::Error,
+        },
+
+        Unknown,
+    }
+}
+
+#[test]
+fn exit_code_comes_from_the_error() {
+    let error = Err::<(), _>(io_error())
+        .context(exit_code::WriteSnafu)
+        .unwrap_err();
+    assert_eq!(Report::from_error(error).exit_code(), 74);
+}
+
+#[test]
+fn exit_code_defaults_to_one_for_errors() {
+    let report = Report::from_error(exit_code::UnknownSnafu.build());
+    assert_eq!(report.exit_code(), 1);
+}
+
+#[test]
+fn exit_code_is_zero_without_an_error() {
+    assert_eq!(Report::<exit_code::Error>::ok().exit_code(), 0);
+}
+
+#[cfg(feature = "rust_1_61")]
+#[test]
+fn implements_termination() {
+    use std::process::{ExitCode, Termination};
+
+    let report = Report::<exit_code::Error>::ok();
+    assert_eq!(report.report(), ExitCode::SUCCESS);
+}
+
+#[test]
+fn showing_the_backtrace_without_one_changes_nothing() {
+    let report = Report::from_error(exit_code::UnknownSnafu.build()).show_backtrace(true);
+    assert_eq!(report.to_string(), "Unknown");
+}
+
+#[test]
+fn compact_string_uses_a_single_line() {
+    let report = Report::from_error(load_config().unwrap_err());
+    assert_eq!(
+        report.to_compact_string(),
+        "Could not load the configuration: Line 3 is invalid: Disk is full",
+    );
+}
+
+#[test]
+fn compact_string_removes_repeated_source_messages() {
+    let error = Err::<(), _>(io_error())
+        .context(chain::StartSnafu)
+        .unwrap_err();
+    let report = Report::from_error(error);
+    assert_eq!(report.to_compact_string(), "Could not start: Disk is full");
+}
+
+#[test]
+fn compact_string_applies_the_filter() {
+    let report = Report::from_error(load_config().unwrap_err())
+        .with_filter(|e, _| !e.is::<chain::ParseError>());
+    assert_eq!(
+        report.to_compact_string(),
+        "Could not load the configuration: Disk is full",
+    );
+}
+
+#[test]
+fn compact_string_is_empty_without_an_error() {
+    assert_eq!(Report::<chain::Error>::ok().to_compact_string(), "");
+}
+
+#[test]
+fn pretty_string_matches_display() {
+    let report = Report::from_error(load_config().unwrap_err());
+    assert_eq!(report.to_string_pretty(), report.to_string());
+}
+
+#[test]
+fn write_to_appends_the_report() {
+    let report = Report::from_error(load_config().unwrap_err());
+    let mut log = String::from("[error] ");
+    report.write_to(&mut log).unwrap();
+    assert_eq!(log, format!("[error] {}", report));
+}
+
+#[test]
+fn write_to_accepts_trait_objects() {
+    let report = Report::from_error(load_config().unwrap_err());
+    let mut log = String::new();
+    report
+        .write_to(&mut log as &mut dyn std::fmt::Write)
+        .unwrap();
+    assert_eq!(log, report.to_string_pretty());
+}
@@ -0,0 +1,67 @@
+use snafu::prelude::*;
+use std::{env, io, process::Command};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not save the document"), exit_code(74))]
+    Save { source: io::Error },
+}
+
+fn save(fail: bool) -> Result<(), io::Error> {
+    if fail {
+        Err(io::Error::new(io::ErrorKind::Other, "Disk is full"))
+    } else {
+        Ok(())
+    }
+}
+
+#[snafu::report]
+fn succeeds() -> Result<(), Error> {
+    save(false).context(SaveSnafu)?;
+    Ok(())
+}
+
+#[snafu::report]
+fn returns_early(values: Vec<u8>) -> Result<(), Error> {
+    if values.is_empty() {
+        return Ok(());
+    }
+    save(false).context(SaveSnafu)
+}
+
+#[test]
+fn successful_functions_return_normally() {
+    let () = succeeds();
+    let () = returns_early(vec![]);
+    let () = returns_early(vec![1]);
+}
+
+const CHILD_ENV_VAR: &str = "SNAFU_TEST_REPORT_ATTRIBUTE_CHILD";
+
+// Exits the process, so it only fails when run by
+// `failing_functions_exit_with_the_report`.
+#[test]
+#[snafu::report]
+fn fails_in_child_process() -> Result<(), Error> {
+    if env::var_os(CHILD_ENV_VAR).is_some() {
+        save(true).context(SaveSnafu)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn failing_functions_exit_with_the_report() {
+    let output = Command::new(env::current_exe().unwrap())
+        .args(&["fails_in_child_process", "--exact", "--nocapture"])
+        .env(CHILD_ENV_VAR, "1")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(74));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Error: Could not save the document\nCaused by: Disk is full\n"),
+        "{}",
+        stderr,
+    );
+}
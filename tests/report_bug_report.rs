@@ -0,0 +1,60 @@
+// Installing a renderer affects every report in the process, so these
+// tests are kept apart from the other report tests.
+
+use snafu::{
+    prelude::*,
+    report::{self, BugReportRenderer},
+    Report,
+};
+use std::{env, io, sync::Once};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Could not save the document"))]
+struct SaveError {
+    source: io::Error,
+}
+
+fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| report::install(BugReportRenderer::new("my-tool", "1.2.3")).unwrap());
+}
+
+fn error() -> SaveError {
+    let e = io::Error::new(io::ErrorKind::Other, "Disk is full");
+    Err::<(), _>(e).context(SaveSnafu).unwrap_err()
+}
+
+#[test]
+fn appends_a_block_for_bug_reports() {
+    install();
+    let report = Report::from_error(error()).show_backtrace(false);
+
+    let expected = format!(
+        "Could not save the document\n\
+         Caused by: Disk is full\n\
+         \n\
+         Please include the following when reporting this problem:\n\
+         \n\
+         ```text\n\
+         name: my-tool\n\
+         version: 1.2.3\n\
+         os: {} ({})\n\
+         error: Could not save the document\n\
+         caused by: Disk is full\n\
+         ```",
+        env::consts::OS,
+        env::consts::ARCH,
+    );
+    assert_eq!(report.to_string(), expected);
+}
+
+#[test]
+fn compact_reports_are_unchanged() {
+    install();
+    let report = Report::from_error(error());
+
+    assert_eq!(
+        report.to_compact_string(),
+        "Could not save the document: Disk is full",
+    );
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "color")]
+
+use snafu::{prelude::*, Report};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Could not save the document"))]
+struct SaveError {
+    source: std::io::Error,
+}
+
+fn save() -> Result<(), SaveError> {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "Disk is full");
+    Err(io).context(SaveSnafu)
+}
+
+#[test]
+fn messages_are_styled_when_colorized() {
+    let report = Report::from_error(save().unwrap_err()).colorize(true);
+    assert_eq!(
+        report.to_string(),
+        "\x1b[1;31mCould not save the document\x1b[0m\n\x1b[2mCaused by:\x1b[0m Disk is full",
+    );
+}
+
+#[test]
+fn messages_are_plain_when_not_colorized() {
+    let report = Report::from_error(save().unwrap_err()).colorize(false);
+    assert_eq!(
+        report.to_string(),
+        "Could not save the document\nCaused by: Disk is full",
+    );
+}
+
+#[test]
+fn no_color_environment_variable_disables_colors() {
+    std::env::set_var("NO_COLOR", "1");
+    let report = Report::from_error(save().unwrap_err());
+    assert!(!report.to_string().contains('\x1b'));
+}
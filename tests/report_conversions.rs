@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use snafu::{assert_unambiguous_conversions, prelude::*, SourceConversion};
+use std::{io, num::ParseIntError};
+
+#[derive(Debug, Snafu)]
+#[snafu(report_conversions)]
+enum Error {
+    Read {
+        source: io::Error,
+    },
+
+    #[snafu(context(false))]
+    Parse {
+        source: ParseIntError,
+    },
+
+    Boxed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    Converted {
+        #[snafu(source(from(String, Into::into)))]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    Wrapped(Vec<u8>, #[snafu(source)] io::Error),
+
+    Missing,
+
+    #[snafu(whatever, display("{}", message))]
+    Whatever {
+        message: String,
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(report_conversions)]
+enum GenericError<T: 'static + std::error::Error> {
+    Generic { source: T },
+}
+
+fn conversion(source_type: &'static str, variant: &'static str) -> SourceConversion {
+    SourceConversion {
+        source_type,
+        variant,
+    }
+}
+
+#[test]
+fn lists_the_source_type_of_each_variant() {
+    assert_eq!(
+        Error::SNAFU_CONVERSIONS,
+        [
+            conversion("io::Error", "Read"),
+            conversion("ParseIntError", "Parse"),
+            conversion("Box<dyn std::error::Error + Send + Sync>", "Boxed"),
+            conversion("String", "Converted"),
+            conversion("io::Error", "Wrapped"),
+        ],
+    );
+}
+
+#[test]
+fn generic_sources_are_listed() {
+    assert_eq!(
+        GenericError::<io::Error>::SNAFU_CONVERSIONS,
+        [conversion("T", "Generic")],
+    );
+    assert_unambiguous_conversions(GenericError::<io::Error>::SNAFU_CONVERSIONS);
+}
+
+#[test]
+#[should_panic(
+    expected = "The source type `io::Error` is accepted by both the `Read` and `Wrapped` variants"
+)]
+fn duplicate_source_types_are_ambiguous() {
+    assert_unambiguous_conversions(Error::SNAFU_CONVERSIONS);
+}
@@ -0,0 +1,91 @@
+// Installing a renderer affects every report in the process, so these
+// tests are kept apart from the other report tests.
+
+use snafu::{
+    prelude::*,
+    report::{self, DefaultRenderer, ReportFormat, ReportParts, ReportRenderer},
+    Report,
+};
+use std::{fmt, sync::Once};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Could not save the document"))]
+struct SaveError {
+    source: std::io::Error,
+}
+
+struct WithFooter;
+
+impl ReportRenderer for WithFooter {
+    fn render(&self, parts: &ReportParts<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DefaultRenderer.render(parts, f)?;
+        if parts.format() == ReportFormat::Compact {
+            return Ok(());
+        }
+
+        let outermost = &parts.messages()[0];
+        write!(
+            f,
+            "\nPlease file a bug about {} ({} errors shown)",
+            outermost.text(),
+            parts.messages().len(),
+        )
+    }
+}
+
+fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| report::install(WithFooter).unwrap());
+}
+
+fn save() -> Result<(), SaveError> {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "Disk is full");
+    Err(io).context(SaveSnafu)
+}
+
+#[test]
+fn installed_renderer_is_used() {
+    install();
+
+    let report = Report::from_error(save().unwrap_err());
+    assert_eq!(
+        report.to_string(),
+        "Could not save the document\n\
+         Caused by: Disk is full\n\
+         Please file a bug about Could not save the document (2 errors shown)",
+    );
+}
+
+#[test]
+fn only_one_renderer_can_be_installed() {
+    install();
+
+    let closure = |_: &ReportParts<'_>, f: &mut fmt::Formatter<'_>| f.write_str("Unused");
+    let error = report::install(closure).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "A report renderer has already been installed",
+    );
+
+    let report = Report::from_error(save().unwrap_err());
+    assert!(report.to_string().ends_with("(2 errors shown)"));
+}
+
+#[test]
+fn renderer_is_not_used_without_an_error() {
+    install();
+
+    let report = Report::<SaveError>::ok();
+    assert_eq!(report.to_string(), "");
+}
+
+#[test]
+fn installed_renderer_can_check_the_format() {
+    install();
+
+    let report = Report::from_error(save().unwrap_err());
+    assert_eq!(
+        report.to_compact_string(),
+        "Could not save the document: Disk is full",
+    );
+}
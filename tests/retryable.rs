@@ -0,0 +1,98 @@
+use snafu::{prelude::*, ErrorCompat, Retryable};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Attempt {} failed", attempt))]
+struct AttemptError {
+    attempt: usize,
+}
+
+fn failures(count: usize, capacity: usize) -> Retryable<AttemptError> {
+    let mut failures = Retryable::with_capacity(AttemptSnafu { attempt: 1usize }.build(), capacity);
+    for attempt in 2..=count {
+        failures.push(AttemptSnafu { attempt }.build());
+    }
+    failures
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Retryable<AttemptError>>();
+}
+
+#[test]
+fn single_attempt_reports_only_the_first_error() {
+    let e = failures(1, 4);
+    assert_eq!(e.attempts(), 1);
+    assert_eq!(e.last_message(), None);
+    assert_eq!(e.to_string(), "Failed after 1 attempt");
+}
+
+#[test]
+fn recent_messages_are_bounded() {
+    let e = failures(10, 3);
+    assert_eq!(e.attempts(), 10);
+
+    let recent: Vec<_> = e.recent_messages().collect();
+    assert_eq!(
+        recent,
+        ["Attempt 8 failed", "Attempt 9 failed", "Attempt 10 failed"]
+    );
+    assert_eq!(
+        e.to_string(),
+        "Failed after 10 attempts; last: Attempt 10 failed",
+    );
+}
+
+#[test]
+fn messages_can_be_disabled() {
+    let e = failures(3, 0);
+    assert_eq!(e.attempts(), 3);
+    assert_eq!(e.recent_messages().count(), 0);
+    assert_eq!(e.to_string(), "Failed after 3 attempts");
+}
+
+#[test]
+fn first_error_is_the_source() {
+    let e = failures(2, 1);
+    let chain: Vec<_> = e.iter_chain().map(ToString::to_string).collect();
+    assert_eq!(
+        chain,
+        [
+            "Failed after 2 attempts; last: Attempt 2 failed",
+            "Attempt 1 failed",
+        ]
+    );
+    assert_eq!(e.into_first().attempt, 1);
+}
+
+#[test]
+fn is_send_when_the_error_is() {
+    fn check<T: Send>() {}
+
+    // `Send` but not `Sync`
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Attempt failed"))]
+    struct UnsyncError {
+        cell: std::cell::Cell<u8>,
+    }
+
+    check::<Retryable<UnsyncError>>();
+}
+
+#[test]
+fn clones_are_independent() {
+    #[derive(Debug, Clone, Snafu)]
+    #[snafu(display("Attempt failed"))]
+    struct CloneError;
+
+    let mut original = Retryable::new(CloneError);
+    original.push("Attempt 2 failed");
+
+    let mut copy = original.clone();
+    copy.push("Attempt 3 failed");
+    assert_eq!(copy.attempts(), 3);
+    assert_eq!(copy.last_message(), Some("Attempt 3 failed"));
+    assert_eq!(original.attempts(), 2);
+    assert_eq!(original.last_message(), Some("Attempt 2 failed"));
+}
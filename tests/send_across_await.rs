@@ -0,0 +1,92 @@
+// This test asserts that holding a context selector across an
+// `.await` does not prevent the future from being `Send`.
+
+#![allow(dead_code)]
+
+use snafu::{prelude::*, Whatever};
+use std::{io, rc::Rc};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Read { path: String, source: io::Error },
+
+    Boom,
+
+    // Not `Send`, but never held by the selectors
+    Local { data: Rc<str> },
+
+    Boxed { source: Box<dyn std::error::Error> },
+}
+
+#[derive(Debug, Snafu)]
+enum GenericError<T> {
+    Value { value: T },
+}
+
+async fn tick() -> bool {
+    true
+}
+
+async fn read() -> io::Result<()> {
+    Err(io::ErrorKind::Other.into())
+}
+
+async fn selector_held_across_await(path: &str) -> Result<(), Error> {
+    let selector = ReadSnafu { path };
+    let result = read().await;
+    result.context(selector)
+}
+
+async fn fieldless_selector_held_across_await() -> Result<(), Error> {
+    let selector = BoomSnafu;
+    tick().await;
+    selector.fail()
+}
+
+async fn selector_for_a_non_send_error() -> Result<(), Error> {
+    let selector = LocalSnafu { data: "local" };
+    tick().await;
+    selector.fail()
+}
+
+async fn generic_selector_held_across_await() -> Result<(), GenericError<u8>> {
+    let selector = ValueSnafu { value: 42u8 };
+    tick().await;
+    selector.fail()
+}
+
+async fn lazy_selector_with_awaited_values(path: String) -> Result<(), Error> {
+    let result = read().await;
+    let suffix = tick().await;
+    result.with_context(|| ReadSnafu {
+        path: format!("{}{}", path, suffix),
+    })
+}
+
+async fn ensure_with_awaited_values() -> Result<(), Error> {
+    ensure!(tick().await, LocalSnafu { data: "local" });
+    Ok(())
+}
+
+async fn boxed_source_conversion() -> Result<(), Error> {
+    read().await.map_err(Box::from).context(BoxedSnafu)
+}
+
+async fn whatever_with_awaited_arguments() -> Result<(), Whatever> {
+    whatever!(read().await, "Read failed: {}", tick().await);
+    Ok(())
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn futures_holding_selectors_are_send() {
+    assert_send(&selector_held_across_await("/tmp"));
+    assert_send(&fieldless_selector_held_across_await());
+    assert_send(&selector_for_a_non_send_error());
+    assert_send(&generic_selector_held_across_await());
+    assert_send(&lazy_selector_with_awaited_values(String::from("/tmp")));
+    assert_send(&ensure_with_awaited_values());
+    assert_send(&boxed_source_conversion());
+    assert_send(&whatever_with_awaited_arguments());
+}
@@ -1,5 +1,7 @@
 // This test asserts that errors can be used across threads.
 
+#![allow(dead_code)]
+
 use std::thread;
 
 use snafu::{ResultExt, Snafu};
@@ -46,7 +48,7 @@ fn implements_thread_safe_error() {
     check_send::<InnerError>();
     check_send::<Error>();
 
-    let t = thread::spawn(move || example());
+    let t = thread::spawn(example);
 
     let v = t.join().expect("Thread panicked");
     v.unwrap_err();
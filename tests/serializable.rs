@@ -0,0 +1,78 @@
+use snafu::{prelude::*, ChainCompat, Report, SerializableError};
+use std::error::Error as _;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not load the configuration"))]
+    LoadConfig { source: ParseError },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Line {} is invalid", line))]
+struct ParseError {
+    line: u32,
+    source: std::io::Error,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("The remote service failed"))]
+struct RemoteError {
+    source: SerializableError,
+}
+
+fn serializable() -> SerializableError {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "Disk is full");
+    let error = Err::<(), _>(io)
+        .context(ParseSnafu { line: 3u32 })
+        .context(LoadConfigSnafu)
+        .unwrap_err();
+    SerializableError::from_error(&error)
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error + Send + Sync + 'static>() {}
+    check::<SerializableError>();
+}
+
+#[test]
+fn sources_are_rebuilt_from_the_chain() {
+    let error = serializable();
+    let messages: Vec<_> = ChainCompat::new(&error).map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            "Could not load the configuration",
+            "Line 3 is invalid",
+            "Disk is full",
+        ],
+    );
+}
+
+#[test]
+fn errors_without_a_chain_have_no_source() {
+    assert!(SerializableError::new("Oh no").source().is_none());
+}
+
+#[test]
+fn clones_have_the_same_sources() {
+    let error = serializable().clone();
+    let source = error.source().unwrap();
+    assert_eq!(source.to_string(), "Line 3 is invalid");
+    assert_eq!(source.source().unwrap().to_string(), "Disk is full");
+}
+
+#[test]
+fn can_be_used_as_a_source() {
+    let error = Err::<(), _>(serializable())
+        .context(RemoteSnafu)
+        .unwrap_err();
+    let report = Report::from_error(error);
+    assert_eq!(
+        report.to_string(),
+        "The remote service failed\n\
+         Caused by: Could not load the configuration\n\
+         Caused by: Line 3 is invalid\n\
+         Caused by: Disk is full",
+    );
+}
@@ -0,0 +1,71 @@
+use snafu::prelude::*;
+use std::io;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Read {
+        #[snafu(source(accessor(source_io)))]
+        source: io::Error,
+    },
+
+    Write {
+        path: String,
+        #[snafu(source(accessor(source_io)))]
+        source: io::Error,
+    },
+
+    Parse {
+        #[snafu(source(accessor))]
+        cause: std::num::ParseIntError,
+    },
+
+    Boxed {
+        #[snafu(source(from(io::Error, Box::new), accessor))]
+        source: Box<io::Error>,
+    },
+
+    Unrelated,
+}
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "missing")
+}
+
+#[test]
+fn implements_error() {
+    fn check<T: std::error::Error>() {}
+    check::<Error>();
+}
+
+#[test]
+fn shared_accessors_cover_every_variant_that_uses_them() {
+    let e = Err::<(), _>(io_error()).context(ReadSnafu).unwrap_err();
+    assert_eq!(
+        e.source_io().map(io::Error::kind),
+        Some(io::ErrorKind::NotFound)
+    );
+
+    let e = Err::<(), _>(io_error())
+        .context(WriteSnafu { path: "/tmp" })
+        .unwrap_err();
+    assert_eq!(
+        e.source_io().map(io::Error::kind),
+        Some(io::ErrorKind::NotFound)
+    );
+
+    assert!(UnrelatedSnafu.build().source_io().is_none());
+}
+
+#[test]
+fn accessors_default_to_the_name_of_the_variant() {
+    let e = "x".parse::<i32>().context(ParseSnafu).unwrap_err();
+    assert!(e.parse_source().is_some());
+    assert!(e.source_io().is_none());
+}
+
+#[test]
+fn accessors_return_the_stored_type() {
+    let e = Err::<(), _>(io_error()).context(BoxedSnafu).unwrap_err();
+    let source: Option<&Box<io::Error>> = e.boxed_source();
+    assert!(source.is_some());
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use snafu::Snafu;
 
 #[derive(Debug, Snafu)]
@@ -58,6 +60,7 @@ mod transformation {
     use std::io;
 
     #[derive(Debug, Snafu)]
+    #[allow(clippy::enum_variant_names)]
     enum Error {
         TransformationViaClosure {
             #[snafu(source(from(InnerError, |e| io::Error::new(io::ErrorKind::InvalidData, e))))]
@@ -34,6 +34,16 @@ mod message_only {
         let e = exercise(false).unwrap_err();
         assert_eq!("This is a code 42 error", e.to_string());
     }
+
+    #[test]
+    fn can_use_named_format_arguments() {
+        fn exercise(v: i32) -> Result<()> {
+            whatever!("The value was {value}, then {value}", value = v + 1);
+        }
+
+        let e = exercise(41).unwrap_err();
+        assert_eq!("The value was 42, then 42", e.to_string());
+    }
 }
 
 // THOUGHT: Must it be boxed trait object?
@@ -246,3 +256,249 @@ mod struck {
         }
     }
 }
+
+mod thread_safe_source {
+    use snafu::{prelude::*, Snafu};
+    use std::rc::Rc;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(whatever, display("{}", message))]
+    struct Error {
+        message: String,
+        #[snafu(source(from(Box<dyn std::error::Error + Send + Sync>, Some)))]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(whatever, display("{}", message))]
+    struct LocalError {
+        message: String,
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+    }
+
+    #[derive(Debug, Snafu)]
+    struct NotSendError {
+        value: Rc<i32>,
+    }
+
+    #[test]
+    fn can_be_send_and_sync() {
+        fn check<T: Send + Sync>() {}
+        check::<Error>();
+
+        let e = std::fs::read("/this/does/not/exist")
+            .whatever_context::<_, Error>("Could not read")
+            .unwrap_err();
+        let e = std::thread::spawn(move || e).join().unwrap();
+        assert_eq!(e.to_string(), "Could not read");
+    }
+
+    #[test]
+    fn can_wrap_errors_that_are_not_send() {
+        let e = NotSendSnafu { value: Rc::new(1) }
+            .fail::<()>()
+            .whatever_context::<_, LocalError>("Could not use the value")
+            .unwrap_err();
+        assert_eq!(e.to_string(), "Could not use the value");
+    }
+}
+
+mod details {
+    use snafu::{
+        prelude::*, Backtrace, ErrorCompat, FromString, Location, Metadata, WhateverDetails,
+    };
+
+    #[derive(Debug, Snafu)]
+    #[snafu(whatever, display("{}", message))]
+    struct Error {
+        message: String,
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+        location: snafu::Location,
+        metadata: snafu::Metadata,
+        backtrace: Backtrace,
+    }
+
+    #[derive(Debug, Snafu)]
+    enum EnumError {
+        #[snafu(whatever, display("{}", message))]
+        Whatever {
+            message: String,
+            location: snafu::Location,
+        },
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(whatever, display("{}", message))]
+    struct ImportedTypesError {
+        message: String,
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(implicit)]
+        metadata: Metadata,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Underlying"))]
+    struct UnderlyingError;
+
+    #[test]
+    fn captures_the_location_of_the_macro() {
+        fn exercise() -> Result<(), Error> {
+            whatever!("Something went wrong")
+        }
+
+        let line = line!() - 3;
+        let e = exercise().unwrap_err();
+        assert_eq!(e.location.file, file!());
+        assert_eq!(e.location.line, line);
+    }
+
+    #[test]
+    fn captures_key_value_pairs() {
+        fn exercise(id: i32) -> Result<(), Error> {
+            whatever!("Could not load user {}", id; user_id = id, retry = false)
+        }
+
+        let e = exercise(42).unwrap_err();
+        assert_eq!(e.to_string(), "Could not load user 42");
+        assert_eq!(e.metadata.get("user_id"), Some("42"));
+        assert_eq!(e.metadata.get("retry"), Some("false"));
+        assert_eq!(ErrorCompat::metadata(&e), Some(&e.metadata));
+    }
+
+    #[test]
+    fn implicit_fields_can_use_imported_types() {
+        fn exercise() -> Result<(), ImportedTypesError> {
+            whatever!("Something went wrong"; id = 7)
+        }
+
+        let line = line!() - 3;
+        let e = exercise().unwrap_err();
+        assert_eq!(e.location.line, line);
+        assert_eq!(e.metadata.get("id"), Some("7"));
+        assert_eq!(ErrorCompat::metadata(&e), Some(&e.metadata));
+    }
+
+    #[test]
+    fn named_format_arguments_are_not_key_value_pairs() {
+        fn exercise(id: i32) -> Result<(), Error> {
+            whatever!("Could not load user {user}", user = id * 2; attempt = 1)
+        }
+
+        let e = exercise(21).unwrap_err();
+        assert_eq!(e.to_string(), "Could not load user 42");
+        assert_eq!(e.metadata.get("user"), None);
+        assert_eq!(e.metadata.get("attempt"), Some("1"));
+    }
+
+    #[test]
+    fn captures_key_value_pairs_with_a_source() {
+        fn exercise() -> Result<i32, Error> {
+            let v = whatever!(
+                Err::<i32, _>(UnderlyingError),
+                "Could not load {}",
+                "the user";
+                attempt = 1 + 2,
+            );
+            Ok(v)
+        }
+
+        let e = exercise().unwrap_err();
+        assert_eq!(e.to_string(), "Could not load the user");
+        assert_eq!(e.metadata.to_string(), "attempt: 3");
+        assert!(!e.location.is_unknown());
+        assert!(e.source.is_some());
+    }
+
+    #[test]
+    fn captures_the_location_in_enum_variants() {
+        fn exercise() -> Result<(), EnumError> {
+            whatever!("Something went wrong")
+        }
+
+        let EnumError::Whatever { location, .. } = exercise().unwrap_err();
+        assert_eq!(location.file, file!());
+    }
+
+    #[test]
+    fn captures_typed_key_value_pairs() {
+        #[derive(Debug, Snafu)]
+        #[snafu(whatever, display("{}", message))]
+        struct TypedError {
+            message: String,
+            fields: snafu::Fields,
+        }
+
+        fn exercise() -> Result<(), TypedError> {
+            whatever!("Oops"; count = 3u32, name = %"widget")
+        }
+
+        let e = exercise().unwrap_err();
+        assert_eq!(e.fields.get("count"), Some(&snafu::FieldValue::U64(3)));
+        assert_eq!(
+            e.fields.get("name"),
+            Some(&snafu::FieldValue::from("widget"))
+        );
+    }
+
+    #[test]
+    fn typed_key_value_pairs_can_share_names_with_format_arguments() {
+        #[derive(Debug, Snafu)]
+        #[snafu(whatever, display("{}", message))]
+        struct TypedError {
+            message: String,
+            fields: snafu::Fields,
+        }
+
+        fn exercise(path: &str) -> Result<(), TypedError> {
+            whatever!("Could not open {path}", path = path; path = ?path)
+        }
+
+        let e = exercise("a.txt").unwrap_err();
+        assert_eq!(e.to_string(), "Could not open a.txt");
+        assert_eq!(
+            e.fields.get("path"),
+            Some(&snafu::FieldValue::from(r#""a.txt""#))
+        );
+    }
+
+    #[test]
+    fn methods_without_details_use_an_unknown_location() {
+        let e = Err::<(), _>(UnderlyingError)
+            .whatever_context::<_, Error>("Could not load")
+            .unwrap_err();
+        assert!(e.location.is_unknown());
+        assert!(e.metadata.is_empty());
+    }
+
+    #[test]
+    fn details_are_ignored_by_default() {
+        #[derive(Debug)]
+        struct Manual(String);
+
+        impl FromString for Manual {
+            type Source = UnderlyingError;
+
+            fn without_source(message: String) -> Self {
+                Manual(message)
+            }
+
+            fn with_source(_: Self::Source, message: String) -> Self {
+                Manual(message)
+            }
+        }
+
+        fn exercise() -> Result<(), Manual> {
+            whatever!("Manual error"; id = 1)
+        }
+
+        let details = WhateverDetails::new(Location::unknown());
+        assert_eq!(
+            Manual::without_source_and_details("a".into(), details).0,
+            "a"
+        );
+        assert_eq!(exercise().unwrap_err().0, "Manual error");
+    }
+}
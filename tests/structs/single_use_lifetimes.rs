@@ -1,3 +1,4 @@
+#![allow(dead_code)]
 #![deny(single_use_lifetimes)]
 
 use snafu::Snafu;
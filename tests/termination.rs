@@ -0,0 +1,100 @@
+#![cfg(feature = "rust_1_61")]
+// The generated code uses `Termination` and `ExitCode`, which are
+// newer than the crate's minimum supported Rust version.
+#![allow(clippy::incompatible_msrv)]
+
+use snafu::prelude::*;
+use std::{
+    env, io,
+    process::{Command, ExitCode, Termination},
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(termination)]
+enum Error {
+    #[snafu(display("Could not save the document"), exit_code(74))]
+    Save { source: io::Error },
+
+    #[snafu(display("Something went wrong"))]
+    Unknown,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(termination, exit_code(3))]
+struct NamedError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(termination)]
+struct OpaqueError(Error);
+
+#[derive(Debug, Snafu)]
+#[snafu(termination)]
+enum GenericError<T> {
+    Value { value: T },
+}
+
+fn save() -> Result<(), io::Error> {
+    Err(io::Error::new(io::ErrorKind::Other, "Disk is full"))
+}
+
+fn same_exit_code(left: ExitCode, right: ExitCode) -> bool {
+    format!("{:?}", left) == format!("{:?}", right)
+}
+
+#[test]
+fn implements_termination() {
+    fn check<T: Termination>() {}
+    check::<Error>();
+    check::<NamedError>();
+    check::<OpaqueError>();
+    check::<GenericError<i32>>();
+}
+
+#[test]
+fn converts_into_the_exit_code() {
+    let e = save().context(SaveSnafu).unwrap_err();
+    assert!(same_exit_code(e.into(), ExitCode::from(74)));
+
+    let e = UnknownSnafu.build();
+    assert!(same_exit_code(e.into(), ExitCode::FAILURE));
+
+    let e = NamedSnafu { id: 1u32 }.build();
+    assert!(same_exit_code(e.into(), ExitCode::from(3)));
+
+    let e = OpaqueError::from(save().context(SaveSnafu).unwrap_err());
+    assert!(same_exit_code(e.into(), ExitCode::from(74)));
+
+    let e: GenericError<i32> = ValueSnafu { value: 42 }.build();
+    assert!(same_exit_code(e.into(), ExitCode::FAILURE));
+}
+
+const CHILD_ENV_VAR: &str = "SNAFU_TEST_TERMINATION_CHILD";
+
+// Only reports anything when run by `reports_the_error_to_stderr`.
+#[test]
+fn report_in_child_process() {
+    if env::var_os(CHILD_ENV_VAR).is_some() {
+        let e = save().context(SaveSnafu).unwrap_err();
+        let code = e.report();
+        assert!(same_exit_code(code, ExitCode::from(74)));
+    }
+}
+
+#[test]
+fn reports_the_error_to_stderr() {
+    let output = Command::new(env::current_exe().unwrap())
+        .args(&["report_in_child_process", "--exact", "--nocapture"])
+        .env(CHILD_ENV_VAR, "1")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Error: Could not save the document\nCaused by: Disk is full\n"),
+        "{}",
+        stderr,
+    );
+}
@@ -0,0 +1,68 @@
+use snafu::{prelude::*, DelayedContext, Timing};
+use std::time::Duration;
+
+#[derive(Debug, Snafu)]
+struct InnerError {
+    timing: snafu::Timing,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Slow {
+        source: InnerError,
+        timing: snafu::Timing,
+    },
+}
+
+fn slow_operation() -> Result<(), InnerError> {
+    std::thread::sleep(Duration::from_millis(10));
+    InnerSnafu.fail()
+}
+
+#[test]
+fn records_the_elapsed_time() {
+    let e = snafu::timed_context(SlowSnafu, slow_operation).unwrap_err();
+    let Error::Slow { timing, .. } = e;
+    assert!(timing.elapsed().unwrap() >= Duration::from_millis(10));
+}
+
+#[test]
+fn errors_created_by_the_operation_are_unaffected() {
+    let e = snafu::timed_context(SlowSnafu, slow_operation).unwrap_err();
+    let Error::Slow { source, .. } = e;
+    assert_eq!(source.timing.elapsed(), None);
+}
+
+#[test]
+fn errors_created_afterwards_are_unaffected() {
+    let _ = snafu::timed_context(SlowSnafu, slow_operation);
+    let e = InnerSnafu.build();
+    assert_eq!(e.timing.elapsed(), None);
+    assert_eq!(e.timing.to_string(), "unknown");
+}
+
+#[test]
+fn boxed_context_selectors_record_the_elapsed_time() {
+    let context: Box<dyn DelayedContext<InnerError, Error>> = Box::new(SlowSnafu);
+    let e = snafu::timed_context(context, slow_operation).unwrap_err();
+    let Error::Slow { timing, .. } = e;
+    assert!(timing.elapsed().unwrap() >= Duration::from_millis(10));
+}
+
+#[test]
+fn plain_context_leaves_the_elapsed_time_unknown() {
+    let e = slow_operation().context(SlowSnafu).unwrap_err();
+    let Error::Slow { timing, .. } = e;
+    assert_eq!(timing.elapsed(), None);
+}
+
+#[test]
+fn success_is_unchanged() {
+    let r: Result<i32, Error> = snafu::timed_context(SlowSnafu, || Ok(42));
+    assert_eq!(r.unwrap(), 42);
+}
+
+#[test]
+fn default_is_unknown() {
+    assert_eq!(Timing::default().elapsed(), None);
+}
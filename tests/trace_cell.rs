@@ -0,0 +1,74 @@
+use snafu::{prelude::*, ErrorCompat, TraceCell};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("The sensor did not respond"))]
+    Timeout { backtrace: TraceCell },
+
+    #[snafu(display("The flash write failed"))]
+    Flash {
+        source: std::io::Error,
+        #[snafu(backtrace)]
+        trace: TraceCell,
+    },
+}
+
+// Every test installs the same hook, so the order they run in does
+// not matter.
+fn hook(addresses: &mut [usize]) -> usize {
+    for (i, address) in addresses.iter_mut().enumerate() {
+        *address = 0x1000 + i * 0x10;
+    }
+    addresses.len() + 100
+}
+
+#[test]
+fn backtrace_fields_capture_using_the_hook() {
+    TraceCell::set_hook(hook);
+
+    let backtrace = match TimeoutSnafu.build() {
+        Error::Timeout { backtrace } => backtrace,
+        other => panic!("Unexpected error: {:?}", other),
+    };
+
+    assert_eq!(backtrace.addresses().len(), TraceCell::CAPACITY);
+    assert_eq!(backtrace.addresses()[..2], [0x1000, 0x1010]);
+}
+
+#[test]
+fn fields_marked_as_backtraces_capture_using_the_hook() {
+    TraceCell::set_hook(hook);
+
+    let e = Err::<(), _>(std::io::Error::from(std::io::ErrorKind::Other))
+        .context(FlashSnafu)
+        .unwrap_err();
+
+    let trace = match e {
+        Error::Flash { trace, .. } => trace,
+        other => panic!("Unexpected error: {:?}", other),
+    };
+    assert!(!trace.is_empty());
+}
+
+#[test]
+fn trace_cells_are_not_backtraces() {
+    TraceCell::set_hook(hook);
+
+    let e = TimeoutSnafu.build();
+    assert!(ErrorCompat::backtrace(&e).is_none());
+}
+
+#[test]
+fn trace_cells_display_one_address_per_line() {
+    TraceCell::set_hook(hook);
+
+    let trace = TraceCell::capture();
+    let text = trace.to_string();
+    let mut lines = text.lines();
+
+    assert_eq!(lines.next(), Some("   0: 0x1000"));
+    assert_eq!(lines.next(), Some("   1: 0x1010"));
+    assert_eq!(text.lines().count(), TraceCell::CAPACITY);
+
+    assert_eq!(TraceCell::empty().to_string(), "");
+}
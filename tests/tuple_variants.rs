@@ -0,0 +1,84 @@
+use snafu::{prelude::*, Backtrace, ErrorCompat};
+use std::io;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Io(io::Error),
+
+    #[snafu(display("Could not read {} on attempt {}", _0, _1))]
+    Read(String, u32),
+
+    #[snafu(display("Could not parse line {}", _1))]
+    Parse(#[snafu(source)] std::num::ParseIntError, usize),
+
+    Traced(#[snafu(source(false), backtrace)] Backtrace),
+
+    Delegated(#[snafu(backtrace)] InnerError),
+
+    #[snafu(display("Flag {}", _0))]
+    NotASource(#[snafu(source(false))] bool),
+
+    #[snafu(context(false))]
+    Converted(ConvertError),
+}
+
+#[derive(Debug, Snafu)]
+struct InnerError {
+    backtrace: Backtrace,
+}
+
+#[derive(Debug, Snafu)]
+struct ConvertError;
+
+fn io_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "disk on fire")
+}
+
+#[test]
+fn single_fields_are_sources() {
+    let e = Err::<(), _>(io_error()).context(IoSnafu).unwrap_err();
+    assert_eq!(e.to_string(), "Io: disk on fire");
+
+    let chain: Vec<_> = e.iter_chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["Io: disk on fire", "disk on fire"]);
+}
+
+#[test]
+fn multiple_fields_have_positional_selectors() {
+    let e = ReadSnafu("config.toml", 3u32).build();
+    assert_eq!(e.to_string(), "Could not read config.toml on attempt 3");
+    assert!(matches!(e, Error::Read(ref path, 3) if path == "config.toml"));
+}
+
+#[test]
+fn positional_selectors_skip_the_source() {
+    let e = "x".parse::<i32>().context(ParseSnafu(7usize)).unwrap_err();
+    assert_eq!(e.to_string(), "Could not parse line 7");
+    assert!(std::error::Error::source(&e).is_some());
+}
+
+#[test]
+fn positional_fields_can_be_backtraces() {
+    let e = TracedSnafu.build();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+
+    let e = InnerSnafu.fail::<()>().context(DelegatedSnafu).unwrap_err();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
+
+#[test]
+fn single_fields_can_opt_out_of_being_a_source() {
+    let e = NotASourceSnafu(true).build();
+    assert_eq!(e.to_string(), "Flag true");
+    assert!(std::error::Error::source(&e).is_none());
+}
+
+#[test]
+fn single_fields_can_be_converted_without_context() {
+    fn convert() -> Result<(), Error> {
+        ConvertSnafu.fail()?;
+        Ok(())
+    }
+
+    assert!(matches!(convert(), Err(Error::Converted(_))));
+}
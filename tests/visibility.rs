@@ -1,5 +1,7 @@
 // There are also sad-path tests
 
+#![allow(dead_code)]
+
 mod outer {
     pub mod inner {
         use snafu::Snafu;